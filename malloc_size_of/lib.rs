@@ -733,6 +733,7 @@ where
             | Component::ImplicitScope
             | Component::ParentSelector
             | Component::Nth(..)
+            | Component::NthCol { .. }
             | Component::Host(None)
             | Component::RelativeSelectorAnchor
             | Component::Invalid(..) => 0,
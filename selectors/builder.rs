@@ -347,6 +347,7 @@ where
             | Component::Root
             | Component::Empty
             | Component::Nth(..)
+            | Component::NthCol { .. }
             | Component::NonTSPseudoClass(..) => {
                 specificity.class_like_selectors += 1;
             },
@@ -20,4 +20,4 @@ pub mod visitor;
 
 pub use crate::nth_index_cache::NthIndexCache;
 pub use crate::parser::{Parser, SelectorImpl, SelectorList};
-pub use crate::tree::{Element, OpaqueElement};
+pub use crate::tree::{CellColumns, Element, OpaqueElement};
@@ -9,8 +9,8 @@ use crate::attr::{
 use crate::bloom::{BloomFilter, BLOOM_HASH_MASK};
 use crate::kleene_value::KleeneValue;
 use crate::parser::{
-    AncestorHashes, Combinator, Component, LocalName, MatchesFeaturelessHost, NthSelectorData,
-    RelativeSelectorMatchHint,
+    AnPlusB, AncestorHashes, Combinator, Component, LocalName, MatchesFeaturelessHost,
+    NthSelectorData, RelativeSelectorMatchHint,
 };
 use crate::parser::{
     NonTSPseudoClass, RelativeSelector, Selector, SelectorImpl, SelectorIter, SelectorList,
@@ -80,6 +80,15 @@ bitflags! {
         /// The element is reached by a relative selector search in the ancestor direction.
         const RELATIVE_SELECTOR_SEARCH_DIRECTION_ANCESTOR = 1 << 9;
 
+        /// The element is a table cell matched against :nth-col(),
+        /// :nth-last-col() or the column combinator, or a column element a
+        /// cell was matched against through the column combinator.
+        ///
+        /// The cells of the table need to be restyled when the structure of
+        /// the table changes, or when such a column element changes, since
+        /// the cells aren't reachable from it during invalidation.
+        const HAS_COLUMN_SELECTOR = 1 << 10;
+
         // The element is reached by a relative selector search in both sibling and ancestor directions.
         const RELATIVE_SELECTOR_SEARCH_DIRECTION_ANCESTOR_SIBLING =
             Self::RELATIVE_SELECTOR_SEARCH_DIRECTION_SIBLING.bits() |
@@ -94,7 +103,8 @@ impl ElementSelectorFlags {
             | ElementSelectorFlags::ANCHORS_RELATIVE_SELECTOR
             | ElementSelectorFlags::ANCHORS_RELATIVE_SELECTOR_NON_SUBJECT
             | ElementSelectorFlags::RELATIVE_SELECTOR_SEARCH_DIRECTION_SIBLING
-            | ElementSelectorFlags::RELATIVE_SELECTOR_SEARCH_DIRECTION_ANCESTOR)
+            | ElementSelectorFlags::RELATIVE_SELECTOR_SEARCH_DIRECTION_ANCESTOR
+            | ElementSelectorFlags::HAS_COLUMN_SELECTOR)
     }
 
     /// Returns the subset of flags that apply to the parent.
@@ -839,9 +849,69 @@ where
         Combinator::PseudoElement => {
             NextElement::new(element.pseudo_element_originating_element(), false)
         },
+        Combinator::Column => unsafe {
+            debug_unreachable!("Column elements are handled by matches_column_combinator")
+        },
     }
 }
 
+/// Matches the rest of the selector against the column elements of the given
+/// cell, which matches if any of them does.
+///
+/// Column elements aren't related to each other in the same way ancestors or
+/// siblings are, so a failure for one column doesn't tell us anything about
+/// the others, nor about other candidates for the combinators to the right.
+fn matches_column_combinator<E>(
+    selector_iter: &SelectorIter<E::Impl>,
+    element: &E,
+    context: &mut MatchingContext<E::Impl>,
+    rightmost: SubjectOrPseudoElement,
+    first_subject_compound: SubjectOrPseudoElement,
+) -> SelectorMatchingResult
+where
+    E: Element,
+{
+    if context.needs_selector_flags() {
+        element.apply_selector_flags(ElementSelectorFlags::HAS_COLUMN_SELECTOR);
+    }
+    let Some(columns) = element.table_cell_columns() else {
+        return SelectorMatchingResult::NotMatchedAndRestartFromClosestLaterSibling;
+    };
+    let mut previous_column_element = None;
+    let mut result = SelectorMatchingResult::NotMatchedAndRestartFromClosestLaterSibling;
+    for column in columns.start..columns.start.saturating_add(columns.span) {
+        let Some(column_element) = element.column_element(column) else {
+            continue;
+        };
+        // A column element can span several columns.
+        let opaque = column_element.opaque();
+        if previous_column_element == Some(opaque) {
+            continue;
+        }
+        previous_column_element = Some(opaque);
+        if context.needs_selector_flags() {
+            column_element.apply_selector_flags(ElementSelectorFlags::HAS_COLUMN_SELECTOR);
+        }
+
+        let column_result =
+            context.with_visited_handling_mode(VisitedHandlingMode::AllLinksUnvisited, |context| {
+                matches_complex_selector_internal(
+                    selector_iter.clone(),
+                    &column_element,
+                    context,
+                    rightmost,
+                    first_subject_compound,
+                )
+            });
+        match column_result {
+            SelectorMatchingResult::Matched => return column_result,
+            SelectorMatchingResult::Unknown => result = column_result,
+            _ => {},
+        }
+    }
+    result
+}
+
 fn matches_complex_selector_internal<E>(
     mut selector_iter: SelectorIter<E::Impl>,
     element: &E,
@@ -908,6 +978,21 @@ where
         SelectorMatchingResult::NotMatchedGlobally
     };
 
+    if combinator == Combinator::Column {
+        return match matches_column_combinator(
+            &selector_iter,
+            element,
+            context,
+            rightmost,
+            first_subject_compound,
+        ) {
+            SelectorMatchingResult::Matched if !matches_compound_selector.to_bool(false) => {
+                SelectorMatchingResult::Unknown
+            },
+            result => result,
+        };
+    }
+
     let mut element = element.clone();
     loop {
         if element.is_link() {
@@ -979,7 +1064,8 @@ where
             Combinator::NextSibling
             | Combinator::PseudoElement
             | Combinator::Part
-            | Combinator::SlotAssignment => {
+            | Combinator::SlotAssignment
+            | Combinator::Column => {
                 // NOTE(emilio): Conceptually, PseudoElement / Part / SlotAssignment should return
                 // `candidate_not_found`, but it doesn't matter in practice since they don't have
                 // sibling / descendant combinators to the right of them. This hopefully saves one
@@ -1319,6 +1405,15 @@ where
         Component::Nth(ref nth_data) => {
             return matches_generic_nth_child(element, context.shared, nth_data, &[], rightmost);
         },
+        Component::NthCol {
+            ref an_plus_b,
+            from_end,
+        } => {
+            if context.shared.needs_selector_flags() {
+                element.apply_selector_flags(ElementSelectorFlags::HAS_COLUMN_SELECTOR);
+            }
+            matches_nth_col(element, an_plus_b, from_end)
+        },
         Component::NthOf(ref nth_of_data) => {
             return context.shared.nest(|context| {
                 matches_generic_nth_child(
@@ -1393,6 +1488,24 @@ pub fn to_unconditional_case_sensitivity<'a, E: Element>(
     }
 }
 
+fn matches_nth_col<E>(element: &E, an_plus_b: &AnPlusB, from_end: bool) -> bool
+where
+    E: Element,
+{
+    let Some(columns) = element.table_cell_columns() else {
+        return false;
+    };
+    // A cell belongs to all the columns it spans, and the index is one-based.
+    (columns.start..columns.start.saturating_add(columns.span)).any(|column| {
+        let index = if from_end {
+            columns.count.saturating_sub(column)
+        } else {
+            column + 1
+        };
+        an_plus_b.matches_index(index as i32)
+    })
+}
+
 fn matches_generic_nth_child<E>(
     element: &E,
     context: &mut MatchingContext<E::Impl>,
@@ -309,6 +309,12 @@ pub trait Parser<'i> {
         false
     }
 
+    /// Whether to parse the column combinator (`||`) and the `:nth-col()`
+    /// and `:nth-last-col()` pseudo-classes.
+    fn parse_column_selectors(&self) -> bool {
+        false
+    }
+
     /// Whether the given function name is an alias for the `:is()` function.
     fn is_is_alias(&self, _name: &str) -> bool {
         false
@@ -1252,6 +1258,7 @@ impl<Impl: SelectorImpl> Selector<Impl> {
                 | Scope
                 | ImplicitScope
                 | Nth(..)
+                | NthCol { .. }
                 | NonTSPseudoClass(..)
                 | PseudoElement(..)
                 | Combinator(..)
@@ -1597,10 +1604,15 @@ impl<'a, Impl: 'a + SelectorImpl> AncestorIter<'a, Impl> {
             // If this is ever changed to stop at the "pseudo-element"
             // combinator, we will need to fix the way we compute hashes for
             // revalidation selectors.
-            if self.0.next_sequence().map_or(true, |x| {
-                matches!(x, Combinator::Child | Combinator::Descendant)
-            }) {
-                break;
+            match self.0.next_sequence() {
+                None | Some(Combinator::Child | Combinator::Descendant) => break,
+                Some(Combinator::Column) => {
+                    // The ancestors of a column element aren't necessarily
+                    // ancestors of the cell, so skip everything to the left.
+                    while self.0.next().is_some() || self.0.next_sequence().is_some() {}
+                    break;
+                },
+                Some(_) => {},
             }
         }
     }
@@ -1646,6 +1658,11 @@ pub enum Combinator {
     /// Another combinator used for `::part()`, which represents the jump from
     /// the part to the containing shadow host.
     Part,
+    /// The column combinator (`||`), which represents the jump from a table
+    /// cell to the column elements it belongs to.
+    ///
+    /// https://drafts.csswg.org/selectors-4/#the-column-combinator
+    Column,
 }
 
 impl Combinator {
@@ -1921,7 +1938,10 @@ impl RelativeSelectorMatchHint {
                     RelativeSelectorMatchHint::InSiblingSubtree
                 }
             },
-            Combinator::Part | Combinator::PseudoElement | Combinator::SlotAssignment => {
+            Combinator::Part
+            | Combinator::PseudoElement
+            | Combinator::SlotAssignment
+            | Combinator::Column => {
                 debug_assert!(false, "Unexpected relative combinator");
                 RelativeSelectorMatchHint::InSubtree
             },
@@ -1976,9 +1996,10 @@ impl RelativeSelectorCombinatorCount {
                 Combinator::NextSibling | Combinator::LaterSibling => {
                     result.adjacent_or_next_siblings += 1;
                 },
-                Combinator::Part | Combinator::PseudoElement | Combinator::SlotAssignment => {
-                    continue
-                },
+                Combinator::Part
+                | Combinator::PseudoElement
+                | Combinator::SlotAssignment
+                | Combinator::Column => continue,
             };
         }
         result
@@ -2026,9 +2047,10 @@ impl CombinatorComposition {
                 Combinator::NextSibling | Combinator::LaterSibling => {
                     result.insert(Self::SIBLINGS);
                 },
-                Combinator::Part | Combinator::PseudoElement | Combinator::SlotAssignment => {
-                    continue
-                },
+                Combinator::Part
+                | Combinator::PseudoElement
+                | Combinator::SlotAssignment
+                | Combinator::Column => continue,
             };
             if result.is_all() {
                 break;
@@ -2131,6 +2153,13 @@ pub enum Component<Impl: SelectorImpl> {
     ParentSelector,
     Nth(NthSelectorData),
     NthOf(NthOfSelectorData<Impl>),
+    /// The `:nth-col()` and `:nth-last-col()` pseudo-classes.
+    ///
+    /// https://drafts.csswg.org/selectors-4/#the-nth-col-pseudo
+    NthCol {
+        an_plus_b: AnPlusB,
+        from_end: bool,
+    },
     NonTSPseudoClass(#[cfg_attr(feature = "to_shmem", shmem(field_bound))] Impl::NonTSPseudoClass),
     /// The ::slotted() pseudo-element:
     ///
@@ -2572,6 +2601,7 @@ impl Combinator {
             Combinator::Descendant => Ok(()),
             Combinator::NextSibling => dest.write_str("+ "),
             Combinator::LaterSibling => dest.write_str("~ "),
+            Combinator::Column => dest.write_str("|| "),
             Combinator::PseudoElement | Combinator::Part | Combinator::SlotAssignment => unsafe {
                 debug_unreachable!("Already handled")
             },
@@ -2708,6 +2738,18 @@ impl<Impl: SelectorImpl> ToCss for Component<Impl> {
                 serialize_selector_list(nth_of_data.selectors().iter(), dest)?;
                 dest.write_char(')')
             },
+            NthCol {
+                ref an_plus_b,
+                from_end,
+            } => {
+                dest.write_str(if from_end {
+                    ":nth-last-col("
+                } else {
+                    ":nth-col("
+                })?;
+                an_plus_b.to_css(dest)?;
+                dest.write_char(')')
+            },
             Is(ref list) | Where(ref list) | Negation(ref list) => {
                 match *self {
                     Where(..) => dest.write_str(":where(")?,
@@ -2796,7 +2838,7 @@ where
     input.skip_whitespace();
 
     if parse_relative != ParseRelative::No {
-        let combinator = try_parse_combinator(input);
+        let combinator = try_parse_combinator(parser, input, state);
         match parse_relative {
             ParseRelative::ForHas => {
                 builder.push_simple_selector(Component::RelativeSelectorAnchor);
@@ -2842,7 +2884,7 @@ where
             break;
         }
 
-        let combinator = if let Ok(c) = try_parse_combinator(input) {
+        let combinator = if let Ok(c) = try_parse_combinator(parser, input, state) {
             c
         } else {
             break;
@@ -2857,7 +2899,20 @@ where
     return Ok(Selector(builder.build(parse_relative)));
 }
 
-fn try_parse_combinator<'i, 't>(input: &mut CssParser<'i, 't>) -> Result<Combinator, ()> {
+fn try_parse_combinator<'i, 't, P, Impl>(
+    parser: &P,
+    input: &mut CssParser<'i, 't>,
+    state: SelectorParsingState,
+) -> Result<Combinator, ()>
+where
+    P: Parser<'i, Impl = Impl>,
+    Impl: SelectorImpl,
+{
+    // Relative selector matching and invalidation only look at the subtree
+    // and siblings of the anchor, so the column combinator can't be used
+    // inside `:has()`.
+    let allows_column = parser.parse_column_selectors()
+        && !state.intersects(SelectorParsingState::DISALLOW_RELATIVE_SELECTOR);
     let mut any_whitespace = false;
     loop {
         let before_this_token = input.state();
@@ -2873,6 +2928,19 @@ fn try_parse_combinator<'i, 't>(input: &mut CssParser<'i, 't>) -> Result<Combina
             Ok(&Token::Delim('~')) => {
                 return Ok(Combinator::LaterSibling);
             },
+            Ok(&Token::Delim('|')) if allows_column => {
+                // `||` is tokenized as two `|` delimiters, and a single one
+                // starts a namespace prefix, like `|foo`.
+                if let Ok(&Token::Delim('|')) = input.next_including_whitespace() {
+                    return Ok(Combinator::Column);
+                }
+                input.reset(&before_this_token);
+                if any_whitespace {
+                    return Ok(Combinator::Descendant);
+                } else {
+                    return Err(());
+                }
+            },
             Ok(_) => {
                 input.reset(&before_this_token);
                 if any_whitespace {
@@ -3471,6 +3539,8 @@ where
         "nth-of-type" => return parse_nth_pseudo_class(parser, input, state, NthType::OfType),
         "nth-last-child" => return parse_nth_pseudo_class(parser, input, state, NthType::LastChild),
         "nth-last-of-type" => return parse_nth_pseudo_class(parser, input, state, NthType::LastOfType),
+        "nth-col" if parser.parse_column_selectors() => return parse_nth_col_pseudo_class(input, state, false),
+        "nth-last-col" if parser.parse_column_selectors() => return parse_nth_col_pseudo_class(input, state, true),
         "is" if parser.parse_is_and_where() => return parse_is_where(parser, input, state, Component::Is),
         "where" if parser.parse_is_and_where() => return parse_is_where(parser, input, state, Component::Where),
         "has" if parser.parse_has() => return parse_has(parser, input, state),
@@ -3545,6 +3615,25 @@ where
     )))
 }
 
+fn parse_nth_col_pseudo_class<'i, 't, E, Impl>(
+    input: &mut CssParser<'i, 't>,
+    state: SelectorParsingState,
+    from_end: bool,
+) -> Result<Component<Impl>, ParseError<'i, E>>
+where
+    E: From<SelectorParseErrorKind<'i>>,
+    Impl: SelectorImpl,
+{
+    if !state.allows_tree_structural_pseudo_classes() {
+        return Err(input.new_custom_error(SelectorParseErrorKind::InvalidState));
+    }
+    let (a, b) = parse_nth(input)?;
+    Ok(Component::NthCol {
+        an_plus_b: AnPlusB(a, b),
+        from_end,
+    })
+}
+
 /// Returns whether the name corresponds to a CSS2 pseudo-element that
 /// can be specified with the single colon syntax (in addition to the
 /// double-colon syntax, which can be used for all pseudo-elements).
@@ -3974,6 +4063,10 @@ pub mod tests {
             true
         }
 
+        fn parse_column_selectors(&self) -> bool {
+            true
+        }
+
         fn parse_non_ts_pseudo_class(
             &self,
             location: SourceLocation,
@@ -4626,6 +4719,48 @@ pub mod tests {
         assert!(parse("foo:where(::before)").is_ok());
     }
 
+    #[test]
+    fn column_selectors() {
+        assert_eq!(
+            parse_expected("col.selected||td", Some("col.selected || td")),
+            Ok(SelectorList::from_vec(vec![Selector::from_vec(
+                vec![
+                    Component::LocalName(LocalName {
+                        name: DummyAtom::from("col"),
+                        lower_name: DummyAtom::from("col"),
+                    }),
+                    Component::Class(DummyAtom::from("selected")),
+                    Component::Combinator(Combinator::Column),
+                    Component::LocalName(LocalName {
+                        name: DummyAtom::from("td"),
+                        lower_name: DummyAtom::from("td"),
+                    }),
+                ],
+                specificity(0, 1, 2),
+                SelectorFlags::empty(),
+            )]))
+        );
+        assert!(parse_expected("col || td", Some("col || td")).is_ok());
+        assert!(parse_expected("table |td", Some("table |td")).is_ok());
+        assert!(parse("col | | td").is_err());
+        assert!(parse(":has(|| td)").is_err());
+        assert!(parse(":has(col || td)").is_err());
+
+        assert_eq!(
+            parse(":nth-col(2n+1)"),
+            Ok(SelectorList::from_vec(vec![Selector::from_vec(
+                vec![Component::NthCol {
+                    an_plus_b: AnPlusB(2, 1),
+                    from_end: false,
+                }],
+                specificity(0, 1, 0),
+                SelectorFlags::empty(),
+            )]))
+        );
+        assert!(parse_expected(":nth-last-col( -n + 3 )", Some(":nth-last-col(-n+3)")).is_ok());
+        assert!(parse(":nth-col(2n+1 of td)").is_err());
+    }
+
     #[test]
     fn parent_selector() {
         assert!(parse("foo &").is_ok());
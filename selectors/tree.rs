@@ -43,6 +43,17 @@ impl OpaqueElement {
     }
 }
 
+/// The columns spanned by a table cell.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CellColumns {
+    /// The zero-based index of the first column spanned by the cell.
+    pub start: u32,
+    /// The number of columns spanned by the cell.
+    pub span: u32,
+    /// The number of columns of the table.
+    pub count: u32,
+}
+
 pub trait Element: Sized + Clone + Debug {
     type Impl: SelectorImpl;
 
@@ -178,6 +189,21 @@ pub trait Element: Sized + Clone + Debug {
         false
     }
 
+    /// Returns the columns this element spans, if it's a cell of a table or
+    /// of another grid-structured host language construct.
+    ///
+    /// This is used for the column combinator and the `:nth-col()` and
+    /// `:nth-last-col()` pseudo-classes.
+    fn table_cell_columns(&self) -> Option<CellColumns> {
+        None
+    }
+
+    /// Returns the column element for the given zero-based column of the
+    /// table this cell belongs to, if any.
+    fn column_element(&self, _column: u32) -> Option<Self> {
+        None
+    }
+
     /// Add hashes unique to this element to the given filter, returning true
     /// if any got added.
     fn add_element_unique_hashes(&self, filter: &mut BloomFilter) -> bool;
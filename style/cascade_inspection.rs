@@ -0,0 +1,328 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Inspection of the cascade of an element, for developer tools.
+//!
+//! This exposes the rules that took part in the cascade of a style, in
+//! cascade order, along with the outcome of the cascade for each of their
+//! declarations.
+
+use crate::applicable_declarations::{ApplicableDeclarationList, CascadePriority};
+use crate::dom::{AttributeTracker, TElement, TShadowRoot};
+use crate::properties::{
+    CSSWideKeyword, ComputedValues, CustomDeclarationValue, Importance, OwnedPropertyDeclarationId,
+    PropertyDeclaration, PropertyDeclarationId, ShorthandsWithPropertyReferencesCache,
+};
+use crate::rule_cache::RuleCacheConditions;
+use crate::rule_tree::{CascadeLevel, ShadowCascadeOrder, StyleSource};
+use crate::selector_parser::PseudoElement;
+use crate::shared_lock::StylesheetGuards;
+use crate::stylesheets::layer_rule::{LayerName, LayerOrder};
+use crate::stylesheets::Origin;
+use crate::stylist::Stylist;
+use crate::values::computed;
+use cssparser::SourceLocation;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::borrow::Cow;
+
+/// The outcome of the cascade for a declaration.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeclarationStatus {
+    /// The declaration won the cascade, and determines the specified value of
+    /// its property.
+    Applied,
+    /// A declaration with higher precedence won the cascade, or rolled it back
+    /// past this one.
+    Overridden,
+    /// The declaration is `revert` or `revert-layer`, so the value of its
+    /// property comes from a declaration of a previous origin or layer.
+    Reverted,
+    /// The declaration won the cascade, but substituting its `var()`
+    /// references failed, so its longhand is invalid at computed-value time.
+    ///
+    /// https://drafts.csswg.org/css-variables/#invalid-at-computed-value-time
+    InvalidAtComputedValueTime,
+    /// The property of the declaration doesn't apply to the pseudo-element,
+    /// like most properties on `::first-letter` or `::marker`.
+    Ignored,
+}
+
+/// A declaration of a matched rule, with the outcome of the cascade for it.
+#[derive(Clone, Debug)]
+pub struct InspectedDeclaration {
+    /// The index of the declaration in its declaration block.
+    pub index: usize,
+    /// The property of the declaration.
+    pub id: OwnedPropertyDeclarationId,
+    /// Whether the declaration is `!important`.
+    pub importance: Importance,
+    /// The outcome of the cascade for the declaration.
+    pub status: DeclarationStatus,
+}
+
+/// A rule that took part in the cascade of a style.
+///
+/// Besides style rules, this includes the other declaration blocks in the
+/// cascade, like the style attribute, presentational hints or animations.
+#[derive(Clone, Debug)]
+pub struct MatchedRule {
+    /// The declaration block of the rule.
+    pub source: StyleSource,
+    /// The cascade level of the normal declarations of the rule. Its
+    /// `!important` declarations cascade at `cascade_level.important()`.
+    pub cascade_level: CascadeLevel,
+    /// The order of the cascade layer of the rule.
+    pub layer_order: LayerOrder,
+    /// The name of the cascade layer of the rule, if it's in a layer of the
+    /// tree of the element. Anonymous layers get a synthesized name.
+    pub layer_name: Option<LayerName>,
+    /// The specificity of the most specific selector of the rule that
+    /// matched, or `None` if the declarations don't come from selector
+    /// matching, like animations.
+    pub specificity: Option<u32>,
+    /// The proximity of the element to the `@scope` root, if the rule is
+    /// scoped.
+    pub scope_proximity: Option<u16>,
+    /// The location of the rule in its style sheet, if it's a style rule of
+    /// the document or of a shadow tree.
+    pub source_location: Option<SourceLocation>,
+    /// The declarations of the rule, in source order.
+    pub declarations: Vec<InspectedDeclaration>,
+}
+
+impl MatchedRule {
+    /// The origin of the rule.
+    #[inline]
+    pub fn origin(&self) -> Origin {
+        self.cascade_level.origin()
+    }
+
+    /// Returns the declaration of the rule that won the cascade for the given
+    /// property, if any.
+    pub fn applied_declaration(
+        &self,
+        id: &OwnedPropertyDeclarationId,
+    ) -> Option<&InspectedDeclaration> {
+        self.declarations
+            .iter()
+            .find(|d| d.status == DeclarationStatus::Applied && d.id == *id)
+    }
+}
+
+/// Returns the name of the cascade layer of a rule with the given priority, if
+/// the cascade data of the rule is known.
+fn layer_name_for<E>(stylist: &Stylist, element: E, priority: CascadePriority) -> Option<LayerName>
+where
+    E: TElement,
+{
+    let order = priority.layer_order();
+    match priority.cascade_level() {
+        level @ (CascadeLevel::UANormal | CascadeLevel::UserNormal) => stylist
+            .cascade_data()
+            .borrow_for_origin(level.origin())
+            .layer_name(order)
+            .cloned(),
+        CascadeLevel::AuthorNormal {
+            shadow_cascade_order,
+        } if shadow_cascade_order == ShadowCascadeOrder::for_same_tree() => {
+            match element.containing_shadow() {
+                Some(shadow) => shadow.style_data()?.layer_name(order).cloned(),
+                None => stylist
+                    .cascade_data()
+                    .borrow_for_origin(Origin::Author)
+                    .layer_name(order)
+                    .cloned(),
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Builds the matched rules of `style` out of its rule node path, and runs the
+/// cascade over their declarations.
+///
+/// `matched` are the declarations the element matches, which provide the
+/// specificity and scope proximity the rule tree doesn't keep around.
+pub(crate) fn inspect_cascade<E>(
+    stylist: &Stylist,
+    element: E,
+    pseudo: Option<&PseudoElement>,
+    style: &ComputedValues,
+    guards: &StylesheetGuards,
+    matched: &ApplicableDeclarationList,
+) -> Vec<MatchedRule>
+where
+    E: TElement,
+{
+    // The cascade data of every tree whose rules may apply to the element.
+    let mut cascade_data = vec![];
+    stylist.for_each_cascade_data_with_scope(element, |data, _| cascade_data.push(data));
+
+    // Rule nodes from the most to the least important one, along with the
+    // index of their rule.
+    let nodes: Vec<_> = style.rules().self_and_ancestors().collect();
+    let mut node_rules = vec![None; nodes.len()];
+    let mut rules: Vec<MatchedRule> = vec![];
+    for (i, node) in nodes.iter().enumerate().rev() {
+        let Some(source) = node.style_source() else {
+            continue;
+        };
+        let priority = node.cascade_priority();
+        let level = priority.cascade_level();
+        let existing = rules.iter().rposition(|rule| {
+            rule.source == *source
+                && rule.cascade_level == level.unimportant()
+                && rule.layer_order == priority.layer_order()
+        });
+        if level.is_important() {
+            // Important declarations are inserted again in the rule tree, after
+            // all the normal ones.
+            node_rules[i] = existing;
+            continue;
+        }
+        if let Some(index) = existing {
+            // A rule is inserted once for each of its selectors that match, so
+            // move it to the position of the most specific one.
+            let rule = rules.remove(index);
+            rules.push(rule);
+            for other in node_rules[i..].iter_mut().flatten() {
+                if *other == index {
+                    *other = rules.len() - 1;
+                } else if *other > index {
+                    *other -= 1;
+                }
+            }
+            node_rules[i] = Some(rules.len() - 1);
+            continue;
+        }
+
+        let block = source.read(level.guard(guards));
+        let declarations = block
+            .declaration_importance_iter()
+            .enumerate()
+            .map(|(index, (declaration, importance))| InspectedDeclaration {
+                index,
+                id: declaration.id().to_owned(),
+                importance,
+                status: DeclarationStatus::Overridden,
+            })
+            .collect();
+        // A rule matches once for each of its selectors that match, so take
+        // the most specific of them.
+        let matched_block = matched
+            .iter()
+            .filter(|block| block.source == *source && block.cascade_priority == priority)
+            .max_by_key(|block| block.specificity);
+        let source_location = matched_block.and_then(|block| {
+            cascade_data
+                .iter()
+                .find_map(|data| data.style_rule_location(source, block.source_order()))
+        });
+        let layer_name = layer_name_for(stylist, element, priority);
+        node_rules[i] = Some(rules.len());
+        rules.push(MatchedRule {
+            source: source.clone(),
+            cascade_level: level,
+            layer_order: priority.layer_order(),
+            layer_name,
+            specificity: matched_block.map(|block| block.specificity),
+            scope_proximity: matched_block.and_then(|block| block.scope_proximity.get()),
+            source_location,
+            declarations,
+        });
+    }
+
+    // Now run the cascade, visiting declarations from the most to the least
+    // important one, as `properties::cascade` does.
+    let restriction = pseudo.and_then(|p| p.property_restriction());
+    let mut seen = FxHashSet::<OwnedPropertyDeclarationId>::default();
+    let mut reverted = FxHashMap::<OwnedPropertyDeclarationId, (CascadePriority, bool)>::default();
+    let mut rule_cache_conditions = RuleCacheConditions::default();
+    // Variables are substituted with the computed custom properties of the
+    // style, so a context without an inherited style is enough.
+    let context =
+        computed::Context::new_for_initial_at_property_value(stylist, &mut rule_cache_conditions);
+    let mut shorthand_cache = ShorthandsWithPropertyReferencesCache::default();
+    let mut attribute_tracker = AttributeTracker::new(&element);
+    for (node, node_rule) in nodes.iter().zip(node_rules.iter()) {
+        let (Some(source), Some(rule_index)) = (node.style_source(), *node_rule) else {
+            continue;
+        };
+        let priority = node.cascade_priority();
+        let level = priority.cascade_level();
+        let origin = level.origin();
+        let importance = level.importance();
+        let block = source.read(level.guard(guards));
+        let declarations: Vec<_> = block.declaration_importance_iter().collect();
+        for (index, &(declaration, declaration_importance)) in declarations.iter().enumerate().rev()
+        {
+            if declaration_importance != importance {
+                continue;
+            }
+            let status = &mut rules[rule_index].declarations[index].status;
+            let longhand = match declaration.id() {
+                PropertyDeclarationId::Longhand(longhand) => {
+                    if let Some(restriction) = restriction {
+                        if !longhand.flags().contains(restriction) && origin != Origin::UserAgent {
+                            *status = DeclarationStatus::Ignored;
+                            continue;
+                        }
+                    }
+                    Some(longhand)
+                },
+                PropertyDeclarationId::Custom(..) => None,
+            };
+
+            let id = declaration.id().to_physical(style.writing_mode).to_owned();
+            if seen.contains(&id) {
+                continue;
+            }
+            if let Some(&(reverted_priority, origin_revert)) = reverted.get(&id) {
+                if !reverted_priority.allows_when_reverted(&priority, origin_revert) {
+                    continue;
+                }
+            }
+
+            let declaration = match (declaration, longhand) {
+                (PropertyDeclaration::WithVariables(ref unparsed), Some(longhand)) => {
+                    match unparsed.value.try_substitute_variables(
+                        longhand,
+                        style.custom_properties(),
+                        stylist,
+                        &context,
+                        &mut shorthand_cache,
+                        &mut attribute_tracker,
+                    ) {
+                        Ok(declaration) => declaration,
+                        Err(()) => {
+                            *status = DeclarationStatus::InvalidAtComputedValueTime;
+                            seen.insert(id);
+                            continue;
+                        },
+                    }
+                },
+                (declaration, _) => Cow::Borrowed(declaration),
+            };
+            let keyword = match *declaration {
+                PropertyDeclaration::Custom(ref custom) => match custom.value {
+                    CustomDeclarationValue::CSSWideKeyword(keyword) => Some(keyword),
+                    _ => None,
+                },
+                ref declaration => declaration.get_css_wide_keyword(),
+            };
+            match keyword {
+                Some(keyword @ (CSSWideKeyword::Revert | CSSWideKeyword::RevertLayer)) => {
+                    *status = DeclarationStatus::Reverted;
+                    reverted.insert(id, (priority, keyword == CSSWideKeyword::Revert));
+                },
+                _ => {
+                    *status = DeclarationStatus::Applied;
+                    seen.insert(id);
+                },
+            }
+        }
+    }
+
+    rules
+}
@@ -12,15 +12,15 @@ use super::{
 };
 use crate::derives::*;
 use crate::{
-    parser::ParserContext,
+    parser::{FromTypedError, ParserContext},
     values::{
         animated::ToAnimatedValue,
         generics::calc::{CalcUnits, GenericCalcNode},
-        specified::calc::{AllowParse, Leaf},
+        specified::calc::{AllowParse, CalcNode, Leaf},
     },
 };
 use cssparser::{color::OPAQUE, Parser, Token};
-use style_traits::{ParseError, ToCss};
+use style_traits::{NumericValue, NumericValueOrNone, ParseError, ToCss};
 
 /// A single color component.
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, ToShmem)]
@@ -108,6 +108,28 @@ impl<ValueType: ColorComponentType> ColorComponent<ValueType> {
         }
     }
 
+    /// Builds a single [ColorComponent] out of a Typed OM color channel.
+    pub fn from_typed(
+        context: &ParserContext,
+        value: &NumericValueOrNone,
+    ) -> Result<Self, FromTypedError> {
+        let value = match *value {
+            NumericValueOrNone::None => return Ok(ColorComponent::None),
+            NumericValueOrNone::Numeric(NumericValue::Unit { value, .. }) if !value.is_finite() => {
+                return Err(FromTypedError::TypeMismatch)
+            },
+            NumericValueOrNone::Numeric(ref value) => value,
+        };
+        let mut node = CalcNode::from_numeric(context, value, ValueType::units())?;
+        if let GenericCalcNode::Leaf(ref leaf) = node {
+            return ValueType::try_from_leaf(leaf)
+                .map(Self::Value)
+                .map_err(|()| FromTypedError::TypeMismatch);
+        }
+        node.simplify_and_sort();
+        Ok(Self::Calc(Box::new(node)))
+    }
+
     /// Resolve a [ColorComponent] into a float.  None is "none".
     pub fn resolve(&self, origin_color: Option<&AbsoluteColor>) -> Result<Option<ValueType>, ()> {
         Ok(match self {
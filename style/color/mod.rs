@@ -18,6 +18,10 @@ use crate::derives::*;
 pub use color_function::*;
 use component::ColorComponent;
 use cssparser::color::PredefinedColorSpace;
+use style_traits::{
+    ColorValue, ColorValueFunction, CssString, NumericValue, NumericValueOrNone, ToCss, ToTyped,
+    TypedValue,
+};
 
 /// Number of color-mix items to reserve on the stack to avoid heap allocations.
 pub const PRE_ALLOCATED_COLOR_MIX_ITEMS: usize = 3;
@@ -202,7 +206,7 @@ bitflags! {
 
 /// An absolutely specified color, using either rgb(), rgba(), lab(), lch(),
 /// oklab(), oklch() or color().
-#[derive(Copy, Clone, Debug, MallocSizeOf, PartialEq, ToShmem, ToTyped)]
+#[derive(Copy, Clone, Debug, MallocSizeOf, PartialEq, ToShmem)]
 #[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
 #[repr(C)]
 pub struct AbsoluteColor {
@@ -640,6 +644,81 @@ impl AbsoluteColor {
     }
 }
 
+impl ToTyped for AbsoluteColor {
+    /// Reifies the color into the `CSSColorValue` subclass of the color
+    /// function it serializes as.
+    fn to_typed(&self) -> Option<TypedValue> {
+        fn channel(value: Option<f32>, f: fn(f32) -> NumericValue) -> NumericValueOrNone {
+            match value {
+                Some(value) => NumericValueOrNone::Numeric(f(value)),
+                None => NumericValueOrNone::None,
+            }
+        }
+
+        let (function, color_space, channels) = match self.color_space {
+            ColorSpace::Srgb if self.flags.contains(ColorFlags::IS_LEGACY_SRGB) => (
+                ColorValueFunction::Rgb,
+                CssString::new(),
+                [
+                    channel(self.c0(), NumericValue::percentage),
+                    channel(self.c1(), NumericValue::percentage),
+                    channel(self.c2(), NumericValue::percentage),
+                ],
+            ),
+            // Serialized as rgb() or color(srgb ..), just like in to_css().
+            ColorSpace::Hsl | ColorSpace::Hwb => {
+                return if self.flags.contains(ColorFlags::IS_LEGACY_SRGB) {
+                    self.into_srgb_legacy().to_typed()
+                } else {
+                    self.to_color_space(ColorSpace::Srgb).to_typed()
+                };
+            },
+            ColorSpace::Lab | ColorSpace::Oklab => (
+                if self.color_space == ColorSpace::Lab {
+                    ColorValueFunction::Lab
+                } else {
+                    ColorValueFunction::Oklab
+                },
+                CssString::new(),
+                [
+                    channel(self.c0(), NumericValue::number),
+                    channel(self.c1(), NumericValue::number),
+                    channel(self.c2(), NumericValue::number),
+                ],
+            ),
+            ColorSpace::Lch | ColorSpace::Oklch => (
+                if self.color_space == ColorSpace::Lch {
+                    ColorValueFunction::Lch
+                } else {
+                    ColorValueFunction::Oklch
+                },
+                CssString::new(),
+                [
+                    channel(self.c0(), NumericValue::number),
+                    channel(self.c1(), NumericValue::number),
+                    channel(self.c2(), NumericValue::degrees),
+                ],
+            ),
+            _ => (
+                ColorValueFunction::Color,
+                self.color_space.to_css_cssstring(),
+                [
+                    channel(self.c0(), NumericValue::number),
+                    channel(self.c1(), NumericValue::number),
+                    channel(self.c2(), NumericValue::number),
+                ],
+            ),
+        };
+
+        Some(TypedValue::Color(ColorValue {
+            function,
+            color_space,
+            channels,
+            alpha: channel(self.alpha(), NumericValue::number),
+        }))
+    }
+}
+
 impl From<PredefinedColorSpace> for ColorSpace {
     fn from(value: PredefinedColorSpace) -> Self {
         match value {
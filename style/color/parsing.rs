@@ -13,7 +13,7 @@ use super::{
 };
 use crate::derives::*;
 use crate::{
-    parser::{Parse, ParserContext},
+    parser::{parse_typed_keyword, FromTypedError, Parse, ParserContext},
     values::{
         generics::{calc::CalcUnits, Optional},
         specified::{angle::Angle as SpecifiedAngle, calc::Leaf, color::Color as SpecifiedColor},
@@ -23,7 +23,7 @@ use cssparser::{
     color::{parse_hash_color, PredefinedColorSpace, OPAQUE},
     match_ignore_ascii_case, CowRcStr, Parser, Token,
 };
-use style_traits::{ParseError, StyleParseErrorKind};
+use style_traits::{ColorValue, ColorValueFunction, ParseError, StyleParseErrorKind};
 
 /// Returns true if the relative color syntax pref is enabled.
 #[inline]
@@ -120,6 +120,79 @@ pub fn parse_color_with<'i, 't>(
     .map_err(|()| location.new_unexpected_token_error(token.clone()))
 }
 
+/// Builds the color function a Typed OM color value stands for.
+pub fn color_function_from_typed(
+    context: &ParserContext,
+    value: &ColorValue,
+) -> Result<ColorFunction<SpecifiedColor>, FromTypedError> {
+    let [ref c0, ref c1, ref c2] = value.channels;
+    let alpha = ColorComponent::from_typed(context, &value.alpha)?;
+    Ok(match value.function {
+        ColorValueFunction::Rgb => ColorFunction::Rgb(
+            Optional::None,
+            ColorComponent::from_typed(context, c0)?,
+            ColorComponent::from_typed(context, c1)?,
+            ColorComponent::from_typed(context, c2)?,
+            alpha,
+        ),
+        ColorValueFunction::Hsl => ColorFunction::Hsl(
+            Optional::None,
+            ColorComponent::from_typed(context, c0)?,
+            ColorComponent::from_typed(context, c1)?,
+            ColorComponent::from_typed(context, c2)?,
+            alpha,
+        ),
+        ColorValueFunction::Hwb => ColorFunction::Hwb(
+            Optional::None,
+            ColorComponent::from_typed(context, c0)?,
+            ColorComponent::from_typed(context, c1)?,
+            ColorComponent::from_typed(context, c2)?,
+            alpha,
+        ),
+        ColorValueFunction::Lab => ColorFunction::Lab(
+            Optional::None,
+            ColorComponent::from_typed(context, c0)?,
+            ColorComponent::from_typed(context, c1)?,
+            ColorComponent::from_typed(context, c2)?,
+            alpha,
+        ),
+        ColorValueFunction::Lch => ColorFunction::Lch(
+            Optional::None,
+            ColorComponent::from_typed(context, c0)?,
+            ColorComponent::from_typed(context, c1)?,
+            ColorComponent::from_typed(context, c2)?,
+            alpha,
+        ),
+        ColorValueFunction::Oklab => ColorFunction::Oklab(
+            Optional::None,
+            ColorComponent::from_typed(context, c0)?,
+            ColorComponent::from_typed(context, c1)?,
+            ColorComponent::from_typed(context, c2)?,
+            alpha,
+        ),
+        ColorValueFunction::Oklch => ColorFunction::Oklch(
+            Optional::None,
+            ColorComponent::from_typed(context, c0)?,
+            ColorComponent::from_typed(context, c1)?,
+            ColorComponent::from_typed(context, c2)?,
+            alpha,
+        ),
+        ColorValueFunction::Color => {
+            let color_space = parse_typed_keyword(&value.color_space, |input| {
+                PredefinedColorSpace::parse(input).map_err(Into::into)
+            })?;
+            ColorFunction::Color(
+                Optional::None,
+                ColorComponent::from_typed(context, c0)?,
+                ColorComponent::from_typed(context, c1)?,
+                ColorComponent::from_typed(context, c2)?,
+                alpha,
+                color_space.into(),
+            )
+        },
+    })
+}
+
 /// Parse one of the color functions: rgba(), lab(), color(), etc.
 #[inline]
 fn parse_color_function<'i, 't>(
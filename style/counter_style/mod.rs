@@ -29,6 +29,10 @@ use style_traits::{
     SpecifiedValueInfo, StyleParseErrorKind, ToCss,
 };
 
+mod representation;
+
+pub use self::representation::ResolvedCounterStyle;
+
 /// https://drafts.csswg.org/css-counter-styles/#typedef-symbols-type
 #[allow(missing_docs)]
 #[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
@@ -179,7 +183,7 @@ impl SpecifiedValueInfo for CounterStyle {
         // But that's a non-trivial change itself, so we use a simpler
         // approach here.
         macro_rules! predefined {
-            ($($name:expr,)+) => {
+            ($($name:expr => $definition:expr,)+) => {
                 f(&["symbols", "none", $($name,)+])
             }
         }
@@ -199,7 +203,7 @@ fn counter_style_name_from_ident<'i>(
     location: SourceLocation,
 ) -> Result<CustomIdent, ParseError<'i>> {
     macro_rules! predefined {
-        ($($name: tt,)+) => {{
+        ($($name: tt => $definition:expr,)+) => {{
             ascii_case_insensitive_phf_map! {
                 predefined -> Atom = {
                     $(
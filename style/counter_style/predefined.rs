@@ -2,60 +2,63 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
+// The names come from update_predefined.py, and the definitions are evaluated
+// by `representation::predefined`.
 predefined! {
-    "decimal",
-    "decimal-leading-zero",
-    "arabic-indic",
-    "armenian",
-    "upper-armenian",
-    "lower-armenian",
-    "bengali",
-    "cambodian",
-    "khmer",
-    "cjk-decimal",
-    "devanagari",
-    "georgian",
-    "gujarati",
-    "gurmukhi",
-    "hebrew",
-    "kannada",
-    "lao",
-    "malayalam",
-    "mongolian",
-    "myanmar",
-    "oriya",
-    "persian",
-    "lower-roman",
-    "upper-roman",
-    "tamil",
-    "telugu",
-    "thai",
-    "tibetan",
-    "lower-alpha",
-    "lower-latin",
-    "upper-alpha",
-    "upper-latin",
-    "cjk-earthly-branch",
-    "cjk-heavenly-stem",
-    "lower-greek",
-    "hiragana",
-    "hiragana-iroha",
-    "katakana",
-    "katakana-iroha",
-    "disc",
-    "circle",
-    "square",
-    "disclosure-open",
-    "disclosure-closed",
-    "japanese-informal",
-    "japanese-formal",
-    "korean-hangul-formal",
-    "korean-hanja-informal",
-    "korean-hanja-formal",
-    "simp-chinese-informal",
-    "simp-chinese-formal",
-    "trad-chinese-informal",
-    "trad-chinese-formal",
-    "cjk-ideographic",
-    "ethiopic-numeric",
+    "decimal" => decimal(),
+    "decimal-leading-zero" => decimal().with_pad(2, "0"),
+    "arabic-indic" => numeric(&ARABIC_INDIC),
+    "armenian" => additive(&UPPER_ARMENIAN, &ARMENIAN_RANGE),
+    "upper-armenian" => additive(&UPPER_ARMENIAN, &ARMENIAN_RANGE),
+    "lower-armenian" => additive(&LOWER_ARMENIAN, &ARMENIAN_RANGE),
+    "bengali" => numeric(&BENGALI),
+    "cambodian" => numeric(&KHMER),
+    "khmer" => numeric(&KHMER),
+    "cjk-decimal" => numeric(&CJK_DECIMAL).with_suffix("、"),
+    "devanagari" => numeric(&DEVANAGARI),
+    "georgian" => additive(&GEORGIAN, &GEORGIAN_RANGE),
+    "gujarati" => numeric(&GUJARATI),
+    "gurmukhi" => numeric(&GURMUKHI),
+    "hebrew" => additive(&HEBREW, &HEBREW_RANGE),
+    "kannada" => numeric(&KANNADA),
+    "lao" => numeric(&LAO),
+    "malayalam" => numeric(&MALAYALAM),
+    "mongolian" => numeric(&MONGOLIAN),
+    "myanmar" => numeric(&MYANMAR),
+    "oriya" => numeric(&ORIYA),
+    "persian" => numeric(&PERSIAN),
+    "lower-roman" => additive(&LOWER_ROMAN, &ROMAN_RANGE),
+    "upper-roman" => additive(&UPPER_ROMAN, &ROMAN_RANGE),
+    "tamil" => numeric(&TAMIL),
+    "telugu" => numeric(&TELUGU),
+    "thai" => numeric(&THAI),
+    "tibetan" => numeric(&TIBETAN),
+    "lower-alpha" => alphabetic(&LOWER_LATIN),
+    "lower-latin" => alphabetic(&LOWER_LATIN),
+    "upper-alpha" => alphabetic(&UPPER_LATIN),
+    "upper-latin" => alphabetic(&UPPER_LATIN),
+    "cjk-earthly-branch" => alphabetic(&CJK_EARTHLY_BRANCH).with_suffix("、"),
+    "cjk-heavenly-stem" => alphabetic(&CJK_HEAVENLY_STEM).with_suffix("、"),
+    "lower-greek" => alphabetic(&LOWER_GREEK),
+    "hiragana" => alphabetic(&HIRAGANA).with_suffix("、"),
+    "hiragana-iroha" => alphabetic(&HIRAGANA_IROHA).with_suffix("、"),
+    "katakana" => alphabetic(&KATAKANA).with_suffix("、"),
+    "katakana-iroha" => alphabetic(&KATAKANA_IROHA).with_suffix("、"),
+    "disc" => cyclic(&["•"]),
+    "circle" => cyclic(&["◦"]),
+    "square" => cyclic(&["▪"]),
+    "disclosure-open" => cyclic(&["▾"]),
+    // TODO: This should be ◂ in right-to-left text.
+    "disclosure-closed" => cyclic(&["▸"]),
+    "japanese-informal" => cjk(&JAPANESE_INFORMAL, "マイナス"),
+    "japanese-formal" => cjk(&JAPANESE_FORMAL, "マイナス"),
+    "korean-hangul-formal" => cjk(&KOREAN_HANGUL_FORMAL, "마이너스 ").with_suffix(", "),
+    "korean-hanja-informal" => cjk(&KOREAN_HANJA_INFORMAL, "마이너스 ").with_suffix(", "),
+    "korean-hanja-formal" => cjk(&KOREAN_HANJA_FORMAL, "마이너스 ").with_suffix(", "),
+    "simp-chinese-informal" => cjk(&CHINESE_INFORMAL, "负"),
+    "simp-chinese-formal" => cjk(&SIMP_CHINESE_FORMAL, "负"),
+    "trad-chinese-informal" => cjk(&CHINESE_INFORMAL, "負"),
+    "trad-chinese-formal" => cjk(&TRAD_CHINESE_FORMAL, "負"),
+    "cjk-ideographic" => cjk(&CHINESE_INFORMAL, "負"),
+    "ethiopic-numeric" => Definition::new(Ethiopic).with_suffix("/ "),
 }
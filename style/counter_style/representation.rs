@@ -0,0 +1,1012 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Generating the representation of a counter value in a given counter style.
+//!
+//! <https://drafts.csswg.org/css-counter-styles/#generate-a-counter>
+
+use super::{
+    AdditiveTuple, CounterBound, CounterRange, CounterStyle, CounterStyleRuleData, Symbol,
+    SymbolsType, System,
+};
+use crate::Atom;
+use smallvec::SmallVec;
+use std::borrow::Cow;
+
+/// The maximum number of symbols that padding, or the repetition of symbols
+/// in the symbolic and additive systems, can add to a representation.
+///
+/// The spec allows user agents to limit this to avoid generating huge strings,
+/// this matches the limit Gecko uses.
+const LENGTH_LIMIT: usize = 150;
+
+/// Returns the text of a symbol.
+fn symbol_text(symbol: &Symbol) -> Cow<'_, str> {
+    match *symbol {
+        Symbol::String(ref s) => Cow::Borrowed(&**s),
+        Symbol::Ident(ref ident) => Cow::Owned(ident.0.to_string()),
+    }
+}
+
+/// The symbols of a counter style, either from an `@counter-style` rule or
+/// `symbols()`, or from a predefined counter style.
+#[derive(Clone, Copy, Debug)]
+enum SymbolList<'a> {
+    Rule(&'a [Symbol]),
+    Static(&'static [&'static str]),
+}
+
+impl<'a> SymbolList<'a> {
+    fn len(&self) -> usize {
+        match *self {
+            SymbolList::Rule(symbols) => symbols.len(),
+            SymbolList::Static(symbols) => symbols.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> Cow<'a, str> {
+        match *self {
+            SymbolList::Rule(symbols) => symbol_text(&symbols[index]),
+            SymbolList::Static(symbols) => Cow::Borrowed(symbols[index]),
+        }
+    }
+}
+
+/// The additive symbols of a counter style, sorted by descending weight.
+#[derive(Clone, Copy, Debug)]
+enum AdditiveSymbolList<'a> {
+    Rule(&'a [AdditiveTuple]),
+    Static(&'static [(u32, &'static str)]),
+}
+
+impl<'a> AdditiveSymbolList<'a> {
+    fn len(&self) -> usize {
+        match *self {
+            AdditiveSymbolList::Rule(tuples) => tuples.len(),
+            AdditiveSymbolList::Static(tuples) => tuples.len(),
+        }
+    }
+
+    fn get(&self, index: usize) -> (u32, Cow<'a, str>) {
+        match *self {
+            AdditiveSymbolList::Rule(tuples) => {
+                let tuple = &tuples[index];
+                (tuple.weight.value() as u32, symbol_text(&tuple.symbol))
+            },
+            AdditiveSymbolList::Static(tuples) => {
+                let (weight, symbol) = tuples[index];
+                (weight, Cow::Borrowed(symbol))
+            },
+        }
+    }
+}
+
+/// The range of a counter style.
+#[derive(Clone, Copy, Debug)]
+enum Ranges<'a> {
+    /// `auto`, which depends on the algorithm of the counter style.
+    Auto,
+    Rule(&'a [CounterRange]),
+    Static(&'static [(i32, i32)]),
+}
+
+/// How the digit one is written before a digit marker in the CJK systems.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CjkOne {
+    /// The digit is always written.
+    Kept,
+    /// The digit is dropped before any digit marker.
+    Dropped,
+    /// The digit is dropped for values from ten to nineteen.
+    DroppedInTeens,
+}
+
+/// The parameters of the longhand East Asian counter styles.
+///
+/// <https://drafts.csswg.org/css-counter-styles/#limited-chinese>
+#[derive(Debug)]
+struct CjkSystem {
+    digits: [&'static str; 10],
+    /// The markers for the tens, hundreds and thousands.
+    markers: [&'static str; 3],
+    one: CjkOne,
+    /// Whether zero digits are written (collapsed into a single one, as in
+    /// Chinese), or just dropped (as in Japanese and Korean).
+    writes_zeros: bool,
+}
+
+/// The algorithm used to generate the initial representation of a counter
+/// value.
+#[derive(Clone, Debug)]
+enum Algorithm<'a> {
+    Cyclic(SymbolList<'a>),
+    Fixed {
+        first_symbol_value: i32,
+        symbols: SymbolList<'a>,
+    },
+    Symbolic(SymbolList<'a>),
+    Alphabetic(SymbolList<'a>),
+    Numeric(SymbolList<'a>),
+    Additive(AdditiveSymbolList<'a>),
+    Cjk(&'static CjkSystem),
+    Ethiopic,
+    /// The same text for every value, for `none` and `<string>` values.
+    Literal(Cow<'a, str>),
+}
+
+impl<'a> Algorithm<'a> {
+    /// <https://drafts.csswg.org/css-counter-styles/#counter-style-negative>
+    fn uses_negative_sign(&self) -> bool {
+        match *self {
+            Algorithm::Symbolic(..)
+            | Algorithm::Alphabetic(..)
+            | Algorithm::Numeric(..)
+            | Algorithm::Additive(..)
+            | Algorithm::Cjk(..)
+            | Algorithm::Ethiopic => true,
+            Algorithm::Cyclic(..) | Algorithm::Fixed { .. } | Algorithm::Literal(..) => false,
+        }
+    }
+
+    /// <https://drafts.csswg.org/css-counter-styles/#descdef-counter-style-range>
+    fn auto_range_contains(&self, value: i32) -> bool {
+        match *self {
+            Algorithm::Symbolic(..) | Algorithm::Alphabetic(..) => value >= 1,
+            Algorithm::Additive(..) => value >= 0,
+            Algorithm::Cjk(..) => (-9999..=9999).contains(&value),
+            Algorithm::Ethiopic => value >= 1,
+            Algorithm::Cyclic(..)
+            | Algorithm::Fixed { .. }
+            | Algorithm::Numeric(..)
+            | Algorithm::Literal(..) => true,
+        }
+    }
+
+    /// Generates the initial representation of a value, or `None` if the
+    /// algorithm can't represent it.
+    ///
+    /// Algorithms that use a negative sign get the absolute value of the
+    /// counter value.
+    fn generate(&self, value: i32) -> Option<String> {
+        let magnitude = value.unsigned_abs();
+        match *self {
+            Algorithm::Cyclic(symbols) => {
+                let index = (value as i64 - 1).rem_euclid(symbols.len() as i64);
+                Some(symbols.get(index as usize).into_owned())
+            },
+            Algorithm::Fixed {
+                first_symbol_value,
+                symbols,
+            } => {
+                let index = value as i64 - first_symbol_value as i64;
+                if index < 0 || index >= symbols.len() as i64 {
+                    return None;
+                }
+                Some(symbols.get(index as usize).into_owned())
+            },
+            Algorithm::Symbolic(symbols) => symbolic(magnitude, symbols),
+            Algorithm::Alphabetic(symbols) => alphabetic(magnitude, symbols),
+            Algorithm::Numeric(symbols) => Some(numeric(magnitude, symbols)),
+            Algorithm::Additive(symbols) => additive(magnitude, symbols),
+            Algorithm::Cjk(system) => cjk(magnitude, system),
+            Algorithm::Ethiopic => ethiopic(magnitude),
+            Algorithm::Literal(ref text) => Some(text.clone().into_owned()),
+        }
+    }
+}
+
+/// <https://drafts.csswg.org/css-counter-styles/#symbolic-system>
+fn symbolic(value: u32, symbols: SymbolList) -> Option<String> {
+    if value == 0 {
+        return None;
+    }
+    let value = value as usize - 1;
+    let repetitions = value / symbols.len() + 1;
+    if repetitions > LENGTH_LIMIT {
+        return None;
+    }
+    Some(symbols.get(value % symbols.len()).repeat(repetitions))
+}
+
+/// <https://drafts.csswg.org/css-counter-styles/#alphabetic-system>
+fn alphabetic(mut value: u32, symbols: SymbolList) -> Option<String> {
+    if value == 0 {
+        return None;
+    }
+    let base = symbols.len() as u32;
+    let mut digits = SmallVec::<[_; 8]>::new();
+    while value != 0 {
+        value -= 1;
+        digits.push(symbols.get((value % base) as usize));
+        value /= base;
+    }
+    Some(digits.into_iter().rev().collect())
+}
+
+/// <https://drafts.csswg.org/css-counter-styles/#numeric-system>
+fn numeric(mut value: u32, symbols: SymbolList) -> String {
+    if value == 0 {
+        return symbols.get(0).into_owned();
+    }
+    let base = symbols.len() as u32;
+    let mut digits = SmallVec::<[_; 10]>::new();
+    while value != 0 {
+        digits.push(symbols.get((value % base) as usize));
+        value /= base;
+    }
+    digits.into_iter().rev().collect()
+}
+
+/// <https://drafts.csswg.org/css-counter-styles/#additive-system>
+fn additive(mut value: u32, symbols: AdditiveSymbolList) -> Option<String> {
+    if value == 0 {
+        return (0..symbols.len())
+            .map(|i| symbols.get(i))
+            .find(|&(weight, _)| weight == 0)
+            .map(|(_, symbol)| symbol.into_owned());
+    }
+    let mut result = String::new();
+    let mut length = 0;
+    for i in 0..symbols.len() {
+        let (weight, symbol) = symbols.get(i);
+        if weight == 0 || weight > value {
+            continue;
+        }
+        let repetitions = (value / weight) as usize;
+        length += repetitions;
+        if length > LENGTH_LIMIT {
+            return None;
+        }
+        result.push_str(&symbol.repeat(repetitions));
+        value %= weight;
+        if value == 0 {
+            return Some(result);
+        }
+    }
+    None
+}
+
+/// <https://drafts.csswg.org/css-counter-styles/#limited-chinese>
+fn cjk(value: u32, system: &CjkSystem) -> Option<String> {
+    if value == 0 {
+        return Some(system.digits[0].to_owned());
+    }
+    if value > 9999 {
+        return None;
+    }
+    let mut result = String::new();
+    let mut pending_zero = false;
+    for position in (0..4).rev() {
+        let digit = (value / 10u32.pow(position)) % 10;
+        if digit == 0 {
+            // Leading zeros are never written, and trailing ones are dropped
+            // as no other digit follows them.
+            pending_zero |= system.writes_zeros && !result.is_empty();
+            continue;
+        }
+        if pending_zero {
+            result.push_str(system.digits[0]);
+            pending_zero = false;
+        }
+        let drop_one = digit == 1
+            && position > 0
+            && match system.one {
+                CjkOne::Kept => false,
+                CjkOne::Dropped => true,
+                CjkOne::DroppedInTeens => value < 20,
+            };
+        if !drop_one {
+            result.push_str(system.digits[digit as usize]);
+        }
+        if position > 0 {
+            result.push_str(system.markers[position as usize - 1]);
+        }
+    }
+    Some(result)
+}
+
+/// <https://drafts.csswg.org/css-counter-styles/#ethiopic-numeric-counter-style>
+fn ethiopic(value: u32) -> Option<String> {
+    const ONES: [char; 9] = ['፩', '፪', '፫', '፬', '፭', '፮', '፯', '፰', '፱'];
+    const TENS: [char; 9] = ['፲', '፳', '፴', '፵', '፶', '፷', '፸', '፹', '፺'];
+
+    if value == 0 {
+        return None;
+    }
+    if value == 1 {
+        return Some(ONES[0].to_string());
+    }
+
+    let mut groups = SmallVec::<[u32; 5]>::new();
+    let mut remaining = value;
+    while remaining != 0 {
+        groups.push(remaining % 100);
+        remaining /= 100;
+    }
+
+    let mut result = String::new();
+    let most_significant = groups.len() - 1;
+    for (index, &group) in groups.iter().enumerate().rev() {
+        let odd = index % 2 == 1;
+        let removed = group == 0 || (group == 1 && (index == most_significant || odd));
+        if !removed {
+            if group >= 10 {
+                result.push(TENS[(group / 10 - 1) as usize]);
+            }
+            if group % 10 != 0 {
+                result.push(ONES[(group % 10 - 1) as usize]);
+            }
+        }
+        if odd && group != 0 {
+            result.push('፻');
+        } else if !odd && index != 0 {
+            result.push('፼');
+        }
+    }
+    Some(result)
+}
+
+/// A counter style resolved down to its algorithm and descriptors, with
+/// `extends` already applied.
+#[derive(Clone, Debug)]
+struct Definition<'a> {
+    algorithm: Algorithm<'a>,
+    negative: (Cow<'a, str>, Cow<'a, str>),
+    prefix: Cow<'a, str>,
+    suffix: Cow<'a, str>,
+    range: Ranges<'a>,
+    pad: Option<(usize, Cow<'a, str>)>,
+    fallback: Option<Atom>,
+}
+
+impl<'a> Definition<'a> {
+    fn new(algorithm: Algorithm<'a>) -> Self {
+        Self {
+            algorithm,
+            negative: (Cow::Borrowed("-"), Cow::Borrowed("")),
+            prefix: Cow::Borrowed(""),
+            suffix: Cow::Borrowed(". "),
+            range: Ranges::Auto,
+            pad: None,
+            fallback: None,
+        }
+    }
+
+    fn with_suffix(mut self, suffix: &'static str) -> Self {
+        self.suffix = Cow::Borrowed(suffix);
+        self
+    }
+
+    fn with_negative(mut self, negative: &'static str) -> Self {
+        self.negative.0 = Cow::Borrowed(negative);
+        self
+    }
+
+    fn with_range(mut self, range: &'static [(i32, i32)]) -> Self {
+        self.range = Ranges::Static(range);
+        self
+    }
+
+    fn with_pad(mut self, min_length: usize, symbol: &'static str) -> Self {
+        self.pad = Some((min_length, Cow::Borrowed(symbol)));
+        self
+    }
+
+    fn with_fallback(mut self, fallback: Atom) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    fn in_range(&self, value: i32) -> bool {
+        let bound = |bound: &CounterBound, infinite| match *bound {
+            CounterBound::Integer(ref integer) => integer.value(),
+            CounterBound::Infinite => infinite,
+        };
+        match self.range {
+            Ranges::Auto => self.algorithm.auto_range_contains(value),
+            Ranges::Rule(ranges) => ranges.iter().any(|range| {
+                bound(&range.start, i32::MIN) <= value && value <= bound(&range.end, i32::MAX)
+            }),
+            Ranges::Static(ranges) => ranges
+                .iter()
+                .any(|&(start, end)| start <= value && value <= end),
+        }
+    }
+
+    /// Generates the representation of `value` in this counter style, without
+    /// going to the fallback style.
+    fn generate(&self, value: i32) -> Option<String> {
+        if !self.in_range(value) {
+            return None;
+        }
+        let use_negative = value < 0 && self.algorithm.uses_negative_sign();
+        let initial = self.algorithm.generate(value)?;
+        let (negative_prefix, negative_suffix) = if use_negative {
+            (&*self.negative.0, &*self.negative.1)
+        } else {
+            ("", "")
+        };
+
+        let mut result = String::from(negative_prefix);
+        if let Some((min_length, ref symbol)) = self.pad {
+            // Grapheme clusters are approximated by characters here.
+            let length = initial.chars().count()
+                + negative_prefix.chars().count()
+                + negative_suffix.chars().count();
+            if length < min_length {
+                result.push_str(&symbol.repeat((min_length - length).min(LENGTH_LIMIT)));
+            }
+        }
+        result.push_str(&initial);
+        result.push_str(negative_suffix);
+        Some(result)
+    }
+}
+
+static CJK_DECIMAL: [&str; 10] = ["〇", "一", "二", "三", "四", "五", "六", "七", "八", "九"];
+
+static JAPANESE_INFORMAL: CjkSystem = CjkSystem {
+    digits: CJK_DECIMAL,
+    markers: ["十", "百", "千"],
+    one: CjkOne::Dropped,
+    writes_zeros: false,
+};
+
+static JAPANESE_FORMAL: CjkSystem = CjkSystem {
+    digits: ["零", "壱", "弐", "参", "四", "伍", "六", "七", "八", "九"],
+    markers: ["拾", "百", "阡"],
+    one: CjkOne::Kept,
+    writes_zeros: false,
+};
+
+static KOREAN_HANGUL_FORMAL: CjkSystem = CjkSystem {
+    digits: ["영", "일", "이", "삼", "사", "오", "육", "칠", "팔", "구"],
+    markers: ["십", "백", "천"],
+    one: CjkOne::Kept,
+    writes_zeros: false,
+};
+
+static KOREAN_HANJA_INFORMAL: CjkSystem = CjkSystem {
+    digits: ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"],
+    markers: ["十", "百", "千"],
+    one: CjkOne::Dropped,
+    writes_zeros: false,
+};
+
+static KOREAN_HANJA_FORMAL: CjkSystem = CjkSystem {
+    digits: ["零", "壹", "貳", "參", "四", "五", "六", "七", "八", "九"],
+    markers: ["拾", "百", "仟"],
+    one: CjkOne::Kept,
+    writes_zeros: false,
+};
+
+static CHINESE_INFORMAL: CjkSystem = CjkSystem {
+    digits: ["零", "一", "二", "三", "四", "五", "六", "七", "八", "九"],
+    markers: ["十", "百", "千"],
+    one: CjkOne::DroppedInTeens,
+    writes_zeros: true,
+};
+
+static SIMP_CHINESE_FORMAL: CjkSystem = CjkSystem {
+    digits: ["零", "壹", "贰", "叁", "肆", "伍", "陆", "柒", "捌", "玖"],
+    markers: ["拾", "佰", "仟"],
+    one: CjkOne::Kept,
+    writes_zeros: true,
+};
+
+static TRAD_CHINESE_FORMAL: CjkSystem = CjkSystem {
+    digits: ["零", "壹", "貳", "參", "肆", "伍", "陸", "柒", "捌", "玖"],
+    markers: ["拾", "佰", "仟"],
+    one: CjkOne::Kept,
+    writes_zeros: true,
+};
+
+static ARMENIAN_RANGE: [(i32, i32); 1] = [(1, 9999)];
+static UPPER_ARMENIAN: [(u32, &str); 36] = [
+    (9000, "Ք"),
+    (8000, "Փ"),
+    (7000, "Ւ"),
+    (6000, "Ց"),
+    (5000, "Ր"),
+    (4000, "Տ"),
+    (3000, "Վ"),
+    (2000, "Ս"),
+    (1000, "Ռ"),
+    (900, "Ջ"),
+    (800, "Պ"),
+    (700, "Չ"),
+    (600, "Ո"),
+    (500, "Շ"),
+    (400, "Ն"),
+    (300, "Յ"),
+    (200, "Մ"),
+    (100, "Ճ"),
+    (90, "Ղ"),
+    (80, "Ձ"),
+    (70, "Հ"),
+    (60, "Կ"),
+    (50, "Ծ"),
+    (40, "Խ"),
+    (30, "Լ"),
+    (20, "Ի"),
+    (10, "Ժ"),
+    (9, "Թ"),
+    (8, "Ը"),
+    (7, "Է"),
+    (6, "Զ"),
+    (5, "Ե"),
+    (4, "Դ"),
+    (3, "Գ"),
+    (2, "Բ"),
+    (1, "Ա"),
+];
+static LOWER_ARMENIAN: [(u32, &str); 36] = [
+    (9000, "ք"),
+    (8000, "փ"),
+    (7000, "ւ"),
+    (6000, "ց"),
+    (5000, "ր"),
+    (4000, "տ"),
+    (3000, "վ"),
+    (2000, "ս"),
+    (1000, "ռ"),
+    (900, "ջ"),
+    (800, "պ"),
+    (700, "չ"),
+    (600, "ո"),
+    (500, "շ"),
+    (400, "ն"),
+    (300, "յ"),
+    (200, "մ"),
+    (100, "ճ"),
+    (90, "ղ"),
+    (80, "ձ"),
+    (70, "հ"),
+    (60, "կ"),
+    (50, "ծ"),
+    (40, "խ"),
+    (30, "լ"),
+    (20, "ի"),
+    (10, "ժ"),
+    (9, "թ"),
+    (8, "ը"),
+    (7, "է"),
+    (6, "զ"),
+    (5, "ե"),
+    (4, "դ"),
+    (3, "գ"),
+    (2, "բ"),
+    (1, "ա"),
+];
+
+static GEORGIAN_RANGE: [(i32, i32); 1] = [(1, 19999)];
+static GEORGIAN: [(u32, &str); 37] = [
+    (10000, "ჵ"),
+    (9000, "ჰ"),
+    (8000, "ჯ"),
+    (7000, "ჴ"),
+    (6000, "ხ"),
+    (5000, "ჭ"),
+    (4000, "წ"),
+    (3000, "ძ"),
+    (2000, "ც"),
+    (1000, "ჩ"),
+    (900, "შ"),
+    (800, "ყ"),
+    (700, "ღ"),
+    (600, "ქ"),
+    (500, "ფ"),
+    (400, "ჳ"),
+    (300, "ტ"),
+    (200, "ს"),
+    (100, "რ"),
+    (90, "ჟ"),
+    (80, "პ"),
+    (70, "ო"),
+    (60, "ჲ"),
+    (50, "ნ"),
+    (40, "მ"),
+    (30, "ლ"),
+    (20, "კ"),
+    (10, "ი"),
+    (9, "თ"),
+    (8, "ჱ"),
+    (7, "ზ"),
+    (6, "ვ"),
+    (5, "ე"),
+    (4, "დ"),
+    (3, "გ"),
+    (2, "ბ"),
+    (1, "ა"),
+];
+
+static HEBREW_RANGE: [(i32, i32); 1] = [(1, 10999)];
+static HEBREW: [(u32, &str); 37] = [
+    (10000, "י׳"),
+    (9000, "ט׳"),
+    (8000, "ח׳"),
+    (7000, "ז׳"),
+    (6000, "ו׳"),
+    (5000, "ה׳"),
+    (4000, "ד׳"),
+    (3000, "ג׳"),
+    (2000, "ב׳"),
+    (1000, "א׳"),
+    (400, "ת"),
+    (300, "ש"),
+    (200, "ר"),
+    (100, "ק"),
+    (90, "צ"),
+    (80, "פ"),
+    (70, "ע"),
+    (60, "ס"),
+    (50, "נ"),
+    (40, "מ"),
+    (30, "ל"),
+    (20, "כ"),
+    (19, "יט"),
+    (18, "יח"),
+    (17, "יז"),
+    (16, "טז"),
+    (15, "טו"),
+    (10, "י"),
+    (9, "ט"),
+    (8, "ח"),
+    (7, "ז"),
+    (6, "ו"),
+    (5, "ה"),
+    (4, "ד"),
+    (3, "ג"),
+    (2, "ב"),
+    (1, "א"),
+];
+
+static ROMAN_RANGE: [(i32, i32); 1] = [(1, 3999)];
+static LOWER_ROMAN: [(u32, &str); 13] = [
+    (1000, "m"),
+    (900, "cm"),
+    (500, "d"),
+    (400, "cd"),
+    (100, "c"),
+    (90, "xc"),
+    (50, "l"),
+    (40, "xl"),
+    (10, "x"),
+    (9, "ix"),
+    (5, "v"),
+    (4, "iv"),
+    (1, "i"),
+];
+static UPPER_ROMAN: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+static CJK_RANGE: [(i32, i32); 1] = [(-9999, 9999)];
+
+static LOWER_LATIN: [&str; 26] = [
+    "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n", "o", "p", "q", "r", "s",
+    "t", "u", "v", "w", "x", "y", "z",
+];
+static UPPER_LATIN: [&str; 26] = [
+    "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P", "Q", "R", "S",
+    "T", "U", "V", "W", "X", "Y", "Z",
+];
+static LOWER_GREEK: [&str; 24] = [
+    "α", "β", "γ", "δ", "ε", "ζ", "η", "θ", "ι", "κ", "λ", "μ", "ν", "ξ", "ο", "π", "ρ", "σ", "τ",
+    "υ", "φ", "χ", "ψ", "ω",
+];
+static CJK_EARTHLY_BRANCH: [&str; 12] = [
+    "子", "丑", "寅", "卯", "辰", "巳", "午", "未", "申", "酉", "戌", "亥",
+];
+static CJK_HEAVENLY_STEM: [&str; 10] = ["甲", "乙", "丙", "丁", "戊", "己", "庚", "辛", "壬", "癸"];
+static HIRAGANA: [&str; 48] = [
+    "あ", "い", "う", "え", "お", "か", "き", "く", "け", "こ", "さ", "し", "す", "せ", "そ", "た",
+    "ち", "つ", "て", "と", "な", "に", "ぬ", "ね", "の", "は", "ひ", "ふ", "へ", "ほ", "ま", "み",
+    "む", "め", "も", "や", "ゆ", "よ", "ら", "り", "る", "れ", "ろ", "わ", "ゐ", "ゑ", "を", "ん",
+];
+static HIRAGANA_IROHA: [&str; 47] = [
+    "い", "ろ", "は", "に", "ほ", "へ", "と", "ち", "り", "ぬ", "る", "を", "わ", "か", "よ", "た",
+    "れ", "そ", "つ", "ね", "な", "ら", "む", "う", "ゐ", "の", "お", "く", "や", "ま", "け", "ふ",
+    "こ", "え", "て", "あ", "さ", "き", "ゆ", "め", "み", "し", "ゑ", "ひ", "も", "せ", "す",
+];
+static KATAKANA: [&str; 48] = [
+    "ア", "イ", "ウ", "エ", "オ", "カ", "キ", "ク", "ケ", "コ", "サ", "シ", "ス", "セ", "ソ", "タ",
+    "チ", "ツ", "テ", "ト", "ナ", "ニ", "ヌ", "ネ", "ノ", "ハ", "ヒ", "フ", "ヘ", "ホ", "マ", "ミ",
+    "ム", "メ", "モ", "ヤ", "ユ", "ヨ", "ラ", "リ", "ル", "レ", "ロ", "ワ", "ヰ", "ヱ", "ヲ", "ン",
+];
+static KATAKANA_IROHA: [&str; 47] = [
+    "イ", "ロ", "ハ", "ニ", "ホ", "ヘ", "ト", "チ", "リ", "ヌ", "ル", "ヲ", "ワ", "カ", "ヨ", "タ",
+    "レ", "ソ", "ツ", "ネ", "ナ", "ラ", "ム", "ウ", "ヰ", "ノ", "オ", "ク", "ヤ", "マ", "ケ", "フ",
+    "コ", "エ", "テ", "ア", "サ", "キ", "ユ", "メ", "ミ", "シ", "ヱ", "ヒ", "モ", "セ", "ス",
+];
+
+static DECIMAL: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+static ARABIC_INDIC: [&str; 10] = ["٠", "١", "٢", "٣", "٤", "٥", "٦", "٧", "٨", "٩"];
+static BENGALI: [&str; 10] = ["০", "১", "২", "৩", "৪", "৫", "৬", "৭", "৮", "৯"];
+static KHMER: [&str; 10] = ["០", "១", "២", "៣", "៤", "៥", "៦", "៧", "៨", "៩"];
+static DEVANAGARI: [&str; 10] = ["०", "१", "२", "३", "४", "५", "६", "७", "८", "९"];
+static GUJARATI: [&str; 10] = ["૦", "૧", "૨", "૩", "૪", "૫", "૬", "૭", "૮", "૯"];
+static GURMUKHI: [&str; 10] = ["੦", "੧", "੨", "੩", "੪", "੫", "੬", "੭", "੮", "੯"];
+static KANNADA: [&str; 10] = ["೦", "೧", "೨", "೩", "೪", "೫", "೬", "೭", "೮", "೯"];
+static LAO: [&str; 10] = ["໐", "໑", "໒", "໓", "໔", "໕", "໖", "໗", "໘", "໙"];
+static MALAYALAM: [&str; 10] = ["൦", "൧", "൨", "൩", "൪", "൫", "൬", "൭", "൮", "൯"];
+static MONGOLIAN: [&str; 10] = ["᠐", "᠑", "᠒", "᠓", "᠔", "᠕", "᠖", "᠗", "᠘", "᠙"];
+static MYANMAR: [&str; 10] = ["၀", "၁", "၂", "၃", "၄", "၅", "၆", "၇", "၈", "၉"];
+static ORIYA: [&str; 10] = ["୦", "୧", "୨", "୩", "୪", "୫", "୬", "୭", "୮", "୯"];
+static PERSIAN: [&str; 10] = ["۰", "۱", "۲", "۳", "۴", "۵", "۶", "۷", "۸", "۹"];
+static TAMIL: [&str; 10] = ["௦", "௧", "௨", "௩", "௪", "௫", "௬", "௭", "௮", "௯"];
+static TELUGU: [&str; 10] = ["౦", "౧", "౨", "౩", "౪", "౫", "౬", "౭", "౮", "౯"];
+static THAI: [&str; 10] = ["๐", "๑", "๒", "๓", "๔", "๕", "๖", "๗", "๘", "๙"];
+static TIBETAN: [&str; 10] = ["༠", "༡", "༢", "༣", "༤", "༥", "༦", "༧", "༨", "༩"];
+
+fn decimal() -> Definition<'static> {
+    Definition::new(Algorithm::Numeric(SymbolList::Static(&DECIMAL)))
+}
+
+/// Returns the definition of the predefined counter style with the given name.
+///
+/// <https://drafts.csswg.org/css-counter-styles/#predefined-counters>
+fn predefined(name: &Atom) -> Option<Definition<'static>> {
+    use self::Algorithm::*;
+
+    let numeric = |symbols| Definition::new(Numeric(SymbolList::Static(symbols)));
+    let alphabetic = |symbols| Definition::new(Alphabetic(SymbolList::Static(symbols)));
+    let additive = |symbols, range| {
+        Definition::new(Additive(AdditiveSymbolList::Static(symbols))).with_range(range)
+    };
+    let cyclic = |symbol| Definition::new(Cyclic(SymbolList::Static(symbol))).with_suffix(" ");
+    let cjk = |system, negative| {
+        Definition::new(Cjk(system))
+            .with_range(&CJK_RANGE)
+            .with_negative(negative)
+            .with_suffix("、")
+            .with_fallback(atom!("cjk-decimal"))
+    };
+
+    macro_rules! predefined {
+        ($($name:tt => $definition:expr,)+) => {{
+            $(
+                if *name == atom!($name) {
+                    return Some($definition);
+                }
+            )+
+        }}
+    }
+    include!("predefined.rs");
+    None
+}
+
+/// A counter style, resolved against a set of `@counter-style` rules so that
+/// it can generate the representation of counter values.
+///
+/// Names are looked up in the rules first, then in the predefined counter
+/// styles, and unknown names behave as `decimal`.
+///
+/// <https://drafts.csswg.org/css-counter-styles/#generate-a-counter>
+pub struct ResolvedCounterStyle<'a, L> {
+    definition: Definition<'a>,
+    lookup: L,
+}
+
+impl<'a, L> ResolvedCounterStyle<'a, L>
+where
+    L: Fn(&Atom) -> Option<&'a CounterStyleRuleData>,
+{
+    /// Resolves `style`, looking up `@counter-style` rules with `lookup`.
+    pub fn new(style: &'a CounterStyle, lookup: L) -> Self {
+        let definition = match *style {
+            CounterStyle::None => {
+                let mut definition = Definition::new(Algorithm::Literal(Cow::Borrowed("")));
+                definition.suffix = Cow::Borrowed("");
+                definition
+            },
+            CounterStyle::String(ref string) => {
+                let mut definition =
+                    Definition::new(Algorithm::Literal(Cow::Owned(string.to_string())));
+                definition.suffix = Cow::Borrowed("");
+                definition
+            },
+            CounterStyle::Name(ref name) => resolve_name(&name.0, &lookup),
+            CounterStyle::Symbols { ty, ref symbols } => {
+                let symbols = SymbolList::Rule(&symbols.0);
+                Definition::new(match ty {
+                    SymbolsType::Cyclic => Algorithm::Cyclic(symbols),
+                    SymbolsType::Numeric => Algorithm::Numeric(symbols),
+                    SymbolsType::Alphabetic => Algorithm::Alphabetic(symbols),
+                    SymbolsType::Symbolic => Algorithm::Symbolic(symbols),
+                    SymbolsType::Fixed => Algorithm::Fixed {
+                        first_symbol_value: 1,
+                        symbols,
+                    },
+                })
+                .with_suffix(" ")
+            },
+        };
+        Self { definition, lookup }
+    }
+
+    /// Resolves the counter style with the given name, looking up
+    /// `@counter-style` rules with `lookup`.
+    pub fn from_name(name: &Atom, lookup: L) -> Self {
+        let definition = resolve_name(name, &lookup);
+        Self { definition, lookup }
+    }
+
+    /// Returns the representation of `value` in this counter style, using the
+    /// fallback counter styles if needed.
+    ///
+    /// This is the text `counter()` and `counters()` generate, without the
+    /// prefix and suffix of the counter style.
+    pub fn representation(&self, value: i32) -> String {
+        if let Some(representation) = self.definition.generate(value) {
+            return representation;
+        }
+
+        let mut seen = SmallVec::<[Atom; 4]>::new();
+        let mut fallback = self.definition.fallback.clone();
+        loop {
+            let name = fallback.unwrap_or(atom!("decimal"));
+            if seen.contains(&name) {
+                break;
+            }
+            let definition = resolve_name(&name, &self.lookup);
+            if let Some(representation) = definition.generate(value) {
+                return representation;
+            }
+            seen.push(name);
+            fallback = definition.fallback;
+        }
+        // The fallback styles loop, but decimal can represent any value.
+        decimal().generate(value).unwrap()
+    }
+
+    /// Returns the prefix of this counter style.
+    pub fn prefix(&self) -> &str {
+        &self.definition.prefix
+    }
+
+    /// Returns the suffix of this counter style.
+    pub fn suffix(&self) -> &str {
+        &self.definition.suffix
+    }
+
+    /// Returns the text of a marker for `value`, that is, its representation
+    /// between the prefix and suffix of this counter style.
+    ///
+    /// <https://drafts.csswg.org/css-lists/#content-property>
+    pub fn marker_text(&self, value: i32) -> String {
+        let mut text = String::from(self.prefix());
+        text.push_str(&self.representation(value));
+        text.push_str(self.suffix());
+        text
+    }
+
+    /// Returns the text `counters()` generates for the values of all the
+    /// counters with its name in scope, from the outermost to the innermost.
+    ///
+    /// <https://drafts.csswg.org/css-lists/#counter-functions>
+    pub fn counters_representation(&self, values: &[i32], separator: &str) -> String {
+        let mut text = String::new();
+        for (i, value) in values.iter().enumerate() {
+            if i != 0 {
+                text.push_str(separator);
+            }
+            text.push_str(&self.representation(*value));
+        }
+        text
+    }
+}
+
+/// Returns whether the `@counter-style` rule named `name` extends itself,
+/// directly or through other rules.
+fn extends_itself<'a, L>(name: &Atom, lookup: &L) -> bool
+where
+    L: Fn(&Atom) -> Option<&'a CounterStyleRuleData>,
+{
+    let mut seen = SmallVec::<[&Atom; 4]>::new();
+    let mut current = name;
+    while let Some(rule) = lookup(current) {
+        let extended = match *rule.resolved_system() {
+            System::Extends(ref extended) => &extended.0,
+            _ => return false,
+        };
+        if extended == name {
+            return true;
+        }
+        if seen.contains(&extended) {
+            // Some other rules form a loop, which this one leads into.
+            return false;
+        }
+        seen.push(extended);
+        current = extended;
+    }
+    false
+}
+
+fn resolve_name<'a, L>(name: &Atom, lookup: &L) -> Definition<'a>
+where
+    L: Fn(&Atom) -> Option<&'a CounterStyleRuleData>,
+{
+    let rule = match lookup(name) {
+        Some(rule) => rule,
+        None => return predefined(name).unwrap_or_else(decimal),
+    };
+
+    let symbols = rule.symbols().map(|symbols| SymbolList::Rule(&symbols.0));
+    let additive_symbols = rule
+        .additive_symbols()
+        .map(|symbols| AdditiveSymbolList::Rule(&symbols.0));
+    let mut definition = match (rule.resolved_system(), symbols, additive_symbols) {
+        (System::Cyclic, Some(symbols), _) => Definition::new(Algorithm::Cyclic(symbols)),
+        (System::Fixed { first_symbol_value }, Some(symbols), _) => {
+            Definition::new(Algorithm::Fixed {
+                first_symbol_value: first_symbol_value.map_or(1, |value| value.value()),
+                symbols,
+            })
+        },
+        (System::Symbolic, Some(symbols), _) => Definition::new(Algorithm::Symbolic(symbols)),
+        (System::Alphabetic, Some(symbols), _) if symbols.len() >= 2 => {
+            Definition::new(Algorithm::Alphabetic(symbols))
+        },
+        (System::Numeric, Some(symbols), _) if symbols.len() >= 2 => {
+            Definition::new(Algorithm::Numeric(symbols))
+        },
+        (System::Additive, _, Some(symbols)) => Definition::new(Algorithm::Additive(symbols)),
+        (System::Extends(extended), _, _) => {
+            // https://drafts.csswg.org/css-counter-styles/#extends-system:
+            //
+            //     If a @counter-style uses the extends system, it must not
+            //     contain a symbols or additive-symbols descriptor, or else
+            //     the @counter-style rule is invalid.
+            //
+            // Rules that extend themselves act as if they extended decimal.
+            if extends_itself(name, lookup) {
+                decimal()
+            } else {
+                resolve_name(&extended.0, lookup)
+            }
+        },
+        // The rule was made invalid through CSSOM, treat it as decimal.
+        _ => decimal(),
+    };
+
+    if let Some(negative) = rule.negative() {
+        definition.negative = (
+            symbol_text(&negative.0),
+            negative.1.as_ref().map_or(Cow::Borrowed(""), symbol_text),
+        );
+    }
+    if let Some(prefix) = rule.prefix() {
+        definition.prefix = symbol_text(prefix);
+    }
+    if let Some(suffix) = rule.suffix() {
+        definition.suffix = symbol_text(suffix);
+    }
+    if let Some(range) = rule.range() {
+        definition.range = if range.0.is_empty() {
+            Ranges::Auto
+        } else {
+            Ranges::Rule(&range.0)
+        };
+    }
+    if let Some(pad) = rule.pad() {
+        definition.pad = Some((pad.0.value() as usize, symbol_text(&pad.1)));
+    }
+    if let Some(fallback) = rule.fallback() {
+        definition.fallback = Some(fallback.0 .0.clone());
+    }
+    definition
+}
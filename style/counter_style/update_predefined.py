@@ -16,6 +16,21 @@ def main(filename):
         if b'data-dfn-for="<counter-style-name>"' in line
         or b'data-dfn-for="<counter-style>"' in line
     ]
+    # Keep the definitions, and the comments before them, of the names that
+    # are already there. New names are written without a definition, so that
+    # the build fails until one is added.
+    definitions = {}
+    comments = []
+    with open(filename) as f:
+        for line in f:
+            line = line.strip()
+            if line.startswith("//"):
+                comments.append(line)
+                continue
+            match = re.match(r'"([^"]+)" => (.+),$', line)
+            if match:
+                definitions[match.group(1)] = (comments, match.group(2))
+            comments = []
     with open(filename, "w") as f:
         f.write(
             """\
@@ -23,11 +38,20 @@ def main(filename):
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
+// The names come from update_predefined.py, and the definitions are evaluated
+// by `representation::predefined`.
 predefined! {
 """
         )
         for name in names:
-            f.write('    "%s",\n' % name)
+            if name not in definitions:
+                print("Add a definition for %s" % name)
+                f.write('    "%s",\n' % name)
+                continue
+            comments, definition = definitions[name]
+            for comment in comments:
+                f.write("    %s\n" % comment)
+            f.write('    "%s" => %s,\n' % (name, definition))
         f.write("}\n")
 
 
@@ -41,7 +41,11 @@ use std::collections::hash_map::Entry;
 use std::fmt::{self, Write};
 use std::ops::{Index, IndexMut};
 use std::{cmp, num};
-use style_traits::{CssWriter, ParseError, StyleParseErrorKind, ToCss};
+use style_traits::{
+    CssString, CssWriter, ParseError, StyleParseErrorKind, ToCss, ToTyped, TypedValue,
+    UnparsedSegment,
+};
+use thin_vec::ThinVec;
 
 /// The environment from which to get `env` function values.
 ///
@@ -280,6 +284,64 @@ impl ToCss for SpecifiedValue {
     }
 }
 
+impl ToTyped for VariableValue {
+    /// https://drafts.css-houdini.org/css-typed-om-1/#reify-a-list-of-component-values
+    fn to_typed(&self) -> Option<TypedValue> {
+        let mut references = self.references.refs.iter().peekable();
+        Some(TypedValue::Unparsed(unparsed_segments(
+            &self.css,
+            0,
+            self.css.len(),
+            &mut references,
+        )))
+    }
+}
+
+/// Splits the given range of a value into runs of tokens and var() references,
+/// consuming the references in that range. env() and attr() functions are
+/// kept as tokens.
+fn unparsed_segments(
+    css: &str,
+    start: usize,
+    end: usize,
+    references: &mut std::iter::Peekable<std::slice::Iter<SubstitutionFunctionReference>>,
+) -> ThinVec<UnparsedSegment> {
+    let mut segments = ThinVec::new();
+    let mut cur_pos = start;
+    while let Some(reference) = references.next_if(|reference| reference.end <= end) {
+        if reference.substitution_kind != SubstitutionFunctionKind::Var {
+            // Skip the references inside the fallback of this function.
+            while references
+                .next_if(|next_ref| next_ref.end <= reference.end)
+                .is_some()
+            {}
+            continue;
+        }
+        if reference.start != cur_pos {
+            segments.push(UnparsedSegment::String(CssString::from(
+                &css[cur_pos..reference.start],
+            )));
+        }
+        let fallback = reference.fallback.as_ref().map(|fallback| {
+            unparsed_segments(
+                css,
+                fallback.start.get(),
+                reference.end - 1, // Skip the closing parenthesis of the reference value.
+                references,
+            )
+        });
+        segments.push(UnparsedSegment::VariableReference {
+            variable: CssString::from(format!("--{}", reference.name).as_str()),
+            fallback,
+        });
+        cur_pos = reference.end;
+    }
+    if cur_pos != end {
+        segments.push(UnparsedSegment::String(CssString::from(&css[cur_pos..end])));
+    }
+    segments
+}
+
 /// A pair of separate CustomPropertiesMaps, split between custom properties
 /// that have the inherit flag set and those with the flag unset.
 #[repr(C)]
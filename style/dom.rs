@@ -17,6 +17,7 @@ use crate::properties::{AnimationDeclarations, ComputedValues, PropertyDeclarati
 use crate::selector_map::PrecomputedHashSet;
 use crate::selector_parser::{AttrValue, Lang, PseudoElement, RestyleDamage, SelectorImpl};
 use crate::shared_lock::{Locked, SharedRwLock};
+use crate::stylesheets::container_rule::ContainerScrollState;
 use crate::stylesheets::scope_rule::ImplicitScopeRoot;
 use crate::stylist::CascadeData;
 use crate::values::computed::Display;
@@ -938,10 +939,10 @@ pub trait TElement:
     ) where
         V: Push<ApplicableDeclarationBlock>;
 
-    /// Generate the proper applicable declarations due to view transition dynamic rules, and
-    /// insert them into `rules`.
+    /// Generate the proper applicable declarations due to view transition dynamic rules for the
+    /// given named view transition pseudo-element, and insert them into `rules`.
     /// https://drafts.csswg.org/css-view-transitions-1/#document-dynamic-view-transition-style-sheet
-    fn synthesize_view_transition_dynamic_rules<V>(&self, _rules: &mut V)
+    fn synthesize_view_transition_dynamic_rules<V>(&self, _pseudo: &PseudoElement, _rules: &mut V)
     where
         V: Push<ApplicableDeclarationBlock>,
     {
@@ -962,6 +963,15 @@ pub trait TElement:
         display: &Display,
     ) -> euclid::default::Size2D<Option<app_units::Au>>;
 
+    /// Returns the scroll state of the element to be used in scroll-state
+    /// container queries, if the element is a scroll-state container.
+    ///
+    /// Embedders need to restyle the element when this changes, see
+    /// `ContainerScrollState::restyle_hint_for_change`.
+    fn query_container_scroll_state(&self) -> ContainerScrollState {
+        ContainerScrollState::default()
+    }
+
     /// Returns true if the element has all of specified selector flags.
     fn has_selector_flags(&self, flags: ElementSelectorFlags) -> bool;
 
@@ -82,7 +82,9 @@ fn work_unit_max() -> usize {
 ///
 /// We use an adaptive traversal strategy. We start out with simple sequential processing, until we
 /// arrive at a wide enough level in the DOM that the parallel traversal would parallelize it.
-/// If a thread pool is provided, we then transfer control over to the parallel traversal.
+/// If a thread pool is provided, we then transfer control over to the parallel traversal. The pool
+/// is usually `STYLE_THREAD_POOL`, but embedders can pass their own pool built with a
+/// `StyleThreadPoolBuilder`.
 ///
 /// Returns true if the traversal was parallel, and also returns the statistics object containing
 /// information on nodes traversed (on nightly only). Not all of its fields will be initialized
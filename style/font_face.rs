@@ -324,7 +324,7 @@ impl_range!(FontWeightRange, AbsoluteFontWeight);
 /// specified::Number.
 #[repr(C)]
 #[allow(missing_docs)]
-pub struct ComputedFontWeightRange(f32, f32);
+pub struct ComputedFontWeightRange(pub f32, pub f32);
 
 #[inline]
 fn sort_range<T: PartialOrd>(a: T, b: T) -> (T, T) {
@@ -354,7 +354,7 @@ impl_range!(FontStretchRange, SpecifiedFontStretch);
 /// easily.
 #[repr(C)]
 #[allow(missing_docs)]
-pub struct ComputedFontStretchRange(FontStretch, FontStretch);
+pub struct ComputedFontStretchRange(pub FontStretch, pub FontStretch);
 
 impl FontStretchRange {
     /// Returns a computed font-stretch range.
@@ -384,6 +384,7 @@ pub enum FontStyle {
 
 /// The computed representation of the above, with angles in degrees, so that
 /// Gecko can read them easily.
+#[derive(Clone, Copy, Debug)]
 #[repr(u8)]
 #[allow(missing_docs)]
 pub enum ComputedFontStyleDescriptor {
@@ -0,0 +1,361 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Font matching against the effective `@font-face` rules.
+//!
+//! https://drafts.csswg.org/css-fonts-4/#font-matching-algorithm
+
+use crate::font_face::{
+    ComputedFontStyleDescriptor, FontDisplay, FontFaceRuleData, FontFaceSourceFormat,
+    FontFaceSourceFormatKeyword, FontFaceSourceTechFlags, Source,
+};
+use crate::values::computed::font::{
+    FamilyName, FontFamily, FontStretch, FontStyle, FontWeight, SingleFontFamily,
+};
+use cssparser::{match_ignore_ascii_case, UnicodeRange};
+use std::cmp::Ordering;
+
+/// The oblique angle, in degrees, above which oblique faces are looked up in
+/// ascending order first.
+const OBLIQUE_THRESHOLD: f32 = 11.;
+
+/// The angle used to look up oblique faces for italic text.
+const DEFAULT_OBLIQUE_ANGLE: f32 = 14.;
+
+/// The font properties of the text a font face is looked up for.
+#[derive(Clone, Debug)]
+pub struct FontQuery<'a> {
+    /// The computed `font-family` list.
+    pub family: &'a FontFamily,
+    /// The computed `font-weight`.
+    pub weight: FontWeight,
+    /// The computed `font-stretch`.
+    pub stretch: FontStretch,
+    /// The computed `font-style`.
+    pub style: FontStyle,
+    /// The code points that need to be rendered, or `None` to match faces
+    /// regardless of their `unicode-range`. A single code point is a range
+    /// whose start and end are equal.
+    pub code_points: Option<UnicodeRange>,
+}
+
+/// The font formats and technologies the embedder is able to load, used to
+/// skip `@font-face` sources that it can't use.
+#[derive(Clone, Copy, Debug)]
+pub struct FontSourceSupport<'a> {
+    /// The supported `format()` keywords.
+    pub formats: &'a [FontFaceSourceFormatKeyword],
+    /// The supported `tech()` technologies.
+    pub tech: FontFaceSourceTechFlags,
+}
+
+impl<'a> FontSourceSupport<'a> {
+    /// Whether the given source can be loaded. `local()` sources are always
+    /// considered supported, as are `url()` sources without a format hint.
+    ///
+    /// https://drafts.csswg.org/css-fonts-4/#font-face-src-parsing
+    pub fn supports(&self, source: &Source) -> bool {
+        let url = match *source {
+            Source::Local(..) => return true,
+            Source::Url(ref url) => url,
+        };
+        if !self.tech.contains(url.tech_flags) {
+            return false;
+        }
+        let keyword = match url.format_hint {
+            None => return true,
+            Some(FontFaceSourceFormat::Keyword(keyword)) => keyword,
+            Some(FontFaceSourceFormat::String(ref string)) => format_keyword_for_string(string),
+        };
+        self.formats.contains(&keyword)
+    }
+}
+
+/// Maps a legacy `format()` string to the equivalent keyword.
+fn format_keyword_for_string(string: &str) -> FontFaceSourceFormatKeyword {
+    match_ignore_ascii_case! { string,
+        "collection" => FontFaceSourceFormatKeyword::Collection,
+        "embedded-opentype" => FontFaceSourceFormatKeyword::EmbeddedOpentype,
+        "opentype" => FontFaceSourceFormatKeyword::Opentype,
+        "svg" => FontFaceSourceFormatKeyword::Svg,
+        "truetype" => FontFaceSourceFormatKeyword::Truetype,
+        "woff" => FontFaceSourceFormatKeyword::Woff,
+        "woff2" => FontFaceSourceFormatKeyword::Woff2,
+        _ => FontFaceSourceFormatKeyword::Unknown,
+    }
+}
+
+/// A `@font-face` rule selected for some text, along with the sources to try
+/// loading, in order.
+#[derive(Clone, Debug)]
+pub struct FontFaceCandidate<'a> {
+    /// The index in the `font-family` list of the family this face matched.
+    pub family_index: usize,
+    /// The matched rule.
+    pub rule: &'a FontFaceRuleData,
+    /// The supported sources of the rule, in `src` order.
+    pub sources: Vec<&'a Source>,
+    /// The `font-display` of the rule, which determines how long text is
+    /// blocked while the face loads.
+    pub display: FontDisplay,
+}
+
+/// A rule that could be used, together with its supported sources.
+struct Face<'a> {
+    index: usize,
+    rule: &'a FontFaceRuleData,
+    sources: Vec<&'a Source>,
+}
+
+/// How close a face is to the desired value of some property. Lower values
+/// are better: the tier is the group of faces the spec says to check first,
+/// and the distance orders faces within that group.
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+struct Score {
+    tier: u8,
+    distance: f32,
+}
+
+impl Score {
+    const EXACT: Self = Score {
+        tier: 0,
+        distance: 0.,
+    };
+
+    fn new(tier: u8, distance: f32) -> Self {
+        Score { tier, distance }
+    }
+}
+
+/// Scores a face range against a desired `font-stretch`, as a percentage.
+fn stretch_score(desired: f32, min: f32, max: f32) -> Score {
+    if min <= desired && desired <= max {
+        return Score::EXACT;
+    }
+    // Narrower widths are checked first for normal or condensed values, and
+    // wider widths first for expanded values.
+    let below = max < desired;
+    match (desired <= 100., below) {
+        (true, true) => Score::new(1, desired - max),
+        (true, false) => Score::new(2, min - desired),
+        (false, false) => Score::new(1, min - desired),
+        (false, true) => Score::new(2, desired - max),
+    }
+}
+
+/// Scores a face range against a desired `font-weight`.
+fn weight_score(desired: f32, min: f32, max: f32) -> Score {
+    if min <= desired && desired <= max {
+        return Score::EXACT;
+    }
+    let below = max < desired;
+    if (400. ..=500.).contains(&desired) {
+        // Weights up to 500 are checked in ascending order, then weights
+        // below the desired one in descending order, then weights above 500.
+        return if !below && min <= 500. {
+            Score::new(1, min - desired)
+        } else if below {
+            Score::new(2, desired - max)
+        } else {
+            Score::new(3, min - desired)
+        };
+    }
+    match (desired < 400., below) {
+        (true, true) => Score::new(1, desired - max),
+        (true, false) => Score::new(2, min - desired),
+        (false, false) => Score::new(1, min - desired),
+        (false, true) => Score::new(2, desired - max),
+    }
+}
+
+/// Scores an oblique face range against a desired oblique angle, in degrees.
+/// Italic faces come right after the oblique faces on the same side of zero.
+fn oblique_score(desired: f32, min: f32, max: f32) -> Score {
+    if min <= desired && desired <= max {
+        return Score::EXACT;
+    }
+    // Negative angles mirror positive ones.
+    let (desired, min, max) = if desired < 0. {
+        (-desired, -max, -min)
+    } else {
+        (desired, min, max)
+    };
+    if max <= 0. {
+        // The face leans the other way.
+        return Score::new(4, desired - max);
+    }
+    let below = max < desired;
+    match (desired >= OBLIQUE_THRESHOLD, below) {
+        (true, false) => Score::new(1, min - desired),
+        (true, true) => Score::new(2, desired - max),
+        (false, true) => Score::new(1, desired - max),
+        (false, false) => Score::new(2, min - desired),
+    }
+}
+
+/// Scores a face `font-style` descriptor against a desired `font-style`.
+fn style_score(desired: FontStyle, face: ComputedFontStyleDescriptor) -> Score {
+    if desired == FontStyle::ITALIC {
+        // Italic faces, then oblique faces, then normal faces.
+        return match face {
+            ComputedFontStyleDescriptor::Italic => Score::EXACT,
+            ComputedFontStyleDescriptor::Oblique(min, max) => {
+                let score = oblique_score(DEFAULT_OBLIQUE_ANGLE, min, max);
+                Score::new(score.tier + 1, score.distance)
+            },
+        };
+    }
+    match face {
+        ComputedFontStyleDescriptor::Italic => Score::new(3, 0.),
+        ComputedFontStyleDescriptor::Oblique(min, max) => {
+            oblique_score(desired.oblique_degrees(), min, max)
+        },
+    }
+}
+
+/// Keeps only the faces with the best score.
+fn narrow<'a>(faces: &mut Vec<Face<'a>>, score: impl Fn(&Face<'a>) -> Score) {
+    let best = faces
+        .iter()
+        .map(&score)
+        .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    if let Some(best) = best {
+        faces.retain(|face| score(face) == best);
+    }
+}
+
+fn family_matches(rule: &FontFaceRuleData, family: &FamilyName) -> bool {
+    rule.family
+        .as_ref()
+        .is_some_and(|name| name.name.eq_ignore_ascii_case(&family.name))
+}
+
+fn covers(rule: &FontFaceRuleData, code_points: &Option<UnicodeRange>) -> bool {
+    let (code_points, ranges) = match (code_points, &rule.unicode_range) {
+        (Some(code_points), Some(ranges)) => (code_points, ranges),
+        _ => return true,
+    };
+    ranges
+        .iter()
+        .any(|range| range.start <= code_points.end && code_points.start <= range.end)
+}
+
+/// Runs the font matching algorithm over the given `@font-face` rules, which
+/// must be in cascade order (that is, later rules take precedence).
+///
+/// Returns the candidate faces in the order they should be tried: faces for
+/// earlier families in the `font-family` list come first, and within a family,
+/// faces defined later come first. Generic families don't match any face, so
+/// it's up to the caller to fall back to platform fonts for them, and for the
+/// code points none of the candidates support.
+pub fn match_font_faces<'a>(
+    faces: &[&'a FontFaceRuleData],
+    query: &FontQuery,
+    support: &FontSourceSupport,
+) -> Vec<FontFaceCandidate<'a>> {
+    let weight = query.weight.value();
+    let stretch = query.stretch.to_percentage().0 * 100.;
+
+    let mut candidates = vec![];
+    for (family_index, family) in query.family.families.iter().enumerate() {
+        let family = match *family {
+            SingleFontFamily::FamilyName(ref name) => name,
+            SingleFontFamily::Generic(..) => continue,
+        };
+        let mut family_faces = faces
+            .iter()
+            .enumerate()
+            .filter(|(_, rule)| family_matches(rule, family))
+            .filter_map(|(index, rule)| {
+                let sources: Vec<_> = rule
+                    .sources
+                    .as_ref()?
+                    .0
+                    .iter()
+                    .filter(|source| support.supports(source))
+                    .collect();
+                if sources.is_empty() {
+                    return None;
+                }
+                Some(Face {
+                    index,
+                    rule,
+                    sources,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        narrow(&mut family_faces, |face| match face.rule.stretch {
+            Some(ref range) => {
+                let range = range.compute();
+                stretch_score(
+                    stretch,
+                    range.0.to_percentage().0 * 100.,
+                    range.1.to_percentage().0 * 100.,
+                )
+            },
+            None => stretch_score(stretch, 100., 100.),
+        });
+        narrow(&mut family_faces, |face| {
+            let descriptor = match face.rule.style {
+                Some(ref style) => style.compute(),
+                None => ComputedFontStyleDescriptor::Oblique(0., 0.),
+            };
+            style_score(query.style, descriptor)
+        });
+        narrow(&mut family_faces, |face| match face.rule.weight {
+            Some(ref range) => {
+                let range = range.compute();
+                weight_score(weight, range.0, range.1)
+            },
+            None => weight_score(weight, 400., 400.),
+        });
+
+        family_faces.retain(|face| covers(face.rule, &query.code_points));
+        family_faces.sort_by_key(|face| std::cmp::Reverse(face.index));
+        candidates.extend(family_faces.into_iter().map(|face| FontFaceCandidate {
+            family_index,
+            rule: face.rule,
+            sources: face.sources,
+            display: face.rule.display.unwrap_or(FontDisplay::Auto),
+        }));
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weight_scores() {
+        // For 400, 450 is better than 300, which is better than 600.
+        let at_450 = weight_score(400., 450., 450.);
+        let at_300 = weight_score(400., 300., 300.);
+        let at_600 = weight_score(400., 600., 600.);
+        assert!(at_450 < at_300 && at_300 < at_600);
+        // For 700, heavier weights are preferred.
+        assert!(weight_score(700., 900., 900.) < weight_score(700., 600., 600.));
+        // For 300, lighter weights are preferred.
+        assert!(weight_score(300., 100., 200.) < weight_score(300., 400., 400.));
+        assert!(weight_score(300., 200., 400.) == Score::EXACT);
+    }
+
+    #[test]
+    fn style_scores() {
+        let italic = ComputedFontStyleDescriptor::Italic;
+        let oblique = ComputedFontStyleDescriptor::Oblique(10., 20.);
+        let normal = ComputedFontStyleDescriptor::Oblique(0., 0.);
+        let score = |desired, face| style_score(desired, face);
+        assert!(score(FontStyle::ITALIC, italic) < score(FontStyle::ITALIC, oblique));
+        assert!(score(FontStyle::ITALIC, oblique) < score(FontStyle::ITALIC, normal));
+        assert!(score(FontStyle::NORMAL, normal) < score(FontStyle::NORMAL, oblique));
+        assert!(score(FontStyle::NORMAL, oblique) < score(FontStyle::NORMAL, italic));
+        let oblique_30 = FontStyle::oblique(30.);
+        assert!(
+            score(oblique_30, ComputedFontStyleDescriptor::Oblique(40., 40.))
+                < score(oblique_30, oblique)
+        );
+    }
+}
@@ -14,7 +14,6 @@ use crate::properties::{ComputedValues, PropertyFlags};
 use crate::selector_parser::{PseudoElementCascadeType, SelectorImpl};
 use crate::str::{starts_with_ignore_ascii_case, string_as_ascii_lowercase};
 use crate::string_cache::Atom;
-use crate::values::serialize_atom_identifier;
 use crate::values::AtomIdent;
 use cssparser::{Parser, ToCss};
 use selectors::parser::PseudoElement as PseudoElementTrait;
@@ -27,149 +26,7 @@ include!(concat!(
     "/gecko/pseudo_element_definition.rs"
 ));
 
-/// The target we are using for parsing pseudo-elements.
-pub enum Target {
-    /// When parsing a selector, we want to use the full syntax.
-    Selector,
-    /// When parsing the pseudo-element string (from CSSOM), we only accept CusomIdent for named
-    /// view transition pseudo-elements.
-    Cssom,
-}
-
-/// The type to hold the value of `<pt-name-and-class-selector>`.
-///
-/// `<pt-name-and-class-selector> = <pt-name-selector> <pt-class-selector>? | <pt-class-selector>`
-/// `<pt-name-selector> = '*' | <custom-ident>`
-/// `<pt-class-selector> = ['.' <custom-ident>]+`
-///
-/// This type should have at least one element.
-/// If there is no <pt-name-selector>, the first element would be the universal symbol, i.e. '*'.
-/// In other words, when we match it, ".abc" is the same as "*.abc".
-/// Note that we also serialize ".abc" as "*.abc".
-///
-/// We use a single ThinVec<> to represent this structure to avoid allocating too much memory for a
-/// single selectors::parser::Component (max: 24 bytes) and PseudoElement (max: 16 bytes).
-///
-/// https://drafts.csswg.org/css-view-transitions-2/#typedef-pt-name-and-class-selector
-#[derive(Clone, Debug, Eq, Hash, MallocSizeOf, PartialEq, ToShmem)]
-pub struct PtNameAndClassSelector(thin_vec::ThinVec<Atom>);
-
-impl PtNameAndClassSelector {
-    /// Constructs a new one from a name.
-    pub fn from_name(name: Atom) -> Self {
-        Self(thin_vec::thin_vec![name])
-    }
-
-    /// Returns the name component.
-    pub fn name(&self) -> &Atom {
-        debug_assert!(!self.0.is_empty());
-        self.0.first().expect("Shouldn't be empty")
-    }
-
-    /// Returns the classes component.
-    pub fn classes(&self) -> &[Atom] {
-        debug_assert!(!self.0.is_empty());
-        &self.0[1..]
-    }
-
-    /// Returns the vector we store.
-    pub fn name_and_classes(&self) -> &thin_vec::ThinVec<Atom> {
-        &self.0
-    }
-
-    /// Parse the pseudo-element tree name and/or class.
-    /// |for_selector| is true if we are parsing the CSS selectors and so need to check the
-    /// universal symbol, i.e. '*', and classes.
-    // Note: We share the same type for both pseudo-element and pseudo-element selector. The
-    // universal symbol (i.e. '*') and `<pt-class-selector>` are used only in the selector (for
-    // matching).
-    pub fn parse<'i, 't>(
-        input: &mut Parser<'i, 't>,
-        target: Target,
-    ) -> Result<Self, ParseError<'i>> {
-        use crate::values::CustomIdent;
-        use cssparser::Token;
-        use style_traits::StyleParseErrorKind;
-
-        // <pt-name-selector> = '*' | <custom-ident>
-        let parse_pt_name = |input: &mut Parser<'i, '_>| {
-            // For pseudo-element string, we don't accept '*'.
-            if matches!(target, Target::Selector)
-                && input.try_parse(|i| i.expect_delim('*')).is_ok()
-            {
-                Ok(atom!("*"))
-            } else {
-                CustomIdent::parse(input, &[]).map(|c| c.0)
-            }
-        };
-        let name = input.try_parse(parse_pt_name);
-
-        // Skip <pt-class-selector> for pseudo-element string.
-        if matches!(target, Target::Cssom) {
-            return name.map(Self::from_name);
-        }
-
-        // <pt-class-selector> = ['.' <custom-ident>]+
-        let parse_pt_class = |input: &mut Parser<'i, '_>| {
-            // The white space is forbidden:
-            // 1. Between <pt-name-selector> and <pt-class-selector>
-            // 2. Between any of the components of <pt-class-selector>.
-            let location = input.current_source_location();
-            match input.next_including_whitespace()? {
-                Token::Delim('.') => (),
-                t => return Err(location.new_unexpected_token_error(t.clone())),
-            }
-            // Whitespace is not allowed between '.' and the class name.
-            if let Ok(token) = input.try_parse(|i| i.expect_whitespace()) {
-                return Err(input.new_unexpected_token_error(Token::WhiteSpace(token)));
-            }
-            CustomIdent::parse(input, &[]).map(|c| c.0)
-        };
-        // If there is no `<pt-name-selector>`, it's fine to have whitespaces before the first '.'.
-        if name.is_err() {
-            input.skip_whitespace();
-        }
-        let mut classes = thin_vec::ThinVec::new();
-        while let Ok(class) = input.try_parse(parse_pt_class) {
-            classes.push(class);
-        }
-
-        // If we don't have `<pt-name-selector>`, we must have `<pt-class-selector>`, per the
-        // syntax: `<pt-name-selector> <pt-class-selector>? | <pt-class-selector>`.
-        if name.is_err() && classes.is_empty() {
-            return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
-        }
-
-        // Use the universal symbol as the first element to present the part of
-        // `<pt-name-selector>` because they are equivalent (and the serialization is the same).
-        let mut result = thin_vec::thin_vec![name.unwrap_or(atom!("*"))];
-        result.append(&mut classes);
-
-        Ok(Self(result))
-    }
-}
-
-impl ToCss for PtNameAndClassSelector {
-    fn to_css<W>(&self, dest: &mut W) -> fmt::Result
-    where
-        W: fmt::Write,
-    {
-        let name = self.name();
-        if name == &atom!("*") {
-            // serialize_atom_identifier() may serialize "*" as "\*", so we handle it separately.
-            dest.write_char('*')?;
-        } else {
-            serialize_atom_identifier(name, dest)?;
-        }
-
-        for class in self.classes() {
-            dest.write_char('.')?;
-            serialize_atom_identifier(class, dest)?;
-        }
-
-        Ok(())
-    }
-}
+pub use crate::selector_parser::{PtNameAndClassSelector, Target};
 
 impl PseudoElementTrait for PseudoElement {
     type Impl = SelectorImpl;
@@ -1630,7 +1630,7 @@ impl<'le> TElement for GeckoElement<'le> {
         unsafe { bindings::Gecko_IsDocumentBody(self.0) }
     }
 
-    fn synthesize_view_transition_dynamic_rules<V>(&self, rules: &mut V)
+    fn synthesize_view_transition_dynamic_rules<V>(&self, _pseudo: &PseudoElement, rules: &mut V)
     where
         V: Push<ApplicableDeclarationBlock>,
     {
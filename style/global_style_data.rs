@@ -15,7 +15,7 @@ use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use std::os::unix::thread::{JoinHandleExt, RawPthread};
 #[cfg(windows)]
 use std::os::windows::{io::AsRawHandle, prelude::RawHandle};
-use std::{io, sync::LazyLock, thread};
+use std::{cmp, io, sync::LazyLock, thread};
 use thin_vec::ThinVec;
 
 /// Platform-specific handle to a thread.
@@ -154,6 +154,88 @@ impl StyleThreadPool {
     }
 }
 
+/// A builder for thread pools suitable for parallel styling.
+///
+/// `STYLE_THREAD_POOL` is built with this, and embedders can use it to build
+/// their own pools, to pass to `driver::traverse_dom`, for example in order
+/// not to share threads between independent documents.
+pub struct StyleThreadPoolBuilder {
+    num_threads: usize,
+    thread_name: Box<dyn FnMut(usize) -> String>,
+    stack_size: usize,
+    use_current_thread: bool,
+}
+
+impl Default for StyleThreadPoolBuilder {
+    fn default() -> Self {
+        Self {
+            num_threads: 0,
+            thread_name: Box::new(thread_name),
+            stack_size: STYLE_THREAD_STACK_SIZE_KB * 1024,
+            use_current_thread: false,
+        }
+    }
+}
+
+impl StyleThreadPoolBuilder {
+    /// Creates a builder with the default configuration, that is, rayon's
+    /// default number of threads, named `StyleThread#<index>`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of threads of the pool, including the current thread
+    /// if `use_current_thread` is set. Zero means rayon's default.
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        self.num_threads = num_threads;
+        self
+    }
+
+    /// Sets the function that names the threads of the pool, given their
+    /// index.
+    pub fn thread_name(mut self, thread_name: impl FnMut(usize) -> String + 'static) -> Self {
+        self.thread_name = Box::new(thread_name);
+        self
+    }
+
+    /// Sets the stack size of the threads of the pool, in bytes.
+    ///
+    /// Sizes below `STYLE_THREAD_STACK_SIZE_KB` are raised to it, since the
+    /// traversal relies on having at least that much stack.
+    pub fn stack_size(mut self, stack_size: usize) -> Self {
+        self.stack_size = cmp::max(stack_size, STYLE_THREAD_STACK_SIZE_KB * 1024);
+        self
+    }
+
+    /// Makes the current thread the first thread of the pool, as the main
+    /// thread is for the global pool.
+    ///
+    /// A thread can only be part of one pool, so this is only useful for
+    /// pools that live as long as the current thread.
+    pub fn use_current_thread(mut self) -> Self {
+        self.use_current_thread = true;
+        self
+    }
+
+    fn rayon_builder(self) -> rayon::ThreadPoolBuilder {
+        let mut builder = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.num_threads)
+            .thread_name(self.thread_name)
+            .start_handler(thread_startup)
+            .exit_handler(thread_shutdown)
+            .stack_size(self.stack_size);
+        if self.use_current_thread {
+            builder = builder.use_current_thread();
+        }
+        builder
+    }
+
+    /// Builds the thread pool.
+    pub fn build(self) -> Result<rayon::ThreadPool, rayon::ThreadPoolBuildError> {
+        self.rayon_builder().build()
+    }
+}
+
 #[cfg(feature = "servo")]
 fn stylo_threads_pref() -> i32 {
     static_prefs::pref!("layout.threads")
@@ -170,7 +252,6 @@ pub(crate) const STYLO_MAX_THREADS: usize = 6;
 
 /// Global thread pool
 pub static STYLE_THREAD_POOL: LazyLock<StyleThreadPool> = LazyLock::new(|| {
-    use std::cmp;
     // We always set this pref on startup, before layout or script have had a chance of
     // accessing (and thus creating) the thread-pool.
     let threads_pref: i32 = stylo_threads_pref();
@@ -199,14 +280,11 @@ pub static STYLE_THREAD_POOL: LazyLock<StyleThreadPool> = LazyLock::new(|| {
     let (pool, num_threads) = if num_threads <= 1 {
         (None, None)
     } else {
-        let workers = rayon::ThreadPoolBuilder::new()
-            .spawn_handler(thread_spawn)
-            .use_current_thread()
+        let workers = StyleThreadPoolBuilder::new()
             .num_threads(num_threads)
-            .thread_name(thread_name)
-            .start_handler(thread_startup)
-            .exit_handler(thread_shutdown)
-            .stack_size(STYLE_THREAD_STACK_SIZE_KB * 1024)
+            .use_current_thread()
+            .rayon_builder()
+            .spawn_handler(thread_spawn)
             .build();
         (workers.ok(), Some(num_threads))
     };
@@ -101,6 +101,16 @@ pub enum NormalDependencyInvalidationKind {
     SlottedElements,
     /// This dependency may affect parts of the element that changed.
     Parts,
+    /// This dependency may affect the cells in the columns of the element that
+    /// changed, through the column combinator.
+    ///
+    /// Those aren't reachable from the element during the traversal, so this
+    /// doesn't generate invalidations. Instead, the embedder checks these
+    /// dependencies before the traversal with
+    /// `StateAndAttrInvalidationProcessor::invalidates_columns`, and
+    /// invalidates the cells with
+    /// `table_columns::invalidate_cells_for_column_element`.
+    Columns,
 }
 
 /// The kind of elements up the tree this relative selector dependency may
@@ -178,6 +188,7 @@ fn get_non_relative_invalidation_kind(
         Combinator::PseudoElement => NormalDependencyInvalidationKind::ElementAndDescendants,
         Combinator::SlotAssignment => NormalDependencyInvalidationKind::SlottedElements,
         Combinator::Part => NormalDependencyInvalidationKind::Parts,
+        Combinator::Column => NormalDependencyInvalidationKind::Columns,
     })
 }
 
@@ -1358,7 +1369,10 @@ impl<'a, 'b> RelativeSelectorDependencyCollector<'a, 'b> {
                     Combinator::NextSibling | Combinator::LaterSibling => {
                         self.combinator_count.adjacent_or_next_siblings -= 1
                     },
-                    Combinator::Part | Combinator::PseudoElement | Combinator::SlotAssignment => (),
+                    Combinator::Part
+                    | Combinator::PseudoElement
+                    | Combinator::SlotAssignment
+                    | Combinator::Column => (),
                 }
             } else {
                 return true;
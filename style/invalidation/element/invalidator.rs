@@ -400,7 +400,8 @@ impl<'a> Invalidation<'a> {
             Combinator::Part
             | Combinator::SlotAssignment
             | Combinator::NextSibling
-            | Combinator::Child => false,
+            | Combinator::Child
+            | Combinator::Column => false,
         }
     }
 
@@ -422,6 +423,7 @@ impl<'a> Invalidation<'a> {
                 InvalidationKind::Descendant(DescendantInvalidationKind::Slotted)
             },
             Combinator::NextSibling | Combinator::LaterSibling => InvalidationKind::Sibling,
+            Combinator::Column => unreachable!("Column invalidations are never pushed"),
         }
     }
 }
@@ -1282,6 +1284,12 @@ where
                     .selector
                     .combinator_at_parse_order(next_invalidation.offset - 1);
 
+                if matches!(next_combinator, Combinator::Column) {
+                    // The cells of the columns of this element aren't reachable
+                    // from here, see `NormalDependencyInvalidationKind::Columns`.
+                    continue;
+                }
+
                 if matches!(next_combinator, Combinator::PseudoElement)
                     && self.processor.invalidates_on_pseudo_element()
                 {
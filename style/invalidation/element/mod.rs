@@ -11,3 +11,4 @@ pub mod invalidator;
 pub mod relative_selector;
 pub mod restyle_hints;
 pub mod state_and_attributes;
+pub mod table_columns;
@@ -51,6 +51,10 @@ where
     descendant_invalidations: &'a mut DescendantInvalidationLists<'selectors>,
     sibling_invalidations: &'a mut InvalidationVector<'selectors>,
     invalidates_self: bool,
+    /// Whether we only look for dependencies of the cells in the columns of
+    /// the element, see `NormalDependencyInvalidationKind::Columns`.
+    only_columns: bool,
+    invalidates_columns: bool,
 }
 
 /// An invalidation processor for style changes due to state and attribute
@@ -92,6 +96,178 @@ impl<'a, 'b: 'a, E: TElement + 'b> StateAndAttrInvalidationProcessor<'a, 'b, E>
     }
 }
 
+impl<'a, 'b: 'a, E: 'a> StateAndAttrInvalidationProcessor<'a, 'b, E>
+where
+    E: TElement,
+{
+    /// Returns whether the state or attribute changes of the element may
+    /// affect the cells in its columns, through the column combinator.
+    ///
+    /// Those cells aren't reachable from the element during the traversal, so
+    /// the embedder needs to check this before it, and invalidate them with
+    /// `table_columns::invalidate_cells_for_column_element`.
+    pub fn invalidates_columns(&mut self) -> bool {
+        let mut descendant_invalidations = DescendantInvalidationLists::default();
+        let mut sibling_invalidations = InvalidationVector::new();
+        self.collect_dependencies(
+            self.element,
+            &mut descendant_invalidations,
+            &mut sibling_invalidations,
+            /* only_columns = */ true,
+        )
+        .is_some_and(|(_, invalidates_columns)| invalidates_columns)
+    }
+
+    /// Collects the dependencies of the changes in the snapshot of `element`,
+    /// returning whether it needs to be restyled and whether the cells in its
+    /// columns need to be restyled, or `None` if nothing changed.
+    fn collect_dependencies(
+        &mut self,
+        element: E,
+        descendant_invalidations: &mut DescendantInvalidationLists<'a>,
+        sibling_invalidations: &mut InvalidationVector<'a>,
+        only_columns: bool,
+    ) -> Option<(bool, bool)> {
+        debug_assert_eq!(element, self.element);
+        debug_assert!(element.has_snapshot(), "Why bothering?");
+
+        let wrapper = ElementWrapper::new(element, &*self.shared_context.snapshot_map);
+
+        let state_changes = wrapper.state_changes();
+        let snapshot = wrapper.snapshot()?;
+
+        if !snapshot.has_attrs() && !snapshot.has_custom_states() && state_changes.is_empty() {
+            return None;
+        }
+
+        let mut classes_removed = SmallVec::<[Atom; 8]>::new();
+        let mut classes_added = SmallVec::<[Atom; 8]>::new();
+        if snapshot.class_changed() {
+            // TODO(emilio): Do this more efficiently!
+            snapshot.each_class(|c| {
+                if !element.has_class(c, CaseSensitivity::CaseSensitive) {
+                    classes_removed.push(c.0.clone())
+                }
+            });
+
+            element.each_class(|c| {
+                if !snapshot.has_class(c, CaseSensitivity::CaseSensitive) {
+                    classes_added.push(c.0.clone())
+                }
+            })
+        }
+
+        let mut custom_states_removed = SmallVec::<[AtomIdent; 8]>::new();
+        let mut custom_states_added = SmallVec::<[AtomIdent; 8]>::new();
+        if snapshot.has_custom_states() {
+            snapshot.each_custom_state(|s| {
+                if !element.has_custom_state(s) {
+                    custom_states_removed.push(s.clone())
+                }
+            });
+            element.each_custom_state(|s| {
+                if !snapshot.has_custom_state(s) {
+                    custom_states_added.push(s.clone())
+                }
+            })
+        }
+
+        let mut id_removed = None;
+        let mut id_added = None;
+        if snapshot.id_changed() {
+            let old_id = snapshot.id_attr();
+            let current_id = element.id();
+
+            if old_id != current_id {
+                id_removed = old_id;
+                id_added = current_id;
+            }
+        }
+
+        if log_enabled!(::log::Level::Debug) {
+            debug!("Collecting changes for: {:?}", element);
+            if !state_changes.is_empty() {
+                debug!(" > state: {:?}", state_changes);
+            }
+            if snapshot.id_changed() {
+                debug!(" > id changed: +{:?} -{:?}", id_added, id_removed);
+            }
+            if snapshot.class_changed() {
+                debug!(
+                    " > class changed: +{:?} -{:?}",
+                    classes_added, classes_removed
+                );
+            }
+            let mut attributes_changed = false;
+            snapshot.each_attr_changed(|_| {
+                attributes_changed = true;
+            });
+            if attributes_changed {
+                debug!(
+                    " > attributes changed, old: {}",
+                    snapshot.debug_list_attributes()
+                )
+            }
+        }
+
+        let lookup_element = if element.implemented_pseudo_element().is_some() {
+            element.pseudo_element_originating_element().unwrap()
+        } else {
+            element
+        };
+
+        let mut shadow_rule_datas = SmallVec::<[_; 3]>::new();
+        let matches_document_author_rules =
+            element.each_applicable_non_document_style_rule_data(|data, host| {
+                shadow_rule_datas.push((data, host.opaque()))
+            });
+
+        let invalidated = {
+            let mut collector = Collector {
+                wrapper,
+                lookup_element,
+                state_changes,
+                element,
+                snapshot: &snapshot,
+                matching_context: &mut self.matching_context,
+                removed_id: id_removed,
+                added_id: id_added,
+                classes_removed: &classes_removed,
+                classes_added: &classes_added,
+                custom_states_removed: &custom_states_removed,
+                custom_states_added: &custom_states_added,
+                descendant_invalidations,
+                sibling_invalidations,
+                invalidates_self: false,
+                only_columns,
+                invalidates_columns: false,
+            };
+
+            let document_origins = if !matches_document_author_rules {
+                OriginSet::ORIGIN_USER_AGENT | OriginSet::ORIGIN_USER
+            } else {
+                OriginSet::all()
+            };
+
+            for (cascade_data, origin) in self.shared_context.stylist.iter_origins() {
+                if document_origins.contains(origin.into()) {
+                    collector
+                        .collect_dependencies_in_invalidation_map(cascade_data.invalidation_map());
+                }
+            }
+
+            for &(ref data, ref host) in &shadow_rule_datas {
+                collector.matching_context.current_host = Some(host.clone());
+                collector.collect_dependencies_in_invalidation_map(data.invalidation_map());
+            }
+
+            (collector.invalidates_self, collector.invalidates_columns)
+        };
+
+        Some(invalidated)
+    }
+}
+
 /// Checks a dependency against a given element and wrapper, to see if something
 /// changed.
 pub fn check_dependency<E, W>(
@@ -264,140 +440,13 @@ where
         descendant_invalidations: &mut DescendantInvalidationLists<'a>,
         sibling_invalidations: &mut InvalidationVector<'a>,
     ) -> bool {
-        debug_assert_eq!(element, self.element);
-        debug_assert!(element.has_snapshot(), "Why bothering?");
-
-        let wrapper = ElementWrapper::new(element, &*self.shared_context.snapshot_map);
-
-        let state_changes = wrapper.state_changes();
-        let Some(snapshot) = wrapper.snapshot() else {
-            return false;
-        };
-
-        if !snapshot.has_attrs() && !snapshot.has_custom_states() && state_changes.is_empty() {
+        let Some((invalidated_self, _)) = self.collect_dependencies(
+            element,
+            descendant_invalidations,
+            sibling_invalidations,
+            /* only_columns = */ false,
+        ) else {
             return false;
-        }
-
-        let mut classes_removed = SmallVec::<[Atom; 8]>::new();
-        let mut classes_added = SmallVec::<[Atom; 8]>::new();
-        if snapshot.class_changed() {
-            // TODO(emilio): Do this more efficiently!
-            snapshot.each_class(|c| {
-                if !element.has_class(c, CaseSensitivity::CaseSensitive) {
-                    classes_removed.push(c.0.clone())
-                }
-            });
-
-            element.each_class(|c| {
-                if !snapshot.has_class(c, CaseSensitivity::CaseSensitive) {
-                    classes_added.push(c.0.clone())
-                }
-            })
-        }
-
-        let mut custom_states_removed = SmallVec::<[AtomIdent; 8]>::new();
-        let mut custom_states_added = SmallVec::<[AtomIdent; 8]>::new();
-        if snapshot.has_custom_states() {
-            snapshot.each_custom_state(|s| {
-                if !element.has_custom_state(s) {
-                    custom_states_removed.push(s.clone())
-                }
-            });
-            element.each_custom_state(|s| {
-                if !snapshot.has_custom_state(s) {
-                    custom_states_added.push(s.clone())
-                }
-            })
-        }
-
-        let mut id_removed = None;
-        let mut id_added = None;
-        if snapshot.id_changed() {
-            let old_id = snapshot.id_attr();
-            let current_id = element.id();
-
-            if old_id != current_id {
-                id_removed = old_id;
-                id_added = current_id;
-            }
-        }
-
-        if log_enabled!(::log::Level::Debug) {
-            debug!("Collecting changes for: {:?}", element);
-            if !state_changes.is_empty() {
-                debug!(" > state: {:?}", state_changes);
-            }
-            if snapshot.id_changed() {
-                debug!(" > id changed: +{:?} -{:?}", id_added, id_removed);
-            }
-            if snapshot.class_changed() {
-                debug!(
-                    " > class changed: +{:?} -{:?}",
-                    classes_added, classes_removed
-                );
-            }
-            let mut attributes_changed = false;
-            snapshot.each_attr_changed(|_| {
-                attributes_changed = true;
-            });
-            if attributes_changed {
-                debug!(
-                    " > attributes changed, old: {}",
-                    snapshot.debug_list_attributes()
-                )
-            }
-        }
-
-        let lookup_element = if element.implemented_pseudo_element().is_some() {
-            element.pseudo_element_originating_element().unwrap()
-        } else {
-            element
-        };
-
-        let mut shadow_rule_datas = SmallVec::<[_; 3]>::new();
-        let matches_document_author_rules =
-            element.each_applicable_non_document_style_rule_data(|data, host| {
-                shadow_rule_datas.push((data, host.opaque()))
-            });
-
-        let invalidated_self = {
-            let mut collector = Collector {
-                wrapper,
-                lookup_element,
-                state_changes,
-                element,
-                snapshot: &snapshot,
-                matching_context: &mut self.matching_context,
-                removed_id: id_removed,
-                added_id: id_added,
-                classes_removed: &classes_removed,
-                classes_added: &classes_added,
-                custom_states_removed: &custom_states_removed,
-                custom_states_added: &custom_states_added,
-                descendant_invalidations,
-                sibling_invalidations,
-                invalidates_self: false,
-            };
-
-            let document_origins = if !matches_document_author_rules {
-                OriginSet::ORIGIN_USER_AGENT | OriginSet::ORIGIN_USER
-            } else {
-                OriginSet::all()
-            };
-
-            for (cascade_data, origin) in self.shared_context.stylist.iter_origins() {
-                if document_origins.contains(origin.into()) {
-                    collector
-                        .collect_dependencies_in_invalidation_map(cascade_data.invalidation_map());
-                }
-            }
-
-            for &(ref data, ref host) in &shadow_rule_datas {
-                collector.matching_context.current_host = Some(host.clone());
-                collector.collect_dependencies_in_invalidation_map(data.invalidation_map());
-            }
-
-            collector.invalidates_self
         };
 
         // If we generated a ton of descendant invalidations, it's probably not
@@ -571,6 +620,13 @@ where
     fn note_dependency(&mut self, dependency: &'selectors Dependency, set_scope: bool) {
         debug_assert!(self.dependency_may_be_relevant(dependency));
         let invalidation_kind = dependency.invalidation_kind();
+        if matches!(
+            invalidation_kind,
+            DependencyInvalidationKind::Normal(NormalDependencyInvalidationKind::Columns)
+        ) {
+            self.invalidates_columns = true;
+            return;
+        }
         if matches!(
             invalidation_kind,
             DependencyInvalidationKind::Normal(NormalDependencyInvalidationKind::Element)
@@ -655,9 +711,17 @@ where
             DependencyInvalidationKind::FullSelector | DependencyInvalidationKind::Relative(_) => {
                 unreachable!()
             },
-            DependencyInvalidationKind::Scope(_) => true,
+            DependencyInvalidationKind::Scope(_) => !self.only_columns,
             DependencyInvalidationKind::Normal(kind) => match kind {
+                // Inner selectors may lead to a column dependency outwards.
+                NormalDependencyInvalidationKind::Element if self.only_columns => {
+                    dependency.next.is_some()
+                },
                 NormalDependencyInvalidationKind::Element => !self.invalidates_self,
+                NormalDependencyInvalidationKind::Columns => {
+                    self.only_columns && !self.invalidates_columns
+                },
+                _ if self.only_columns => false,
                 NormalDependencyInvalidationKind::SlottedElements => {
                     self.element.is_html_slot_element()
                 },
@@ -691,7 +755,8 @@ pub(crate) fn push_invalidation<'a>(
             true
         },
         DependencyInvalidationKind::Normal(kind) => match kind {
-            NormalDependencyInvalidationKind::Element => unreachable!(),
+            NormalDependencyInvalidationKind::Element
+            | NormalDependencyInvalidationKind::Columns => unreachable!(),
             NormalDependencyInvalidationKind::ElementAndDescendants => {
                 descendant_invalidations.dom_descendants.push(invalidation);
                 true
@@ -731,6 +796,7 @@ pub(crate) fn dependency_may_be_relevant<E: TElement>(
             NormalDependencyInvalidationKind::Element => !already_invalidated_self,
             NormalDependencyInvalidationKind::SlottedElements => element.is_html_slot_element(),
             NormalDependencyInvalidationKind::Parts => element.shadow_root().is_some(),
+            NormalDependencyInvalidationKind::Columns => false,
             NormalDependencyInvalidationKind::ElementAndDescendants
             | NormalDependencyInvalidationKind::Siblings
             | NormalDependencyInvalidationKind::Descendants => true,
@@ -0,0 +1,77 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Invalidation of table cells whose style depends on the columns of their
+//! table, through the column combinator, `:nth-col()` and `:nth-last-col()`.
+//!
+//! The cells aren't reachable from the column elements they were matched
+//! against during the traversal, and their columns depend on the structure of
+//! the whole table, so the embedder needs to call into this before restyling,
+//! while it still has exclusive access to the table.
+
+use crate::dom::{TElement, TNode};
+use selectors::matching::ElementSelectorFlags;
+use selectors::CellColumns;
+
+/// Finds the cells of `table` that were matched against `column_element`
+/// through the column combinator, calling `invalidated` for each of them.
+///
+/// This needs to be called when an attribute or the state of a column element
+/// changes in a way that affects its column dependencies, that is, when
+/// `StateAndAttrInvalidationProcessor::invalidates_columns` returns true for
+/// it. The subtree of each invalidated cell needs to be restyled, since the
+/// column combinator may be followed by other combinators.
+pub fn invalidate_cells_for_column_element<E, F>(table: E, column_element: E, invalidated: F)
+where
+    E: TElement,
+    F: FnMut(E),
+{
+    if !column_element.has_selector_flags(ElementSelectorFlags::HAS_COLUMN_SELECTOR) {
+        return;
+    }
+    let column_element = column_element.opaque();
+    invalidate_cells(table, invalidated, |cell, columns| {
+        (columns.start..columns.start.saturating_add(columns.span)).any(|column| {
+            cell.column_element(column)
+                .is_some_and(|element| element.opaque() == column_element)
+        })
+    })
+}
+
+/// Finds the cells of `table` whose style may depend on its columns, calling
+/// `invalidated` for each of them.
+///
+/// This needs to be called when the structure of the table changes, that is,
+/// when rows, cells or column elements are inserted or removed, or when their
+/// spans change. As above, the subtree of each invalidated cell needs to be
+/// restyled.
+pub fn invalidate_cells_for_structure_change<E, F>(table: E, invalidated: F)
+where
+    E: TElement,
+    F: FnMut(E),
+{
+    invalidate_cells(table, invalidated, |_, _| true)
+}
+
+fn invalidate_cells<E, F, P>(table: E, mut invalidated: F, mut depends_on_change: P)
+where
+    E: TElement,
+    F: FnMut(E),
+    P: FnMut(E, CellColumns) -> bool,
+{
+    for node in table.as_node().dom_descendants() {
+        let Some(cell) = node.as_element() else {
+            continue;
+        };
+        if !cell.has_selector_flags(ElementSelectorFlags::HAS_COLUMN_SELECTOR) {
+            continue;
+        }
+        let Some(columns) = cell.table_cell_columns() else {
+            continue;
+        };
+        if depends_on_change(cell, columns) {
+            invalidated(cell);
+        }
+    }
+}
@@ -66,6 +66,7 @@ pub mod applicable_declarations;
 pub mod author_styles;
 pub mod bezier;
 pub mod bloom;
+pub mod cascade_inspection;
 pub mod color;
 #[path = "properties/computed_value_flags.rs"]
 pub mod computed_value_flags;
@@ -79,6 +80,7 @@ pub mod dom_apis;
 pub mod driver;
 pub mod error_reporting;
 pub mod font_face;
+pub mod font_matching;
 pub mod font_metrics;
 #[cfg(feature = "gecko")]
 #[allow(unsafe_code)]
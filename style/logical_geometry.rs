@@ -1653,6 +1653,14 @@ impl PhysicalSide {
             Self::Left => Self::Right,
         }
     }
+
+    /// Returns the axis this side is perpendicular to.
+    pub fn axis(self) -> PhysicalAxis {
+        match self {
+            Self::Top | Self::Bottom => PhysicalAxis::Vertical,
+            Self::Right | Self::Left => PhysicalAxis::Horizontal,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
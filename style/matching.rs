@@ -699,14 +699,14 @@ trait PrivateMatchMethods: TElement {
             context,
             old_values.as_deref(),
             new_values,
-            pseudo_element,
+            pseudo_element.clone(),
         );
 
         let might_need_transitions_update = self.might_need_transitions_update(
             context,
             old_values.as_deref(),
             new_values,
-            pseudo_element,
+            pseudo_element.clone(),
         );
 
         let mut after_change_style = None;
@@ -726,7 +726,9 @@ trait PrivateMatchMethods: TElement {
         // Starting animations is expensive, because we have to recalculate the style
         // for all the keyframes. We only want to do this if we think that there's a
         // chance that the animations really changed.
-        if needs_animations_update {
+        let script_animations_need_resolution =
+            animation_set.script_animations_need_resolution(old_values.as_deref(), new_values);
+        if needs_animations_update || script_animations_need_resolution {
             let mut resolver = StyleResolverForElement::new(
                 *self,
                 context,
@@ -734,14 +736,29 @@ trait PrivateMatchMethods: TElement {
                 PseudoElementResolution::IfApplicable,
             );
 
-            animation_set.update_animations_for_new_style::<Self>(
-                *self,
-                &shared_context,
-                &new_values,
-                &mut resolver,
-            );
+            if needs_animations_update {
+                animation_set.update_animations_for_new_style::<Self>(
+                    *self,
+                    &shared_context,
+                    &new_values,
+                    &mut resolver,
+                );
+            }
+
+            if script_animations_need_resolution {
+                animation_set.resolve_script_animations::<Self>(
+                    *self,
+                    &shared_context,
+                    old_values.as_deref(),
+                    &new_values,
+                    &mut resolver,
+                );
+            }
         }
 
+        animation_set
+            .update_script_animation_play_states(shared_context.current_time_for_animations);
+
         animation_set.update_transitions_for_new_style(
             might_need_transitions_update,
             &shared_context,
@@ -8,10 +8,10 @@ use crate::context::QuirksMode;
 use crate::error_reporting::{ContextualParseError, ParseErrorReporter};
 use crate::stylesheets::{CssRuleType, CssRuleTypes, Namespaces, Origin, UrlExtraData};
 use crate::use_counters::UseCounters;
-use cssparser::{Parser, SourceLocation, UnicodeRange};
+use cssparser::{serialize_identifier, Parser, ParserInput, SourceLocation, UnicodeRange};
 use selectors::parser::ParseRelative;
 use std::borrow::Cow;
-use style_traits::{OneOrMoreSeparated, ParseError, ParsingMode, Separator};
+use style_traits::{OneOrMoreSeparated, ParseError, ParsingMode, Separator, TypedValue};
 
 /// Nesting context for parsing rules.
 #[derive(Clone, Copy)]
@@ -180,6 +180,32 @@ impl<'a> ParserContext<'a> {
     }
 }
 
+/// Why `Parse::from_typed` couldn't build a value out of a Typed OM value.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FromTypedError {
+    /// The type doesn't know how to build this kind of value. Keywords may
+    /// still match its grammar, see `parse_typed_keyword`, but other values
+    /// don't.
+    Unsupported,
+    /// The value doesn't match the type, even if its serialization could
+    /// parse as one, like `CSS.number(0)` for a `<length>`.
+    TypeMismatch,
+}
+
+/// Matches a Typed OM keyword against a grammar, as the single identifier it
+/// stands for, for the types that don't build keywords in `Parse::from_typed`.
+pub fn parse_typed_keyword<T, F>(keyword: &str, parse: F) -> Result<T, FromTypedError>
+where
+    F: for<'i, 't> FnOnce(&mut Parser<'i, 't>) -> Result<T, ParseError<'i>>,
+{
+    let mut ident = String::new();
+    serialize_identifier(keyword, &mut ident).unwrap();
+    let mut input = ParserInput::new(&ident);
+    Parser::new(&mut input)
+        .parse_entirely(parse)
+        .map_err(|_| FromTypedError::TypeMismatch)
+}
+
 /// A trait to abstract parsing of a specified value given a `ParserContext` and
 /// CSS input.
 ///
@@ -204,6 +230,18 @@ pub trait Parse: Sized {
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self, ParseError<'i>>;
+
+    /// Builds a value of this type out of a Typed OM value, without going
+    /// through its CSS text.
+    ///
+    /// This must never accept a value that `parse` would reject. Keywords
+    /// that this returns `FromTypedError::Unsupported` for are matched against
+    /// `parse` by the callers, see `parse_typed_keyword`.
+    ///
+    /// `#[derive(Parse)]` implements this for enums that only have keywords.
+    fn from_typed(_context: &ParserContext, _value: &TypedValue) -> Result<Self, FromTypedError> {
+        Err(FromTypedError::Unsupported)
+    }
 }
 
 impl<T> Parse for Vec<T>
@@ -229,6 +267,10 @@ where
     ) -> Result<Self, ParseError<'i>> {
         T::parse(context, input).map(Box::new)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        T::from_typed(context, value).map(Box::new)
+    }
 }
 
 impl Parse for crate::OwnedStr {
@@ -343,6 +343,7 @@ class Longhand(Property):
         initial_value=None,
         initial_specified_value=None,
         parse_method='parse',
+        from_typed_method=None,
         spec=None,
         animation_type="normal",
         keyword=None,
@@ -383,6 +384,12 @@ class Longhand(Property):
         self.flags += self.affects_flags()
 
         self.parse_method = parse_method
+        # The method that builds the specified value out of a Typed OM value,
+        # see `Parse::from_typed`. Properties with a custom parse method need
+        # to name one that matches the same grammar, if any.
+        if from_typed_method is None and parse_method == "parse":
+            from_typed_method = "from_typed"
+        self.from_typed_method = from_typed_method
         self.initial_value = initial_value
         self.initial_specified_value = initial_specified_value
         self.keyword = keyword
@@ -46,7 +46,7 @@ use std::iter::Zip;
 use std::slice::Iter;
 use style_traits::{
     CssString, CssStringWriter, CssWriter, ParseError, ParsingMode, StyleParseErrorKind, ToCss,
-    TypedValue,
+    TypedValue, TypedValueError,
 };
 use thin_vec::ThinVec;
 
@@ -1467,6 +1467,31 @@ pub fn parse_one_declaration_into(
         })
 }
 
+/// Builds the declarations of a given property out of a list of Typed OM
+/// values, like `parse_one_declaration_into` does out of CSS text.
+#[inline]
+pub fn parse_typed_declaration_into(
+    declarations: &mut SourcePropertyDeclaration,
+    id: PropertyId,
+    values: &[TypedValue],
+    origin: Origin,
+    url_data: &UrlExtraData,
+    quirks_mode: QuirksMode,
+    rule_type: CssRuleType,
+) -> Result<(), TypedValueError> {
+    let context = ParserContext::new(
+        origin,
+        url_data,
+        Some(rule_type),
+        ParsingMode::DEFAULT,
+        quirks_mode,
+        /* namespaces = */ Default::default(),
+        None,
+        None,
+    );
+    PropertyDeclaration::from_typed_into(declarations, id, values, &context)
+}
+
 /// A struct to parse property declarations.
 struct PropertyDeclarationParser<'a, 'b: 'a, 'i> {
     context: &'a ParserContext<'b>,
@@ -16,7 +16,7 @@ pub mod ${property.ident} {
     #[allow(unused_imports)]
     use cssparser::{Parser, BasicParseError, Token};
     #[allow(unused_imports)]
-    use crate::parser::{Parse, ParserContext};
+    use crate::parser::{FromTypedError, Parse, ParserContext};
     #[allow(unused_imports)]
     use crate::properties::{self, UnparsedValue, ShorthandId};
     #[allow(unused_imports)]
@@ -163,6 +163,24 @@ pub mod ${property.ident} {
             .map(PropertyDeclaration::${property.camel_case})
     }
 
+    pub fn from_typed_declared(
+        context: &ParserContext,
+        values: &[style_traits::TypedValue],
+    ) -> Result<PropertyDeclaration, FromTypedError> {
+        % if property.vector:
+        from_typed(context, values)
+        % else:
+        match *values {
+            [ref value] => from_typed(context, value),
+            _ => Err(FromTypedError::TypeMismatch),
+        }
+        % endif
+        % if property.boxed:
+            .map(Box::new)
+        % endif
+            .map(PropertyDeclaration::${property.camel_case})
+    }
+
     % if property.vector:
     pub mod single_value {
         use super::*;
@@ -256,6 +274,31 @@ pub mod ${property.ident} {
         }
     }
     % endif
+
+    /// Builds a specified value out of a Typed OM value, see
+    /// `Parse::from_typed`.
+    #[allow(unused_variables)]
+    pub fn from_typed(
+        context: &ParserContext,
+        value: &style_traits::TypedValue,
+    ) -> Result<SpecifiedValue, FromTypedError> {
+        % if property.keyword:
+        <SpecifiedValue as Parse>::from_typed(context, value)
+        % else:
+        % if property.from_typed_method:
+        match specified::${property.predefined_type}::${property.from_typed_method}(context, value) {
+            Err(FromTypedError::Unsupported) => {},
+            result => return result,
+        }
+        % endif
+        match *value {
+            style_traits::TypedValue::Keyword(ref keyword) => {
+                crate::parser::parse_typed_keyword(keyword, |input| parse(context, input))
+            },
+            _ => Err(FromTypedError::TypeMismatch),
+        }
+        % endif
+    }
     % if property.vector:
     } // single_value
     % endif
@@ -504,6 +547,33 @@ pub mod ${property.ident} {
         Ok(SpecifiedValue(v.into()))
     }
 
+    /// Builds a specified value out of a list of Typed OM values, see
+    /// `Parse::from_typed`.
+    pub fn from_typed(
+        context: &ParserContext,
+        values: &[style_traits::TypedValue],
+    ) -> Result<SpecifiedValue, FromTypedError> {
+        % if allow_empty or property.vector.none_value:
+        if let [style_traits::TypedValue::Keyword(ref keyword)] = *values {
+            if keyword.eq_ignore_ascii_case("none") {
+                % if allow_empty:
+                return Ok(SpecifiedValue(Default::default()))
+                % else:
+                return Ok(SpecifiedValue(crate::OwnedSlice::from(vec![${property.vector.none_value}])))
+                % endif
+            }
+        }
+        % endif
+        if values.is_empty() {
+            return Err(FromTypedError::TypeMismatch);
+        }
+        let v = values
+            .iter()
+            .map(|value| single_value::from_typed(context, value))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(SpecifiedValue(v.into()))
+    }
+
     pub use self::single_value::SpecifiedValue as SingleSpecifiedValue;
 
     % if not property.vector.simple_bindings and engine == "gecko":
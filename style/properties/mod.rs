@@ -42,7 +42,7 @@ use crate::dom::AttributeTracker;
 #[cfg(feature = "gecko")]
 use crate::gecko_bindings::structs::{CSSPropertyId, NonCustomCSSPropertyId, RefPtr};
 use crate::logical_geometry::WritingMode;
-use crate::parser::ParserContext;
+use crate::parser::{parse_typed_keyword, ParserContext};
 use crate::stylesheets::CssRuleType;
 use crate::stylesheets::Origin;
 use crate::stylist::Stylist;
@@ -58,7 +58,7 @@ use std::{
 };
 use style_traits::{
     CssString, CssWriter, KeywordsCollectFn, ParseError, ParsingMode, SpecifiedValueInfo, ToCss,
-    ToTyped, TypedValue,
+    ToTyped, TypedValue, TypedValueError,
 };
 
 bitflags! {
@@ -168,6 +168,7 @@ impl ToTyped for WideKeywordDeclaration {
 
 /// An unparsed declaration that contains `var()` functions.
 #[derive(Clone, PartialEq, ToCss, ToShmem, MallocSizeOf, ToTyped)]
+#[typed_value(derive_fields)]
 pub struct VariableDeclaration {
     /// The id of the property this declaration represents.
     #[css(skip)]
@@ -179,11 +180,13 @@ pub struct VariableDeclaration {
 
 /// A custom property declaration value is either an unparsed value or a CSS
 /// wide-keyword.
-#[derive(Clone, PartialEq, ToCss, ToShmem)]
+#[derive(Clone, PartialEq, ToCss, ToShmem, ToTyped)]
+#[typed_value(derive_fields)]
 pub enum CustomDeclarationValue {
     /// An unparsed value.
     Unparsed(Arc<custom_properties::SpecifiedValue>),
     /// An already-parsed value.
+    #[typed_value(todo)]
     Parsed(Arc<crate::properties_and_values::value::SpecifiedValue>),
     /// A wide keyword.
     CSSWideKeyword(CSSWideKeyword),
@@ -191,6 +194,7 @@ pub enum CustomDeclarationValue {
 
 /// A custom property declaration with the property name and the declared value.
 #[derive(Clone, PartialEq, ToCss, ToShmem, MallocSizeOf, ToTyped)]
+#[typed_value(derive_fields)]
 pub struct CustomDeclaration {
     /// The name of the custom property.
     #[css(skip)]
@@ -980,6 +984,111 @@ impl PropertyDeclaration {
         }
         Ok(())
     }
+
+    /// Builds the declarations of the given property out of a list of Typed
+    /// OM values, validating them against the grammar of the property.
+    ///
+    /// This is the typed counterpart of `parse_into`: CSS-wide keywords and
+    /// the values of longhands are turned into declarations directly, see
+    /// `Parse::from_typed`, and keywords are matched against the grammar of
+    /// the property as the identifiers they stand for. Only `CSSUnparsedValue`s
+    /// go through their CSS text, since they have to be parsed as token
+    /// streams anyway.
+    ///
+    /// https://drafts.css-houdini.org/css-typed-om-1/#create-an-internal-representation
+    pub fn from_typed_into(
+        declarations: &mut SourcePropertyDeclaration,
+        id: PropertyId,
+        values: &[TypedValue],
+        context: &ParserContext,
+    ) -> Result<(), TypedValueError> {
+        assert!(declarations.is_empty());
+        debug_assert!(id.allowed_in(context), "{:?}", id);
+
+        let keyword = match *values {
+            [] => return Err(TypedValueError::TypeMismatch),
+            [ref value @ TypedValue::Unparsed(..)] => {
+                return Self::from_unparsed_into(declarations, id, value, context)
+            },
+            [TypedValue::Keyword(ref keyword)] => Some(keyword),
+            _ => None,
+        };
+        let wide_keyword = keyword.and_then(|keyword| CSSWideKeyword::from_ident(keyword).ok());
+
+        let non_custom_id = match id {
+            PropertyId::Custom(name) => {
+                let value = match (wide_keyword, keyword) {
+                    (Some(wk), _) => CustomDeclarationValue::CSSWideKeyword(wk),
+                    (None, Some(keyword)) => {
+                        let value = parse_typed_keyword(keyword, |input| {
+                            custom_properties::VariableValue::parse(input, context.url_data)
+                        })
+                        .map_err(|_| TypedValueError::TypeMismatch)?;
+                        CustomDeclarationValue::Unparsed(Arc::new(value))
+                    },
+                    // Custom properties only accept keywords and unparsed
+                    // values.
+                    (None, None) => return Err(TypedValueError::InvalidKind),
+                };
+                declarations.push(PropertyDeclaration::Custom(CustomDeclaration {
+                    name,
+                    value,
+                }));
+                return Ok(());
+            },
+            PropertyId::NonCustom(id) => id,
+        };
+        match non_custom_id.longhand_or_shorthand() {
+            Ok(longhand_id) => {
+                let declaration = match wide_keyword {
+                    Some(wk) => PropertyDeclaration::css_wide_keyword(longhand_id, wk),
+                    None => longhand_id
+                        .from_typed_value(context, values)
+                        .map_err(|_| TypedValueError::TypeMismatch)?,
+                };
+                declarations.push(declaration);
+            },
+            Err(shorthand_id) => match (wide_keyword, keyword) {
+                (Some(wk), _) => {
+                    if shorthand_id == ShorthandId::All {
+                        declarations.all_shorthand = AllShorthand::CSSWideKeyword(wk)
+                    } else {
+                        for longhand in shorthand_id.longhands() {
+                            declarations.push(PropertyDeclaration::css_wide_keyword(longhand, wk));
+                        }
+                    }
+                },
+                (None, Some(keyword)) => {
+                    parse_typed_keyword(keyword, |input| {
+                        shorthand_id.parse_into(declarations, context, input)
+                    })
+                    .map_err(|_| TypedValueError::TypeMismatch)?;
+                },
+                // Shorthands only accept keywords and unparsed values.
+                (None, None) => return Err(TypedValueError::InvalidKind),
+            },
+        }
+        if let Some(use_counters) = context.use_counters {
+            use_counters.non_custom_properties.record(non_custom_id);
+        }
+        Ok(())
+    }
+
+    /// Builds the declarations of the given property out of a
+    /// `CSSUnparsedValue`, by parsing its CSS text.
+    fn from_unparsed_into(
+        declarations: &mut SourcePropertyDeclaration,
+        id: PropertyId,
+        value: &TypedValue,
+        context: &ParserContext,
+    ) -> Result<(), TypedValueError> {
+        let mut css = String::new();
+        value.to_css(&mut CssWriter::new(&mut css)).unwrap();
+        let mut input = ParserInput::new(&css);
+        Parser::new(&mut input)
+            .parse_entirely(|input| Self::parse_into(declarations, id, context, input))
+            .map_err(|_| TypedValueError::TypeMismatch)
+    }
 }
 
 /// A PropertyDeclarationId without references, for use as a hash map key.
@@ -1432,6 +1541,16 @@ impl ToCss for UnparsedValue {
     }
 }
 
+impl ToTyped for UnparsedValue {
+    fn to_typed(&self) -> Option<TypedValue> {
+        // https://drafts.csswg.org/css-variables/#variables-in-shorthands
+        if self.from_shorthand.is_some() {
+            return None;
+        }
+        self.variable_value.to_typed()
+    }
+}
+
 /// A simple cache for properties that come from a shorthand and have variable
 /// references.
 ///
@@ -1451,21 +1570,42 @@ impl UnparsedValue {
         shorthand_cache: &'cache mut ShorthandsWithPropertyReferencesCache,
         attribute_tracker: &mut AttributeTracker,
     ) -> Cow<'cache, PropertyDeclaration> {
-        let invalid_at_computed_value_time = || {
+        self.try_substitute_variables(
+            longhand_id,
+            custom_properties,
+            stylist,
+            computed_context,
+            shorthand_cache,
+            attribute_tracker,
+        )
+        .unwrap_or_else(|()| {
             let keyword = if longhand_id.inherited() {
                 CSSWideKeyword::Inherit
             } else {
                 CSSWideKeyword::Initial
             };
             Cow::Owned(PropertyDeclaration::css_wide_keyword(longhand_id, keyword))
-        };
+        })
+    }
 
+    /// Substitutes the variable references of this value and parses the
+    /// result as a declaration of the given longhand, returning `Err(())` if
+    /// the declaration is invalid at computed-value time.
+    pub(crate) fn try_substitute_variables<'cache>(
+        &self,
+        longhand_id: LonghandId,
+        custom_properties: &ComputedCustomProperties,
+        stylist: &Stylist,
+        computed_context: &computed::Context,
+        shorthand_cache: &'cache mut ShorthandsWithPropertyReferencesCache,
+        attribute_tracker: &mut AttributeTracker,
+    ) -> Result<Cow<'cache, PropertyDeclaration>, ()> {
         if computed_context
             .builder
             .invalid_non_custom_properties
             .contains(longhand_id)
         {
-            return invalid_at_computed_value_time();
+            return Err(());
         }
 
         if let Some(shorthand_id) = self.from_shorthand {
@@ -1475,20 +1615,17 @@ impl UnparsedValue {
                 // doesn't like that, see:
                 //
                 // https://github.com/rust-lang/rust/issues/82146
-                return Cow::Borrowed(&shorthand_cache[&key]);
+                return Ok(Cow::Borrowed(&shorthand_cache[&key]));
             }
         }
 
-        let css = match custom_properties::substitute(
+        let css = custom_properties::substitute(
             &self.variable_value,
             custom_properties,
             stylist,
             computed_context,
             attribute_tracker,
-        ) {
-            Ok(css) => css,
-            Err(..) => return invalid_at_computed_value_time(),
-        };
+        )?;
 
         // As of this writing, only the base URL is used for property
         // values.
@@ -1516,16 +1653,18 @@ impl UnparsedValue {
         input.skip_whitespace();
 
         if let Ok(keyword) = input.try_parse(CSSWideKeyword::parse) {
-            return Cow::Owned(PropertyDeclaration::css_wide_keyword(longhand_id, keyword));
+            return Ok(Cow::Owned(PropertyDeclaration::css_wide_keyword(
+                longhand_id,
+                keyword,
+            )));
         }
 
         let shorthand = match self.from_shorthand {
             None => {
-                return match input.parse_entirely(|input| longhand_id.parse_value(&context, input))
-                {
-                    Ok(decl) => Cow::Owned(decl),
-                    Err(..) => invalid_at_computed_value_time(),
-                }
+                return input
+                    .parse_entirely(|input| longhand_id.parse_value(&context, input))
+                    .map(Cow::Owned)
+                    .map_err(|_| ())
             },
             Some(shorthand) => shorthand,
         };
@@ -1536,7 +1675,7 @@ impl UnparsedValue {
             .parse_into(&mut decls, &context, &mut input)
             .is_err()
         {
-            return invalid_at_computed_value_time();
+            return Err(());
         }
 
         for declaration in decls.declarations.drain(..) {
@@ -1553,7 +1692,7 @@ impl UnparsedValue {
 
         let key = (shorthand, longhand_id);
         match shorthand_cache.get(&key) {
-            Some(decl) => Cow::Borrowed(decl),
+            Some(decl) => Ok(Cow::Borrowed(decl)),
             // NOTE: Under normal circumstances we should always have a value, but when prefs
             // change we might hit this case. Consider something like `animation-timeline`, which
             // is a conditionally-enabled longhand of `animation`:
@@ -1565,7 +1704,7 @@ impl UnparsedValue {
             // `animation-timeline`, parse_into won't generate any declaration for it anymore, so
             // we haven't inserted in the cache. Computing to invalid / initial seems like the most
             // sensible thing to do here.
-            None => invalid_at_computed_value_time(),
+            None => Err(()),
         }
     }
 }
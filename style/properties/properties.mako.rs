@@ -20,7 +20,7 @@ use malloc_size_of::{MallocSizeOf, MallocSizeOfOps};
 use crate::computed_value_flags::*;
 use cssparser::Parser;
 use crate::media_queries::Device;
-use crate::parser::ParserContext;
+use crate::parser::{FromTypedError, ParserContext};
 use crate::selector_parser::PseudoElement;
 use crate::stylist::Stylist;
 use style_traits::{CssStringWriter, CssWriter, KeywordsCollectFn, ParseError, SpecifiedValueInfo, StyleParseErrorKind, ToCss, TypedValue, ToTyped};
@@ -902,6 +902,25 @@ impl LonghandId {
         (PARSE_PROPERTY[self as usize])(context, input)
     }
 
+    /// Builds a declaration of this property out of a list of Typed OM
+    /// values, see `Parse::from_typed`.
+    pub(super) fn from_typed_value(
+        self,
+        context: &ParserContext,
+        values: &[TypedValue],
+    ) -> Result<PropertyDeclaration, FromTypedError> {
+        type FromTypedFn = fn(
+            context: &ParserContext,
+            values: &[TypedValue],
+        ) -> Result<PropertyDeclaration, FromTypedError>;
+        static FROM_TYPED: [FromTypedFn; ${len(data.longhands)}] = [
+        % for property in data.longhands:
+            longhands::${property.ident}::from_typed_declared,
+        % endfor
+        ];
+        (FROM_TYPED[self as usize])(context, values)
+    }
+
     /// Return the relevant data to map a particular logical property into physical.
     fn logical_mapping_data(self) -> Option<&'static LogicalMappingData> {
         const LOGICAL_MAPPING_DATA: [Option<LogicalMappingData>; ${len(data.longhands)}] = [
@@ -1544,7 +1563,8 @@ pub mod style_structs {
                 self.animation_fill_mode_iter().eq(other.animation_fill_mode_iter()) &&
                 self.animation_iteration_count_iter().eq(other.animation_iteration_count_iter()) &&
                 self.animation_play_state_iter().eq(other.animation_play_state_iter()) &&
-                self.animation_timing_function_iter().eq(other.animation_timing_function_iter())
+                self.animation_timing_function_iter().eq(other.animation_timing_function_iter()) &&
+                self.animation_composition_iter().eq(other.animation_composition_iter())
             }
 
         % elif style_struct.name == "Column":
@@ -2090,6 +2110,30 @@ impl ComputedValuesInner {
         ))
     }
 
+    /// Gets the logical computed scroll margin from this style.
+    #[inline]
+    pub fn logical_scroll_margin(&self) -> LogicalMargin<&computed::Length> {
+        let margin_style = self.get_margin();
+        LogicalMargin::from_physical(self.writing_mode, SideOffsets2D::new(
+            &margin_style.scroll_margin_top,
+            &margin_style.scroll_margin_right,
+            &margin_style.scroll_margin_bottom,
+            &margin_style.scroll_margin_left,
+        ))
+    }
+
+    /// Gets the logical computed scroll padding from this style.
+    #[inline]
+    pub fn logical_scroll_padding(&self) -> LogicalMargin<&computed::NonNegativeLengthPercentageOrAuto> {
+        let padding_style = self.get_padding();
+        LogicalMargin::from_physical(self.writing_mode, SideOffsets2D::new(
+            &padding_style.scroll_padding_top,
+            &padding_style.scroll_padding_right,
+            &padding_style.scroll_padding_bottom,
+            &padding_style.scroll_padding_left,
+        ))
+    }
+
     /// Gets the logical position from this style.
     #[inline]
     pub fn logical_position(&self) -> LogicalMargin<&computed::Inset> {
@@ -2833,7 +2877,7 @@ macro_rules! longhand_properties_idents {
 #[cfg(feature = "gecko")]
 size_of_test!(ComputedValues, 256);
 #[cfg(feature = "servo")]
-size_of_test!(ComputedValues, 224);
+size_of_test!(ComputedValues, 248);
 
 // FFI relies on this.
 size_of_test!(Option<Arc<ComputedValues>>, 8);
@@ -2861,7 +2905,7 @@ const_assert!(std::mem::size_of::<longhands::${longhand.ident}::SpecifiedValue>(
 % endfor
 
 % if engine == "servo":
-% for effect_name in ["repaint", "recalculate_overflow", "rebuild_stacking_context", "rebuild_box"]:
+% for effect_name in ["repaint", "recalculate_overflow", "rebuild_stacking_context", "rebuild_box", "update_scroll_snap"]:
 pub(crate) fn restyle_damage_${effect_name} (old: &ComputedValues, new: &ComputedValues) -> bool {
     % for style_struct in data.active_style_structs():
         <% longhands_affected = [effect_name in longhand.servo_restyle_damage.split() for longhand in style_struct.longhands if not longhand.logical] %>
@@ -2884,4 +2928,12 @@ pub(crate) fn restyle_damage_${effect_name} (old: &ComputedValues, new: &Compute
     false
 }
 % endfor
+
+/// The names of the preferences that enable properties, which default to false.
+pub(crate) const PROPERTY_PREFS: &[&str] = &[
+<% property_prefs = set(p.servo_pref for p in data.longhands + data.shorthands + data.all_aliases() if p.servo_pref) %>
+% for pref in sorted(property_prefs):
+    "${pref}",
+% endfor
+];
 % endif
@@ -1287,7 +1287,6 @@ pub mod gap {
     }
 }
 
-#[cfg(feature = "gecko")]
 pub mod marker {
     pub use crate::properties::shorthands_generated::marker::*;
 
@@ -1739,7 +1738,6 @@ pub mod grid_area {
     }
 }
 
-#[cfg(feature = "gecko")]
 pub mod position_try {
     pub use crate::properties::shorthands_generated::position_try::*;
 
@@ -1768,7 +1766,12 @@ pub mod position_try {
         where
             W: fmt::Write,
         {
-            if let Some(o) = self.position_try_order {
+            // position-try-order is behind its own pref in Gecko only.
+            #[cfg(feature = "gecko")]
+            let order = self.position_try_order;
+            #[cfg(feature = "servo")]
+            let order = Some(self.position_try_order);
+            if let Some(o) = order {
                 if *o != PositionTryOrder::Normal {
                     o.to_css(dest)?;
                     dest.write_char(' ')?;
@@ -1779,7 +1782,6 @@ pub mod position_try {
     }
 }
 
-#[cfg(feature = "gecko")]
 fn timeline_to_css<W>(
     name: &[specified::TimelineName],
     axes: &[specified::ScrollAxis],
@@ -1804,7 +1806,6 @@ where
     Ok(())
 }
 
-#[cfg(feature = "gecko")]
 pub mod scroll_timeline {
     pub use crate::properties::shorthands_generated::scroll_timeline::*;
 
@@ -1847,7 +1848,6 @@ pub mod scroll_timeline {
     }
 }
 
-#[cfg(feature = "gecko")]
 pub mod view_timeline {
     pub use crate::properties::shorthands_generated::view_timeline::*;
 
@@ -3405,10 +3405,11 @@ pub mod animation {
                 return Ok(());
             }
 
-            if self
-                .animation_timeline
-                .map_or(false, |v| v.0.len() != 1 || !v.0[0].is_auto())
-            {
+            #[cfg(feature = "gecko")]
+            let animation_timeline = self.animation_timeline;
+            #[cfg(feature = "servo")]
+            let animation_timeline = Some(self.animation_timeline);
+            if animation_timeline.map_or(false, |v| v.0.len() != 1 || !v.0[0].is_auto()) {
                 return Ok(());
             }
 
@@ -3478,7 +3479,6 @@ pub mod animation {
     }
 }
 
-#[cfg(feature = "gecko")]
 pub mod mask {
     pub use crate::properties::shorthands_generated::mask::*;
 
@@ -3505,12 +3505,15 @@ pub mod mask {
                 mask_origin::single_value::SpecifiedValue::BorderBox => {
                     mask_clip::single_value::SpecifiedValue::BorderBox
                 },
+                #[cfg(feature = "gecko")]
                 mask_origin::single_value::SpecifiedValue::FillBox => {
                     mask_clip::single_value::SpecifiedValue::FillBox
                 },
+                #[cfg(feature = "gecko")]
                 mask_origin::single_value::SpecifiedValue::StrokeBox => {
                     mask_clip::single_value::SpecifiedValue::StrokeBox
                 },
+                #[cfg(feature = "gecko")]
                 mask_origin::single_value::SpecifiedValue::ViewBox => {
                     mask_clip::single_value::SpecifiedValue::ViewBox
                 },
@@ -3737,7 +3740,13 @@ pub mod mask {
                     writer.item(repeat)?;
                 }
 
-                if has_origin || (has_clip && *clip != Clip::NoClip) {
+                // A single box sets both the origin and the clip, so a clip
+                // other than `no-clip` needs the origin before it.
+                #[cfg(feature = "gecko")]
+                let clip_needs_origin = has_clip && *clip != Clip::NoClip;
+                #[cfg(feature = "servo")]
+                let clip_needs_origin = has_clip;
+                if has_origin || clip_needs_origin {
                     writer.item(origin)?;
                 }
 
@@ -3759,7 +3768,6 @@ pub mod mask {
     }
 }
 
-#[cfg(feature = "gecko")]
 pub mod mask_position {
     pub use crate::properties::shorthands_generated::mask_position::*;
 
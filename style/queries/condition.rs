@@ -232,6 +232,9 @@ pub enum QueryCondition {
     Style(StyleFeature),
     /// A -moz-pref() query.
     MozPref(MozPrefFeature),
+    /// A scroll-state() query, containing either a scroll-state feature or a
+    /// condition on them.
+    ScrollState(Box<QueryCondition>),
     /// [ <function-token> <any-value>? ) ] | [ ( <any-value>? ) ]
     GeneralEnclosed(String),
 }
@@ -269,6 +272,15 @@ impl ToCss for QueryCondition {
                 c.to_css(dest)?;
                 dest.write_char(')')
             },
+            QueryCondition::ScrollState(ref c) => {
+                dest.write_str("scroll-state")?;
+                if let QueryCondition::Feature(ref f) = **c {
+                    return f.to_css(dest);
+                }
+                dest.write_char('(')?;
+                c.to_css(dest)?;
+                dest.write_char(')')
+            },
             QueryCondition::Operation(ref list, op) => {
                 let mut iter = list.iter();
                 iter.next().unwrap().to_css(dest)?;
@@ -317,7 +329,7 @@ impl QueryCondition {
                     cond.visit(visitor);
                 }
             },
-            Self::InParens(ref cond) => cond.visit(visitor),
+            Self::InParens(ref cond) | Self::ScrollState(ref cond) => cond.visit(visitor),
         }
     }
 
@@ -417,6 +429,32 @@ impl QueryCondition {
         Err(feature_error)
     }
 
+    /// Parses the contents of a `scroll-state()` function, which can be a
+    /// scroll-state feature without parentheses, or a condition on them.
+    ///
+    /// https://drafts.csswg.org/css-conditional-5/#typedef-scroll-state-query
+    fn parse_scroll_state<'i>(
+        context: &ParserContext,
+        input: &mut Parser<'i, '_>,
+        feature_type: FeatureType,
+    ) -> Result<Self, ParseError<'i>> {
+        if !static_prefs::pref!("layout.css.scroll-state.enabled")
+            || feature_type != FeatureType::Container
+        {
+            return Err(input.new_custom_error(StyleParseErrorKind::UnspecifiedError));
+        }
+        if let Ok(feature) = input.try_parse(|input| {
+            QueryFeatureExpression::parse_in_parenthesis_block(
+                context,
+                input,
+                FeatureType::ScrollState,
+            )
+        }) {
+            return Ok(Self::Feature(feature));
+        }
+        Self::parse(context, input, FeatureType::ScrollState)
+    }
+
     fn try_parse_block<'i, T, F>(
         context: &ParserContext,
         input: &mut Parser<'i, '_>,
@@ -478,6 +516,14 @@ impl QueryCondition {
                             return Ok(Self::MozPref(feature));
                         }
                     },
+                    "scroll-state" => {
+                        let condition = Self::try_parse_block(context, input, start, |input| {
+                            Self::parse_scroll_state(context, input, feature_type)
+                        });
+                        if let Some(condition) = condition {
+                            return Ok(Self::ScrollState(Box::new(condition)));
+                        }
+                    },
                     _ => {},
                 }
             },
@@ -505,6 +551,7 @@ impl QueryCondition {
             QueryCondition::Not(ref c) => !c.matches(context, custom),
             QueryCondition::Style(ref c) => c.matches(context),
             QueryCondition::MozPref(ref c) => c.matches(context),
+            QueryCondition::ScrollState(ref c) => c.matches(context, custom),
             QueryCondition::Operation(ref conditions, op) => {
                 debug_assert!(!conditions.is_empty(), "We never create an empty op");
                 match op {
@@ -104,10 +104,10 @@ macro_rules! keyword_evaluator {
 
 /// Different flags or toggles that change how a expression is parsed or
 /// evaluated.
-#[derive(Clone, Copy, Debug, ToShmem)]
-pub struct FeatureFlags(u8);
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ToShmem)]
+pub struct FeatureFlags(u16);
 bitflags! {
-    impl FeatureFlags : u8 {
+    impl FeatureFlags : u16 {
         /// The feature should only be parsed in chrome and ua sheets.
         const CHROME_AND_UA_ONLY = 1 << 0;
         /// The feature requires a -webkit- prefix.
@@ -124,6 +124,16 @@ bitflags! {
         const VIEWPORT_DEPENDENT = 1 << 6;
         /// The feature evaluation depends on style queries.
         const STYLE = 1 << 7;
+        /// The feature requires a scroll-state container.
+        const CONTAINER_REQUIRES_SCROLL_STATE = 1 << 8;
+        /// The feature evaluation depends on the `stuck` scroll state.
+        const SCROLL_STATE_STUCK = 1 << 9;
+        /// The feature evaluation depends on the `snapped` scroll state.
+        const SCROLL_STATE_SNAPPED = 1 << 10;
+        /// The feature evaluation depends on the `scrollable` scroll state.
+        const SCROLL_STATE_SCROLLABLE = 1 << 11;
+        /// The feature evaluation depends on the `scrolled` scroll state.
+        const SCROLL_STATE_SCROLLED = 1 << 12;
     }
 }
 
@@ -145,6 +155,25 @@ impl FeatureFlags {
     pub fn container_axes(self) -> Self {
         self.intersection(Self::all_container_axes())
     }
+
+    /// Returns our subset of flags that the container type of a query
+    /// container needs to support.
+    pub fn container_requirements(self) -> Self {
+        self.intersection(Self::all_container_axes() | Self::CONTAINER_REQUIRES_SCROLL_STATE)
+    }
+
+    /// Returns all the scroll state flags.
+    pub fn all_scroll_states() -> Self {
+        Self::SCROLL_STATE_STUCK
+            | Self::SCROLL_STATE_SNAPPED
+            | Self::SCROLL_STATE_SCROLLABLE
+            | Self::SCROLL_STATE_SCROLLED
+    }
+
+    /// Returns our subset of scroll state flags.
+    pub fn scroll_states(self) -> Self {
+        self.intersection(Self::all_scroll_states())
+    }
 }
 
 /// Whether a feature allows ranges or not.
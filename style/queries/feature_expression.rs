@@ -27,6 +27,8 @@ pub enum FeatureType {
     Media,
     /// We're parsing a container feature.
     Container,
+    /// We're parsing a scroll-state container feature, inside `scroll-state()`.
+    ScrollState,
 }
 
 impl FeatureType {
@@ -36,11 +38,12 @@ impl FeatureType {
         #[cfg(feature = "servo")]
         use crate::servo::media_queries::MEDIA_FEATURES;
 
-        use crate::stylesheets::container_rule::CONTAINER_FEATURES;
+        use crate::stylesheets::container_rule::{CONTAINER_FEATURES, SCROLL_STATE_FEATURES};
 
         match *self {
             FeatureType::Media => &MEDIA_FEATURES,
             FeatureType::Container => &CONTAINER_FEATURES,
+            FeatureType::ScrollState => &SCROLL_STATE_FEATURES,
         }
     }
 
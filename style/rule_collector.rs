@@ -161,22 +161,17 @@ where
 
     fn collect_user_agent_rules(&mut self) {
         self.collect_stylist_rules(Origin::UserAgent);
-        #[cfg(feature = "gecko")]
         self.collect_view_transition_dynamic_rules();
     }
 
-    #[cfg(feature = "gecko")]
     fn collect_view_transition_dynamic_rules(&mut self) {
-        if !self
-            .pseudo_elements
-            .first()
-            .is_some_and(|p| p.is_named_view_transition())
-        {
-            return;
-        }
+        let pseudo = match self.pseudo_elements.first() {
+            Some(pseudo) if pseudo.is_named_view_transition() => pseudo,
+            _ => return,
+        };
         let len_before_vt_rules = self.rules.len();
         self.element
-            .synthesize_view_transition_dynamic_rules(self.rules);
+            .synthesize_view_transition_dynamic_rules(pseudo, self.rules);
         if cfg!(debug_assertions) && self.rules.len() != len_before_vt_rules {
             for declaration in &self.rules[len_before_vt_rules..] {
                 assert_eq!(declaration.level(), CascadeLevel::UANormal);
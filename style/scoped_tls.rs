@@ -8,6 +8,7 @@
 #![deny(missing_docs)]
 
 use crate::global_style_data::STYLO_MAX_THREADS;
+use smallvec::SmallVec;
 use std::cell::{Ref, RefCell, RefMut};
 use std::ops::DerefMut;
 
@@ -20,7 +21,10 @@ use std::ops::DerefMut;
 /// the Send bound.
 pub struct ScopedTLS<'scope, T: Send> {
     pool: Option<&'scope rayon::ThreadPool>,
-    slots: [RefCell<Option<T>>; STYLO_MAX_THREADS],
+    /// One slot per thread of the pool. Pools built by embedders can be
+    /// larger than the global one, in which case the slots are allocated on
+    /// the heap.
+    slots: SmallVec<[RefCell<Option<T>>; STYLO_MAX_THREADS]>,
 }
 
 /// The scoped TLS is `Sync` because no more than one worker thread can access a
@@ -31,10 +35,10 @@ impl<'scope, T: Send> ScopedTLS<'scope, T> {
     /// Create a new scoped TLS that will last as long as this rayon threadpool
     /// reference.
     pub fn new(pool: Option<&'scope rayon::ThreadPool>) -> Self {
-        debug_assert!(pool.map_or(true, |p| p.current_num_threads() <= STYLO_MAX_THREADS));
+        let num_threads = pool.map_or(1, |p| p.current_num_threads());
         ScopedTLS {
             pool,
-            slots: Default::default(),
+            slots: (0..num_threads).map(|_| RefCell::new(None)).collect(),
         }
     }
 
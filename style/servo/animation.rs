@@ -11,6 +11,7 @@ use crate::context::{CascadeInputs, SharedStyleContext};
 use crate::derives::*;
 use crate::dom::{OpaqueNode, TDocument, TElement, TNode};
 use crate::properties::animated_properties::{AnimationValue, AnimationValueMap};
+use crate::properties::longhands::animation_composition::computed_value::single_value::T as AnimationComposition;
 use crate::properties::longhands::animation_direction::computed_value::single_value::T as AnimationDirection;
 use crate::properties::longhands::animation_fill_mode::computed_value::single_value::T as AnimationFillMode;
 use crate::properties::longhands::animation_play_state::computed_value::single_value::T as AnimationPlayState;
@@ -21,15 +22,21 @@ use crate::properties::{
 };
 use crate::rule_tree::CascadeLevel;
 use crate::selector_parser::PseudoElement;
-use crate::shared_lock::{Locked, SharedRwLock};
+use crate::servo::scroll_timeline::{
+    ScrollState, ScrollTimelineGeometry, TimelineResolver, TimelineSource,
+};
+use crate::shared_lock::{Locked, SharedRwLock, SharedRwLockReadGuard};
 use crate::style_resolver::StyleResolverForElement;
-use crate::stylesheets::keyframes_rule::{KeyframesAnimation, KeyframesStep, KeyframesStepValue};
+use crate::stylesheets::keyframes_rule::{
+    KeyframePercentage, KeyframesAnimation, KeyframesStep, KeyframesStepValue,
+};
 use crate::stylesheets::layer_rule::LayerOrder;
 use crate::values::animated::{Animate, Procedure};
-use crate::values::computed::TimingFunction;
+use crate::values::computed::{AnimationTimeline, TimingFunction};
 use crate::values::generics::easing::BeforeFlag;
 use crate::values::specified::TransitionBehavior;
 use crate::Atom;
+use euclid::default::Rect;
 use parking_lot::RwLock;
 use rustc_hash::FxHashMap;
 use servo_arc::Arc;
@@ -133,6 +140,7 @@ pub enum KeyframesIterationState {
 struct IntermediateComputedKeyframe {
     declarations: PropertyDeclarationBlock,
     timing_function: Option<TimingFunction>,
+    composition: Option<AnimationComposition>,
     start_percentage: f32,
 }
 
@@ -141,6 +149,7 @@ impl IntermediateComputedKeyframe {
         IntermediateComputedKeyframe {
             declarations: PropertyDeclarationBlock::new(),
             timing_function: None,
+            composition: None,
             start_percentage,
         }
     }
@@ -186,6 +195,11 @@ impl IntermediateComputedKeyframe {
             self.timing_function = Some(timing_function.to_computed_value_without_context());
         }
 
+        // The same goes for the composite operation.
+        if let Some(composition) = step.get_animation_composition(&guard) {
+            self.composition = Some(composition);
+        }
+
         let block = match step.value {
             KeyframesStepValue::ComputedValues => return,
             KeyframesStepValue::Declarations { ref block } => block,
@@ -268,6 +282,24 @@ struct ComputedKeyframe {
     /// The animation values to transition to and from when processing this
     /// keyframe animation step.
     values: Box<[AnimationValue]>,
+
+    /// How each of the values in `values` combines with the underlying value
+    /// of its property. Values that this keyframe doesn't specify take the
+    /// composite operation of the keyframe they come from.
+    compositions: Box<[AnimationComposition]>,
+}
+
+/// The physical properties that change in the given animation.
+fn animating_properties(
+    animation: &KeyframesAnimation,
+    base_style: &ComputedValues,
+) -> PropertyDeclarationIdSet {
+    let mut animating_properties = PropertyDeclarationIdSet::default();
+    for property in animation.properties_changed.iter() {
+        debug_assert!(property.is_animatable());
+        animating_properties.insert(property.to_physical(base_style.writing_mode));
+    }
+    animating_properties
 }
 
 impl ComputedKeyframe {
@@ -277,17 +309,13 @@ impl ComputedKeyframe {
         context: &SharedStyleContext,
         base_style: &Arc<ComputedValues>,
         default_timing_function: TimingFunction,
+        default_composition: AnimationComposition,
         resolver: &mut StyleResolverForElement<E>,
     ) -> Box<[Self]>
     where
         E: TElement,
     {
-        let mut animating_properties = PropertyDeclarationIdSet::default();
-        for property in animation.properties_changed.iter() {
-            debug_assert!(property.is_animatable());
-            animating_properties.insert(property.to_physical(base_style.writing_mode));
-        }
-
+        let animating_properties = animating_properties(animation, base_style);
         let animation_values_from_style: Vec<AnimationValue> = animating_properties
             .iter()
             .map(|property| {
@@ -304,49 +332,151 @@ impl ComputedKeyframe {
             let start_percentage = step.start_percentage;
             let properties_changed_in_step = step.declarations.property_ids().clone();
             let step_timing_function = step.timing_function.clone();
+            let step_composition = step.composition.unwrap_or(default_composition);
             let step_style = step.resolve_style(element, context, base_style, resolver);
             let timing_function =
                 step_timing_function.unwrap_or_else(|| default_timing_function.clone());
 
-            let values = {
-                // If a value is not set in a property declaration we use the value from
-                // the style for the first and last keyframe. For intermediate ones, we
-                // use the value from the previous keyframe.
-                //
-                // TODO(mrobinson): According to the spec, we should use an interpolated
-                // value for properties missing from keyframe declarations.
-                let default_values = if start_percentage == 0. || start_percentage == 1.0 {
-                    animation_values_from_style.as_slice()
+            // If a value is not set in a property declaration we use the value from
+            // the style for the first and last keyframe, which replaces the underlying
+            // value. For intermediate ones, we use the value from the previous keyframe.
+            //
+            // TODO(mrobinson): According to the spec, we should use an interpolated
+            // value for properties missing from keyframe declarations.
+            let (default_values, default_compositions) =
+                if start_percentage == 0. || start_percentage == 1.0 {
+                    (animation_values_from_style.as_slice(), None)
                 } else {
                     debug_assert!(step_index != 0);
-                    &computed_steps[step_index - 1].values
+                    let previous_step = &computed_steps[step_index - 1];
+                    (&*previous_step.values, Some(&*previous_step.compositions))
                 };
 
-                // For each property that is animating, pull the value from the resolved
-                // style for this step if it's in one of the declarations. Otherwise, we
-                // use the default value from the set we calculated above.
-                animating_properties
-                    .iter()
-                    .zip(default_values.iter())
-                    .map(|(property_declaration, default_value)| {
-                        if properties_changed_in_step.contains(property_declaration) {
-                            AnimationValue::from_computed_values(property_declaration, &step_style)
-                                .unwrap_or_else(|| default_value.clone())
-                        } else {
-                            default_value.clone()
-                        }
-                    })
-                    .collect()
-            };
+            // For each property that is animating, pull the value from the resolved
+            // style for this step if it's in one of the declarations. Otherwise, we
+            // use the default value from the set we calculated above.
+            let (values, compositions): (Vec<_>, Vec<_>) = animating_properties
+                .iter()
+                .zip(default_values.iter())
+                .enumerate()
+                .map(|(index, (property_declaration, default_value))| {
+                    let value = if properties_changed_in_step.contains(property_declaration) {
+                        AnimationValue::from_computed_values(property_declaration, &step_style)
+                    } else {
+                        None
+                    };
+                    match value {
+                        Some(value) => (value, step_composition),
+                        None => (
+                            default_value.clone(),
+                            default_compositions
+                                .map_or(AnimationComposition::Replace, |compositions| {
+                                    compositions[index]
+                                }),
+                        ),
+                    }
+                })
+                .unzip();
 
             computed_steps.push(ComputedKeyframe {
                 timing_function,
                 start_percentage,
-                values,
+                values: values.into_boxed_slice(),
+                compositions: compositions.into_boxed_slice(),
             });
         }
         computed_steps.into_boxed_slice()
     }
+
+    /// The value of the property at `index` in this keyframe, composited onto
+    /// its underlying value. That is the result of the animations below this
+    /// one if there are any, or its value in the style without animations
+    /// otherwise.
+    ///
+    /// <https://drafts.csswg.org/web-animations-1/#effect-composition>
+    fn composited_value(
+        &self,
+        index: usize,
+        underlying_values: &[AnimationValue],
+        map: &AnimationValueMap,
+    ) -> AnimationValue {
+        let value = &self.values[index];
+        let procedure = match self.compositions[index] {
+            AnimationComposition::Replace => return value.clone(),
+            AnimationComposition::Add => Procedure::Add,
+            AnimationComposition::Accumulate => Procedure::Accumulate { count: 1 },
+        };
+
+        let underlying_value = match map
+            .get(&value.id().to_owned())
+            .or_else(|| underlying_values.get(index))
+        {
+            Some(underlying_value) => underlying_value,
+            None => return value.clone(),
+        };
+
+        // Values that can't be added or accumulated replace the underlying value.
+        underlying_value
+            .animate(value, procedure)
+            .unwrap_or_else(|()| value.clone())
+    }
+}
+
+/// Compute the values of the properties animated by the given animation in the
+/// given style, once the effect of animations is removed from it. These are
+/// the underlying values that keyframes which add or accumulate are composited
+/// onto.
+///
+/// Returns an empty list if no keyframe needs them.
+fn generate_underlying_values<E>(
+    animation: &KeyframesAnimation,
+    computed_steps: &[ComputedKeyframe],
+    context: &SharedStyleContext,
+    base_style: &Arc<ComputedValues>,
+    resolver: &mut StyleResolverForElement<E>,
+) -> Box<[AnimationValue]>
+where
+    E: TElement,
+{
+    let replaces_underlying_values = computed_steps.iter().all(|step| {
+        step.compositions
+            .iter()
+            .all(|composition| *composition == AnimationComposition::Replace)
+    });
+    if replaces_underlying_values {
+        return Box::new([]);
+    }
+
+    let mut important_rules_changed = false;
+    let new_node = context.stylist.rule_tree().update_rule_at_level(
+        CascadeLevel::Animations,
+        LayerOrder::root(),
+        None,
+        base_style.rules(),
+        &context.guards,
+        &mut important_rules_changed,
+    );
+    let style = match new_node {
+        Some(rules) => {
+            let inputs = CascadeInputs {
+                rules: Some(rules),
+                visited_rules: base_style.visited_rules().cloned(),
+                flags: base_style.flags.for_cascade_inputs(),
+            };
+            resolver
+                .cascade_style_and_visited_with_default_parents(inputs)
+                .0
+        },
+        None => base_style.clone(),
+    };
+
+    animating_properties(animation, base_style)
+        .iter()
+        .map(|property| {
+            AnimationValue::from_computed_values(property, &style)
+                .expect("Unexpected non-animatable property.")
+        })
+        .collect()
 }
 
 /// A CSS Animation
@@ -361,6 +491,10 @@ pub struct Animation {
     /// The computed style for each keyframe of this animation.
     computed_steps: Box<[ComputedKeyframe]>,
 
+    /// The values that keyframes which don't replace the underlying value are
+    /// composited onto, when no other animation below this one sets them.
+    underlying_values: Box<[AnimationValue]>,
+
     /// The time this animation started at, which is the current value of the animation
     /// timeline when this animation was created plus any animation delay.
     pub started_at: f64,
@@ -374,6 +508,10 @@ pub struct Animation {
     /// The `animation-fill-mode` property of this animation.
     pub fill_mode: AnimationFillMode,
 
+    /// The `animation-composition` property of this animation, which applies
+    /// to keyframes that don't specify their own.
+    pub composition: AnimationComposition,
+
     /// The current iteration state for the animation.
     pub iteration_state: KeyframesIterationState,
 
@@ -386,6 +524,20 @@ pub struct Animation {
     /// The current animation direction. This can only be `normal` or `reverse`.
     pub current_direction: AnimationDirection,
 
+    /// The `animation-timeline` property of this animation.
+    pub timeline: AnimationTimeline,
+
+    /// The scroll progress or view progress timeline that drives this
+    /// animation, once resolved by `DocumentAnimationSet::resolve_timelines`.
+    /// This is `None` for animations driven by the document timeline, and for
+    /// animations whose timeline doesn't exist.
+    #[ignore_malloc_size_of = "No heap data"]
+    pub timeline_source: Option<TimelineSource>,
+
+    /// The current progress of the timeline that drives this animation, or
+    /// `None` if it is inactive.
+    pub timeline_progress: Option<f64>,
+
     /// The original cascade style, needed to compute the generated keyframes of
     /// the animation.
     #[ignore_malloc_size_of = "ComputedValues"]
@@ -409,13 +561,21 @@ impl Animation {
         };
 
         new_ui.animation_duration_mod(index).seconds() == 0.
+            && new_ui.animation_timeline_mod(index).is_auto()
+    }
+
+    /// Whether this animation is driven by a scroll progress or view progress
+    /// timeline rather than by the document timeline.
+    pub fn is_scroll_driven(&self) -> bool {
+        !self.timeline.is_auto()
     }
 
     /// Given the current time, advances this animation to the next iteration,
     /// updates times, and then toggles the direction if appropriate. Otherwise
     /// does nothing. Returns true if this animation has iterated.
     pub fn iterate_if_necessary(&mut self, time: f64) -> bool {
-        if !self.iteration_over(time) {
+        // The iteration of scroll-driven animations follows their timeline.
+        if self.is_scroll_driven() || !self.iteration_over(time) {
             return false;
         }
 
@@ -493,6 +653,12 @@ impl Animation {
     /// not take into account canceling i.e. when an animation or transition is
     /// canceled due to changes in the style.
     pub fn has_ended(&self, time: f64) -> bool {
+        if self.is_scroll_driven() {
+            return self
+                .timeline_progress
+                .is_some_and(|progress| progress >= 1.);
+        }
+
         if !self.on_last_iteration() {
             return false;
         }
@@ -529,12 +695,21 @@ impl Animation {
         let old_direction = self.current_direction;
         let old_state = self.state.clone();
         let old_iteration_state = self.iteration_state.clone();
+        let old_timeline = std::mem::replace(&mut self.timeline, AnimationTimeline::Auto);
+        let old_timeline_source = self.timeline_source.take();
+        let old_timeline_progress = self.timeline_progress;
 
         *self = other.clone();
 
         self.started_at = old_started_at;
         self.current_direction = old_direction;
 
+        // Keep the resolved timeline until it's resolved again.
+        if self.timeline == old_timeline {
+            self.timeline_source = old_timeline_source;
+            self.timeline_progress = old_timeline_progress;
+        }
+
         // Don't update the iteration count, just the iteration limit.
         // TODO: see how changing the limit affects rendering in other browsers.
         // We might need to keep the iteration count even when it's infinite.
@@ -584,6 +759,10 @@ impl Animation {
     fn get_property_declaration_at_time(&self, now: f64, map: &mut AnimationValueMap) {
         debug_assert!(!self.computed_steps.is_empty());
 
+        if self.is_scroll_driven() {
+            return self.get_property_declaration_for_timeline_progress(map);
+        }
+
         let total_progress = match self.state {
             AnimationState::Running | AnimationState::Pending | AnimationState::Finished => {
                 (now - self.started_at) / self.duration
@@ -608,90 +787,113 @@ impl Animation {
             .min(self.current_iteration_end_progress())
             .max(0.0);
 
-        // Get the indices of the previous (from) keyframe and the next (to) keyframe.
-        let next_keyframe_index;
-        let prev_keyframe_index;
-        let num_steps = self.computed_steps.len();
-        match self.current_direction {
-            AnimationDirection::Normal => {
-                next_keyframe_index = self
-                    .computed_steps
-                    .iter()
-                    .position(|step| total_progress as f32 <= step.start_percentage);
-                prev_keyframe_index = next_keyframe_index
-                    .and_then(|pos| if pos != 0 { Some(pos - 1) } else { None })
-                    .unwrap_or(0);
-            },
-            AnimationDirection::Reverse => {
-                next_keyframe_index = self
-                    .computed_steps
-                    .iter()
-                    .rev()
-                    .position(|step| total_progress as f32 <= 1. - step.start_percentage)
-                    .map(|pos| num_steps - pos - 1);
-                prev_keyframe_index = next_keyframe_index
-                    .and_then(|pos| {
-                        if pos != num_steps - 1 {
-                            Some(pos + 1)
-                        } else {
-                            None
-                        }
-                    })
-                    .unwrap_or(num_steps - 1)
-            },
-            _ => unreachable!(),
-        }
-
-        debug!(
-            "Animation::get_property_declaration_at_time: keyframe from {:?} to {:?}",
-            prev_keyframe_index, next_keyframe_index
+        interpolate_computed_keyframes(
+            &self.computed_steps,
+            &self.underlying_values,
+            total_progress,
+            self.current_direction,
+            self.duration,
+            map,
         );
+    }
 
-        let prev_keyframe = &self.computed_steps[prev_keyframe_index];
-        let next_keyframe = match next_keyframe_index {
-            Some(index) => &self.computed_steps[index],
+    /// Fill in an `AnimationValueMap` with values calculated from the progress
+    /// of the scroll-driven timeline of this animation.
+    ///
+    /// The delay and the iterations of the animation are laid out over the
+    /// timeline in proportion to their durations, and an `auto` duration
+    /// spreads the iterations over the whole timeline.
+    fn get_property_declaration_for_timeline_progress(&self, map: &mut AnimationValueMap) {
+        if self.state == AnimationState::Canceled {
+            return;
+        }
+        let progress = match self.timeline_progress {
+            Some(progress) => progress,
             None => return,
         };
 
-        // If we only need to take into account one keyframe, then exit early
-        // in order to avoid doing more work.
-        let mut add_declarations_to_map = |keyframe: &ComputedKeyframe| {
-            for value in keyframe.values.iter() {
-                map.insert(value.id().to_owned(), value.clone());
-            }
+        let (duration, delay) = if self.duration > 0. {
+            (self.duration, self.delay.max(0.))
+        } else {
+            (1., 0.)
         };
-        if total_progress <= 0.0 {
-            add_declarations_to_map(&prev_keyframe);
-            return;
-        }
-        if total_progress >= 1.0 {
-            add_declarations_to_map(&next_keyframe);
-            return;
-        }
+        let (iteration, iteration_progress) = match self.iteration_state {
+            KeyframesIterationState::Finite(_, iterations) => {
+                let active_duration = duration * iterations;
+                let local_time = progress * (delay + active_duration) - delay;
+
+                let overall_progress = if local_time < 0. {
+                    if self.fill_mode != AnimationFillMode::Backwards
+                        && self.fill_mode != AnimationFillMode::Both
+                    {
+                        return;
+                    }
+                    0.
+                } else if local_time > active_duration {
+                    if self.fill_mode != AnimationFillMode::Forwards
+                        && self.fill_mode != AnimationFillMode::Both
+                    {
+                        return;
+                    }
+                    iterations
+                } else {
+                    local_time / duration
+                };
 
-        let percentage_between_keyframes =
-            (next_keyframe.start_percentage - prev_keyframe.start_percentage).abs() as f64;
-        let duration_between_keyframes = percentage_between_keyframes * self.duration;
-        let direction_aware_prev_keyframe_start_percentage = match self.current_direction {
-            AnimationDirection::Normal => prev_keyframe.start_percentage as f64,
-            AnimationDirection::Reverse => 1. - prev_keyframe.start_percentage as f64,
-            _ => unreachable!(),
+                // The end of the last iteration is the end of that iteration
+                // rather than the start of the next one.
+                let mut iteration = overall_progress.floor();
+                if iteration >= iterations && iterations > 0. {
+                    iteration = iterations.ceil() - 1.;
+                }
+                (iteration, overall_progress - iteration)
+            },
+            // Converting an infinite active duration to a proportion of the
+            // timeline makes the iteration duration and the delay zero, so the
+            // animation is in its after phase from the start of the timeline,
+            // at the end of its infinitely many iterations.
+            //
+            // https://drafts.csswg.org/web-animations-2/#converting-a-time-to-a-proportion
+            KeyframesIterationState::Infinite(_) => {
+                if progress < 0. {
+                    if self.fill_mode != AnimationFillMode::Backwards
+                        && self.fill_mode != AnimationFillMode::Both
+                    {
+                        return;
+                    }
+                    (0., 0.)
+                } else {
+                    if self.fill_mode != AnimationFillMode::Forwards
+                        && self.fill_mode != AnimationFillMode::Both
+                    {
+                        return;
+                    }
+                    (f64::INFINITY, 1.)
+                }
+            },
+        };
+        // The alternating directions play forwards after infinitely many
+        // iterations.
+        let reversed = match self.direction {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => iteration.is_finite() && iteration % 2. == 1.,
+            AnimationDirection::AlternateReverse => iteration.is_finite() && iteration % 2. == 0.,
+        };
+        let direction = if reversed {
+            AnimationDirection::Reverse
+        } else {
+            AnimationDirection::Normal
         };
-        let progress_between_keyframes = (total_progress
-            - direction_aware_prev_keyframe_start_percentage)
-            / percentage_between_keyframes;
-
-        for (from, to) in prev_keyframe.values.iter().zip(next_keyframe.values.iter()) {
-            let animation = PropertyAnimation {
-                from: from.clone(),
-                to: to.clone(),
-                timing_function: prev_keyframe.timing_function.clone(),
-                duration: duration_between_keyframes as f64,
-            };
 
-            let value = animation.calculate_value(progress_between_keyframes);
-            map.insert(value.id().to_owned(), value);
-        }
+        interpolate_computed_keyframes(
+            &self.computed_steps,
+            &self.underlying_values,
+            iteration_progress,
+            direction,
+            duration,
+            map,
+        );
     }
 }
 
@@ -702,6 +904,7 @@ impl fmt::Debug for Animation {
             .field("started_at", &self.started_at)
             .field("duration", &self.duration)
             .field("delay", &self.delay)
+            .field("composition", &self.composition)
             .field("iteration_state", &self.iteration_state)
             .field("state", &self.state)
             .field("direction", &self.direction)
@@ -711,6 +914,763 @@ impl fmt::Debug for Animation {
     }
 }
 
+/// Fill in an `AnimationValueMap` with the values of the given computed keyframes
+/// at the given progress through an iteration, which lasts `duration` seconds.
+///
+/// The map may already contain the values of the animations below this one,
+/// which keyframes that don't replace the underlying value are composited onto.
+fn interpolate_computed_keyframes(
+    computed_steps: &[ComputedKeyframe],
+    underlying_values: &[AnimationValue],
+    total_progress: f64,
+    direction: AnimationDirection,
+    duration: f64,
+    map: &mut AnimationValueMap,
+) {
+    // Get the indices of the previous (from) keyframe and the next (to) keyframe.
+    let next_keyframe_index;
+    let prev_keyframe_index;
+    let num_steps = computed_steps.len();
+    match direction {
+        AnimationDirection::Normal => {
+            next_keyframe_index = computed_steps
+                .iter()
+                .position(|step| total_progress as f32 <= step.start_percentage);
+            prev_keyframe_index = next_keyframe_index
+                .and_then(|pos| if pos != 0 { Some(pos - 1) } else { None })
+                .unwrap_or(0);
+        },
+        AnimationDirection::Reverse => {
+            next_keyframe_index = computed_steps
+                .iter()
+                .rev()
+                .position(|step| total_progress as f32 <= 1. - step.start_percentage)
+                .map(|pos| num_steps - pos - 1);
+            prev_keyframe_index = next_keyframe_index
+                .and_then(|pos| {
+                    if pos != num_steps - 1 {
+                        Some(pos + 1)
+                    } else {
+                        None
+                    }
+                })
+                .unwrap_or(num_steps - 1)
+        },
+        _ => unreachable!(),
+    }
+
+    debug!(
+        "interpolate_computed_keyframes: keyframe from {:?} to {:?}",
+        prev_keyframe_index, next_keyframe_index
+    );
+
+    let prev_keyframe = &computed_steps[prev_keyframe_index];
+    let next_keyframe = match next_keyframe_index {
+        Some(index) => &computed_steps[index],
+        None => return,
+    };
+
+    // If we only need to take into account one keyframe, then exit early
+    // in order to avoid doing more work.
+    let mut add_declarations_to_map = |keyframe: &ComputedKeyframe| {
+        for index in 0..keyframe.values.len() {
+            let value = keyframe.composited_value(index, underlying_values, map);
+            map.insert(value.id().to_owned(), value);
+        }
+    };
+    if total_progress <= 0.0 {
+        add_declarations_to_map(&prev_keyframe);
+        return;
+    }
+    if total_progress >= 1.0 {
+        add_declarations_to_map(&next_keyframe);
+        return;
+    }
+
+    let percentage_between_keyframes =
+        (next_keyframe.start_percentage - prev_keyframe.start_percentage).abs() as f64;
+    let duration_between_keyframes = percentage_between_keyframes * duration;
+    let direction_aware_prev_keyframe_start_percentage = match direction {
+        AnimationDirection::Normal => prev_keyframe.start_percentage as f64,
+        AnimationDirection::Reverse => 1. - prev_keyframe.start_percentage as f64,
+        _ => unreachable!(),
+    };
+    let progress_between_keyframes = (total_progress
+        - direction_aware_prev_keyframe_start_percentage)
+        / percentage_between_keyframes;
+
+    for index in 0..prev_keyframe.values.len() {
+        let animation = PropertyAnimation {
+            from: prev_keyframe.composited_value(index, underlying_values, map),
+            to: next_keyframe.composited_value(index, underlying_values, map),
+            timing_function: prev_keyframe.timing_function.clone(),
+            duration: duration_between_keyframes as f64,
+        };
+
+        let value = animation.calculate_value(progress_between_keyframes);
+        map.insert(value.id().to_owned(), value);
+    }
+}
+
+/// An identifier for an animation created by script.
+#[derive(Clone, Copy, Debug, Eq, Hash, MallocSizeOf, PartialEq)]
+pub struct ScriptAnimationId(u64);
+
+impl ScriptAnimationId {
+    /// Returns a new identifier, unique within this process.
+    fn next() -> Self {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static NEXT_SCRIPT_ANIMATION_ID: AtomicU64 = AtomicU64::new(0);
+
+        ScriptAnimationId(NEXT_SCRIPT_ANIMATION_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A keyframe of an animation created by script, like the ones given to
+/// `Element.animate()`.
+#[derive(Clone, Debug)]
+pub struct ScriptKeyframe {
+    /// The offset of this keyframe, from 0 to 1. Keyframes without an offset
+    /// are spaced evenly between their neighbours.
+    pub offset: Option<f32>,
+
+    /// The declarations of this keyframe. As in `@keyframes`, an
+    /// `animation-timing-function` declaration sets the easing used from this
+    /// keyframe to the next one.
+    pub block: Arc<Locked<PropertyDeclarationBlock>>,
+}
+
+/// Computes the offset of every keyframe, returning an error if the given
+/// offsets are out of range or not in ascending order.
+///
+/// <https://drafts.csswg.org/web-animations-1/#compute-missing-keyframe-offsets>
+fn compute_keyframe_offsets(keyframes: &[ScriptKeyframe]) -> Result<Vec<f32>, ()> {
+    let mut offsets: Vec<Option<f32>> = keyframes.iter().map(|keyframe| keyframe.offset).collect();
+
+    let mut previous_offset = 0.;
+    for &offset in offsets.iter().flatten() {
+        if !(0. ..=1.).contains(&offset) || offset < previous_offset {
+            return Err(());
+        }
+        previous_offset = offset;
+    }
+
+    if let Some(last) = offsets.last_mut() {
+        last.get_or_insert(1.);
+    }
+    if let Some(first) = offsets.first_mut() {
+        first.get_or_insert(0.);
+    }
+
+    let mut previous_index = 0;
+    for index in 1..offsets.len() {
+        let end = match offsets[index] {
+            Some(offset) => offset,
+            None => continue,
+        };
+        let start = offsets[previous_index].unwrap();
+        let count = (index - previous_index) as f32;
+        for (i, offset) in offsets[previous_index + 1..index].iter_mut().enumerate() {
+            *offset = Some(start + (end - start) * (i + 1) as f32 / count);
+        }
+        previous_index = index;
+    }
+
+    Ok(offsets.into_iter().map(Option::unwrap).collect())
+}
+
+/// The timing options of an animation created by script.
+///
+/// <https://drafts.csswg.org/web-animations-1/#the-effecttiming-dictionaries>
+#[derive(Clone, Debug, MallocSizeOf)]
+pub struct ScriptAnimationTiming {
+    /// The delay before the active interval starts, in seconds.
+    delay: f64,
+
+    /// The delay after the active interval ends, in seconds.
+    end_delay: f64,
+
+    /// Whether this animation has an effect outside of its active interval.
+    fill: AnimationFillMode,
+
+    /// The number of iterations, which may be infinite.
+    iterations: f64,
+
+    /// The duration of a single iteration, in seconds, which may be infinite.
+    duration: f64,
+
+    /// The direction in which iterations play.
+    direction: AnimationDirection,
+
+    /// The timing function applied to each iteration as a whole.
+    easing: TimingFunction,
+}
+
+impl Default for ScriptAnimationTiming {
+    fn default() -> Self {
+        ScriptAnimationTiming {
+            delay: 0.,
+            end_delay: 0.,
+            fill: AnimationFillMode::None,
+            iterations: 1.,
+            duration: 0.,
+            direction: AnimationDirection::Normal,
+            easing: TimingFunction::linear(),
+        }
+    }
+}
+
+/// The phase of an animation relative to its active interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum AnimationPhase {
+    Before,
+    Active,
+    After,
+}
+
+impl ScriptAnimationTiming {
+    /// Create new timing options, or return `None` if the delays aren't
+    /// finite, or the iteration count or duration are negative or NaN.
+    ///
+    /// <https://drafts.csswg.org/web-animations-1/#update-the-timing-properties-of-an-animation-effect>
+    pub fn new(
+        delay: f64,
+        end_delay: f64,
+        fill: AnimationFillMode,
+        iterations: f64,
+        duration: f64,
+        direction: AnimationDirection,
+        easing: TimingFunction,
+    ) -> Option<Self> {
+        // Comparisons with NaN are false, so this also rejects NaN values.
+        let valid =
+            delay.is_finite() && end_delay.is_finite() && iterations >= 0. && duration >= 0.;
+        if !valid {
+            return None;
+        }
+        Some(ScriptAnimationTiming {
+            delay,
+            end_delay,
+            fill,
+            iterations,
+            duration,
+            direction,
+            easing,
+        })
+    }
+
+    /// The delay before the active interval starts, in seconds.
+    pub fn delay(&self) -> f64 {
+        self.delay
+    }
+
+    /// The delay after the active interval ends, in seconds.
+    pub fn end_delay(&self) -> f64 {
+        self.end_delay
+    }
+
+    /// Whether this animation has an effect outside of its active interval.
+    pub fn fill(&self) -> AnimationFillMode {
+        self.fill
+    }
+
+    /// The number of iterations, which may be infinite.
+    pub fn iterations(&self) -> f64 {
+        self.iterations
+    }
+
+    /// The duration of a single iteration, in seconds, which may be infinite.
+    pub fn duration(&self) -> f64 {
+        self.duration
+    }
+
+    /// The direction in which iterations play.
+    pub fn direction(&self) -> AnimationDirection {
+        self.direction
+    }
+
+    /// The timing function applied to each iteration as a whole.
+    pub fn easing(&self) -> &TimingFunction {
+        &self.easing
+    }
+
+    /// The length of the active interval, in seconds.
+    pub fn active_duration(&self) -> f64 {
+        if self.duration == 0. || self.iterations == 0. {
+            return 0.;
+        }
+        self.duration * self.iterations
+    }
+
+    /// The time at which this animation ends, including both delays.
+    pub fn end_time(&self) -> f64 {
+        (self.delay + self.active_duration() + self.end_delay).max(0.)
+    }
+
+    /// The progress through the current iteration at the given local time,
+    /// after applying the direction and easing, or `None` if the animation has
+    /// no effect at that time.
+    ///
+    /// <https://drafts.csswg.org/web-animations-1/#calculating-the-transformed-progress>
+    fn transformed_progress(&self, local_time: f64, playback_rate: f64) -> Option<f64> {
+        let active_duration = self.active_duration();
+        let end_time = self.end_time();
+        let before_active_boundary = self.delay.clamp(0., end_time);
+        let active_after_boundary = (self.delay + active_duration).clamp(0., end_time);
+        let going_backwards = playback_rate < 0.;
+
+        let fills_backwards = matches!(
+            self.fill,
+            AnimationFillMode::Backwards | AnimationFillMode::Both
+        );
+        let fills_forwards = matches!(
+            self.fill,
+            AnimationFillMode::Forwards | AnimationFillMode::Both
+        );
+
+        let (phase, active_time) = if local_time < before_active_boundary
+            || (going_backwards && local_time == before_active_boundary)
+        {
+            if !fills_backwards {
+                return None;
+            }
+            (AnimationPhase::Before, (local_time - self.delay).max(0.))
+        } else if local_time > active_after_boundary
+            || (!going_backwards && local_time == active_after_boundary)
+        {
+            if !fills_forwards {
+                return None;
+            }
+            let active_time = (local_time - self.delay).clamp(0., active_duration);
+            (AnimationPhase::After, active_time)
+        } else {
+            (AnimationPhase::Active, local_time - self.delay)
+        };
+
+        let overall_progress = if self.duration == 0. {
+            match phase {
+                AnimationPhase::Before => 0.,
+                _ => self.iterations,
+            }
+        } else {
+            active_time / self.duration
+        };
+
+        let mut simple_iteration_progress = if overall_progress.is_infinite() {
+            0.
+        } else {
+            overall_progress % 1.
+        };
+        if simple_iteration_progress == 0.
+            && phase != AnimationPhase::Before
+            && active_time == active_duration
+            && self.iterations != 0.
+        {
+            simple_iteration_progress = 1.;
+        }
+
+        let current_iteration = if phase == AnimationPhase::After && self.iterations.is_infinite() {
+            f64::INFINITY
+        } else if simple_iteration_progress == 1. {
+            overall_progress.floor() - 1.
+        } else {
+            overall_progress.floor()
+        };
+
+        let forwards = match self.direction {
+            AnimationDirection::Normal => true,
+            AnimationDirection::Reverse => false,
+            AnimationDirection::Alternate => {
+                current_iteration.is_infinite() || current_iteration % 2. == 0.
+            },
+            AnimationDirection::AlternateReverse => {
+                current_iteration.is_infinite() || current_iteration % 2. == 1.
+            },
+        };
+        let directed_progress = if forwards {
+            simple_iteration_progress
+        } else {
+            1. - simple_iteration_progress
+        };
+
+        let before_flag = match phase {
+            AnimationPhase::Before if forwards => BeforeFlag::Set,
+            AnimationPhase::After if !forwards => BeforeFlag::Set,
+            _ => BeforeFlag::Unset,
+        };
+        let epsilon = 1. / (200. * self.duration);
+        Some(
+            self.easing
+                .calculate_output(directed_progress, before_flag, epsilon),
+        )
+    }
+}
+
+/// The play state of an animation created by script.
+///
+/// <https://drafts.csswg.org/web-animations-1/#play-states>
+#[derive(Clone, Copy, Debug, Eq, MallocSizeOf, PartialEq)]
+pub enum ScriptAnimationPlayState {
+    /// The animation has no current time, either because it was never played
+    /// or because it was canceled.
+    Idle,
+    /// The animation is playing.
+    Running,
+    /// The animation is paused.
+    Paused,
+    /// The animation has reached the end of its effect, or its start when
+    /// playing backwards.
+    Finished,
+}
+
+/// How the current time of a `ScriptAnimation` is computed.
+#[derive(Clone, Debug, MallocSizeOf)]
+enum ScriptAnimationTime {
+    /// The animation has no current time.
+    Idle,
+    /// The animation is playing, and its current time was `current_time` when
+    /// the timeline was at `timeline_time`.
+    Playing {
+        timeline_time: f64,
+        current_time: f64,
+    },
+    /// The animation is paused at the given time.
+    Paused { current_time: f64 },
+    /// The animation has finished at the given time.
+    Finished { current_time: f64 },
+}
+
+/// An animation created by script, following the Web Animations model. This
+/// is what embedders use to implement `Element.animate()` and the `Animation`
+/// interface.
+///
+/// All the times taken and returned by these methods are in seconds, and `now`
+/// is the current value of the animation timeline.
+///
+/// <https://drafts.csswg.org/web-animations-1/#animations>
+#[derive(Clone, MallocSizeOf)]
+pub struct ScriptAnimation {
+    /// The identifier of this animation.
+    pub id: ScriptAnimationId,
+
+    /// The keyframes of this animation.
+    keyframes: KeyframesAnimation,
+
+    /// The computed style for each keyframe of this animation. These are
+    /// resolved against the style of the target during the next restyle.
+    computed_steps: Box<[ComputedKeyframe]>,
+
+    /// The values that keyframes which don't replace the underlying value are
+    /// composited onto, when no other animation below this one sets them.
+    underlying_values: Box<[AnimationValue]>,
+
+    /// The composite operation of keyframes that don't specify their own.
+    composite: AnimationComposition,
+
+    /// Whether `computed_steps` needs to be computed again.
+    needs_resolution: bool,
+
+    /// The timing options of this animation.
+    timing: ScriptAnimationTiming,
+
+    /// The playback rate of this animation.
+    playback_rate: f64,
+
+    /// How the current time of this animation is computed.
+    time: ScriptAnimationTime,
+}
+
+impl ScriptAnimation {
+    /// Create a new idle animation from the given keyframes and timing.
+    fn new(
+        keyframes: &[ScriptKeyframe],
+        timing: ScriptAnimationTiming,
+        guard: &SharedRwLockReadGuard,
+    ) -> Result<Self, ()> {
+        let offsets = compute_keyframe_offsets(keyframes)?;
+        let blocks: Vec<_> = offsets
+            .into_iter()
+            .zip(keyframes.iter())
+            .map(|(offset, keyframe)| (KeyframePercentage::new(offset), keyframe.block.clone()))
+            .collect();
+        let keyframes = KeyframesAnimation::from_declaration_blocks(&blocks, None, guard);
+
+        Ok(ScriptAnimation {
+            id: ScriptAnimationId::next(),
+            needs_resolution: !keyframes.steps.is_empty(),
+            keyframes,
+            computed_steps: Box::new([]),
+            underlying_values: Box::new([]),
+            composite: AnimationComposition::Replace,
+            timing,
+            playback_rate: 1.,
+            time: ScriptAnimationTime::Idle,
+        })
+    }
+
+    /// The timing options of this animation.
+    pub fn timing(&self) -> &ScriptAnimationTiming {
+        &self.timing
+    }
+
+    /// Replace the timing options of this animation, keeping its current time.
+    pub fn set_timing(&mut self, timing: ScriptAnimationTiming, now: f64) {
+        let current_time = self.current_time(now);
+        self.timing = timing;
+        if let (ScriptAnimationTime::Finished { .. }, Some(current_time)) =
+            (&self.time, current_time)
+        {
+            self.time = ScriptAnimationTime::Playing {
+                timeline_time: now,
+                current_time,
+            };
+        }
+        self.update_finished_state(now);
+    }
+
+    /// The composite operation of keyframes that don't specify their own.
+    pub fn composite(&self) -> AnimationComposition {
+        self.composite
+    }
+
+    /// Change the composite operation of keyframes that don't specify their
+    /// own. This takes effect the next time the target is restyled.
+    pub fn set_composite(&mut self, composite: AnimationComposition) {
+        self.composite = composite;
+        self.needs_resolution = !self.keyframes.steps.is_empty();
+    }
+
+    /// The playback rate of this animation.
+    pub fn playback_rate(&self) -> f64 {
+        self.playback_rate
+    }
+
+    /// Change the playback rate of this animation, keeping its current time.
+    pub fn set_playback_rate(&mut self, playback_rate: f64, now: f64) {
+        let current_time = self.current_time(now);
+        self.playback_rate = playback_rate;
+        if let (
+            ScriptAnimationTime::Playing { .. } | ScriptAnimationTime::Finished { .. },
+            Some(current_time),
+        ) = (&self.time, current_time)
+        {
+            self.time = ScriptAnimationTime::Playing {
+                timeline_time: now,
+                current_time,
+            };
+        }
+        self.update_finished_state(now);
+    }
+
+    /// The current time of this animation, or `None` if it is idle.
+    ///
+    /// <https://drafts.csswg.org/web-animations-1/#the-current-time-of-an-animation>
+    pub fn current_time(&self, now: f64) -> Option<f64> {
+        let (timeline_time, current_time) = match self.time {
+            ScriptAnimationTime::Idle => return None,
+            ScriptAnimationTime::Paused { current_time }
+            | ScriptAnimationTime::Finished { current_time } => return Some(current_time),
+            ScriptAnimationTime::Playing {
+                timeline_time,
+                current_time,
+            } => (timeline_time, current_time),
+        };
+
+        // A playing animation stops at its end, or its start when playing
+        // backwards, unless it was seeked past it.
+        let unconstrained_time = current_time + (now - timeline_time) * self.playback_rate;
+        let end_time = self.timing.end_time();
+        if self.playback_rate > 0. && unconstrained_time >= end_time {
+            return Some(current_time.max(end_time));
+        }
+        if self.playback_rate < 0. && unconstrained_time <= 0. {
+            return Some(current_time.min(0.));
+        }
+        Some(unconstrained_time)
+    }
+
+    /// Seek this animation to the given time.
+    pub fn set_current_time(&mut self, current_time: f64, now: f64) {
+        self.time = match self.time {
+            ScriptAnimationTime::Idle | ScriptAnimationTime::Paused { .. } => {
+                ScriptAnimationTime::Paused { current_time }
+            },
+            ScriptAnimationTime::Playing { .. } | ScriptAnimationTime::Finished { .. } => {
+                ScriptAnimationTime::Playing {
+                    timeline_time: now,
+                    current_time,
+                }
+            },
+        };
+        self.update_finished_state(now);
+    }
+
+    /// The play state of this animation.
+    pub fn play_state(&self, now: f64) -> ScriptAnimationPlayState {
+        match self.time {
+            ScriptAnimationTime::Idle => ScriptAnimationPlayState::Idle,
+            ScriptAnimationTime::Paused { .. } => ScriptAnimationPlayState::Paused,
+            ScriptAnimationTime::Finished { .. } => ScriptAnimationPlayState::Finished,
+            ScriptAnimationTime::Playing { .. } => {
+                let finished = self.current_time(now).is_some_and(|current_time| {
+                    (self.playback_rate > 0. && current_time >= self.timing.end_time())
+                        || (self.playback_rate < 0. && current_time <= 0.)
+                });
+                if finished {
+                    ScriptAnimationPlayState::Finished
+                } else {
+                    ScriptAnimationPlayState::Running
+                }
+            },
+        }
+    }
+
+    /// Stop advancing the current time of this animation if it has finished,
+    /// so that it no longer needs to be ticked.
+    fn update_finished_state(&mut self, now: f64) {
+        if let (ScriptAnimationTime::Playing { .. }, Some(current_time)) =
+            (&self.time, self.current_time(now))
+        {
+            if self.play_state(now) == ScriptAnimationPlayState::Finished {
+                self.time = ScriptAnimationTime::Finished { current_time };
+            }
+        }
+    }
+
+    /// The time to seek to when playing or pausing this animation from the
+    /// given time, or an error if the animation would need to seek to the end
+    /// of an infinite effect.
+    fn auto_rewind_time(&self, current_time: Option<f64>) -> Result<f64, ()> {
+        let end_time = self.timing.end_time();
+        let out_of_range = if self.playback_rate > 0. {
+            current_time.is_none_or(|time| time < 0. || time >= end_time)
+        } else if self.playback_rate < 0. {
+            current_time.is_none_or(|time| time <= 0. || time > end_time)
+        } else {
+            current_time.is_none()
+        };
+
+        if !out_of_range {
+            return Ok(current_time.unwrap());
+        }
+        if self.playback_rate >= 0. {
+            return Ok(0.);
+        }
+        if end_time.is_infinite() {
+            return Err(());
+        }
+        Ok(end_time)
+    }
+
+    /// Play this animation, rewinding it first if it has finished.
+    ///
+    /// <https://drafts.csswg.org/web-animations-1/#playing-an-animation-section>
+    pub fn play(&mut self, now: f64) -> Result<(), ()> {
+        let current_time = self.auto_rewind_time(self.current_time(now))?;
+        self.time = ScriptAnimationTime::Playing {
+            timeline_time: now,
+            current_time,
+        };
+        self.update_finished_state(now);
+        Ok(())
+    }
+
+    /// Pause this animation at its current time.
+    ///
+    /// <https://drafts.csswg.org/web-animations-1/#pausing-an-animation-section>
+    pub fn pause(&mut self, now: f64) -> Result<(), ()> {
+        let current_time = match self.current_time(now) {
+            Some(current_time) => current_time,
+            None => self.auto_rewind_time(None)?,
+        };
+        self.time = ScriptAnimationTime::Paused { current_time };
+        Ok(())
+    }
+
+    /// Play this animation in the opposite direction.
+    ///
+    /// <https://drafts.csswg.org/web-animations-1/#reversing-an-animation-section>
+    pub fn reverse(&mut self, now: f64) -> Result<(), ()> {
+        let original_playback_rate = self.playback_rate;
+        self.set_playback_rate(-original_playback_rate, now);
+        if self.play(now).is_err() {
+            self.set_playback_rate(original_playback_rate, now);
+            return Err(());
+        }
+        Ok(())
+    }
+
+    /// Seek this animation to its end, or its start when playing backwards.
+    ///
+    /// <https://drafts.csswg.org/web-animations-1/#finishing-an-animation-section>
+    pub fn finish(&mut self) -> Result<(), ()> {
+        let end_time = self.timing.end_time();
+        if self.playback_rate == 0. || (self.playback_rate > 0. && end_time.is_infinite()) {
+            return Err(());
+        }
+
+        let current_time = if self.playback_rate > 0. {
+            end_time
+        } else {
+            0.
+        };
+        self.time = ScriptAnimationTime::Finished { current_time };
+        Ok(())
+    }
+
+    /// Cancel this animation, removing its effect and making it idle.
+    pub fn cancel(&mut self) {
+        self.time = ScriptAnimationTime::Idle;
+    }
+
+    /// Whether or not the current time of this animation advances with the
+    /// timeline.
+    fn needs_to_be_ticked(&self) -> bool {
+        matches!(self.time, ScriptAnimationTime::Playing { .. })
+    }
+
+    /// Fill in an `AnimationValueMap` with values calculated from this animation at
+    /// the given time value.
+    fn get_property_declaration_at_time(&self, now: f64, map: &mut AnimationValueMap) {
+        if self.computed_steps.is_empty() {
+            return;
+        }
+
+        let local_time = match self.current_time(now) {
+            Some(local_time) => local_time,
+            None => return,
+        };
+        let progress = match self
+            .timing
+            .transformed_progress(local_time, self.playback_rate)
+        {
+            Some(progress) => progress,
+            None => return,
+        };
+
+        interpolate_computed_keyframes(
+            &self.computed_steps,
+            &self.underlying_values,
+            progress,
+            AnimationDirection::Normal,
+            self.timing.duration,
+            map,
+        );
+    }
+}
+
+impl fmt::Debug for ScriptAnimation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScriptAnimation")
+            .field("id", &self.id)
+            .field("timing", &self.timing)
+            .field("composite", &self.composite)
+            .field("playback_rate", &self.playback_rate)
+            .field("time", &self.time)
+            .finish()
+    }
+}
+
 /// A CSS Transition
 #[derive(Clone, Debug, MallocSizeOf)]
 pub struct Transition {
@@ -860,6 +1820,10 @@ pub struct ElementAnimationSet {
     /// The transitions for this element.
     pub transitions: Vec<Transition>,
 
+    /// The animations created by script for this element, in the order they
+    /// were created.
+    pub script_animations: Vec<ScriptAnimation>,
+
     /// Whether or not this ElementAnimationSet has had animations or transitions
     /// which have been added, removed, or had their state changed.
     pub dirty: bool,
@@ -917,7 +1881,9 @@ impl ElementAnimationSet {
     /// Whether this `ElementAnimationSet` is empty, which means it doesn't
     /// hold any animations in any state.
     pub fn is_empty(&self) -> bool {
-        self.animations.is_empty() && self.transitions.is_empty()
+        self.animations.is_empty()
+            && self.transitions.is_empty()
+            && self.script_animations.is_empty()
     }
 
     /// Whether or not this state needs animation ticks for its transitions
@@ -930,6 +1896,10 @@ impl ElementAnimationSet {
                 .transitions
                 .iter()
                 .any(|transition| transition.state.needs_to_be_ticked())
+            || self
+                .script_animations
+                .iter()
+                .any(|animation| animation.needs_to_be_ticked())
     }
 
     /// The number of running animations and transitions for this `ElementAnimationSet`.
@@ -943,6 +1913,11 @@ impl ElementAnimationSet {
                 .iter()
                 .filter(|transition| transition.state.needs_to_be_ticked())
                 .count()
+            + self
+                .script_animations
+                .iter()
+                .filter(|animation| animation.needs_to_be_ticked())
+                .count()
     }
 
     /// If this `ElementAnimationSet` has any any active animations.
@@ -979,6 +1954,71 @@ impl ElementAnimationSet {
         maybe_start_animations(element, &context, &new_style, self, resolver);
     }
 
+    /// Whether the keyframes of any script animation need to be resolved
+    /// against a new style.
+    pub fn script_animations_need_resolution(
+        &self,
+        old_style: Option<&ComputedValues>,
+        new_style: &ComputedValues,
+    ) -> bool {
+        let writing_mode_changed =
+            old_style.is_some_and(|old| old.writing_mode != new_style.writing_mode);
+        self.script_animations.iter().any(|animation| {
+            animation.needs_resolution
+                || (writing_mode_changed && !animation.keyframes.steps.is_empty())
+        })
+    }
+
+    /// Resolve the keyframes of script animations against a new style, for
+    /// those that need it.
+    pub fn resolve_script_animations<E>(
+        &mut self,
+        element: E,
+        context: &SharedStyleContext,
+        old_style: Option<&ComputedValues>,
+        new_style: &Arc<ComputedValues>,
+        resolver: &mut StyleResolverForElement<E>,
+    ) where
+        E: TElement,
+    {
+        let writing_mode_changed =
+            old_style.is_some_and(|old| old.writing_mode != new_style.writing_mode);
+        for animation in self.script_animations.iter_mut() {
+            if animation.keyframes.steps.is_empty() {
+                continue;
+            }
+            if !animation.needs_resolution && !writing_mode_changed {
+                continue;
+            }
+
+            animation.computed_steps = ComputedKeyframe::generate_for_keyframes(
+                element,
+                &animation.keyframes,
+                context,
+                new_style,
+                TimingFunction::linear(),
+                animation.composite,
+                resolver,
+            );
+            animation.underlying_values = generate_underlying_values(
+                &animation.keyframes,
+                &animation.computed_steps,
+                context,
+                new_style,
+                resolver,
+            );
+            animation.needs_resolution = false;
+            self.dirty = true;
+        }
+    }
+
+    /// Stop ticking the script animations that have finished at the given time.
+    pub fn update_script_animation_play_states(&mut self, now: f64) {
+        for animation in self.script_animations.iter_mut() {
+            animation.update_finished_state(now);
+        }
+    }
+
     /// Update our transitions given a new style, canceling or starting new animations
     /// when appropriate.
     pub fn update_transitions_for_new_style(
@@ -1274,7 +2314,8 @@ impl ElementAnimationSet {
     /// Generate a `AnimationValueMap` for this `ElementAnimationSet`'s
     /// active animations at the given time value.
     pub fn get_value_map_for_active_animations(&self, now: f64) -> Option<AnimationValueMap> {
-        if !self.has_active_animation() {
+        let has_active_animation = self.has_active_animation();
+        if !has_active_animation && self.script_animations.is_empty() {
             return None;
         }
 
@@ -1283,6 +2324,15 @@ impl ElementAnimationSet {
             animation.get_property_declaration_at_time(now, &mut map);
         }
 
+        // Animations created by script apply on top of CSS animations, in the
+        // order they were created.
+        for animation in &self.script_animations {
+            animation.get_property_declaration_at_time(now, &mut map);
+        }
+
+        if !has_active_animation && map.is_empty() {
+            return None;
+        }
         Some(map)
     }
 }
@@ -1329,6 +2379,10 @@ pub struct DocumentAnimationSet {
     /// The `ElementAnimationSet`s that this set contains.
     #[ignore_malloc_size_of = "Arc is hard"]
     pub sets: Arc<RwLock<FxHashMap<AnimationSetKey, ElementAnimationSet>>>,
+
+    /// The layout information that drives scroll-driven animations.
+    #[ignore_malloc_size_of = "Arc is hard"]
+    pub scroll_timeline_geometry: Arc<RwLock<ScrollTimelineGeometry>>,
 }
 
 impl DocumentAnimationSet {
@@ -1422,6 +2476,156 @@ impl DocumentAnimationSet {
             set.cancel_all_animations();
         }
     }
+
+    /// Create an idle animation for the given key from a list of keyframes and
+    /// timing options, returning its id, or an error if the keyframe offsets
+    /// are invalid. The animation has no effect until it is played.
+    ///
+    /// Its keyframes are resolved the next time the target is restyled, so
+    /// embedders need to restyle it after creating or modifying an animation.
+    pub fn create_script_animation(
+        &self,
+        key: &AnimationSetKey,
+        keyframes: &[ScriptKeyframe],
+        timing: ScriptAnimationTiming,
+        guard: &SharedRwLockReadGuard,
+    ) -> Result<ScriptAnimationId, ()> {
+        let animation = ScriptAnimation::new(keyframes, timing, guard)?;
+        let id = animation.id;
+        let mut sets = self.sets.write();
+        let set = sets.entry(key.clone()).or_default();
+        set.script_animations.push(animation);
+        set.dirty = true;
+        Ok(id)
+    }
+
+    /// Run the given function on the script animation with the given key and
+    /// id, returning `None` if there is no such animation. This is how
+    /// animations are played, paused, seeked, reversed and canceled.
+    pub fn with_script_animation<F, R>(
+        &self,
+        key: &AnimationSetKey,
+        id: ScriptAnimationId,
+        f: F,
+    ) -> Option<R>
+    where
+        F: FnOnce(&mut ScriptAnimation) -> R,
+    {
+        let mut sets = self.sets.write();
+        let set = sets.get_mut(key)?;
+        let animation = set
+            .script_animations
+            .iter_mut()
+            .find(|animation| animation.id == id)?;
+        let result = f(animation);
+        set.dirty = true;
+        Some(result)
+    }
+
+    /// Set the scroll state of a scroll container, which is the root element
+    /// for the document viewport. This doesn't update the progress of the
+    /// animations it drives until `update_timeline_progress` is called.
+    pub fn set_scroll_state(&self, container: OpaqueNode, state: ScrollState) {
+        self.scroll_timeline_geometry
+            .write()
+            .scroll_states
+            .insert(container, state);
+    }
+
+    /// Set the border box of a view progress timeline subject, relative to the
+    /// scrolled content of its nearest scroll container when it is not
+    /// scrolled.
+    pub fn set_view_timeline_subject_rect(&self, subject: OpaqueNode, rect: Rect<f64>) {
+        self.scroll_timeline_geometry
+            .write()
+            .subject_rects
+            .insert(subject, rect);
+    }
+
+    /// Forget the layout information of the given node, which usually happens
+    /// when it is removed from the document or no longer has a box.
+    pub fn clear_scroll_timeline_geometry(&self, node: OpaqueNode) {
+        let mut geometry = self.scroll_timeline_geometry.write();
+        geometry.scroll_states.remove(&node);
+        geometry.subject_rects.remove(&node);
+    }
+
+    /// Resolve the timelines of scroll-driven animations against the styles of
+    /// the document rooted at `root`, and update their progress, returning
+    /// the keys of the animation sets whose progress changed.
+    ///
+    /// Embedders need to call this after restyles that may have started
+    /// scroll-driven animations or changed which timelines are declared.
+    pub fn resolve_timelines<E>(&self, root: E) -> Vec<AnimationSetKey>
+    where
+        E: TElement,
+    {
+        let mut sets = self.sets.write();
+        if !sets
+            .values()
+            .any(|set| set.animations.iter().any(Animation::is_scroll_driven))
+        {
+            return vec![];
+        }
+
+        let resolver = TimelineResolver::new(root);
+        for (key, set) in sets.iter_mut() {
+            for animation in set.animations.iter_mut() {
+                if animation.is_scroll_driven() {
+                    animation.timeline_source = resolver.resolve(key.node, &animation.timeline);
+                }
+            }
+        }
+        drop(sets);
+        self.update_timeline_progress()
+    }
+
+    /// Update the progress of scroll-driven animations from the current scroll
+    /// states, returning the keys of the animation sets whose progress changed
+    /// and thus need to be restyled. The progress of paused animations is not
+    /// updated.
+    pub fn update_timeline_progress(&self) -> Vec<AnimationSetKey> {
+        let geometry = self.scroll_timeline_geometry.read();
+        let mut changed = vec![];
+        for (key, set) in self.sets.write().iter_mut() {
+            let mut set_changed = false;
+            for animation in set.animations.iter_mut() {
+                if !animation.is_scroll_driven()
+                    || matches!(animation.state, AnimationState::Paused(..))
+                {
+                    continue;
+                }
+                let progress = animation
+                    .timeline_source
+                    .as_ref()
+                    .and_then(|source| source.progress(&geometry));
+                if progress != animation.timeline_progress {
+                    animation.timeline_progress = progress;
+                    set_changed = true;
+                }
+            }
+            if set_changed {
+                changed.push(key.clone());
+            }
+        }
+        changed
+    }
+
+    /// Remove the script animation with the given key and id, which usually
+    /// happens when script no longer holds a reference to it.
+    pub fn remove_script_animation(&self, key: &AnimationSetKey, id: ScriptAnimationId) {
+        let mut sets = self.sets.write();
+        let set = match sets.get_mut(key) {
+            Some(set) => set,
+            None => return,
+        };
+
+        set.script_animations.retain(|animation| animation.id != id);
+        set.dirty = true;
+        if set.is_empty() {
+            sets.remove(key);
+        }
+    }
 }
 
 /// Kick off any new transitions for this node and return all of the properties that are
@@ -1492,7 +2696,8 @@ pub fn maybe_start_animations<E>(
 
         debug!("maybe_start_animations: name={}", name);
         let duration = style.animation_duration_mod(i).seconds() as f64;
-        if duration == 0. {
+        let timeline = style.animation_timeline_mod(i);
+        if duration == 0. && timeline.is_auto() {
             continue;
         }
 
@@ -1542,12 +2747,21 @@ pub fn maybe_start_animations<E>(
             AnimationPlayState::Running => AnimationState::Pending,
         };
 
+        let composition = style.animation_composition_mod(i);
         let computed_steps = ComputedKeyframe::generate_for_keyframes(
             element,
             &keyframe_animation,
             context,
             new_style,
             style.animation_timing_function_mod(i),
+            composition,
+            resolver,
+        );
+        let underlying_values = generate_underlying_values(
+            &keyframe_animation,
+            &computed_steps,
+            context,
+            new_style,
             resolver,
         );
 
@@ -1555,14 +2769,19 @@ pub fn maybe_start_animations<E>(
             name: name.clone(),
             properties_changed: keyframe_animation.properties_changed.clone(),
             computed_steps,
+            underlying_values,
             started_at,
             duration,
             fill_mode: style.animation_fill_mode_mod(i),
+            composition,
             delay: delay as f64,
             iteration_state,
             state,
             direction: animation_direction,
             current_direction: initial_direction,
+            timeline,
+            timeline_source: None,
+            timeline_progress: None,
             cascade_style: new_style.clone(),
             is_new: true,
         };
@@ -1577,18 +2796,170 @@ pub fn maybe_start_animations<E>(
         animation_state.dirty = true;
 
         // If the animation was already present in the list for the node, just update its state.
-        for existing_animation in animation_state.animations.iter_mut() {
-            if existing_animation.state == AnimationState::Canceled {
-                continue;
-            }
-
-            if new_animation.name == existing_animation.name {
-                existing_animation
-                    .update_from_other(&new_animation, context.current_time_for_animations);
-                return;
-            }
+        let existing_animation = animation_state.animations.iter_mut().find(|animation| {
+            animation.state != AnimationState::Canceled && animation.name == new_animation.name
+        });
+        match existing_animation {
+            Some(existing_animation) => existing_animation
+                .update_from_other(&new_animation, context.current_time_for_animations),
+            None => animation_state.animations.push(new_animation),
         }
+    }
+
+    // Animations later in `animation-name` are composited on top of earlier ones,
+    // so keep them in that order.
+    animation_state.animations.sort_by_key(|animation| {
+        style
+            .animation_name_iter()
+            .position(|name| name.as_atom() == Some(&animation.name))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        animation_state.animations.push(new_animation);
+    fn script_animation(timing: ScriptAnimationTiming) -> ScriptAnimation {
+        let lock = SharedRwLock::new();
+        let guard = lock.read();
+        ScriptAnimation::new(&[], timing, &guard).unwrap()
+    }
+
+    #[test]
+    fn missing_keyframe_offsets() {
+        let lock = SharedRwLock::new();
+        let keyframe = |offset| ScriptKeyframe {
+            offset,
+            block: Arc::new(lock.wrap(PropertyDeclarationBlock::new())),
+        };
+
+        let keyframes = [
+            keyframe(None),
+            keyframe(None),
+            keyframe(Some(0.8)),
+            keyframe(None),
+        ];
+        assert_eq!(
+            compute_keyframe_offsets(&keyframes),
+            Ok(vec![0., 0.4, 0.8, 1.])
+        );
+        assert_eq!(compute_keyframe_offsets(&[keyframe(None)]), Ok(vec![1.]));
+
+        let keyframes = [keyframe(Some(0.5)), keyframe(Some(0.2))];
+        assert_eq!(compute_keyframe_offsets(&keyframes), Err(()));
+        assert_eq!(compute_keyframe_offsets(&[keyframe(Some(1.5))]), Err(()));
+    }
+
+    #[test]
+    fn play_pause_seek_and_reverse() {
+        let timing = ScriptAnimationTiming::new(
+            0.,
+            0.,
+            AnimationFillMode::None,
+            1.,
+            1.,
+            AnimationDirection::Normal,
+            TimingFunction::linear(),
+        );
+        let mut animation = script_animation(timing.unwrap());
+        assert_eq!(animation.play_state(0.), ScriptAnimationPlayState::Idle);
+        assert_eq!(animation.current_time(0.), None);
+
+        animation.play(10.).unwrap();
+        assert_eq!(animation.current_time(10.5), Some(0.5));
+        assert_eq!(
+            animation.play_state(10.5),
+            ScriptAnimationPlayState::Running
+        );
+
+        animation.pause(10.5).unwrap();
+        assert_eq!(animation.current_time(20.), Some(0.5));
+        assert_eq!(animation.play_state(20.), ScriptAnimationPlayState::Paused);
+
+        animation.set_playback_rate(2., 20.);
+        animation.play(20.).unwrap();
+        assert_eq!(animation.current_time(20.125), Some(0.75));
+
+        // Playing animations stop at their end.
+        assert_eq!(animation.current_time(30.), Some(1.));
+        assert_eq!(
+            animation.play_state(30.),
+            ScriptAnimationPlayState::Finished
+        );
+        animation.update_finished_state(30.);
+        assert!(!animation.needs_to_be_ticked());
+
+        animation.reverse(30.).unwrap();
+        assert_eq!(animation.playback_rate(), -2.);
+        assert_eq!(animation.current_time(30.25), Some(0.5));
+
+        animation.set_current_time(0.25, 31.);
+        assert_eq!(animation.current_time(31.), Some(0.25));
+        assert_eq!(
+            animation.play_state(32.),
+            ScriptAnimationPlayState::Finished
+        );
+
+        // Playing a finished animation rewinds it.
+        animation.play(40.).unwrap();
+        assert_eq!(animation.current_time(40.), Some(1.));
+
+        animation.cancel();
+        assert_eq!(animation.play_state(40.), ScriptAnimationPlayState::Idle);
+    }
+
+    #[test]
+    fn transformed_progress() {
+        let timing = |fill| {
+            ScriptAnimationTiming::new(
+                1.,
+                0.,
+                fill,
+                2.,
+                1.,
+                AnimationDirection::Alternate,
+                TimingFunction::linear(),
+            )
+            .unwrap()
+        };
+        let timing_without_fill = timing(AnimationFillMode::None);
+        assert_eq!(timing_without_fill.transformed_progress(0.5, 1.), None);
+        assert_eq!(
+            timing_without_fill.transformed_progress(1.25, 1.),
+            Some(0.25)
+        );
+        assert_eq!(
+            timing_without_fill.transformed_progress(2.25, 1.),
+            Some(0.75)
+        );
+        assert_eq!(timing_without_fill.transformed_progress(3.5, 1.), None);
+
+        let timing_with_fill = timing(AnimationFillMode::Both);
+        assert_eq!(timing_with_fill.transformed_progress(0.5, 1.), Some(0.));
+        assert_eq!(timing_with_fill.transformed_progress(3.5, 1.), Some(0.));
+    }
+
+    #[test]
+    fn invalid_timing() {
+        let timing = |delay, end_delay, iterations, duration| {
+            ScriptAnimationTiming::new(
+                delay,
+                end_delay,
+                AnimationFillMode::None,
+                iterations,
+                duration,
+                AnimationDirection::Normal,
+                TimingFunction::linear(),
+            )
+            .is_some()
+        };
+        assert!(timing(-1., -1., 0., 0.));
+        assert!(timing(0., 0., f64::INFINITY, f64::INFINITY));
+        assert!(!timing(f64::INFINITY, 0., 1., 1.));
+        assert!(!timing(0., f64::NAN, 1., 1.));
+        assert!(!timing(0., 0., -1., 1.));
+        assert!(!timing(0., 0., f64::NAN, 1.));
+        assert!(!timing(0., 0., 1., -0.5));
+        assert!(!timing(0., 0., 1., f64::NAN));
     }
 }
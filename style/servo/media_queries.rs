@@ -14,10 +14,10 @@ use crate::media_queries::MediaType;
 use crate::properties::style_structs::Font;
 use crate::properties::ComputedValues;
 use crate::queries::feature::{AllowsRanges, Evaluator, FeatureFlags, QueryFeatureDescription};
-use crate::queries::values::PrefersColorScheme;
+use crate::queries::values::{Orientation, PrefersColorScheme};
 use crate::values::computed::font::GenericFontFamily;
 use crate::values::computed::{
-    CSSPixelLength, Context, Length, LineHeight, NonNegativeLength, Resolution,
+    CSSPixelLength, Context, Length, LineHeight, NonNegativeLength, Ratio, Resolution,
 };
 use crate::values::specified::color::{ColorSchemeFlags, ForcedColors};
 use crate::values::specified::font::{
@@ -62,6 +62,10 @@ pub trait FontMetricsProvider: Debug + Sync {
 /// units. These atomics use relaxed ordering, since when computing the style
 /// of the root element, there can't be any other style being computed at the
 /// same time (given we need the style of the parent to compute everything else).
+///
+/// None of the setters on this structure update any associated `Stylist`. For
+/// this you must call `Stylist::media_features_change_changed_style` and
+/// `Stylist::force_stylesheet_origins_dirty`.
 #[derive(Debug, MallocSizeOf)]
 pub struct Device {
     /// The current media type used by de device.
@@ -117,6 +121,45 @@ pub struct Device {
     /// Whether the user prefers light mode or dark mode
     #[ignore_malloc_size_of = "Pure stack type"]
     prefers_color_scheme: PrefersColorScheme,
+    /// Whether the user prefers reduced motion.
+    prefers_reduced_motion: bool,
+    /// Whether the user prefers reduced transparency.
+    prefers_reduced_transparency: bool,
+    /// The contrast preference of the user.
+    #[ignore_malloc_size_of = "Pure stack type"]
+    prefers_contrast: PrefersContrast,
+    /// Whether page colors are being forced.
+    #[ignore_malloc_size_of = "Pure stack type"]
+    forced_colors: ForcedColors,
+    /// Whether the output device inverts all the colors.
+    inverted_colors: bool,
+    /// The approximate color gamut of the output device.
+    #[ignore_malloc_size_of = "Pure stack type"]
+    color_gamut: ColorGamut,
+    /// The dynamic range supported by the output device.
+    #[ignore_malloc_size_of = "Pure stack type"]
+    dynamic_range: DynamicRange,
+    /// The dynamic range supported by the video plane of the output device.
+    #[ignore_malloc_size_of = "Pure stack type"]
+    video_dynamic_range: DynamicRange,
+    /// The display mode of the top-level browsing context.
+    #[ignore_malloc_size_of = "Pure stack type"]
+    display_mode: DisplayMode,
+    /// Whether scripting is enabled for the document.
+    #[ignore_malloc_size_of = "Pure stack type"]
+    scripting: Scripting,
+    /// The number of bits per color component of the output device, or zero
+    /// if the device is not a color device.
+    color_bits: u32,
+    /// The number of bits per pixel of a monochrome output device, or zero if
+    /// the device is not monochrome.
+    monochrome_bits: u32,
+    /// The capabilities of the primary pointing device.
+    #[ignore_malloc_size_of = "Pure stack type"]
+    primary_pointer_capabilities: PointerCapabilities,
+    /// The union of the capabilities of all the available pointing devices.
+    #[ignore_malloc_size_of = "Pure stack type"]
+    all_pointer_capabilities: PointerCapabilities,
     /// The CssEnvironment object responsible of getting CSS environment
     /// variables.
     environment: CssEnvironment,
@@ -160,6 +203,20 @@ impl Device {
             used_font_metrics: AtomicBool::new(false),
             used_viewport_units: AtomicBool::new(false),
             prefers_color_scheme,
+            prefers_reduced_motion: false,
+            prefers_reduced_transparency: false,
+            prefers_contrast: PrefersContrast::NoPreference,
+            forced_colors: ForcedColors::None,
+            inverted_colors: false,
+            color_gamut: ColorGamut::Srgb,
+            dynamic_range: DynamicRange::Standard,
+            video_dynamic_range: DynamicRange::Standard,
+            display_mode: DisplayMode::Browser,
+            scripting: Scripting::Enabled,
+            color_bits: 8,
+            monochrome_bits: 0,
+            primary_pointer_capabilities: PointerCapabilities::FINE | PointerCapabilities::HOVER,
+            all_pointer_capabilities: PointerCapabilities::FINE | PointerCapabilities::HOVER,
             environment: CssEnvironment,
             font_metrics_provider,
             default_computed_values,
@@ -335,10 +392,6 @@ impl Device {
     }
 
     /// Set the viewport size on this [`Device`].
-    ///
-    /// Note that this does not update any associated `Stylist`. For this you must call
-    /// `Stylist::media_features_change_changed_style` and
-    /// `Stylist::force_stylesheet_origins_dirty`.
     pub fn set_viewport_size(&mut self, viewport_size: Size2D<f32, CSSPixel>) {
         self.viewport_size = viewport_size;
     }
@@ -385,10 +438,6 @@ impl Device {
     }
 
     /// Set a new device pixel ratio on this [`Device`].
-    ///
-    /// Note that this does not update any associated `Stylist`. For this you must call
-    /// `Stylist::media_features_change_changed_style` and
-    /// `Stylist::force_stylesheet_origins_dirty`.
     pub fn set_device_pixel_ratio(
         &mut self,
         device_pixel_ratio: Scale<f32, CSSPixel, DevicePixel>,
@@ -474,7 +523,12 @@ impl Device {
 
     /// Returns whether document colors are enabled.
     pub fn forced_colors(&self) -> ForcedColors {
-        ForcedColors::None
+        self.forced_colors
+    }
+
+    /// Set the [`ForcedColors`] value on this [`Device`].
+    pub fn set_forced_colors(&mut self, forced_colors: ForcedColors) {
+        self.forced_colors = forced_colors;
     }
 
     /// Returns the default background color.
@@ -488,10 +542,6 @@ impl Device {
     }
 
     /// Set the [`PrefersColorScheme`] value on this [`Device`].
-    ///
-    /// Note that this does not update any associated `Stylist`. For this you must call
-    /// `Stylist::media_features_change_changed_style` and
-    /// `Stylist::force_stylesheet_origins_dirty`.
     pub fn set_color_scheme(&mut self, new_color_scheme: PrefersColorScheme) {
         self.prefers_color_scheme = new_color_scheme;
     }
@@ -501,6 +551,115 @@ impl Device {
         self.prefers_color_scheme
     }
 
+    /// Set whether the user prefers reduced motion on this [`Device`].
+    pub fn set_prefers_reduced_motion(&mut self, prefers_reduced_motion: bool) {
+        self.prefers_reduced_motion = prefers_reduced_motion;
+    }
+
+    /// Returns whether the user prefers reduced motion.
+    pub fn prefers_reduced_motion(&self) -> bool {
+        self.prefers_reduced_motion
+    }
+
+    /// Set whether the user prefers reduced transparency on this [`Device`].
+    pub fn set_prefers_reduced_transparency(&mut self, prefers_reduced_transparency: bool) {
+        self.prefers_reduced_transparency = prefers_reduced_transparency;
+    }
+
+    /// Returns whether the user prefers reduced transparency.
+    pub fn prefers_reduced_transparency(&self) -> bool {
+        self.prefers_reduced_transparency
+    }
+
+    /// Set the [`PrefersContrast`] value on this [`Device`].
+    pub fn set_prefers_contrast(&mut self, prefers_contrast: PrefersContrast) {
+        self.prefers_contrast = prefers_contrast;
+    }
+
+    /// Returns the contrast preference of this [`Device`].
+    pub fn prefers_contrast(&self) -> PrefersContrast {
+        self.prefers_contrast
+    }
+
+    /// Set whether the output device inverts colors on this [`Device`].
+    pub fn set_inverted_colors(&mut self, inverted_colors: bool) {
+        self.inverted_colors = inverted_colors;
+    }
+
+    /// Returns whether the output device inverts colors.
+    pub fn inverted_colors(&self) -> bool {
+        self.inverted_colors
+    }
+
+    /// Set the [`ColorGamut`] of the output device on this [`Device`].
+    pub fn set_color_gamut(&mut self, color_gamut: ColorGamut) {
+        self.color_gamut = color_gamut;
+    }
+
+    /// Returns the color gamut of the output device.
+    pub fn color_gamut(&self) -> ColorGamut {
+        self.color_gamut
+    }
+
+    /// Set the [`DynamicRange`] of the output device on this [`Device`].
+    pub fn set_dynamic_range(&mut self, dynamic_range: DynamicRange) {
+        self.dynamic_range = dynamic_range;
+    }
+
+    /// Returns the dynamic range of the output device.
+    pub fn dynamic_range(&self) -> DynamicRange {
+        self.dynamic_range
+    }
+
+    /// Set the [`DynamicRange`] of the video plane of the output device on this [`Device`].
+    pub fn set_video_dynamic_range(&mut self, video_dynamic_range: DynamicRange) {
+        self.video_dynamic_range = video_dynamic_range;
+    }
+
+    /// Returns the dynamic range of the video plane of the output device.
+    pub fn video_dynamic_range(&self) -> DynamicRange {
+        self.video_dynamic_range
+    }
+
+    /// Set the [`DisplayMode`] value on this [`Device`].
+    pub fn set_display_mode(&mut self, display_mode: DisplayMode) {
+        self.display_mode = display_mode;
+    }
+
+    /// Returns the display mode of this [`Device`].
+    pub fn display_mode(&self) -> DisplayMode {
+        self.display_mode
+    }
+
+    /// Set the [`Scripting`] value on this [`Device`].
+    pub fn set_scripting(&mut self, scripting: Scripting) {
+        self.scripting = scripting;
+    }
+
+    /// Returns whether scripting is enabled for this [`Device`].
+    pub fn scripting(&self) -> Scripting {
+        self.scripting
+    }
+
+    /// Set the color depth of the output device on this [`Device`], as the number of
+    /// bits per color component, and the number of bits per pixel for monochrome
+    /// devices. Only one of them should be non-zero.
+    pub fn set_color_depth(&mut self, color_bits: u32, monochrome_bits: u32) {
+        self.color_bits = color_bits;
+        self.monochrome_bits = monochrome_bits;
+    }
+
+    /// Set the capabilities of the primary pointing device, and of all the available
+    /// pointing devices, on this [`Device`].
+    pub fn set_pointer_capabilities(
+        &mut self,
+        primary: PointerCapabilities,
+        all: PointerCapabilities,
+    ) {
+        self.primary_pointer_capabilities = primary;
+        self.all_pointer_capabilities = all | primary;
+    }
+
     pub(crate) fn is_dark_color_scheme(&self, _: ColorSchemeFlags) -> bool {
         false
     }
@@ -539,6 +698,22 @@ fn eval_width(context: &Context) -> CSSPixelLength {
     CSSPixelLength::new(context.device().au_viewport_size().width.to_f32_px())
 }
 
+/// https://drafts.csswg.org/mediaqueries-4/#height
+fn eval_height(context: &Context) -> CSSPixelLength {
+    CSSPixelLength::new(context.device().au_viewport_size().height.to_f32_px())
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#aspect-ratio
+fn eval_aspect_ratio(context: &Context) -> Ratio {
+    let size = context.device().au_viewport_size();
+    Ratio::new(size.width.0 as f32, size.height.0 as f32)
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#orientation
+fn eval_orientation(context: &Context, value: Option<Orientation>) -> bool {
+    Orientation::eval(context.device().au_viewport_size(), value)
+}
+
 #[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
 #[repr(u8)]
 enum Scan {
@@ -553,6 +728,13 @@ fn eval_scan(_: &Context, _: Option<Scan>) -> bool {
     false
 }
 
+/// https://drafts.csswg.org/mediaqueries-4/#grid
+fn eval_grid(_: &Context) -> bool {
+    // Servo doesn't support grid devices (e.g., ttys), so the 'grid' feature
+    // is always 0.
+    false
+}
+
 /// https://drafts.csswg.org/mediaqueries-4/#resolution
 fn eval_resolution(context: &Context) -> Resolution {
     Resolution::from_dppx(context.device().device_pixel_ratio.0)
@@ -563,6 +745,256 @@ fn eval_device_pixel_ratio(context: &Context) -> f32 {
     eval_resolution(context).dppx()
 }
 
+/// https://drafts.csswg.org/mediaqueries-4/#color
+fn eval_color(context: &Context) -> i32 {
+    context.device().color_bits as i32
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#color-index
+fn eval_color_index(_: &Context) -> i32 {
+    // We should return zero if the device does not use a color lookup table.
+    0
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#monochrome
+fn eval_monochrome(context: &Context) -> i32 {
+    context.device().monochrome_bits as i32
+}
+
+/// Values for the color-gamut media feature.
+/// This implements PartialOrd so that lower values will correctly match
+/// higher capabilities.
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, PartialEq, PartialOrd, ToCss)]
+#[repr(u8)]
+pub enum ColorGamut {
+    /// The sRGB gamut.
+    Srgb,
+    /// The gamut specified by the Display P3 Color Space.
+    P3,
+    /// The gamut specified by the ITU-R Recommendation BT.2020 Color Space.
+    Rec2020,
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#color-gamut
+fn eval_color_gamut(context: &Context, query_value: Option<ColorGamut>) -> bool {
+    let query_value = match query_value {
+        Some(v) => v,
+        None => return false,
+    };
+    // Match if our color gamut is at least as wide as the query value
+    query_value <= context.device().color_gamut
+}
+
+/// Values for the dynamic-range and video-dynamic-range media features.
+/// https://drafts.csswg.org/mediaqueries-5/#dynamic-range
+/// This implements PartialOrd so that lower values will correctly match
+/// higher capabilities.
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, PartialEq, PartialOrd, ToCss)]
+#[repr(u8)]
+#[allow(missing_docs)]
+pub enum DynamicRange {
+    Standard,
+    High,
+}
+
+/// https://drafts.csswg.org/mediaqueries-5/#dynamic-range
+fn eval_dynamic_range(context: &Context, query_value: Option<DynamicRange>) -> bool {
+    match query_value {
+        Some(v) => context.device().dynamic_range >= v,
+        None => false,
+    }
+}
+
+/// https://drafts.csswg.org/mediaqueries-5/#video-dynamic-range
+fn eval_video_dynamic_range(context: &Context, query_value: Option<DynamicRange>) -> bool {
+    match query_value {
+        Some(v) => context.device().video_dynamic_range >= v,
+        None => false,
+    }
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+enum PrefersReducedMotion {
+    NoPreference,
+    Reduce,
+}
+
+/// https://drafts.csswg.org/mediaqueries-5/#prefers-reduced-motion
+fn eval_prefers_reduced_motion(
+    context: &Context,
+    query_value: Option<PrefersReducedMotion>,
+) -> bool {
+    let prefers_reduced = context.device().prefers_reduced_motion;
+    let query_value = match query_value {
+        Some(v) => v,
+        None => return prefers_reduced,
+    };
+
+    match query_value {
+        PrefersReducedMotion::NoPreference => !prefers_reduced,
+        PrefersReducedMotion::Reduce => prefers_reduced,
+    }
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+enum PrefersReducedTransparency {
+    NoPreference,
+    Reduce,
+}
+
+/// https://drafts.csswg.org/mediaqueries-5/#prefers-reduced-transparency
+fn eval_prefers_reduced_transparency(
+    context: &Context,
+    query_value: Option<PrefersReducedTransparency>,
+) -> bool {
+    let prefers_reduced = context.device().prefers_reduced_transparency;
+    let query_value = match query_value {
+        Some(v) => v,
+        None => return prefers_reduced,
+    };
+
+    match query_value {
+        PrefersReducedTransparency::NoPreference => !prefers_reduced,
+        PrefersReducedTransparency::Reduce => prefers_reduced,
+    }
+}
+
+/// Possible values for prefers-contrast media query.
+/// https://drafts.csswg.org/mediaqueries-5/#prefers-contrast
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, PartialEq, ToCss)]
+#[repr(u8)]
+pub enum PrefersContrast {
+    /// More contrast is preferred.
+    More,
+    /// Low contrast is preferred.
+    Less,
+    /// Custom (not more, not less).
+    Custom,
+    /// The default value if neither high or low contrast is enabled.
+    NoPreference,
+}
+
+/// https://drafts.csswg.org/mediaqueries-5/#prefers-contrast
+fn eval_prefers_contrast(context: &Context, query_value: Option<PrefersContrast>) -> bool {
+    let prefers_contrast = context.device().prefers_contrast;
+    match query_value {
+        Some(v) => v == prefers_contrast,
+        None => prefers_contrast != PrefersContrast::NoPreference,
+    }
+}
+
+/// https://drafts.csswg.org/mediaqueries-5/#forced-colors
+fn eval_forced_colors(context: &Context, query_value: Option<ForcedColors>) -> bool {
+    let forced = context.device().forced_colors();
+    match query_value {
+        Some(query_value) => query_value == forced,
+        None => forced != ForcedColors::None,
+    }
+}
+
+/// Possible values for the inverted-colors media query.
+/// https://drafts.csswg.org/mediaqueries-5/#inverted
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+enum InvertedColors {
+    /// Colors are displayed normally.
+    None,
+    /// All pixels within the displayed area have been inverted.
+    Inverted,
+}
+
+/// https://drafts.csswg.org/mediaqueries-5/#inverted
+fn eval_inverted_colors(context: &Context, query_value: Option<InvertedColors>) -> bool {
+    let inverted_colors = context.device().inverted_colors;
+    let query_value = match query_value {
+        Some(v) => v,
+        None => return inverted_colors,
+    };
+
+    match query_value {
+        InvertedColors::None => !inverted_colors,
+        InvertedColors::Inverted => inverted_colors,
+    }
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+enum OverflowBlock {
+    None,
+    Scroll,
+    Paged,
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#mf-overflow-block
+fn eval_overflow_block(context: &Context, query_value: Option<OverflowBlock>) -> bool {
+    // Assume that the print media type is the only time when we paginate,
+    // and we are otherwise always scrolling.
+    //
+    // If this ever changes, don't forget to change eval_overflow_inline too.
+    let scrolling = context.device().media_type() != MediaType::print();
+    let query_value = match query_value {
+        Some(v) => v,
+        None => return true,
+    };
+
+    match query_value {
+        OverflowBlock::None => false,
+        OverflowBlock::Scroll => scrolling,
+        OverflowBlock::Paged => !scrolling,
+    }
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+enum OverflowInline {
+    None,
+    Scroll,
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#mf-overflow-inline
+fn eval_overflow_inline(context: &Context, query_value: Option<OverflowInline>) -> bool {
+    // See the note in eval_overflow_block.
+    let scrolling = context.device().media_type() != MediaType::print();
+    let query_value = match query_value {
+        Some(v) => v,
+        None => return scrolling,
+    };
+
+    match query_value {
+        OverflowInline::None => !scrolling,
+        OverflowInline::Scroll => scrolling,
+    }
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+enum Update {
+    None,
+    Slow,
+    Fast,
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#update
+fn eval_update(context: &Context, query_value: Option<Update>) -> bool {
+    // This has similar caveats to those described in eval_overflow_block.
+    // For now, we report that print is `update: none` and that all other
+    // contexts are `update: fast`.
+    let can_update = context.device().media_type() != MediaType::print();
+    let query_value = match query_value {
+        Some(v) => v,
+        None => return can_update,
+    };
+
+    match query_value {
+        Update::None => !can_update,
+        Update::Slow => false,
+        Update::Fast => can_update,
+    }
+}
+
+/// https://drafts.csswg.org/mediaqueries-5/#prefers-color-scheme
 fn eval_prefers_color_scheme(context: &Context, query_value: Option<PrefersColorScheme>) -> bool {
     match query_value {
         Some(v) => context.device().prefers_color_scheme == v,
@@ -570,13 +1002,163 @@ fn eval_prefers_color_scheme(context: &Context, query_value: Option<PrefersColor
     }
 }
 
+/// Values for the display-mode media feature.
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, PartialEq, ToCss)]
+#[repr(u8)]
+#[allow(missing_docs)]
+pub enum DisplayMode {
+    Browser = 0,
+    MinimalUi,
+    Standalone,
+    Fullscreen,
+    PictureInPicture,
+}
+
+/// https://w3c.github.io/manifest/#the-display-mode-media-feature
+fn eval_display_mode(context: &Context, query_value: Option<DisplayMode>) -> bool {
+    match query_value {
+        Some(v) => v == context.device().display_mode,
+        None => true,
+    }
+}
+
+/// Values for the scripting media feature.
+/// https://drafts.csswg.org/mediaqueries-5/#scripting
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, PartialEq, ToCss)]
+#[repr(u8)]
+pub enum Scripting {
+    /// Scripting is not supported or not enabled
+    None,
+    /// Scripting is supported and enabled, but only for initial page load
+    InitialOnly,
+    /// Scripting is supported and enabled
+    Enabled,
+}
+
+/// https://drafts.csswg.org/mediaqueries-5/#scripting
+fn eval_scripting(context: &Context, query_value: Option<Scripting>) -> bool {
+    let scripting = context.device().scripting;
+    match query_value {
+        Some(v) => v == scripting,
+        None => scripting != Scripting::None,
+    }
+}
+
+bitflags! {
+    /// The capabilities of a pointing device.
+    ///
+    /// https://drafts.csswg.org/mediaqueries-4/#mf-interaction
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub struct PointerCapabilities: u8 {
+        /// The pointing device has limited accuracy, like a touchscreen.
+        const COARSE = 1 << 0;
+        /// The pointing device is accurate, like a mouse.
+        const FINE = 1 << 1;
+        /// The pointing device can easily hover over elements.
+        const HOVER = 1 << 2;
+    }
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+enum Pointer {
+    None,
+    Coarse,
+    Fine,
+}
+
+fn eval_pointer_capabilities(
+    query_value: Option<Pointer>,
+    pointer_capabilities: PointerCapabilities,
+) -> bool {
+    let query_value = match query_value {
+        Some(v) => v,
+        None => return !pointer_capabilities.is_empty(),
+    };
+
+    match query_value {
+        Pointer::None => pointer_capabilities.is_empty(),
+        Pointer::Coarse => pointer_capabilities.intersects(PointerCapabilities::COARSE),
+        Pointer::Fine => pointer_capabilities.intersects(PointerCapabilities::FINE),
+    }
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#pointer
+fn eval_pointer(context: &Context, query_value: Option<Pointer>) -> bool {
+    eval_pointer_capabilities(query_value, context.device().primary_pointer_capabilities)
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#descdef-media-any-pointer
+fn eval_any_pointer(context: &Context, query_value: Option<Pointer>) -> bool {
+    eval_pointer_capabilities(query_value, context.device().all_pointer_capabilities)
+}
+
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+enum Hover {
+    None,
+    Hover,
+}
+
+fn eval_hover_capabilities(
+    query_value: Option<Hover>,
+    pointer_capabilities: PointerCapabilities,
+) -> bool {
+    let can_hover = pointer_capabilities.intersects(PointerCapabilities::HOVER);
+    let query_value = match query_value {
+        Some(v) => v,
+        None => return can_hover,
+    };
+
+    match query_value {
+        Hover::None => !can_hover,
+        Hover::Hover => can_hover,
+    }
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#hover
+fn eval_hover(context: &Context, query_value: Option<Hover>) -> bool {
+    eval_hover_capabilities(query_value, context.device().primary_pointer_capabilities)
+}
+
+/// https://drafts.csswg.org/mediaqueries-4/#descdef-media-any-hover
+fn eval_any_hover(context: &Context, query_value: Option<Hover>) -> bool {
+    eval_hover_capabilities(query_value, context.device().all_pointer_capabilities)
+}
+
 /// A list with all the media features that Servo supports.
-pub static MEDIA_FEATURES: [QueryFeatureDescription; 6] = [
+///
+/// Adding new media features requires (1) adding the new feature to this
+/// array, with appropriate entries and (2) making sure that the embedder
+/// calls `Stylist::media_features_change_changed_style` when the value that
+/// would be returned by the evaluator function could change.
+pub static MEDIA_FEATURES: [QueryFeatureDescription; 30] = [
+    // The viewport-sized features are flagged like in Gecko, so that
+    // `MediaList::is_viewport_dependent` tells embedders which media lists
+    // need to be re-evaluated when the viewport is resized.
     feature!(
         atom!("width"),
         AllowsRanges::Yes,
         Evaluator::Length(eval_width),
-        FeatureFlags::empty(),
+        FeatureFlags::VIEWPORT_DEPENDENT,
+    ),
+    feature!(
+        atom!("height"),
+        AllowsRanges::Yes,
+        Evaluator::Length(eval_height),
+        FeatureFlags::VIEWPORT_DEPENDENT,
+    ),
+    feature!(
+        atom!("aspect-ratio"),
+        AllowsRanges::Yes,
+        Evaluator::NumberRatio(eval_aspect_ratio),
+        FeatureFlags::VIEWPORT_DEPENDENT,
+    ),
+    feature!(
+        atom!("orientation"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_orientation, Orientation),
+        FeatureFlags::VIEWPORT_DEPENDENT,
     ),
     feature!(
         atom!("scan"),
@@ -584,6 +1166,12 @@ pub static MEDIA_FEATURES: [QueryFeatureDescription; 6] = [
         keyword_evaluator!(eval_scan, Scan),
         FeatureFlags::empty(),
     ),
+    feature!(
+        atom!("grid"),
+        AllowsRanges::No,
+        Evaluator::BoolInteger(eval_grid),
+        FeatureFlags::empty(),
+    ),
     feature!(
         atom!("resolution"),
         AllowsRanges::Yes,
@@ -602,10 +1190,133 @@ pub static MEDIA_FEATURES: [QueryFeatureDescription; 6] = [
         Evaluator::Float(eval_device_pixel_ratio),
         FeatureFlags::empty(),
     ),
+    feature!(
+        atom!("color"),
+        AllowsRanges::Yes,
+        Evaluator::Integer(eval_color),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("color-index"),
+        AllowsRanges::Yes,
+        Evaluator::Integer(eval_color_index),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("monochrome"),
+        AllowsRanges::Yes,
+        Evaluator::Integer(eval_monochrome),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("color-gamut"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_color_gamut, ColorGamut),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("dynamic-range"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_dynamic_range, DynamicRange),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("video-dynamic-range"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_video_dynamic_range, DynamicRange),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("prefers-reduced-motion"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_prefers_reduced_motion, PrefersReducedMotion),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("prefers-reduced-transparency"),
+        AllowsRanges::No,
+        keyword_evaluator!(
+            eval_prefers_reduced_transparency,
+            PrefersReducedTransparency
+        ),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("prefers-contrast"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_prefers_contrast, PrefersContrast),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("forced-colors"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_forced_colors, ForcedColors),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("inverted-colors"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_inverted_colors, InvertedColors),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("overflow-block"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_overflow_block, OverflowBlock),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("overflow-inline"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_overflow_inline, OverflowInline),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("update"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_update, Update),
+        FeatureFlags::empty(),
+    ),
     feature!(
         atom!("prefers-color-scheme"),
         AllowsRanges::No,
         keyword_evaluator!(eval_prefers_color_scheme, PrefersColorScheme),
         FeatureFlags::empty(),
     ),
+    feature!(
+        atom!("display-mode"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_display_mode, DisplayMode),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("scripting"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_scripting, Scripting),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("pointer"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_pointer, Pointer),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("any-pointer"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_any_pointer, Pointer),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("hover"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_hover, Hover),
+        FeatureFlags::empty(),
+    ),
+    feature!(
+        atom!("any-hover"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_any_hover, Hover),
+        FeatureFlags::empty(),
+    ),
 ];
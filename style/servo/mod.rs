@@ -11,7 +11,10 @@ pub mod animation;
 pub mod attr;
 mod encoding_support;
 pub mod media_queries;
+pub mod prefs;
 pub mod restyle_damage;
+pub mod scroll_timeline;
 pub mod selector_parser;
 mod shadow_parts;
 pub mod url;
+pub mod view_transition;
@@ -0,0 +1,43 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! The runtime preferences the style system reads.
+//!
+//! Preferences are stored in `style_config`, which embedders can also use to
+//! load them from a document, and to observe their changes.
+
+use crate::properties::generated::PROPERTY_PREFS;
+use style_config::{PrefDescription, PrefValue};
+
+/// Returns the description of every preference the style system reads, sorted
+/// by name.
+pub fn registry() -> Vec<PrefDescription> {
+    let mut prefs = static_prefs::registry();
+    for name in PROPERTY_PREFS {
+        if !prefs.iter().any(|pref| pref.name == *name) {
+            prefs.push(PrefDescription {
+                name,
+                default: PrefValue::Bool(false),
+            });
+        }
+    }
+    prefs.sort_by_key(|pref| pref.name);
+    prefs
+}
+
+/// Registers the types of the preferences the style system reads with
+/// `style_config`, so that the values set or loaded from documents afterwards
+/// are checked against them.
+pub fn register() {
+    style_config::register(registry())
+}
+
+/// Returns whether the given preference enables properties.
+///
+/// Properties are enabled when style sheets are parsed, so when such a
+/// preference changes, the style sheets need to be parsed again, and the
+/// stylist rebuilt with them.
+pub fn enables_properties(name: &str) -> bool {
+    PROPERTY_PREFS.contains(&name)
+}
@@ -14,18 +14,22 @@ use crate::dom::TElement;
 use crate::matching::{StyleChange, StyleDifference};
 use crate::properties::{
     restyle_damage_rebuild_box, restyle_damage_rebuild_stacking_context,
-    restyle_damage_recalculate_overflow, restyle_damage_repaint, style_structs, ComputedValues,
+    restyle_damage_recalculate_overflow, restyle_damage_repaint, restyle_damage_update_scroll_snap,
+    style_structs, ComputedValues,
 };
 use crate::values::computed::basic_shape::ClipPath;
-use crate::values::computed::Perspective;
+use crate::values::computed::{Image, Perspective};
 use crate::values::generics::transform::{GenericRotate, GenericScale, GenericTranslate};
 use std::fmt;
 
 bitflags! {
-    /// Major phases of layout that need to be run due to the damage to a node during restyling. In
-    /// addition to the 4 bytes used for that, the rest of the `u16` is exposed as an extension point
-    /// for users of the crate to add their own custom types of damage that correspond to the
-    /// layout system they are implementing.
+    /// Major phases of layout that need to be run due to the damage to a node during restyling.
+    ///
+    /// The lowest 5 bits are used by this crate: 4 for the phases of layout and one for
+    /// `UPDATE_SCROLL_SNAP`. The rest of the `u16`, `EMBEDDER_BITS`, is exposed as an extension
+    /// point for users of the crate to add their own custom types of damage that correspond to the
+    /// layout system they are implementing. Note that this range used to start at the fifth bit,
+    /// before `UPDATE_SCROLL_SNAP` was added.
     #[derive(Clone, Copy, Debug, Eq, PartialEq)]
     pub struct ServoRestyleDamage: u16 {
         /// Repaint the node itself.
@@ -47,12 +51,24 @@ bitflags! {
         ///
         /// Propagates both up and down the flow tree.
         const RELAYOUT = 0b1111;
+
+        /// Recomputes the scroll snap areas and snap containers, without
+        /// running layout again.
+        ///
+        /// This is independent from the other flags, and it's only set when
+        /// the `scroll-snap-*`, `scroll-margin-*` or `scroll-padding-*`
+        /// properties change.
+        const UPDATE_SCROLL_SNAP = 0b1_0000;
     }
 }
 
 malloc_size_of::malloc_size_of_is_0!(ServoRestyleDamage);
 
 impl ServoRestyleDamage {
+    /// The bits that embedders can use for their own types of damage, see
+    /// above.
+    pub const EMBEDDER_BITS: u16 = !0b1_1111;
+
     /// Compute the `StyleDifference` (including the appropriate restyle damage)
     /// for a given style change between `old` and `new`.
     pub fn compute_style_difference<E: TElement>(
@@ -113,6 +129,7 @@ impl fmt::Display for ServoRestyleDamage {
                 "Recalculate overflow",
             ),
             (ServoRestyleDamage::RELAYOUT, "Relayout"),
+            (ServoRestyleDamage::UPDATE_SCROLL_SNAP, "Update scroll snap"),
         ];
 
         for &(damage, damage_str) in &to_iter {
@@ -168,6 +185,12 @@ fn compute_damage(old: &ComputedValues, new: &ComputedValues) -> ServoRestyleDam
         damage.insert(ServoRestyleDamage::REPAINT);
     }
 
+    // Scroll snap data is orthogonal to the rest of the damage, so layout can
+    // update it without a full relayout.
+    if restyle_damage_update_scroll_snap(old, new) {
+        damage.insert(ServoRestyleDamage::UPDATE_SCROLL_SNAP);
+    }
+
     damage
 }
 
@@ -182,10 +205,18 @@ impl ComputedValues {
         self.get_effects().opacity != 1.0
             || self.get_effects().mix_blend_mode != MixBlendMode::Normal
             || self.get_svg().clip_path != ClipPath::None
+            || self.get_svg().has_mask_image()
             || self.get_box().isolation == Isolation::Isolate
     }
 }
 
+impl style_structs::SVG {
+    /// Whether any of the `mask-image` layers references an image.
+    pub fn has_mask_image(&self) -> bool {
+        self.mask_image.0.iter().any(|image| *image != Image::None)
+    }
+}
+
 impl style_structs::Box {
     /// Whether there is a non-default transform or perspective style set
     pub fn has_transform_or_perspective(&self) -> bool {
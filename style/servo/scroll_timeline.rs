@@ -0,0 +1,354 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Scroll progress and view progress timelines, which drive scroll-driven
+//! animations.
+//!
+//! https://drafts.csswg.org/scroll-animations-1/
+
+use crate::dom::{OpaqueNode, TElement, TNode};
+use crate::logical_geometry::PhysicalAxis;
+use crate::properties::ComputedValues;
+use crate::values::computed::{
+    AnimationTimeline, Length, LengthPercentageOrAuto, NonNegativeLengthPercentageOrAuto,
+    ScrollAxis, ViewTimelineInset,
+};
+use crate::values::generics::animation::ViewFunction;
+use crate::values::generics::length::GenericLengthPercentageOrAuto;
+use crate::values::specified::animation::Scroller;
+use crate::Atom;
+use euclid::default::{Rect, Size2D, Vector2D};
+use rustc_hash::FxHashMap;
+use servo_arc::Arc;
+
+/// The scroll geometry of a scroll container, in CSS pixels, which the
+/// embedder supplies to drive the timelines of that scroll container.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScrollState {
+    /// The current scroll offset.
+    pub scroll_offset: Vector2D<f64>,
+    /// The maximum scroll offset, that is, the size of the scrollable overflow
+    /// minus the size of the scrollport.
+    pub max_scroll_offset: Vector2D<f64>,
+    /// The size of the scrollport.
+    pub scrollport_size: Size2D<f64>,
+}
+
+impl ScrollState {
+    fn offset(&self, axis: PhysicalAxis) -> f64 {
+        match axis {
+            PhysicalAxis::Horizontal => self.scroll_offset.x,
+            PhysicalAxis::Vertical => self.scroll_offset.y,
+        }
+    }
+
+    fn max_offset(&self, axis: PhysicalAxis) -> f64 {
+        match axis {
+            PhysicalAxis::Horizontal => self.max_scroll_offset.x,
+            PhysicalAxis::Vertical => self.max_scroll_offset.y,
+        }
+    }
+
+    fn scrollport_size(&self, axis: PhysicalAxis) -> f64 {
+        match axis {
+            PhysicalAxis::Horizontal => self.scrollport_size.width,
+            PhysicalAxis::Vertical => self.scrollport_size.height,
+        }
+    }
+}
+
+/// The layout information that scroll-driven animations depend on, as
+/// supplied by the embedder.
+#[derive(Clone, Debug, Default)]
+pub struct ScrollTimelineGeometry {
+    /// The scroll state of each scroll container. The document viewport is
+    /// keyed by the root element.
+    pub scroll_states: FxHashMap<OpaqueNode, ScrollState>,
+    /// The border box of each view progress timeline subject, relative to the
+    /// scrolled content of its scroll container when it is not scrolled.
+    pub subject_rects: FxHashMap<OpaqueNode, Rect<f64>>,
+}
+
+/// A scroll progress or view progress timeline, resolved to the elements
+/// that drive it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TimelineSource {
+    /// A timeline whose progress is the scroll position of a scroll container.
+    ///
+    /// https://drafts.csswg.org/scroll-animations-1/#scroll-timelines
+    Scroll {
+        /// The scroll container.
+        container: OpaqueNode,
+        /// The scrolling axis.
+        axis: PhysicalAxis,
+    },
+    /// A timeline whose progress is the visibility of a subject element in
+    /// its nearest scroll container.
+    ///
+    /// https://drafts.csswg.org/scroll-animations-1/#view-timelines
+    View {
+        /// The scroll container.
+        container: OpaqueNode,
+        /// The subject element.
+        subject: OpaqueNode,
+        /// The scrolling axis.
+        axis: PhysicalAxis,
+        /// The insets of the view progress visibility range, where `auto`
+        /// insets have been replaced by the scroll padding of the container.
+        inset: ViewTimelineInset,
+    },
+}
+
+impl TimelineSource {
+    /// Computes the progress of this timeline, as a fraction of its range, or
+    /// `None` if the timeline is inactive. The progress of view timelines goes
+    /// outside of [0, 1] when the subject is outside the scrollport.
+    pub fn progress(&self, geometry: &ScrollTimelineGeometry) -> Option<f64> {
+        match *self {
+            TimelineSource::Scroll { container, axis } => {
+                let state = geometry.scroll_states.get(&container)?;
+                let max_offset = state.max_offset(axis);
+                if max_offset <= 0. {
+                    return None;
+                }
+                Some((state.offset(axis) / max_offset).clamp(0., 1.))
+            },
+            TimelineSource::View {
+                container,
+                subject,
+                axis,
+                ref inset,
+            } => {
+                let state = geometry.scroll_states.get(&container)?;
+                let rect = geometry.subject_rects.get(&subject)?;
+                let (subject_start, subject_size) = match axis {
+                    PhysicalAxis::Horizontal => (rect.origin.x, rect.size.width),
+                    PhysicalAxis::Vertical => (rect.origin.y, rect.size.height),
+                };
+                let scrollport_size = state.scrollport_size(axis);
+                let resolve_inset = |inset: &LengthPercentageOrAuto| match *inset {
+                    GenericLengthPercentageOrAuto::LengthPercentage(ref lp) => {
+                        lp.resolve(Length::new(scrollport_size as f32)).px() as f64
+                    },
+                    // The scroll padding of the container was `auto` too.
+                    GenericLengthPercentageOrAuto::Auto => 0.,
+                };
+                // The timeline starts when the subject starts entering the
+                // scrollport and ends when it has completely left it.
+                let start = subject_start - scrollport_size + resolve_inset(&inset.end);
+                let end = subject_start + subject_size - resolve_inset(&inset.start);
+                if end <= start {
+                    return None;
+                }
+                Some((state.offset(axis) - start) / (end - start))
+            },
+        }
+    }
+}
+
+fn is_scroll_container(style: &ComputedValues) -> bool {
+    let box_style = style.get_box();
+    box_style.clone_overflow_x().is_scrollable() || box_style.clone_overflow_y().is_scrollable()
+}
+
+fn physical_axis(axis: ScrollAxis, container_style: &ComputedValues) -> PhysicalAxis {
+    let vertical_writing_mode = container_style.writing_mode.is_vertical();
+    match axis {
+        ScrollAxis::Block if vertical_writing_mode => PhysicalAxis::Horizontal,
+        ScrollAxis::Inline if !vertical_writing_mode => PhysicalAxis::Horizontal,
+        ScrollAxis::Block | ScrollAxis::Inline => PhysicalAxis::Vertical,
+        ScrollAxis::X => PhysicalAxis::Horizontal,
+        ScrollAxis::Y => PhysicalAxis::Vertical,
+    }
+}
+
+struct TimelineElement {
+    node: OpaqueNode,
+    parent: Option<usize>,
+    /// The index after the last descendant of this element.
+    subtree_end: usize,
+    style: Arc<ComputedValues>,
+}
+
+/// A snapshot of the styled elements of a document in tree order, used to
+/// resolve animation timelines to the scroll containers that drive them.
+pub struct TimelineResolver {
+    elements: Vec<TimelineElement>,
+    indices: FxHashMap<OpaqueNode, usize>,
+}
+
+impl TimelineResolver {
+    /// Collects the styled elements in the flat tree rooted at `root`, which
+    /// is usually the root element of the document.
+    pub fn new<E: TElement>(root: E) -> Self {
+        let mut resolver = TimelineResolver {
+            elements: vec![],
+            indices: Default::default(),
+        };
+        resolver.collect(root, None);
+        resolver
+    }
+
+    fn collect<E: TElement>(&mut self, element: E, parent: Option<usize>) {
+        let style = match element.borrow_data() {
+            Some(data) => match data.styles.get_primary() {
+                Some(style) => style.clone(),
+                None => return,
+            },
+            None => return,
+        };
+        let index = self.elements.len();
+        let node = element.as_node().opaque();
+        self.elements.push(TimelineElement {
+            node,
+            parent,
+            subtree_end: index + 1,
+            style,
+        });
+        self.indices.insert(node, index);
+        for child in element.traversal_children() {
+            if let Some(child) = child.as_element() {
+                self.collect(child, Some(index));
+            }
+        }
+        self.elements[index].subtree_end = self.elements.len();
+    }
+
+    /// Returns the nearest ancestor scroll container of the element at the
+    /// given index, falling back to the root element for the viewport.
+    fn nearest_scroll_container(&self, index: usize) -> usize {
+        let mut current = self.elements[index].parent;
+        while let Some(i) = current {
+            if is_scroll_container(&self.elements[i].style) {
+                return i;
+            }
+            current = self.elements[i].parent;
+        }
+        0
+    }
+
+    fn scroll_timeline(&self, container: usize, axis: ScrollAxis) -> TimelineSource {
+        TimelineSource::Scroll {
+            container: self.elements[container].node,
+            axis: physical_axis(axis, &self.elements[container].style),
+        }
+    }
+
+    fn view_timeline(
+        &self,
+        subject: usize,
+        axis: ScrollAxis,
+        inset: ViewTimelineInset,
+    ) -> TimelineSource {
+        let container = self.nearest_scroll_container(subject);
+        let container_style = &self.elements[container].style;
+        let axis = physical_axis(axis, container_style);
+
+        // `auto` insets use the scroll padding of the scroll container.
+        //
+        // https://drafts.csswg.org/scroll-animations-1/#valdef-view-timeline-inset-auto
+        let padding = container_style.get_padding();
+        let (padding_start, padding_end) = match axis {
+            PhysicalAxis::Horizontal => {
+                (&padding.scroll_padding_left, &padding.scroll_padding_right)
+            },
+            PhysicalAxis::Vertical => (&padding.scroll_padding_top, &padding.scroll_padding_bottom),
+        };
+        let or_scroll_padding =
+            |inset: LengthPercentageOrAuto, padding: &NonNegativeLengthPercentageOrAuto| {
+                if inset.is_auto() {
+                    padding.map(|padding| padding.0)
+                } else {
+                    inset
+                }
+            };
+        TimelineSource::View {
+            container: self.elements[container].node,
+            subject: self.elements[subject].node,
+            axis,
+            inset: ViewTimelineInset {
+                start: or_scroll_padding(inset.start, padding_start),
+                end: or_scroll_padding(inset.end, padding_end),
+            },
+        }
+    }
+
+    /// Returns the timeline with the given name declared by the element at the
+    /// given index, if any.
+    fn declared_timeline(&self, index: usize, name: &Atom) -> Option<TimelineSource> {
+        let ui = self.elements[index].style.get_ui();
+        if let Some(i) = ui
+            .scroll_timeline_name_iter()
+            .position(|timeline| timeline.as_atom() == name)
+        {
+            return Some(self.scroll_timeline(index, ui.scroll_timeline_axis_mod(i)));
+        }
+        let i = ui
+            .view_timeline_name_iter()
+            .position(|timeline| timeline.as_atom() == name)?;
+        Some(self.view_timeline(
+            index,
+            ui.view_timeline_axis_mod(i),
+            ui.view_timeline_inset_mod(i),
+        ))
+    }
+
+    /// Looks up a named timeline from the element at the given index.
+    ///
+    /// Named timelines are visible to the element that declares them and its
+    /// descendants, and to the subtree of an ancestor that names them in its
+    /// `timeline-scope` if exactly one of its descendants declares them.
+    ///
+    /// https://drafts.csswg.org/scroll-animations-1/#timeline-scoping
+    fn named_timeline(&self, index: usize, name: &Atom) -> Option<TimelineSource> {
+        let mut current = Some(index);
+        while let Some(i) = current {
+            if let Some(timeline) = self.declared_timeline(i, name) {
+                return Some(timeline);
+            }
+            let element = &self.elements[i];
+            if element.style.get_ui().clone_timeline_scope().contains(name) {
+                let mut declared = (i + 1..element.subtree_end)
+                    .filter_map(|descendant| self.declared_timeline(descendant, name));
+                let timeline = declared.next();
+                if declared.next().is_some() {
+                    return None;
+                }
+                return timeline;
+            }
+            current = element.parent;
+        }
+        None
+    }
+
+    /// Resolves the timeline of an animation on the given element, returning
+    /// `None` for the document timeline and for timelines that don't exist.
+    pub fn resolve(
+        &self,
+        node: OpaqueNode,
+        timeline: &AnimationTimeline,
+    ) -> Option<TimelineSource> {
+        let index = *self.indices.get(&node)?;
+        match *timeline {
+            AnimationTimeline::Auto => None,
+            AnimationTimeline::Timeline(ref name) => {
+                if name.is_none() {
+                    return None;
+                }
+                self.named_timeline(index, name.as_atom())
+            },
+            AnimationTimeline::Scroll(ref function) => {
+                let container = match function.scroller {
+                    Scroller::Nearest => self.nearest_scroll_container(index),
+                    Scroller::Root => 0,
+                    Scroller::SelfElement => index,
+                };
+                Some(self.scroll_timeline(container, function.axis))
+            },
+            AnimationTimeline::View(ViewFunction { axis, ref inset }) => {
+                Some(self.view_timeline(index, axis, inset.clone()))
+            },
+        }
+    }
+}
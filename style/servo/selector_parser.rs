@@ -15,7 +15,9 @@ use crate::invalidation::element::element_wrapper::ElementSnapshot;
 use crate::properties::longhands::display::computed_value::T as Display;
 use crate::properties::{ComputedValues, PropertyFlags};
 use crate::selector_parser::AttrValue as SelectorAttrValue;
-use crate::selector_parser::{PseudoElementCascadeType, SelectorParser};
+use crate::selector_parser::{
+    Direction, PseudoElementCascadeType, PtNameAndClassSelector, SelectorParser, Target,
+};
 use crate::values::{AtomIdent, AtomString};
 use crate::{Atom, CaseSensitivityExt, LocalName, Namespace, Prefix};
 use cssparser::{
@@ -28,31 +30,28 @@ use selectors::attr::{AttrSelectorOperation, CaseSensitivity, NamespaceConstrain
 use selectors::parser::SelectorParseErrorKind;
 use selectors::visitor::SelectorVisitor;
 use std::fmt;
-use std::mem;
 use std::ops::{Deref, DerefMut};
-use style_traits::{ParseError, StyleParseErrorKind};
+use style_traits::{CssWriter, ParseError, StyleParseErrorKind};
 
 /// A pseudo-element, both public and private.
 ///
+/// Like the Gecko one, this isn't `Copy`, since the named view transition
+/// pseudo-elements carry a list of atoms for their name and classes.
+///
 /// NB: If you add to this list, be sure to update `each_simple_pseudo_element` too.
-#[derive(
-    Clone, Copy, Debug, Deserialize, Eq, Hash, MallocSizeOf, PartialEq, Serialize, ToShmem,
-)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, MallocSizeOf, PartialEq, Serialize, ToShmem)]
 #[allow(missing_docs)]
-#[repr(usize)]
 pub enum PseudoElement {
     // Eager pseudos. Keep these first so that eager_index() works.
-    After = 0,
+    After,
     Before,
     Selection,
-    // If/when :first-letter is added, update is_first_letter accordingly.
-
-    // If/when :first-line is added, update is_first_line accordingly.
-
-    // If/when ::first-letter, ::first-line, or ::placeholder are added, adjust
-    // our property_restriction implementation to do property filtering for
-    // them.  Also, make sure the UA sheet has the !important rules some of the
-    // APPLIES_TO_PLACEHOLDER properties expect!
+    FirstLetter,
+    FirstLine,
+    // If/when ::placeholder property filtering is implemented, adjust our
+    // property_restriction implementation to handle it.  Also, make sure the
+    // UA sheet has the !important rules some of the APPLIES_TO_PLACEHOLDER
+    // properties expect!
 
     // Non-eager pseudos.
     Backdrop,
@@ -60,6 +59,16 @@ pub enum PseudoElement {
     DetailsContent,
     Marker,
 
+    // The view transition pseudo-element tree, which hangs from the root
+    // element. The named pseudos carry the `view-transition-name` they
+    // represent, followed by the `view-transition-class` of the captured
+    // element.
+    ViewTransition,
+    ViewTransitionGroup(PtNameAndClassSelector),
+    ViewTransitionImagePair(PtNameAndClassSelector),
+    ViewTransitionOld(PtNameAndClassSelector),
+    ViewTransitionNew(PtNameAndClassSelector),
+
     // Implemented pseudos. These pseudo elements are representing the
     // elements within an UA shadow DOM, and matching the elements with
     // their appropriate styles.
@@ -80,7 +89,7 @@ pub enum PseudoElement {
 }
 
 /// The count of all pseudo-elements.
-pub const PSEUDO_COUNT: usize = PseudoElement::ServoTableWrapper as usize + 1;
+pub const PSEUDO_COUNT: usize = PseudoElement::ServoTableWrapper.index() + 1;
 
 impl ToCss for PseudoElement {
     fn to_css<W>(&self, dest: &mut W) -> fmt::Result
@@ -88,14 +97,42 @@ impl ToCss for PseudoElement {
         W: fmt::Write,
     {
         use self::PseudoElement::*;
-        dest.write_str(match *self {
+        let (name, name_and_class) = match *self {
+            ViewTransitionGroup(ref name_and_class) => ("::view-transition-group(", name_and_class),
+            ViewTransitionImagePair(ref name_and_class) => {
+                ("::view-transition-image-pair(", name_and_class)
+            },
+            ViewTransitionOld(ref name_and_class) => ("::view-transition-old(", name_and_class),
+            ViewTransitionNew(ref name_and_class) => ("::view-transition-new(", name_and_class),
+            _ => {
+                return dest.write_str(self.simple_name());
+            },
+        };
+        dest.write_str(name)?;
+        name_and_class.to_css(dest)?;
+        dest.write_char(')')
+    }
+}
+
+impl PseudoElement {
+    /// The serialization of this pseudo-element, if it has no arguments.
+    fn simple_name(&self) -> &'static str {
+        use self::PseudoElement::*;
+        match *self {
             After => "::after",
             Before => "::before",
             Selection => "::selection",
+            FirstLetter => "::first-letter",
+            FirstLine => "::first-line",
             Backdrop => "::backdrop",
             DetailsSummary => "::-servo-details-summary",
             DetailsContent => "::details-content",
             Marker => "::marker",
+            ViewTransition => "::view-transition",
+            ViewTransitionGroup(..) => "::view-transition-group",
+            ViewTransitionImagePair(..) => "::view-transition-image-pair",
+            ViewTransitionOld(..) => "::view-transition-old",
+            ViewTransitionNew(..) => "::view-transition-new",
             ColorSwatch => "::color-swatch",
             Placeholder => "::placeholder",
             ServoTextControlInnerContainer => "::-servo-text-control-inner-container",
@@ -106,29 +143,88 @@ impl ToCss for PseudoElement {
             ServoAnonymousTableRow => "::-servo-anonymous-table-row",
             ServoTableGrid => "::-servo-table-grid",
             ServoTableWrapper => "::-servo-table-wrapper",
-        })
+        }
     }
 }
 
 impl ::selectors::parser::PseudoElement for PseudoElement {
     type Impl = SelectorImpl;
+
+    #[inline]
+    fn accepts_state_pseudo_classes(&self) -> bool {
+        // Tree-structural pseudo-classes like `:only-child` are allowed after
+        // the pseudo-elements of a pseudo-element tree.
+        self.is_in_pseudo_element_tree()
+    }
+
+    #[inline]
+    fn specificity_count(&self) -> u32 {
+        match *self {
+            PseudoElement::ViewTransitionGroup(ref name_and_class)
+            | PseudoElement::ViewTransitionImagePair(ref name_and_class)
+            | PseudoElement::ViewTransitionOld(ref name_and_class)
+            | PseudoElement::ViewTransitionNew(ref name_and_class) => {
+                // A named view transition pseudo-element selector has the
+                // specificity of a type selector, unless it is `*` without
+                // any class.
+                // https://drafts.csswg.org/css-view-transitions-2/#pseudo-element-class-additions
+                (name_and_class.name() != &atom!("*") || !name_and_class.classes().is_empty())
+                    as u32
+            },
+            _ => 1,
+        }
+    }
+
+    #[inline]
+    fn is_in_pseudo_element_tree(&self) -> bool {
+        self.is_named_view_transition()
+    }
 }
 
 /// The number of eager pseudo-elements. Keep this in sync with cascade_type.
-pub const EAGER_PSEUDO_COUNT: usize = 3;
+pub const EAGER_PSEUDO_COUNT: usize = 5;
 
 impl PseudoElement {
     /// Gets the canonical index of this eagerly-cascaded pseudo-element.
     #[inline]
     pub fn eager_index(&self) -> usize {
         debug_assert!(self.is_eager());
-        self.clone() as usize
+        self.index()
     }
 
     /// An index for this pseudo-element to be indexed in an enumerated array.
+    ///
+    /// All the named view transition pseudo-elements of a given kind share an
+    /// index.
     #[inline]
-    pub fn index(&self) -> usize {
-        self.clone() as usize
+    pub const fn index(&self) -> usize {
+        use self::PseudoElement::*;
+        match *self {
+            After => 0,
+            Before => 1,
+            Selection => 2,
+            FirstLetter => 3,
+            FirstLine => 4,
+            Backdrop => 5,
+            DetailsSummary => 6,
+            DetailsContent => 7,
+            Marker => 8,
+            ViewTransition => 9,
+            ViewTransitionGroup(..) => 10,
+            ViewTransitionImagePair(..) => 11,
+            ViewTransitionOld(..) => 12,
+            ViewTransitionNew(..) => 13,
+            ColorSwatch => 14,
+            Placeholder => 15,
+            ServoTextControlInnerContainer => 16,
+            ServoTextControlInnerEditor => 17,
+            ServoAnonymousBox => 18,
+            ServoAnonymousTable => 19,
+            ServoAnonymousTableCell => 20,
+            ServoAnonymousTableRow => 21,
+            ServoTableGrid => 22,
+            ServoTableWrapper => 23,
+        }
     }
 
     /// An array of `None`, one per pseudo-element.
@@ -139,9 +235,15 @@ impl PseudoElement {
     /// Creates a pseudo-element from an eager index.
     #[inline]
     pub fn from_eager_index(i: usize) -> Self {
-        assert!(i < EAGER_PSEUDO_COUNT);
-        let result: PseudoElement = unsafe { mem::transmute(i) };
-        debug_assert!(result.is_eager());
+        let result = match i {
+            0 => PseudoElement::After,
+            1 => PseudoElement::Before,
+            2 => PseudoElement::Selection,
+            3 => PseudoElement::FirstLetter,
+            4 => PseudoElement::FirstLine,
+            _ => panic!("Not an eager pseudo-element index: {}", i),
+        };
+        debug_assert_eq!(result.eager_index(), i);
         result
     }
 
@@ -184,13 +286,13 @@ impl PseudoElement {
     /// Whether the current pseudo element is :first-letter
     #[inline]
     pub fn is_first_letter(&self) -> bool {
-        false
+        *self == PseudoElement::FirstLetter
     }
 
     /// Whether the current pseudo element is :first-line
     #[inline]
     pub fn is_first_line(&self) -> bool {
-        false
+        *self == PseudoElement::FirstLine
     }
 
     /// Whether this pseudo-element is representing the color swatch
@@ -200,6 +302,60 @@ impl PseudoElement {
         *self == PseudoElement::ColorSwatch
     }
 
+    /// Whether this pseudo-element is a named view transition pseudo-element,
+    /// that is, one of the descendants of `::view-transition`.
+    #[inline]
+    pub fn is_named_view_transition(&self) -> bool {
+        matches!(
+            *self,
+            PseudoElement::ViewTransitionGroup(..)
+                | PseudoElement::ViewTransitionImagePair(..)
+                | PseudoElement::ViewTransitionOld(..)
+                | PseudoElement::ViewTransitionNew(..)
+        )
+    }
+
+    /// Whether this named view transition pseudo-element, which carries the
+    /// name and classes of a captured element, matches the given
+    /// pseudo-element selector.
+    ///
+    /// https://drafts.csswg.org/css-view-transitions-2/#pseudo-element-class-additions
+    pub fn matches_named_view_transition_pseudo_element(&self, selector: &Self) -> bool {
+        let (name_and_class, selector) = match (self, selector) {
+            (
+                &PseudoElement::ViewTransitionGroup(ref name_and_class),
+                &PseudoElement::ViewTransitionGroup(ref selector),
+            )
+            | (
+                &PseudoElement::ViewTransitionImagePair(ref name_and_class),
+                &PseudoElement::ViewTransitionImagePair(ref selector),
+            )
+            | (
+                &PseudoElement::ViewTransitionOld(ref name_and_class),
+                &PseudoElement::ViewTransitionOld(ref selector),
+            )
+            | (
+                &PseudoElement::ViewTransitionNew(ref name_and_class),
+                &PseudoElement::ViewTransitionNew(ref selector),
+            ) => (name_and_class, selector),
+            _ => return false,
+        };
+        if selector.name() != &atom!("*") && selector.name() != name_and_class.name() {
+            return false;
+        }
+        selector
+            .classes()
+            .iter()
+            .all(|class| name_and_class.classes().contains(class))
+    }
+
+    /// Whether this pseudo-element matches the given pseudo-element selector.
+    /// This is equality, except for named view transition pseudo-elements,
+    /// whose selectors can use the universal name and classes.
+    pub fn matches(&self, selector: &Self) -> bool {
+        *self == *selector || self.matches_named_view_transition_pseudo_element(selector)
+    }
+
     /// Whether this pseudo-element is eagerly-cascaded.
     #[inline]
     pub fn is_eager(&self) -> bool {
@@ -240,15 +396,22 @@ impl PseudoElement {
     #[inline]
     pub fn cascade_type(&self) -> PseudoElementCascadeType {
         match *self {
-            PseudoElement::After | PseudoElement::Before | PseudoElement::Selection => {
-                PseudoElementCascadeType::Eager
-            },
+            PseudoElement::After
+            | PseudoElement::Before
+            | PseudoElement::Selection
+            | PseudoElement::FirstLetter
+            | PseudoElement::FirstLine => PseudoElementCascadeType::Eager,
             PseudoElement::Backdrop
             | PseudoElement::ColorSwatch
             | PseudoElement::DetailsSummary
             | PseudoElement::Marker
             | PseudoElement::Placeholder
             | PseudoElement::DetailsContent
+            | PseudoElement::ViewTransition
+            | PseudoElement::ViewTransitionGroup(..)
+            | PseudoElement::ViewTransitionImagePair(..)
+            | PseudoElement::ViewTransitionOld(..)
+            | PseudoElement::ViewTransitionNew(..)
             | PseudoElement::ServoTextControlInnerContainer
             | PseudoElement::ServoTextControlInnerEditor => PseudoElementCascadeType::Lazy,
             PseudoElement::ServoAnonymousBox
@@ -274,7 +437,11 @@ impl PseudoElement {
     /// Property flag that properties must have to apply to this pseudo-element.
     #[inline]
     pub fn property_restriction(&self) -> Option<PropertyFlags> {
-        None
+        Some(match *self {
+            PseudoElement::FirstLetter => PropertyFlags::APPLIES_TO_FIRST_LETTER,
+            PseudoElement::FirstLine => PropertyFlags::APPLIES_TO_FIRST_LINE,
+            _ => return None,
+        })
     }
 
     /// Whether this pseudo-element should actually exist if it has
@@ -312,6 +479,8 @@ pub enum NonTSPseudoClass {
     CustomState(CustomState),
     Default,
     Defined,
+    /// The `:dir()` pseudo-class.
+    Dir(Direction),
     Disabled,
     Enabled,
     Focus,
@@ -392,6 +561,11 @@ impl ToCss for NonTSPseudoClass {
             },
             Self::Default => ":default",
             Self::Defined => ":defined",
+            Self::Dir(ref dir) => {
+                dest.write_str(":dir(")?;
+                style_traits::ToCss::to_css(dir, &mut CssWriter::new(dest))?;
+                return dest.write_char(')');
+            },
             Self::Disabled => ":disabled",
             Self::Enabled => ":enabled",
             Self::Focus => ":focus",
@@ -437,6 +611,7 @@ impl NonTSPseudoClass {
             Self::Checked => ElementState::CHECKED,
             Self::Default => ElementState::DEFAULT,
             Self::Defined => ElementState::DEFINED,
+            Self::Dir(ref dir) => dir.element_state(),
             Self::Disabled => ElementState::DISABLED,
             Self::Enabled => ElementState::ENABLED,
             Self::Focus => ElementState::FOCUS,
@@ -478,7 +653,9 @@ impl NonTSPseudoClass {
 
     /// Returns true if the given pseudoclass should trigger style sharing cache revalidation.
     pub fn needs_cache_revalidation(&self) -> bool {
-        self.state_flag().is_empty()
+        // :dir() depends on state only, but may have an empty state_flag for
+        // invalid arguments.
+        self.state_flag().is_empty() && !matches!(*self, Self::Dir(_))
     }
 }
 
@@ -542,6 +719,11 @@ impl<'a, 'i> ::selectors::Parser<'i> for SelectorParser<'a> {
         true
     }
 
+    #[inline]
+    fn parse_column_selectors(&self) -> bool {
+        true
+    }
+
     #[inline]
     fn parse_part(&self) -> bool {
         true
@@ -615,6 +797,7 @@ impl<'a, 'i> ::selectors::Parser<'i> for SelectorParser<'a> {
             "lang" if !after_part => {
                 NonTSPseudoClass::Lang(parser.expect_ident_or_string()?.as_ref().into())
             },
+            "dir" => NonTSPseudoClass::Dir(Direction::parse(parser)?),
             "state" => {
                 let result = AtomIdent::from(parser.expect_ident()?.as_ref());
                 NonTSPseudoClass::CustomState(CustomState(result))
@@ -636,6 +819,8 @@ impl<'a, 'i> ::selectors::Parser<'i> for SelectorParser<'a> {
             "after" => After,
             "backdrop" => Backdrop,
             "selection" => Selection,
+            "first-letter" => FirstLetter,
+            "first-line" => FirstLine,
             "marker" => Marker,
             "-servo-details-summary" => {
                 if !self.in_user_agent_stylesheet() {
@@ -644,6 +829,12 @@ impl<'a, 'i> ::selectors::Parser<'i> for SelectorParser<'a> {
                 DetailsSummary
             },
             "details-content" => DetailsContent,
+            "view-transition" => {
+                if !self.view_transitions_enabled() {
+                    return Err(location.new_custom_error(SelectorParseErrorKind::UnexpectedIdent(name.clone())))
+                }
+                ViewTransition
+            },
             "color-swatch" => ColorSwatch,
             "placeholder" => {
                 if !self.in_user_agent_stylesheet() {
@@ -706,6 +897,29 @@ impl<'a, 'i> ::selectors::Parser<'i> for SelectorParser<'a> {
         Ok(pseudo_element)
     }
 
+    fn parse_functional_pseudo_element<'t>(
+        &self,
+        name: CowRcStr<'i>,
+        parser: &mut CssParser<'i, 't>,
+    ) -> Result<PseudoElement, ParseError<'i>> {
+        use self::PseudoElement::*;
+        if !self.view_transitions_enabled() {
+            return Err(parser.new_custom_error(SelectorParseErrorKind::UnexpectedIdent(name)));
+        }
+        let name_and_class = |parser: &mut CssParser<'i, 't>| {
+            PtNameAndClassSelector::parse(parser, Target::Selector)
+        };
+        let pseudo_element = match_ignore_ascii_case! { &name,
+            "view-transition-group" => ViewTransitionGroup(name_and_class(parser)?),
+            "view-transition-image-pair" => ViewTransitionImagePair(name_and_class(parser)?),
+            "view-transition-old" => ViewTransitionOld(name_and_class(parser)?),
+            "view-transition-new" => ViewTransitionNew(name_and_class(parser)?),
+            _ => return Err(parser.new_custom_error(SelectorParseErrorKind::UnexpectedIdent(name.clone()))),
+        };
+
+        Ok(pseudo_element)
+    }
+
     fn default_namespace(&self) -> Option<Namespace> {
         self.namespaces.default.as_ref().map(|ns| ns.clone())
     }
@@ -723,6 +937,14 @@ impl<'a, 'i> ::selectors::Parser<'i> for SelectorParser<'a> {
     }
 }
 
+impl<'a> SelectorParser<'a> {
+    /// Whether the view transition pseudo-elements can be parsed.
+    fn view_transitions_enabled(&self) -> bool {
+        self.in_user_agent_stylesheet()
+            || style_config::get_bool("dom.view_transitions.enabled", false)
+    }
+}
+
 impl SelectorImpl {
     /// A helper to traverse each eagerly cascaded pseudo-element, executing
     /// `fun` on it.
@@ -982,3 +1204,37 @@ pub fn extended_filtering(tag: &str, range: &str) -> bool {
         true
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(name_and_classes: &[&str]) -> PseudoElement {
+        let mut atoms = name_and_classes.iter().map(|s| Atom::from(*s));
+        PseudoElement::ViewTransitionGroup(PtNameAndClassSelector::from_name_and_classes(
+            atoms.next().unwrap(),
+            atoms,
+        ))
+    }
+
+    #[test]
+    fn named_view_transition_matching() {
+        let pseudo = group(&["hero", "card", "wide"]);
+        assert!(pseudo.matches(&group(&["hero"])));
+        assert!(pseudo.matches(&group(&["*"])));
+        assert!(pseudo.matches(&group(&["*", "wide", "card"])));
+        assert!(!pseudo.matches(&group(&["other"])));
+        assert!(!pseudo.matches(&group(&["hero", "tall"])));
+        assert!(!pseudo.matches(&PseudoElement::ViewTransitionNew(
+            PtNameAndClassSelector::from_name(Atom::from("hero"))
+        )));
+        assert_eq!(pseudo.index(), group(&["other"]).index());
+    }
+
+    #[test]
+    fn eager_indices() {
+        for i in 0..EAGER_PSEUDO_COUNT {
+            assert_eq!(PseudoElement::from_eager_index(i).eager_index(), i);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Support for same-document view transitions.
+//!
+//! The embedder captures the elements with a `view-transition-name`, builds
+//! the `::view-transition` pseudo-element tree out of them, and styles it
+//! with the static rules of its user-agent style sheet and the dynamic rules
+//! for the captured geometry, which it returns from
+//! `TElement::synthesize_view_transition_dynamic_rules`.
+//!
+//! https://drafts.csswg.org/css-view-transitions-1/
+
+use crate::properties::longhands::{animation_name, isolation};
+use crate::properties::{
+    ComputedValues, Importance, PropertyDeclaration, PropertyDeclarationBlock,
+};
+use crate::selector_parser::{PseudoElement, PtNameAndClassSelector};
+use crate::shared_lock::SharedRwLock;
+use crate::stylesheets::keyframes_rule::{KeyframePercentage, KeyframesAnimation};
+use crate::values::computed::transform::{Matrix3D, TransformOperation};
+use crate::values::computed::{self, Length, ToComputedValue};
+use crate::values::generics::transform::Transform;
+use crate::values::generics::NonNegative;
+use crate::values::specified::{self, AnimationName};
+use crate::values::KeyframesName;
+use crate::Atom;
+use euclid::default::{Size2D, Transform3D};
+use servo_arc::Arc;
+
+/// The static user-agent rules of view transitions, which embedders need to
+/// include in their user-agent style sheet.
+///
+/// https://drafts.csswg.org/css-view-transitions-1/#ua-styles
+pub const USER_AGENT_RULES: &str = "
+:root::view-transition {
+  position: fixed;
+  inset: 0;
+}
+:root::view-transition-group(*) {
+  position: absolute;
+  top: 0;
+  left: 0;
+  animation-duration: 0.25s;
+  animation-fill-mode: both;
+}
+:root::view-transition-image-pair(*) {
+  position: absolute;
+  inset: 0;
+  animation-duration: inherit;
+  animation-fill-mode: inherit;
+  animation-delay: inherit;
+  animation-timing-function: inherit;
+  animation-iteration-count: inherit;
+  animation-direction: inherit;
+  animation-play-state: inherit;
+}
+:root::view-transition-old(*),
+:root::view-transition-new(*) {
+  position: absolute;
+  inset-block-start: 0;
+  inline-size: 100%;
+  block-size: auto;
+  animation-duration: inherit;
+  animation-fill-mode: inherit;
+  animation-delay: inherit;
+  animation-timing-function: inherit;
+  animation-iteration-count: inherit;
+  animation-direction: inherit;
+  animation-play-state: inherit;
+}
+@keyframes -ua-view-transition-fade-out {
+  to { opacity: 0; }
+}
+@keyframes -ua-view-transition-fade-in {
+  from { opacity: 0; }
+}
+@keyframes -ua-mix-blend-mode-plus-lighter {
+  from { mix-blend-mode: plus-lighter; }
+  to { mix-blend-mode: plus-lighter; }
+}
+";
+
+/// The state of a captured element, either in the old or in the new view.
+#[derive(Clone, Debug)]
+pub struct CapturedState {
+    /// The size of the border box of the element, in CSS pixels.
+    pub size: Size2D<f32>,
+    /// The transform from the border box of the element to the snapshot
+    /// containing block, including the transforms of the element and its
+    /// ancestors.
+    pub transform: Transform3D<f32>,
+    /// The computed style of the element.
+    pub style: Arc<ComputedValues>,
+}
+
+impl CapturedState {
+    /// Pushes the declarations that size and position a
+    /// `::view-transition-group()` like this state.
+    ///
+    /// A geometry with non-finite values can't be represented in CSS, so it
+    /// is ignored, and the group keeps the size and position of its
+    /// user-agent rules.
+    fn push_geometry(&self, block: &mut PropertyDeclarationBlock) {
+        let t = &self.transform;
+        let finite = self.size.width.is_finite()
+            && self.size.height.is_finite()
+            && t.to_array().iter().all(|value| value.is_finite());
+        if !finite {
+            return;
+        }
+        let size = |length: f32| {
+            specified::Size::from_computed_value(&computed::Size::LengthPercentage(NonNegative(
+                computed::LengthPercentage::new_length(Length::new(length)),
+            )))
+        };
+        let transform = Transform(vec![TransformOperation::Matrix3D(Matrix3D::from(*t))].into());
+        block.push(
+            PropertyDeclaration::Width(size(self.size.width)),
+            Importance::Normal,
+        );
+        block.push(
+            PropertyDeclaration::Height(size(self.size.height)),
+            Importance::Normal,
+        );
+        block.push(
+            PropertyDeclaration::Transform(specified::Transform::from_computed_value(&transform)),
+            Importance::Normal,
+        );
+    }
+}
+
+/// An element captured by a view transition.
+///
+/// https://drafts.csswg.org/css-view-transitions-1/#captured-element
+#[derive(Clone, Debug)]
+pub struct CapturedElement {
+    /// The `view-transition-name` of the element, followed by its
+    /// `view-transition-class`.
+    pub name_and_class: PtNameAndClassSelector,
+    /// The state of the element in the old view, if it was captured there.
+    pub old: Option<CapturedState>,
+    /// The state of the element in the new view, if it was captured there.
+    pub new: Option<CapturedState>,
+}
+
+fn animation_names(names: &[Atom]) -> PropertyDeclaration {
+    PropertyDeclaration::AnimationName(animation_name::SpecifiedValue(
+        names
+            .iter()
+            .map(|name| AnimationName(KeyframesName::from_atom(name.clone())))
+            .collect::<Vec<_>>()
+            .into(),
+    ))
+}
+
+impl CapturedElement {
+    /// The `::view-transition-group()` pseudo-element of this element.
+    pub fn group(&self) -> PseudoElement {
+        PseudoElement::ViewTransitionGroup(self.name_and_class.clone())
+    }
+
+    /// The `::view-transition-image-pair()` pseudo-element of this element.
+    pub fn image_pair(&self) -> PseudoElement {
+        PseudoElement::ViewTransitionImagePair(self.name_and_class.clone())
+    }
+
+    /// The `::view-transition-old()` pseudo-element of this element, if it
+    /// has an old state.
+    pub fn old_image(&self) -> Option<PseudoElement> {
+        self.old
+            .as_ref()
+            .map(|_| PseudoElement::ViewTransitionOld(self.name_and_class.clone()))
+    }
+
+    /// The `::view-transition-new()` pseudo-element of this element, if it
+    /// has a new state.
+    pub fn new_image(&self) -> Option<PseudoElement> {
+        self.new
+            .as_ref()
+            .map(|_| PseudoElement::ViewTransitionNew(self.name_and_class.clone()))
+    }
+
+    /// The name of the keyframes that animate the `::view-transition-group()`
+    /// of this element from its old to its new geometry.
+    pub fn group_keyframes_name(&self) -> Atom {
+        Atom::from(format!(
+            "-ua-view-transition-group-anim-{}",
+            self.name_and_class.name()
+        ))
+    }
+
+    /// The keyframes named by `group_keyframes_name`, if this element was
+    /// captured in both views.
+    ///
+    /// These are specific to this transition, so the embedder needs to look
+    /// them up itself when it runs the animations of the group.
+    pub fn group_keyframes(&self, lock: &SharedRwLock) -> Option<KeyframesAnimation> {
+        let old = match (self.old.as_ref(), self.new.is_some()) {
+            (Some(old), true) => old,
+            _ => return None,
+        };
+        let mut from = PropertyDeclarationBlock::new();
+        old.push_geometry(&mut from);
+        let guard = lock.read();
+        Some(KeyframesAnimation::from_declaration_blocks(
+            &[(KeyframePercentage::new(0.), Arc::new(lock.wrap(from)))],
+            None,
+            &guard,
+        ))
+    }
+
+    /// Returns the dynamic rule of this element for the given pseudo-element,
+    /// or `None` if the pseudo-element doesn't belong to this element.
+    ///
+    /// These declarations apply at the user-agent level, after the static
+    /// user-agent rules, and need to be recomputed when the geometry of the
+    /// new element changes.
+    ///
+    /// https://drafts.csswg.org/css-view-transitions-1/#setup-transition-pseudo-elements
+    /// https://drafts.csswg.org/css-view-transitions-1/#update-pseudo-element-styles
+    pub fn dynamic_rule(&self, pseudo: &PseudoElement) -> Option<PropertyDeclarationBlock> {
+        let state = self.new.as_ref().or(self.old.as_ref())?;
+        let animates_group = self.old.is_some() && self.new.is_some();
+        let mut block = PropertyDeclarationBlock::new();
+        if *pseudo == self.group() {
+            state.push_geometry(&mut block);
+            let style = &state.style;
+            block.push(
+                PropertyDeclaration::WritingMode(ToComputedValue::from_computed_value(
+                    &style.clone_writing_mode(),
+                )),
+                Importance::Normal,
+            );
+            block.push(
+                PropertyDeclaration::Direction(ToComputedValue::from_computed_value(
+                    &style.clone_direction(),
+                )),
+                Importance::Normal,
+            );
+            block.push(
+                PropertyDeclaration::MixBlendMode(ToComputedValue::from_computed_value(
+                    &style.clone_mix_blend_mode(),
+                )),
+                Importance::Normal,
+            );
+            if animates_group {
+                block.push(
+                    animation_names(&[self.group_keyframes_name()]),
+                    Importance::Normal,
+                );
+            }
+        } else if *pseudo == self.image_pair() {
+            if animates_group {
+                block.push(
+                    PropertyDeclaration::Isolation(isolation::SpecifiedValue::Isolate),
+                    Importance::Normal,
+                );
+            }
+        } else if self.old_image().as_ref() == Some(pseudo) {
+            let mut names = vec![atom!("-ua-view-transition-fade-out")];
+            if animates_group {
+                names.push(atom!("-ua-mix-blend-mode-plus-lighter"));
+            }
+            block.push(animation_names(&names), Importance::Normal);
+        } else if self.new_image().as_ref() == Some(pseudo) {
+            let mut names = vec![atom!("-ua-view-transition-fade-in")];
+            if animates_group {
+                names.push(atom!("-ua-mix-blend-mode-plus-lighter"));
+            }
+            block.push(animation_names(&names), Importance::Normal);
+        } else {
+            return None;
+        }
+        Some(block)
+    }
+}
@@ -9,7 +9,8 @@
 use crate::computed_value_flags::ComputedValueFlags;
 use crate::derives::*;
 use crate::dom::TElement;
-use crate::logical_geometry::{LogicalSize, WritingMode};
+use crate::invalidation::element::restyle_hints::RestyleHint;
+use crate::logical_geometry::{LogicalSize, PhysicalSide, WritingMode};
 use crate::parser::ParserContext;
 use crate::properties::ComputedValues;
 use crate::queries::feature::{AllowsRanges, Evaluator, FeatureFlags, QueryFeatureDescription};
@@ -109,8 +110,8 @@ pub struct ContainerLookupResult<E> {
     pub style: Arc<ComputedValues>,
 }
 
-fn container_type_axes(ty_: ContainerType, wm: WritingMode) -> FeatureFlags {
-    if ty_.intersects(ContainerType::SIZE) {
+fn container_type_flags(ty_: ContainerType, wm: WritingMode) -> FeatureFlags {
+    let mut flags = if ty_.intersects(ContainerType::SIZE) {
         FeatureFlags::all_container_axes()
     } else if ty_.intersects(ContainerType::INLINE_SIZE) {
         let physical_axis = if wm.is_vertical() {
@@ -121,7 +122,11 @@ fn container_type_axes(ty_: ContainerType, wm: WritingMode) -> FeatureFlags {
         FeatureFlags::CONTAINER_REQUIRES_INLINE_AXIS | physical_axis
     } else {
         FeatureFlags::empty()
+    };
+    if ty_.intersects(ContainerType::SCROLL_STATE) {
+        flags.insert(FeatureFlags::CONTAINER_REQUIRES_SCROLL_STATE);
     }
+    flags
 }
 
 enum TraversalResult<T> {
@@ -177,6 +182,12 @@ impl ContainerCondition {
         })
     }
 
+    /// Returns the scroll state flags of the scroll-state features the
+    /// condition queries.
+    pub fn scroll_state_dependencies(&self) -> FeatureFlags {
+        self.flags.scroll_states()
+    }
+
     fn valid_container_info<E>(
         &self,
         potential_container: E,
@@ -201,8 +212,8 @@ impl ContainerCondition {
 
         // Filter by container-type.
         let container_type = box_style.clone_container_type();
-        let available_axes = container_type_axes(container_type, wm);
-        if !available_axes.contains(self.flags.container_axes()) {
+        let available_flags = container_type_flags(container_type, wm);
+        if !available_flags.contains(self.flags.container_requirements()) {
             return TraversalResult::InProgress;
         }
 
@@ -215,10 +226,22 @@ impl ContainerCondition {
         }
 
         let size = potential_container.query_container_size(&box_style.clone_display());
+        let scroll_state = if self
+            .flags
+            .contains(FeatureFlags::CONTAINER_REQUIRES_SCROLL_STATE)
+        {
+            potential_container.query_container_scroll_state()
+        } else {
+            ContainerScrollState::default()
+        };
         let style = style.to_arc();
         TraversalResult::Done(ContainerLookupResult {
             element: potential_container,
-            info: ContainerInfo { size, wm },
+            info: ContainerInfo {
+                size,
+                wm,
+                scroll_state,
+            },
             style,
         })
     }
@@ -295,6 +318,7 @@ impl ContainerCondition {
 pub struct ContainerInfo {
     size: Size2D<Option<Au>>,
     wm: WritingMode,
+    scroll_state: ContainerScrollState,
 }
 
 impl ContainerInfo {
@@ -397,6 +421,294 @@ pub static CONTAINER_FEATURES: [QueryFeatureDescription; 6] = [
     ),
 ];
 
+bitflags! {
+    /// A set of physical sides of a scroll-state container.
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+    pub struct ScrollStateSides: u8 {
+        /// The top side.
+        const TOP = 1 << 0;
+        /// The right side.
+        const RIGHT = 1 << 1;
+        /// The bottom side.
+        const BOTTOM = 1 << 2;
+        /// The left side.
+        const LEFT = 1 << 3;
+        /// The horizontal sides.
+        const X = Self::LEFT.bits() | Self::RIGHT.bits();
+        /// The vertical sides.
+        const Y = Self::TOP.bits() | Self::BOTTOM.bits();
+    }
+}
+
+impl ScrollStateSides {
+    fn for_side(side: PhysicalSide) -> Self {
+        match side {
+            PhysicalSide::Top => Self::TOP,
+            PhysicalSide::Right => Self::RIGHT,
+            PhysicalSide::Bottom => Self::BOTTOM,
+            PhysicalSide::Left => Self::LEFT,
+        }
+    }
+}
+
+bitflags! {
+    /// A set of physical axes of a scroll-state container.
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+    pub struct ScrollStateAxes: u8 {
+        /// The horizontal axis.
+        const X = 1 << 0;
+        /// The vertical axis.
+        const Y = 1 << 1;
+    }
+}
+
+/// The scroll state of a scroll-state container, as provided by the
+/// embedder through `TElement::query_container_scroll_state`.
+///
+/// https://drafts.csswg.org/css-conditional-5/#scroll-state-container
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ContainerScrollState {
+    /// The sides of its scrollport a sticky positioned container is stuck to.
+    pub stuck: ScrollStateSides,
+    /// The axes in which the container is snapped to its snap container.
+    pub snapped: ScrollStateAxes,
+    /// The sides towards which the scroll container can be scrolled.
+    pub scrollable: ScrollStateSides,
+    /// The sides towards which the scroll container was most recently
+    /// scrolled.
+    pub scrolled: ScrollStateSides,
+}
+
+impl ContainerScrollState {
+    /// Returns the scroll state flags of the features whose value differs
+    /// between `self` and `other`.
+    fn changed_scroll_states(&self, other: &Self) -> FeatureFlags {
+        let mut changed = FeatureFlags::empty();
+        changed.set(FeatureFlags::SCROLL_STATE_STUCK, self.stuck != other.stuck);
+        changed.set(
+            FeatureFlags::SCROLL_STATE_SNAPPED,
+            self.snapped != other.snapped,
+        );
+        changed.set(
+            FeatureFlags::SCROLL_STATE_SCROLLABLE,
+            self.scrollable != other.scrollable,
+        );
+        changed.set(
+            FeatureFlags::SCROLL_STATE_SCROLLED,
+            self.scrolled != other.scrolled,
+        );
+        changed
+    }
+
+    /// Returns the restyle hint needed when the scroll state of a container
+    /// with the given style changes from `self` to `new`, given the scroll
+    /// state flags of the features the container queries use, see
+    /// `Stylist::scroll_state_dependencies`.
+    pub fn restyle_hint_for_change(
+        &self,
+        new: &Self,
+        style: &ComputedValues,
+        dependencies: FeatureFlags,
+    ) -> RestyleHint {
+        if !self.changed_scroll_states(new).intersects(dependencies)
+            || !style
+                .get_box()
+                .clone_container_type()
+                .intersects(ContainerType::SCROLL_STATE)
+        {
+            return RestyleHint::empty();
+        }
+        // Pseudo-elements can query their originating element.
+        RestyleHint::RESTYLE_DESCENDANTS | RestyleHint::RESTYLE_PSEUDOS
+    }
+}
+
+/// Values for the `stuck` scroll-state feature.
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+#[allow(missing_docs)]
+pub enum ScrollStateStuck {
+    None,
+    Top,
+    Right,
+    Bottom,
+    Left,
+    BlockStart,
+    BlockEnd,
+    InlineStart,
+    InlineEnd,
+}
+
+/// Values for the `snapped` scroll-state feature.
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+#[allow(missing_docs)]
+pub enum ScrollStateSnapped {
+    None,
+    X,
+    Y,
+    Block,
+    Inline,
+    Both,
+}
+
+/// Values for the `scrollable` and `scrolled` scroll-state features.
+#[derive(Clone, Copy, Debug, FromPrimitive, Parse, ToCss)]
+#[repr(u8)]
+#[allow(missing_docs)]
+pub enum ScrollStateDirection {
+    None,
+    Top,
+    Right,
+    Bottom,
+    Left,
+    BlockStart,
+    BlockEnd,
+    InlineStart,
+    InlineEnd,
+    X,
+    Y,
+    Block,
+    Inline,
+}
+
+impl ScrollStateDirection {
+    fn to_sides(self, wm: WritingMode) -> ScrollStateSides {
+        match self {
+            Self::None => ScrollStateSides::empty(),
+            Self::Top => ScrollStateSides::TOP,
+            Self::Right => ScrollStateSides::RIGHT,
+            Self::Bottom => ScrollStateSides::BOTTOM,
+            Self::Left => ScrollStateSides::LEFT,
+            Self::BlockStart => ScrollStateSides::for_side(wm.block_start_physical_side()),
+            Self::BlockEnd => ScrollStateSides::for_side(wm.block_end_physical_side()),
+            Self::InlineStart => ScrollStateSides::for_side(wm.inline_start_physical_side()),
+            Self::InlineEnd => ScrollStateSides::for_side(wm.inline_end_physical_side()),
+            Self::X => ScrollStateSides::X,
+            Self::Y => ScrollStateSides::Y,
+            Self::Block => {
+                if wm.is_vertical() {
+                    ScrollStateSides::X
+                } else {
+                    ScrollStateSides::Y
+                }
+            },
+            Self::Inline => {
+                if wm.is_vertical() {
+                    ScrollStateSides::Y
+                } else {
+                    ScrollStateSides::X
+                }
+            },
+        }
+    }
+}
+
+/// Evaluates a scroll-state feature against the given sides of the container,
+/// where `none` matches when there are no such sides, and the boolean context
+/// when there are some.
+fn eval_scroll_state_sides(
+    context: &Context,
+    value: Option<ScrollStateDirection>,
+    sides: impl FnOnce(&ContainerScrollState) -> ScrollStateSides,
+) -> KleeneValue {
+    let info = match context.container_info.as_ref() {
+        Some(info) => info,
+        None => return KleeneValue::Unknown,
+    };
+    let sides = sides(&info.scroll_state);
+    KleeneValue::from(match value {
+        None => !sides.is_empty(),
+        Some(ScrollStateDirection::None) => sides.is_empty(),
+        Some(value) => sides.intersects(value.to_sides(info.wm)),
+    })
+}
+
+fn eval_stuck(context: &Context, value: Option<ScrollStateStuck>) -> KleeneValue {
+    let value = value.map(|v| match v {
+        ScrollStateStuck::None => ScrollStateDirection::None,
+        ScrollStateStuck::Top => ScrollStateDirection::Top,
+        ScrollStateStuck::Right => ScrollStateDirection::Right,
+        ScrollStateStuck::Bottom => ScrollStateDirection::Bottom,
+        ScrollStateStuck::Left => ScrollStateDirection::Left,
+        ScrollStateStuck::BlockStart => ScrollStateDirection::BlockStart,
+        ScrollStateStuck::BlockEnd => ScrollStateDirection::BlockEnd,
+        ScrollStateStuck::InlineStart => ScrollStateDirection::InlineStart,
+        ScrollStateStuck::InlineEnd => ScrollStateDirection::InlineEnd,
+    });
+    eval_scroll_state_sides(context, value, |state| state.stuck)
+}
+
+fn eval_snapped(context: &Context, value: Option<ScrollStateSnapped>) -> KleeneValue {
+    let info = match context.container_info.as_ref() {
+        Some(info) => info,
+        None => return KleeneValue::Unknown,
+    };
+    let snapped = info.scroll_state.snapped;
+    let (block, inline) = if info.wm.is_vertical() {
+        (ScrollStateAxes::X, ScrollStateAxes::Y)
+    } else {
+        (ScrollStateAxes::Y, ScrollStateAxes::X)
+    };
+    KleeneValue::from(match value {
+        None => !snapped.is_empty(),
+        Some(ScrollStateSnapped::None) => snapped.is_empty(),
+        Some(ScrollStateSnapped::X) => snapped.contains(ScrollStateAxes::X),
+        Some(ScrollStateSnapped::Y) => snapped.contains(ScrollStateAxes::Y),
+        Some(ScrollStateSnapped::Block) => snapped.contains(block),
+        Some(ScrollStateSnapped::Inline) => snapped.contains(inline),
+        Some(ScrollStateSnapped::Both) => snapped.is_all(),
+    })
+}
+
+fn eval_scrollable(context: &Context, value: Option<ScrollStateDirection>) -> KleeneValue {
+    eval_scroll_state_sides(context, value, |state| state.scrollable)
+}
+
+fn eval_scrolled(context: &Context, value: Option<ScrollStateDirection>) -> KleeneValue {
+    eval_scroll_state_sides(context, value, |state| state.scrolled)
+}
+
+/// https://drafts.csswg.org/css-conditional-5/#scroll-state-container
+pub static SCROLL_STATE_FEATURES: [QueryFeatureDescription; 4] = [
+    feature!(
+        atom!("stuck"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_stuck, ScrollStateStuck),
+        FeatureFlags::from_bits_truncate(
+            FeatureFlags::CONTAINER_REQUIRES_SCROLL_STATE.bits()
+                | FeatureFlags::SCROLL_STATE_STUCK.bits()
+        ),
+    ),
+    feature!(
+        atom!("snapped"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_snapped, ScrollStateSnapped),
+        FeatureFlags::from_bits_truncate(
+            FeatureFlags::CONTAINER_REQUIRES_SCROLL_STATE.bits()
+                | FeatureFlags::SCROLL_STATE_SNAPPED.bits()
+        ),
+    ),
+    feature!(
+        atom!("scrollable"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_scrollable, ScrollStateDirection),
+        FeatureFlags::from_bits_truncate(
+            FeatureFlags::CONTAINER_REQUIRES_SCROLL_STATE.bits()
+                | FeatureFlags::SCROLL_STATE_SCROLLABLE.bits()
+        ),
+    ),
+    feature!(
+        atom!("scrolled"),
+        AllowsRanges::No,
+        keyword_evaluator!(eval_scrolled, ScrollStateDirection),
+        FeatureFlags::from_bits_truncate(
+            FeatureFlags::CONTAINER_REQUIRES_SCROLL_STATE.bits()
+                | FeatureFlags::SCROLL_STATE_SCROLLED.bits()
+        ),
+    ),
+];
+
 /// Result of a container size query, signifying the hypothetical containment boundary in terms of physical axes.
 /// Defined by up to two size containers. Queries on logical axes are resolved with respect to the querying
 /// element's writing mode.
@@ -402,15 +402,14 @@ pub struct KeyframesAnimation {
 
 /// Get all the animated properties in a keyframes animation.
 fn get_animated_properties(
-    keyframes: &[Arc<Locked<Keyframe>>],
+    blocks: &[(KeyframePercentage, Arc<Locked<PropertyDeclarationBlock>>)],
     guard: &SharedRwLockReadGuard,
 ) -> PropertyDeclarationIdSet {
     let mut ret = PropertyDeclarationIdSet::default();
     // NB: declarations are already deduplicated, so we don't have to check for
     // it here.
-    for keyframe in keyframes {
-        let keyframe = keyframe.read_with(&guard);
-        let block = keyframe.block.read_with(guard);
+    for (_, block) in blocks {
+        let block = block.read_with(guard);
         // CSS Animations spec clearly defines that properties with !important
         // in keyframe rules are invalid and ignored, but it's still ambiguous
         // whether we should drop the !important properties or retain the
@@ -448,6 +447,27 @@ impl KeyframesAnimation {
         keyframes: &[Arc<Locked<Keyframe>>],
         vendor_prefix: Option<VendorPrefix>,
         guard: &SharedRwLockReadGuard,
+    ) -> Self {
+        let mut blocks = vec![];
+        for keyframe in keyframes {
+            let keyframe = keyframe.read_with(&guard);
+            for percentage in keyframe.selector.0.iter() {
+                blocks.push((*percentage, keyframe.block.clone()));
+            }
+        }
+
+        Self::from_declaration_blocks(&blocks, vendor_prefix, guard)
+    }
+
+    /// Create a keyframes animation from a list of declaration blocks and the
+    /// offsets at which they apply, in the order they were specified.
+    ///
+    /// This is used for keyframes that don't come from a stylesheet, like the
+    /// ones given to `Element.animate()`.
+    pub fn from_declaration_blocks(
+        blocks: &[(KeyframePercentage, Arc<Locked<PropertyDeclarationBlock>>)],
+        vendor_prefix: Option<VendorPrefix>,
+        guard: &SharedRwLockReadGuard,
     ) -> Self {
         let mut result = KeyframesAnimation {
             steps: vec![],
@@ -455,26 +475,23 @@ impl KeyframesAnimation {
             vendor_prefix,
         };
 
-        if keyframes.is_empty() {
+        if blocks.is_empty() {
             return result;
         }
 
-        result.properties_changed = get_animated_properties(keyframes, guard);
+        result.properties_changed = get_animated_properties(blocks, guard);
         if result.properties_changed.is_empty() {
             return result;
         }
 
-        for keyframe in keyframes {
-            let keyframe = keyframe.read_with(&guard);
-            for percentage in keyframe.selector.0.iter() {
-                result.steps.push(KeyframesStep::new(
-                    *percentage,
-                    KeyframesStepValue::Declarations {
-                        block: keyframe.block.clone(),
-                    },
-                    guard,
-                ));
-            }
+        for (percentage, block) in blocks {
+            result.steps.push(KeyframesStep::new(
+                *percentage,
+                KeyframesStepValue::Declarations {
+                    block: block.clone(),
+                },
+                guard,
+            ));
         }
 
         // Sort by the start percentage, so we can easily find a frame.
@@ -53,6 +53,13 @@ impl LayerOrder {
         Self(0)
     }
 
+    /// Returns the position of this order among the orders of the named
+    /// layers, which are handed out sequentially.
+    #[inline]
+    pub(crate) fn index(self) -> usize {
+        self.0 as usize
+    }
+
     /// Increment the cascade layer order.
     #[inline]
     pub fn inc(&mut self) {
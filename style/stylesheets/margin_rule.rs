@@ -37,6 +37,12 @@ macro_rules! margin_rule_types {
         ];
 
         impl MarginRuleType {
+            /// All the margin rule types, in the order the margin boxes are
+            /// listed in the spec.
+            pub const ALL: [MarginRuleType; MARGIN_RULE_AT_NAMES.len()] = [
+                $( MarginRuleType::$id, )+
+            ];
+
             /// Matches the rule type for this name. This does not expect a
             /// leading '@'.
             pub fn match_name(name: &str) -> Option<Self> {
@@ -121,8 +127,9 @@ impl MarginRuleType {
     fn to_str(&self) -> &'static str {
         &MARGIN_RULE_AT_NAMES[*self as usize]
     }
+    /// Returns the name of this margin rule type, without a leading '@'.
     #[inline]
-    fn name(&self) -> &'static str {
+    pub fn name(&self) -> &'static str {
         // Use the at-name array, skipping the first character to get
         // the name without the @ sign.
         &MARGIN_RULE_AT_NAMES[*self as usize][1..]
@@ -730,7 +730,7 @@ impl<'a, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
                 let name = DashedIdent::parse(&self.context, input)?;
                 AtRulePrelude::FontPaletteValues(name)
             },
-            "counter-style" if cfg!(feature = "gecko") => {
+            "counter-style" => {
                 let name = parse_counter_style_name_definition(input)?;
                 AtRulePrelude::CounterStyle(name)
             },
@@ -750,7 +750,7 @@ impl<'a, 'i> AtRuleParser<'i> for NestedRuleParser<'a, 'i> {
                 let name = KeyframesName::parse(&self.context, input)?;
                 AtRulePrelude::Keyframes(name, prefix)
             },
-            "page" if cfg!(feature = "gecko") => {
+            "page" => {
                 AtRulePrelude::Page(
                     input.try_parse(|i| PageSelectors::parse(&self.context, i)).unwrap_or_default()
                 )
@@ -7,11 +7,14 @@
 use crate::applicable_declarations::{
     ApplicableDeclarationBlock, ApplicableDeclarationList, CascadePriority, ScopeProximity,
 };
+use crate::cascade_inspection::{self, MatchedRule};
 use crate::computed_value_flags::ComputedValueFlags;
 use crate::context::{CascadeInputs, QuirksMode};
+use crate::counter_style::{CounterStyle, ResolvedCounterStyle};
 use crate::custom_properties::ComputedCustomProperties;
 use crate::derives::*;
 use crate::dom::TElement;
+use crate::font_matching::{self, FontFaceCandidate, FontQuery, FontSourceSupport};
 #[cfg(feature = "gecko")]
 use crate::gecko_bindings::structs::{ServoStyleSetSizes, StyleRuleInclusion};
 use crate::invalidation::element::invalidation_map::{
@@ -23,15 +26,14 @@ use crate::invalidation::media_queries::{
 };
 use crate::invalidation::stylesheets::{RuleChangeKind, StylesheetInvalidationSet};
 use crate::media_queries::Device;
-#[cfg(feature = "gecko")]
-use crate::properties::StyleBuilder;
 use crate::properties::{
     self, AnimationDeclarations, CascadeMode, ComputedValues, FirstLineReparenting,
-    PropertyDeclarationBlock,
+    PropertyDeclarationBlock, StyleBuilder,
 };
 use crate::properties_and_values::registry::{
     PropertyRegistration, PropertyRegistrationData, ScriptRegistry as CustomPropertyScriptRegistry,
 };
+use crate::queries::feature::FeatureFlags;
 use crate::rule_cache::{RuleCache, RuleCacheConditions};
 use crate::rule_collector::RuleCollector;
 use crate::rule_tree::{CascadeLevel, RuleTree, StrongRuleNode, StyleSource};
@@ -51,16 +53,17 @@ use crate::stylesheets::scope_rule::{
 };
 use crate::stylesheets::{
     CounterStyleRule, CssRule, CssRuleRef, EffectiveRulesIterator, FontFaceRule,
-    FontFeatureValuesRule, FontPaletteValuesRule, Origin, OriginSet, PagePseudoClassFlags,
-    PageRule, PerOrigin, PerOriginIter, PositionTryRule, StylesheetContents, StylesheetInDocument,
+    FontFeatureValuesRule, FontPaletteValuesRule, MarginRuleType, Origin, OriginSet,
+    PagePseudoClassFlags, PageRule, PerOrigin, PerOriginIter, PositionTryRule, StylesheetContents,
+    StylesheetInDocument,
 };
 use crate::stylesheets::{CustomMediaEvaluator, CustomMediaMap};
-#[cfg(feature = "gecko")]
 use crate::values::specified::position::PositionTryFallbacksItem;
 use crate::values::specified::position::PositionTryFallbacksTryTactic;
 use crate::values::{computed, AtomIdent};
 use crate::AllocErr;
 use crate::{Atom, LocalName, Namespace, ShrinkIfNeeded, WeakAtom};
+use cssparser::SourceLocation;
 use dom::{DocumentState, ElementState};
 #[cfg(feature = "gecko")]
 use malloc_size_of::MallocUnconditionalShallowSizeOf;
@@ -1031,6 +1034,23 @@ impl Stylist {
             .any(|(d, _)| d.document_state_dependencies.intersects(state))
     }
 
+    /// Returns the scroll state flags of the scroll-state features queried by
+    /// the container queries of the rules that may apply to the given
+    /// element, or to its shadow tree.
+    ///
+    /// Embedders pass these to `ContainerScrollState::restyle_hint_for_change`
+    /// when the scroll state of a container changes.
+    pub fn scroll_state_dependencies<E>(&self, element: E) -> FeatureFlags
+    where
+        E: TElement,
+    {
+        let mut dependencies = FeatureFlags::empty();
+        self.for_each_cascade_data_with_scope(element, |data, _| {
+            dependencies.insert(data.scroll_state_dependencies);
+        });
+        dependencies
+    }
+
     /// Flush the list of stylesheets if they changed, ensuring the stylist is
     /// up-to-date.
     pub fn flush(&mut self, guards: &StylesheetGuards) -> StylesheetInvalidationSet {
@@ -1279,6 +1299,47 @@ impl Stylist {
         self.precomputed_values_for_pseudo::<E>(guards, &pseudo, Some(parent_style))
     }
 
+    /// Re-parents `style` so that it inherits from a `::first-line` style.
+    ///
+    /// Layout uses this for the boxes that end up in the first formatted line
+    /// of a block container: `first_line_style` is the `::first-line` style of
+    /// the block (or of the nearest ancestor with one, for nested blocks), and
+    /// `layout_parent_style` is the style of the nearest ancestor with a
+    /// layout box.
+    ///
+    /// Only inherited properties are affected: the reset structs are copied
+    /// from `style` as-is, and no selector matching happens.
+    #[cfg(feature = "servo")]
+    pub fn reparent_style_for_first_line<E>(
+        &self,
+        guards: &StylesheetGuards,
+        element: Option<E>,
+        style: &ComputedValues,
+        first_line_style: &ComputedValues,
+        layout_parent_style: &ComputedValues,
+    ) -> Arc<ComputedValues>
+    where
+        E: TElement,
+    {
+        debug_assert!(first_line_style
+            .pseudo()
+            .map_or(false, |pseudo| pseudo.is_first_line()));
+        self.cascade_style_and_visited(
+            element,
+            style.pseudo(),
+            CascadeInputs::new_from_style(style),
+            guards,
+            Some(first_line_style),
+            Some(layout_parent_style),
+            FirstLineReparenting::Yes {
+                style_to_reparent: style,
+            },
+            &PositionTryFallbacksTryTactic::default(),
+            /* rule_cache = */ None,
+            &mut RuleCacheConditions::default(),
+        )
+    }
+
     /// Computes a pseudo-element style lazily during layout.
     ///
     /// This can only be done for a certain set of pseudo-elements, like
@@ -1318,6 +1379,50 @@ impl Stylist {
         ))
     }
 
+    /// Computes the style of a pseudo-element of the view transition tree,
+    /// which hangs from the root element.
+    ///
+    /// `parent_style` is the style of the parent pseudo-element in the tree,
+    /// or the style of the root element for `::view-transition` itself. Named
+    /// view transition pseudo-elements only match the selectors for their name
+    /// and classes.
+    #[cfg(feature = "servo")]
+    pub fn compute_view_transition_pseudo_style<E>(
+        &self,
+        guards: &StylesheetGuards,
+        root: E,
+        root_style: &ComputedValues,
+        pseudo: &PseudoElement,
+        parent_style: &ComputedValues,
+    ) -> Arc<ComputedValues>
+    where
+        E: TElement,
+    {
+        debug_assert!(
+            *pseudo == PseudoElement::ViewTransition || pseudo.is_named_view_transition(),
+            "Not a view transition pseudo-element: {:?}",
+            pseudo
+        );
+        let cascade_inputs = self
+            .lazy_pseudo_rules(
+                guards,
+                root,
+                root_style,
+                pseudo,
+                /* is_probe = */ false,
+                RuleInclusion::All,
+                None,
+            )
+            .expect("Only probes can fail to resolve rules");
+        self.compute_pseudo_element_style_with_inputs(
+            cascade_inputs,
+            pseudo,
+            guards,
+            Some(parent_style),
+            Some(root),
+        )
+    }
+
     /// Computes a pseudo-element style lazily using the given CascadeInputs.
     /// This can be used for truly lazy pseudo-elements or to avoid redoing
     /// selector matching for eager pseudo-elements when we need to recompute
@@ -1360,7 +1465,6 @@ impl Stylist {
     }
 
     /// Computes a fallback style lazily given the current and parent styles, and name.
-    #[cfg(feature = "gecko")]
     pub fn resolve_position_try<E>(
         &self,
         style: &ComputedValues,
@@ -1395,9 +1499,13 @@ impl Stylist {
         let fallback_block = fallback_rule
             .as_ref()
             .map(|r| &r.read_with(guards.author).block);
-        let pseudo = style
-            .pseudo()
-            .or_else(|| element.implemented_pseudo_element());
+        // Servo styles hand out a reference to their pseudo-element, while
+        // Gecko ones build it on the fly.
+        #[cfg(feature = "servo")]
+        let pseudo = style.pseudo().cloned();
+        #[cfg(feature = "gecko")]
+        let pseudo = style.pseudo();
+        let pseudo = pseudo.or_else(|| element.implemented_pseudo_element());
         let inputs = {
             let mut inputs = CascadeInputs::new_from_style(style);
             // @position-try doesn't care about any :visited-dependent property.
@@ -1550,6 +1658,17 @@ impl Stylist {
             MatchingForInvalidation::No,
         );
 
+        // Named view transition pseudo-elements share the rules of their kind,
+        // so the rules need to be filtered by name and class.
+        #[cfg(feature = "servo")]
+        let view_transition_matching_fn = |selector: &PseudoElement| pseudo.matches(selector);
+        #[cfg(feature = "servo")]
+        let matching_fn = matching_fn.or_else(|| {
+            pseudo
+                .is_named_view_transition()
+                .then_some(&view_transition_matching_fn as &dyn Fn(&PseudoElement) -> bool)
+        });
+
         matching_context.pseudo_element_matching_fn = matching_fn;
         matching_context.extra_data.originating_element_style = Some(originating_element_style);
 
@@ -1797,9 +1916,174 @@ impl Stylist {
         self.lookup_element_dependent_at_rule(element, |data| data.animations.get(name))
     }
 
+    /// Returns the effective `@counter-style` rule for the specified name.
+    ///
+    /// Counter styles are document-global, so rules from shadow trees are not
+    /// looked up.
+    pub fn lookup_counter_style(&self, name: &Atom) -> Option<&Arc<Locked<CounterStyleRule>>> {
+        self.iter_extra_data_origins()
+            .find_map(|(data, _)| data.counter_styles.get(name))
+    }
+
+    /// Resolves a counter style against the effective `@counter-style` rules,
+    /// in order to generate counter representations and marker text.
+    pub fn resolve_counter_style<'a>(
+        &'a self,
+        style: &'a CounterStyle,
+        guard: &'a SharedRwLockReadGuard,
+    ) -> ResolvedCounterStyle<'a, impl Fn(&Atom) -> Option<&'a CounterStyleRule> + 'a> {
+        ResolvedCounterStyle::new(style, move |name| {
+            self.lookup_counter_style(name)
+                .map(|rule| rule.read_with(guard))
+        })
+    }
+
+    /// Runs the font matching algorithm over the effective `@font-face`
+    /// rules, returning the candidate faces and their supported sources in
+    /// the order they should be tried.
+    ///
+    /// Font faces are document-global, so rules from shadow trees are not
+    /// looked up.
+    pub fn match_font_faces<'a>(
+        &'a self,
+        guard: &'a SharedRwLockReadGuard,
+        query: &FontQuery,
+        support: &FontSourceSupport,
+    ) -> Vec<FontFaceCandidate<'a>> {
+        let faces: Vec<_> = self
+            .iter_extra_data_origins_rev()
+            .flat_map(|(data, _)| data.font_faces.iter())
+            .map(|(rule, _)| rule.read_with(guard))
+            .collect();
+        font_matching::match_font_faces(&faces, query, support)
+    }
+
+    /// Returns the rules that took part in the cascade of `style`, the current
+    /// style of `element` or of its `pseudo` pseudo-element, in ascending
+    /// cascade order, along with the outcome of the cascade for each of their
+    /// declarations.
+    ///
+    /// The element is matched again to find the specificity and `@scope`
+    /// proximity of the rules, which the rule tree doesn't keep. Only the
+    /// unvisited style is inspected.
+    pub fn inspect_cascade<E>(
+        &self,
+        guards: &StylesheetGuards,
+        element: E,
+        pseudo: Option<&PseudoElement>,
+        style: &ComputedValues,
+    ) -> Vec<MatchedRule>
+    where
+        E: TElement,
+    {
+        let mut declarations = ApplicableDeclarationList::new();
+        let mut selector_caches = SelectorCaches::default();
+        let matching_mode = if pseudo.is_some() {
+            MatchingMode::ForStatelessPseudoElement
+        } else {
+            MatchingMode::Normal
+        };
+        let mut matching_context = MatchingContext::<'_, E::Impl>::new(
+            matching_mode,
+            None,
+            &mut selector_caches,
+            self.quirks_mode,
+            NeedsSelectorFlags::No,
+            MatchingForInvalidation::No,
+        );
+        let style_attribute = if pseudo.is_none() {
+            element.style_attribute()
+        } else {
+            None
+        };
+        self.push_applicable_declarations(
+            element,
+            pseudo,
+            style_attribute,
+            /* smil_override = */ None,
+            /* animation_declarations = */ Default::default(),
+            RuleInclusion::All,
+            &mut declarations,
+            &mut matching_context,
+        );
+        cascade_inspection::inspect_cascade(self, element, pseudo, style, guards, &declarations)
+    }
+
+    /// Computes the styles of a page box and of its margin boxes, from the
+    /// `@page` rules matching a page with the given name and page
+    /// pseudo-classes (`:first`, `:left`, `:right` and `:blank`).
+    ///
+    /// The page context inherits from `parent_style`, which is usually the
+    /// style of the root element, and the margin boxes inherit from the page
+    /// context.
+    ///
+    /// https://drafts.csswg.org/css-page-3/#page-model
+    pub fn compute_page_styles<E>(
+        &self,
+        guards: &StylesheetGuards,
+        name: &Option<Atom>,
+        pseudos: PagePseudoClassFlags,
+        parent_style: Option<&ComputedValues>,
+    ) -> PageStyles
+    where
+        E: TElement,
+    {
+        let page = self.cascade_page_rules::<E>(guards, parent_style, |pages, origin, rules| {
+            pages.match_and_append_rules(rules, origin, guards, &self.cascade_data, name, pseudos)
+        });
+        let margin_boxes = MarginRuleType::ALL.map(|margin_rule_type| {
+            self.cascade_page_rules::<E>(guards, Some(&page), |pages, origin, rules| {
+                pages.match_and_append_margin_rules(
+                    rules,
+                    origin,
+                    guards,
+                    &self.cascade_data,
+                    name,
+                    pseudos,
+                    margin_rule_type,
+                )
+            })
+        });
+        PageStyles { page, margin_boxes }
+    }
+
+    fn cascade_page_rules<E>(
+        &self,
+        guards: &StylesheetGuards,
+        parent_style: Option<&ComputedValues>,
+        mut collect: impl FnMut(&PageRuleMap, Origin, &mut Vec<ApplicableDeclarationBlock>),
+    ) -> Arc<ComputedValues>
+    where
+        E: TElement,
+    {
+        let mut rules = vec![];
+        // The rule tree expects the rules in ascending cascade order.
+        for (data, origin) in self.iter_extra_data_origins_rev() {
+            collect(&data.pages, origin, &mut rules);
+        }
+        let rule_node = self.rule_tree.insert_ordered_rules_with_important(
+            rules.into_iter().map(|block| block.for_rule_tree()),
+            guards,
+        );
+        properties::cascade::<E>(
+            self,
+            /* pseudo = */ None,
+            &rule_node,
+            guards,
+            parent_style,
+            parent_style,
+            FirstLineReparenting::No,
+            &PositionTryFallbacksTryTactic::default(),
+            /* visited_rules = */ None,
+            Default::default(),
+            /* rule_cache = */ None,
+            &mut RuleCacheConditions::default(),
+            /* element = */ None,
+        )
+    }
+
     /// Returns the registered `@position-try-rule` animation for the specified name.
     #[inline]
-    #[cfg(feature = "gecko")]
     fn lookup_position_try<'a, E>(
         &'a self,
         name: &Atom,
@@ -2104,6 +2388,24 @@ impl<T: 'static> LayerOrderedMap<T> {
     }
 }
 
+/// The computed styles of a page box and of its margin boxes, as returned by
+/// `Stylist::compute_page_styles`.
+#[derive(Clone, Debug)]
+pub struct PageStyles {
+    /// The style of the page context.
+    pub page: Arc<ComputedValues>,
+    /// The styles of the margin boxes, in `MarginRuleType::ALL` order.
+    pub margin_boxes: [Arc<ComputedValues>; MarginRuleType::ALL.len()],
+}
+
+impl PageStyles {
+    /// Returns the style of the given margin box.
+    #[inline]
+    pub fn margin_box(&self, margin_rule_type: MarginRuleType) -> &Arc<ComputedValues> {
+        &self.margin_boxes[margin_rule_type as usize]
+    }
+}
+
 /// Wrapper to allow better tracking of memory usage by page rule lists.
 ///
 /// This includes the layer ID for use with the named page table.
@@ -2140,6 +2442,51 @@ impl PageRuleMap {
         cascade_data: &DocumentCascadeData,
         name: &Option<Atom>,
         pseudos: PagePseudoClassFlags,
+    ) {
+        self.match_and_append(
+            matched_rules,
+            origin,
+            guards,
+            cascade_data,
+            name,
+            pseudos,
+            None,
+        )
+    }
+
+    /// Like `match_and_append_rules`, but appends the declarations of the
+    /// `@margin` rules of the given type nested in the matching page-rules
+    /// instead. These take the specificity of their parent page-rule.
+    pub fn match_and_append_margin_rules(
+        &self,
+        matched_rules: &mut Vec<ApplicableDeclarationBlock>,
+        origin: Origin,
+        guards: &StylesheetGuards,
+        cascade_data: &DocumentCascadeData,
+        name: &Option<Atom>,
+        pseudos: PagePseudoClassFlags,
+        margin_rule_type: MarginRuleType,
+    ) {
+        self.match_and_append(
+            matched_rules,
+            origin,
+            guards,
+            cascade_data,
+            name,
+            pseudos,
+            Some(margin_rule_type),
+        )
+    }
+
+    fn match_and_append(
+        &self,
+        matched_rules: &mut Vec<ApplicableDeclarationBlock>,
+        origin: Origin,
+        guards: &StylesheetGuards,
+        cascade_data: &DocumentCascadeData,
+        name: &Option<Atom>,
+        pseudos: PagePseudoClassFlags,
+        margin_rule_type: Option<MarginRuleType>,
     ) {
         let level = match origin {
             Origin::UserAgent => CascadeLevel::UANormal,
@@ -2156,9 +2503,18 @@ impl PageRuleMap {
             cascade_data,
             &atom!(""),
             pseudos,
+            margin_rule_type,
         );
         if let Some(name) = name {
-            self.match_and_add_rules(matched_rules, level, guards, cascade_data, name, pseudos);
+            self.match_and_add_rules(
+                matched_rules,
+                level,
+                guards,
+                cascade_data,
+                name,
+                pseudos,
+                margin_rule_type,
+            );
         }
 
         // Because page-rules do not have source location information stored,
@@ -2174,26 +2530,43 @@ impl PageRuleMap {
         cascade_data: &CascadeData,
         name: &Atom,
         pseudos: PagePseudoClassFlags,
+        margin_rule_type: Option<MarginRuleType>,
     ) {
         let rules = match self.rules.get(name) {
             Some(rules) => rules,
             None => return,
         };
+        let guard = level.guard(guards);
         for data in rules.iter() {
-            let rule = data.rule.read_with(level.guard(&guards));
+            let rule = data.rule.read_with(guard);
             let specificity = match rule.match_specificity(pseudos) {
                 Some(specificity) => specificity,
                 None => continue,
             };
-            let block = rule.block.clone();
-            extra_declarations.push(ApplicableDeclarationBlock::new(
-                StyleSource::from_declarations(block),
-                0,
-                level,
-                specificity,
-                cascade_data.layer_order_for(data.layer),
-                ScopeProximity::infinity(), // Page rule can't have nested rules anyway.
-            ));
+            let mut push = |block: &Arc<Locked<PropertyDeclarationBlock>>| {
+                extra_declarations.push(ApplicableDeclarationBlock::new(
+                    StyleSource::from_declarations(block.clone()),
+                    0,
+                    level,
+                    specificity,
+                    cascade_data.layer_order_for(data.layer),
+                    ScopeProximity::infinity(), // Page rule can't have nested rules anyway.
+                ));
+            };
+            let margin_rule_type = match margin_rule_type {
+                Some(margin_rule_type) => margin_rule_type,
+                None => {
+                    push(&rule.block);
+                    continue;
+                },
+            };
+            for nested in rule.rules.read_with(guard).0.iter() {
+                if let CssRule::Margin(ref margin_rule) = *nested {
+                    if margin_rule.rule_type == margin_rule_type {
+                        push(&margin_rule.block);
+                    }
+                }
+            }
         }
     }
 }
@@ -2465,6 +2838,7 @@ fn component_needs_revalidation(
         | Component::Empty
         | Component::Nth(_)
         | Component::NthOf(_)
+        | Component::NthCol { .. }
         | Component::Has(_) => true,
         Component::NonTSPseudoClass(ref p) => p.needs_cache_revalidation(),
         _ => false,
@@ -2494,8 +2868,8 @@ impl<'a> SelectorVisitor for StylistSelectorVisitor<'a> {
     type Impl = SelectorImpl;
 
     fn visit_complex_selector(&mut self, combinator: Option<Combinator>) -> bool {
-        *self.needs_revalidation =
-            *self.needs_revalidation || combinator.map_or(false, |c| c.is_sibling());
+        *self.needs_revalidation = *self.needs_revalidation
+            || combinator.is_some_and(|c| c.is_sibling() || c == Combinator::Column);
 
         // NOTE(emilio): this call happens before we visit any of the simple
         // selectors in the next ComplexSelector, so we can use this to skip
@@ -3202,9 +3576,17 @@ pub struct CascadeData {
     /// The list of cascade layers, indexed by their layer id.
     layers: SmallVec<[CascadeLayer; 1]>,
 
+    /// The names of the cascade layers, indexed by their layer order.
+    layer_names: Vec<LayerName>,
+
     /// The list of container conditions, indexed by their id.
     container_conditions: SmallVec<[ContainerConditionReference; 1]>,
 
+    /// The scroll state flags of the scroll-state features the container
+    /// conditions query.
+    #[ignore_malloc_size_of = "Pod"]
+    scroll_state_dependencies: FeatureFlags,
+
     /// The list of scope conditions, indexed by their id.
     scope_conditions: SmallVec<[ScopeConditionReference; 1]>,
 
@@ -3221,6 +3603,11 @@ pub struct CascadeData {
     /// style rule appears in a stylesheet, needed to sort them by source order.
     rules_source_order: u32,
 
+    /// The declaration block and source location of each style rule, indexed
+    /// by its source order.
+    #[ignore_malloc_size_of = "Secondary ref. Primary ref is in StyleRule under Stylesheet."]
+    style_rule_locations: Vec<(StyleSource, SourceLocation)>,
+
     /// The total number of selectors.
     num_selectors: usize,
 
@@ -3280,12 +3667,15 @@ impl CascadeData {
             custom_media: Default::default(),
             layer_id: Default::default(),
             layers: smallvec::smallvec![CascadeLayer::root()],
+            layer_names: vec![],
             container_conditions: smallvec::smallvec![ContainerConditionReference::none()],
+            scroll_state_dependencies: FeatureFlags::empty(),
             scope_conditions: smallvec::smallvec![ScopeConditionReference::none()],
             scope_subject_map: Default::default(),
             extra_data: ExtraStyleData::default(),
             effective_media_query_results: EffectiveMediaQueryResults::new(),
             rules_source_order: 0,
+            style_rule_locations: vec![],
             num_selectors: 0,
             num_declarations: 0,
         }
@@ -3473,6 +3863,23 @@ impl CascadeData {
         self.layers[id.0 as usize].order
     }
 
+    /// Returns the name of the cascade layer with the given order, or `None`
+    /// for the implicit outer layer.
+    pub(crate) fn layer_name(&self, order: LayerOrder) -> Option<&LayerName> {
+        self.layer_names.get(order.index())
+    }
+
+    /// Returns the source location of the style rule with the given source
+    /// order, if `source` is its declaration block.
+    pub(crate) fn style_rule_location(
+        &self,
+        source: &StyleSource,
+        source_order: u32,
+    ) -> Option<SourceLocation> {
+        let (ref rule_source, location) = *self.style_rule_locations.get(source_order as usize)?;
+        (rule_source == source).then_some(location)
+    }
+
     pub(crate) fn container_condition_matches<E>(
         &self,
         mut id: ContainerConditionId,
@@ -3603,6 +4010,12 @@ impl CascadeData {
                 order.inc();
             }
         }
+
+        self.layer_names = vec![LayerName::new_empty(); self.layers.len() - 1];
+        for (name, id) in self.layer_id.iter() {
+            let order = self.layers[id.0 as usize].order;
+            self.layer_names[order.index()] = name.clone();
+        }
         self.extra_data.sort_by_layer(&self.layers);
         self.animations
             .sort_with(&self.layers, compare_keyframes_in_same_layer);
@@ -3670,6 +4083,7 @@ impl CascadeData {
         &mut self,
         selectors: &SelectorList<SelectorImpl>,
         declarations: &Arc<Locked<PropertyDeclarationBlock>>,
+        source_location: SourceLocation,
         ancestor_selectors: Option<&SelectorList<SelectorImpl>>,
         containing_rule_state: &ContainingRuleState,
         mut replaced_selectors: Option<&mut ReplacedSelectors>,
@@ -3846,6 +4260,10 @@ impl CascadeData {
                 rules.insert(rule, quirks_mode)?;
             }
         }
+        self.style_rule_locations.push((
+            StyleSource::from_declarations(declarations.clone()),
+            source_location,
+        ));
         self.rules_source_order += 1;
         Ok(())
     }
@@ -3885,6 +4303,7 @@ impl CascadeData {
                     self.add_styles(
                         &style_rule.selectors,
                         &style_rule.block,
+                        style_rule.source_location,
                         ancestor_selectors,
                         containing_rule_state,
                         if collect_replaced_selectors {
@@ -3917,7 +4336,7 @@ impl CascadeData {
                     if let Some(ref ancestor_selectors) =
                         containing_rule_state.ancestor_selector_lists.last()
                     {
-                        let decls = &rule.read_with(guard).block;
+                        let rule = rule.read_with(guard);
                         let selectors = match containing_rule_state.nested_declarations_context {
                             NestedDeclarationsContext::Style => ancestor_selectors,
                             NestedDeclarationsContext::Scope => &*IMPLICIT_SCOPE,
@@ -3927,7 +4346,8 @@ impl CascadeData {
                             .then(|| Vec::new());
                         self.add_styles(
                             selectors,
-                            decls,
+                            &rule.block,
+                            rule.source_location,
                             /* ancestor_selectors = */ None,
                             containing_rule_state,
                             /* replaced_selectors = */ None,
@@ -4155,6 +4575,8 @@ impl CascadeData {
                 },
                 CssRule::Container(ref rule) => {
                     let id = ContainerConditionId(self.container_conditions.len() as u16);
+                    self.scroll_state_dependencies
+                        .insert(rule.condition.scroll_state_dependencies());
                     self.container_conditions.push(ContainerConditionReference {
                         parent: containing_rule_state.container_condition_id,
                         condition: Some(rule.condition.clone()),
@@ -4525,14 +4947,17 @@ impl CascadeData {
         self.layer_id.clear();
         self.layers.clear();
         self.layers.push(CascadeLayer::root());
+        self.layer_names.clear();
         self.custom_media.clear();
         self.container_conditions.clear();
         self.container_conditions
             .push(ContainerConditionReference::none());
+        self.scroll_state_dependencies = FeatureFlags::empty();
         self.scope_conditions.clear();
         self.scope_conditions.push(ScopeConditionReference::none());
         self.extra_data.clear();
         self.rules_source_order = 0;
+        self.style_rule_locations.clear();
         self.num_selectors = 0;
         self.num_declarations = 0;
     }
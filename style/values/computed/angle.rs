@@ -12,7 +12,7 @@ use std::f64::consts::PI;
 use std::fmt::{self, Write};
 use std::ops::Neg;
 use std::{f32, f64};
-use style_traits::{CssWriter, ToCss};
+use style_traits::{CssWriter, NumericValue, ToCss, ToTyped, TypedValue};
 
 /// A computed angle in degrees.
 #[derive(
@@ -42,6 +42,12 @@ impl ToCss for Angle {
     }
 }
 
+impl ToTyped for Angle {
+    fn to_typed(&self) -> Option<TypedValue> {
+        Some(TypedValue::Numeric(NumericValue::degrees(self.degrees())))
+    }
+}
+
 const RAD_PER_DEG: f64 = PI / 180.0;
 
 impl Angle {
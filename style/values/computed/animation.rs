@@ -14,8 +14,8 @@ use style_traits::{CssWriter, ToCss};
 
 pub use crate::values::specified::animation::{
     AnimationComposition, AnimationDirection, AnimationFillMode, AnimationName, AnimationPlayState,
-    ScrollAxis, TimelineName, TransitionBehavior, TransitionProperty, ViewTransitionClass,
-    ViewTransitionName,
+    ScrollAxis, TimelineName, TimelineScope, TransitionBehavior, TransitionProperty,
+    ViewTransitionClass, ViewTransitionName,
 };
 
 /// A computed value for the `animation-duration` property.
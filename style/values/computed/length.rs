@@ -6,12 +6,14 @@
 
 use super::{Context, Number, ToComputedValue};
 use crate::derives::*;
-use crate::logical_geometry::PhysicalSide;
+use crate::logical_geometry::{PhysicalAxis, PhysicalSide};
 use crate::values::animated::{Context as AnimatedContext, ToAnimatedValue};
-use crate::values::computed::position::TryTacticAdjustment;
+use crate::values::computed::length_percentage::AllowAnchorPosResolutionInCalcPercentage;
+use crate::values::computed::position::{AnchorPosResolver, TryTacticAdjustment};
 use crate::values::computed::{NonNegativeNumber, Percentage, Zoom};
 use crate::values::generics::length::{
-    GenericLengthOrNumber, GenericLengthPercentageOrNormal, GenericMaxSize, GenericSize,
+    GenericAnchorSizeFunction, GenericLengthOrNumber, GenericLengthPercentageOrNormal,
+    GenericMaxSize, GenericSize,
 };
 #[cfg(feature = "gecko")]
 use crate::values::generics::position::TreeScoped;
@@ -529,8 +531,7 @@ pub type MaxSize = GenericMaxSize<NonNegativeLengthPercentage>;
 
 #[cfg(feature = "gecko")]
 use crate::{
-    gecko_bindings::structs::AnchorPosResolutionParams, logical_geometry::PhysicalAxis,
-    values::generics::length::AnchorSizeKeyword,
+    gecko_bindings::structs::AnchorPosResolutionParams, values::generics::length::AnchorSizeKeyword,
 };
 
 /// Resolve the anchor function with the given resolver. Returns `Err()` if no anchor is found.
@@ -567,6 +568,102 @@ pub fn resolve_anchor_size(
 /// A computed type for `margin` properties.
 pub type Margin = generics::GenericMargin<LengthPercentage>;
 
+/// Resolves an `anchor-size()` function to a length, or to its resolved
+/// fallback if the anchor is invalid.
+pub(crate) fn resolve_anchor_size_function<T>(
+    f: &GenericAnchorSizeFunction<T>,
+    axis: PhysicalAxis,
+    resolver: &impl AnchorPosResolver,
+    from_length: impl FnOnce(Length) -> T,
+    resolve_fallback: impl FnOnce(&T) -> Result<T, ()>,
+) -> Result<T, ()> {
+    if f.valid_for(resolver.position_property()) {
+        if let Ok(length) = resolver.resolve_anchor_size(&f.target_element, axis, f.size) {
+            return Ok(from_length(length));
+        }
+    }
+    resolve_fallback(f.fallback.as_ref().ok_or(())?)
+}
+
+macro_rules! size_resolve_anchor {
+    ($size:ident, $invalid:ident) => {
+        impl $size {
+            /// Returns a copy of this value with all `anchor-size()` functions
+            /// resolved for the given axis, falling back to the initial value if
+            /// any of them is invalid and has no fallback.
+            pub fn resolve_anchor(
+                &self,
+                axis: PhysicalAxis,
+                resolver: &impl AnchorPosResolver,
+            ) -> Self {
+                self.try_resolve_anchor(axis, resolver)
+                    .unwrap_or(Self::$invalid)
+            }
+
+            fn try_resolve_anchor(
+                &self,
+                axis: PhysicalAxis,
+                resolver: &impl AnchorPosResolver,
+            ) -> Result<Self, ()> {
+                Ok(match self {
+                    Self::AnchorSizeFunction(f) => resolve_anchor_size_function(
+                        f,
+                        axis,
+                        resolver,
+                        |length| {
+                            Self::LengthPercentage(NonNegative(LengthPercentage::new_length(
+                                length.clamp_to_non_negative(),
+                            )))
+                        },
+                        |fallback| fallback.try_resolve_anchor(axis, resolver),
+                    )?,
+                    Self::AnchorContainingCalcFunction(lp) => {
+                        Self::LengthPercentage(NonNegative(lp.0.resolve_anchor(
+                            AllowAnchorPosResolutionInCalcPercentage::AnchorSizeOnly(axis),
+                            resolver,
+                        )?))
+                    },
+                    _ => self.clone(),
+                })
+            }
+        }
+    };
+}
+
+size_resolve_anchor!(Size, Auto);
+size_resolve_anchor!(MaxSize, None);
+
+impl Margin {
+    /// Returns a copy of this value with all `anchor-size()` functions
+    /// resolved for the given side, falling back to zero if any of them is
+    /// invalid and has no fallback.
+    pub fn resolve_anchor(&self, side: PhysicalSide, resolver: &impl AnchorPosResolver) -> Self {
+        self.try_resolve_anchor(side, resolver)
+            .unwrap_or_else(|()| Self::LengthPercentage(LengthPercentage::zero()))
+    }
+
+    fn try_resolve_anchor(
+        &self,
+        side: PhysicalSide,
+        resolver: &impl AnchorPosResolver,
+    ) -> Result<Self, ()> {
+        Ok(match self {
+            Self::Auto | Self::LengthPercentage(..) => self.clone(),
+            Self::AnchorSizeFunction(f) => resolve_anchor_size_function(
+                f,
+                side.axis(),
+                resolver,
+                |length| Self::LengthPercentage(LengthPercentage::new_length(length)),
+                |fallback| fallback.try_resolve_anchor(side, resolver),
+            )?,
+            Self::AnchorContainingCalcFunction(lp) => Self::LengthPercentage(lp.resolve_anchor(
+                AllowAnchorPosResolutionInCalcPercentage::AnchorSizeOnly(side.axis()),
+                resolver,
+            )?),
+        })
+    }
+}
+
 impl TryTacticAdjustment for MaxSize {
     fn try_tactic_adjustment(&mut self, old_side: PhysicalSide, new_side: PhysicalSide) {
         debug_assert!(
@@ -24,10 +24,11 @@
 //! The assertions in the constructor methods ensure that the tag getter matches
 //! our expectations.
 
-use super::{position::AnchorSide, Context, Length, Percentage, ToComputedValue};
+use super::position::{AnchorPosResolver, AnchorSide};
+use super::{Context, Length, Percentage, ToComputedValue};
 use crate::derives::*;
 #[cfg(feature = "gecko")]
-use crate::gecko_bindings::structs::{AnchorPosOffsetResolutionParams, GeckoFontMetrics};
+use crate::gecko_bindings::structs::GeckoFontMetrics;
 use crate::logical_geometry::{PhysicalAxis, PhysicalSide};
 use crate::values::animated::{
     Animate, Context as AnimatedContext, Procedure, ToAnimatedValue, ToAnimatedZero,
@@ -35,7 +36,6 @@ use crate::values::animated::{
 use crate::values::computed::position::TryTacticAdjustment;
 use crate::values::distance::{ComputeSquaredDistance, SquaredDistance};
 use crate::values::generics::calc::{CalcUnits, PositivePercentageBasis};
-#[cfg(feature = "gecko")]
 use crate::values::generics::length::AnchorResolutionResult;
 use crate::values::generics::position::GenericAnchorSide;
 use crate::values::generics::{calc, ClampToNonNegative, NonNegative};
@@ -49,7 +49,7 @@ use malloc_size_of::{MallocSizeOf, MallocSizeOfOps};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Write};
 use style_traits::values::specified::AllowedNumericType;
-use style_traits::{CssWriter, ToCss};
+use style_traits::{CssWriter, ToCss, ToTyped, TypedValue};
 
 #[doc(hidden)]
 #[derive(Clone, Copy)]
@@ -115,7 +115,6 @@ pub struct TagVariant {
 /// Also we need the union and the variants to be `pub` (even though the member
 /// is private) so that cbindgen generates it. They're not part of the public
 /// API otherwise.
-#[derive(ToTyped)]
 #[repr(transparent)]
 pub struct LengthPercentage(LengthPercentageUnion);
 
@@ -452,6 +451,22 @@ impl LengthPercentage {
         }
     }
 
+    /// Returns a copy of this value with all anchor functions resolved,
+    /// or `Err()` if any of them is invalid and has no fallback.
+    pub fn resolve_anchor(
+        &self,
+        allowed: AllowAnchorPosResolutionInCalcPercentage,
+        resolver: &impl AnchorPosResolver,
+    ) -> Result<Self, ()> {
+        match self.unpack() {
+            Unpacked::Calc(c) => {
+                let (node, clamping_mode) = c.resolve_anchor(allowed, resolver)?;
+                Ok(Self::new_calc(node, clamping_mode))
+            },
+            Unpacked::Length(..) | Unpacked::Percentage(..) => Ok(self.clone()),
+        }
+    }
+
     /// Resolves the percentage.
     #[inline]
     pub fn resolve(&self, basis: Length) -> Length {
@@ -647,6 +662,16 @@ impl ToCss for LengthPercentage {
     }
 }
 
+impl ToTyped for LengthPercentage {
+    fn to_typed(&self) -> Option<TypedValue> {
+        match self.unpack() {
+            Unpacked::Length(l) => l.to_typed(),
+            Unpacked::Percentage(p) => p.to_typed(),
+            Unpacked::Calc(c) => c.node.to_typed(),
+        }
+    }
+}
+
 impl Zero for LengthPercentage {
     fn zero() -> Self {
         LengthPercentage::new_length(Length::zero())
@@ -705,6 +730,7 @@ impl<'de> Deserialize<'de> for LengthPercentage {
 )]
 #[allow(missing_docs)]
 #[repr(u8)]
+#[typed_value(derive_fields)]
 pub enum CalcLengthPercentageLeaf {
     Length(Length),
     Percentage(Percentage),
@@ -938,18 +964,11 @@ pub enum AllowAnchorPosResolutionInCalcPercentage {
 }
 
 impl AllowAnchorPosResolutionInCalcPercentage {
-    #[cfg(feature = "gecko")]
     /// Get the `anchor-size()` resolution axis.
     pub fn to_axis(&self) -> PhysicalAxis {
         match self {
             Self::AnchorSizeOnly(axis) => *axis,
-            Self::Both(side) => {
-                if matches!(side, PhysicalSide::Top | PhysicalSide::Bottom) {
-                    PhysicalAxis::Vertical
-                } else {
-                    PhysicalAxis::Horizontal
-                }
-            },
+            Self::Both(side) => side.axis(),
         }
     }
 }
@@ -994,25 +1013,25 @@ impl CalcLengthPercentage {
     /// Return a clone of this node with all anchor functions computed and replaced with
     /// corresponding values, returning error if the resolution is invalid.
     #[inline]
-    #[cfg(feature = "gecko")]
     pub fn resolve_anchor(
         &self,
         allowed: AllowAnchorPosResolutionInCalcPercentage,
-        params: &AnchorPosOffsetResolutionParams,
+        params: &impl AnchorPosResolver,
     ) -> Result<(CalcNode, AllowedNumericType), ()> {
-        use crate::values::{
-            computed::{length::resolve_anchor_size, AnchorFunction},
-            generics::{length::GenericAnchorSizeFunction, position::GenericAnchorFunction},
+        use crate::values::generics::{
+            length::GenericAnchorSizeFunction, position::GenericAnchorFunction,
         };
 
         fn resolve_anchor_function<'a>(
             f: &'a GenericAnchorFunction<Box<CalcNode>, Box<CalcNode>>,
             side: PhysicalSide,
-            params: &AnchorPosOffsetResolutionParams,
+            params: &impl AnchorPosResolver,
         ) -> AnchorResolutionResult<'a, Box<CalcNode>> {
             let anchor_side: &CalcAnchorSide = &f.side;
-            let resolved = if f.valid_for(side, params.mBaseParams.mPosition) {
-                AnchorFunction::resolve(&f.target_element, &anchor_side.into(), side, params).ok()
+            let resolved = if f.valid_for(side, params.position_property()) {
+                params
+                    .resolve_anchor(&f.target_element, &anchor_side.into(), side)
+                    .ok()
             } else {
                 None
             };
@@ -1046,11 +1065,13 @@ impl CalcLengthPercentage {
         fn resolve_anchor_size_function<'a>(
             f: &'a GenericAnchorSizeFunction<Box<CalcNode>>,
             allowed: AllowAnchorPosResolutionInCalcPercentage,
-            params: &AnchorPosOffsetResolutionParams,
+            params: &impl AnchorPosResolver,
         ) -> AnchorResolutionResult<'a, Box<CalcNode>> {
             let axis = allowed.to_axis();
-            let resolved = if f.valid_for(params.mBaseParams.mPosition) {
-                resolve_anchor_size(&f.target_element, axis, f.size, &params.mBaseParams).ok()
+            let resolved = if f.valid_for(params.position_property()) {
+                params
+                    .resolve_anchor_size(&f.target_element, axis, f.size)
+                    .ok()
             } else {
                 None
             };
@@ -1079,7 +1100,7 @@ impl CalcLengthPercentage {
         fn resolve_anchor_functions(
             node: &CalcNode,
             allowed: AllowAnchorPosResolutionInCalcPercentage,
-            params: &AnchorPosOffsetResolutionParams,
+            params: &impl AnchorPosResolver,
         ) -> Result<Option<CalcNode>, ()> {
             let resolution = match node {
                 CalcNode::Anchor(f) => {
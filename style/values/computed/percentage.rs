@@ -10,7 +10,7 @@ use crate::values::specified::percentage::ToPercentage;
 use crate::values::{serialize_normalized_percentage, CSSFloat};
 use crate::Zero;
 use std::fmt;
-use style_traits::{CssWriter, ToCss};
+use style_traits::{CssWriter, NumericValue, ToCss, ToTyped, TypedValue};
 
 /// A computed percentage.
 #[derive(
@@ -31,11 +31,16 @@ use style_traits::{CssWriter, ToCss};
     ToComputedValue,
     ToResolvedValue,
     ToShmem,
-    ToTyped,
 )]
 #[repr(C)]
 pub struct Percentage(pub CSSFloat);
 
+impl ToTyped for Percentage {
+    fn to_typed(&self) -> Option<TypedValue> {
+        Some(TypedValue::Numeric(NumericValue::percentage(self.0)))
+    }
+}
+
 impl ClampToNonNegative for Percentage {
     #[inline]
     fn clamp_to_non_negative(self) -> Self {
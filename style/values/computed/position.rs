@@ -7,17 +7,19 @@
 //!
 //! [position]: https://drafts.csswg.org/css-backgrounds-3/#position
 
-use crate::logical_geometry::PhysicalSide;
+use crate::logical_geometry::{PhysicalAxis, PhysicalSide};
+use crate::values::computed::length::resolve_anchor_size_function;
+use crate::values::computed::length_percentage::AllowAnchorPosResolutionInCalcPercentage;
 use crate::values::computed::{
-    Context, Integer, LengthPercentage, NonNegativeNumber, Percentage, ToComputedValue,
+    Context, Integer, Length, LengthPercentage, NonNegativeNumber, Percentage, PositionProperty,
+    ToComputedValue,
 };
 use crate::values::generics;
-#[cfg(feature = "gecko")]
-use crate::values::generics::position::TreeScoped;
+use crate::values::generics::length::AnchorSizeKeyword;
 use crate::values::generics::position::{
     AnchorSideKeyword, AspectRatio as GenericAspectRatio, GenericAnchorFunction, GenericAnchorSide,
     GenericInset, Position as GenericPosition, PositionComponent as GenericPositionComponent,
-    PositionOrAuto as GenericPositionOrAuto, ZIndex as GenericZIndex,
+    PositionOrAuto as GenericPositionOrAuto, TreeScoped, ZIndex as GenericZIndex,
 };
 pub use crate::values::specified::position::{
     AnchorName, AnchorScope, DashedIdentAndOrTryTactic, GridAutoFlow, GridTemplateAreas,
@@ -25,6 +27,7 @@ pub use crate::values::specified::position::{
     PositionAreaType, PositionTryFallbacks, PositionTryFallbacksTryTactic,
     PositionTryFallbacksTryTacticKeyword, PositionTryOrder, PositionVisibility,
 };
+use crate::values::DashedIdent;
 use crate::Zero;
 use std::fmt::{self, Write};
 use style_traits::{CssWriter, ToCss};
@@ -64,10 +67,67 @@ impl AnchorSide {
 pub type AnchorFunction = GenericAnchorFunction<Percentage, Inset>;
 
 #[cfg(feature = "gecko")]
-use crate::{
-    gecko_bindings::structs::AnchorPosOffsetResolutionParams,
-    values::{computed::Length, DashedIdent},
-};
+use crate::gecko_bindings::structs::AnchorPosOffsetResolutionParams;
+
+/// Resolves `anchor()` and `anchor-size()` functions against the anchor boxes
+/// of an absolutely positioned element, once layout knows where they are.
+///
+/// https://drafts.csswg.org/css-anchor-position-1/#anchor-pos
+pub trait AnchorPosResolver {
+    /// The `position` of the element whose properties are being resolved.
+    fn position_property(&self) -> PositionProperty;
+
+    /// Returns the offset of `anchor_side` of the anchor box referenced by
+    /// `anchor_name` (or of the default anchor, if the name is empty), relative
+    /// to the edge of the containing block that `prop_side` refers to.
+    /// Returns `Err()` if there's no acceptable anchor box.
+    fn resolve_anchor(
+        &self,
+        anchor_name: &TreeScoped<DashedIdent>,
+        anchor_side: &AnchorSide,
+        prop_side: PhysicalSide,
+    ) -> Result<Length, ()>;
+
+    /// Returns the given dimension of the anchor box referenced by
+    /// `anchor_name`, or the one in `prop_axis` if `anchor_size_keyword` is
+    /// `None`. Returns `Err()` if there's no acceptable anchor box.
+    fn resolve_anchor_size(
+        &self,
+        anchor_name: &TreeScoped<DashedIdent>,
+        prop_axis: PhysicalAxis,
+        anchor_size_keyword: AnchorSizeKeyword,
+    ) -> Result<Length, ()>;
+}
+
+#[cfg(feature = "gecko")]
+impl AnchorPosResolver for AnchorPosOffsetResolutionParams {
+    fn position_property(&self) -> PositionProperty {
+        self.mBaseParams.mPosition
+    }
+
+    fn resolve_anchor(
+        &self,
+        anchor_name: &TreeScoped<DashedIdent>,
+        anchor_side: &AnchorSide,
+        prop_side: PhysicalSide,
+    ) -> Result<Length, ()> {
+        AnchorFunction::resolve(anchor_name, anchor_side, prop_side, self)
+    }
+
+    fn resolve_anchor_size(
+        &self,
+        anchor_name: &TreeScoped<DashedIdent>,
+        prop_axis: PhysicalAxis,
+        anchor_size_keyword: AnchorSizeKeyword,
+    ) -> Result<Length, ()> {
+        super::length::resolve_anchor_size(
+            anchor_name,
+            prop_axis,
+            anchor_size_keyword,
+            &self.mBaseParams,
+        )
+    }
+}
 
 impl AnchorFunction {
     /// Resolve the anchor function with the given resolver. Returns `Err()` if no anchor is found.
@@ -144,6 +204,53 @@ impl<Percentage: TryTacticAdjustment, Fallback: TryTacticAdjustment> TryTacticAd
 
 /// A computed type for `inset` properties.
 pub type Inset = GenericInset<Percentage, LengthPercentage>;
+
+impl Inset {
+    /// Returns a copy of this value with all anchor functions resolved for
+    /// the given side, falling back to `auto` if any of them is invalid and
+    /// has no fallback.
+    pub fn resolve_anchor(&self, side: PhysicalSide, resolver: &impl AnchorPosResolver) -> Self {
+        self.try_resolve_anchor(side, resolver)
+            .unwrap_or(Self::Auto)
+    }
+
+    fn try_resolve_anchor(
+        &self,
+        side: PhysicalSide,
+        resolver: &impl AnchorPosResolver,
+    ) -> Result<Self, ()> {
+        Ok(match self {
+            Self::Auto | Self::LengthPercentage(..) => self.clone(),
+            Self::AnchorFunction(f) => {
+                let resolved = if f.valid_for(side, resolver.position_property()) {
+                    resolver.resolve_anchor(&f.target_element, &f.side, side)
+                } else {
+                    Err(())
+                };
+                match resolved {
+                    Ok(length) => Self::LengthPercentage(LengthPercentage::new_length(length)),
+                    Err(()) => f
+                        .fallback
+                        .as_ref()
+                        .ok_or(())?
+                        .try_resolve_anchor(side, resolver)?,
+                }
+            },
+            Self::AnchorSizeFunction(f) => resolve_anchor_size_function(
+                f,
+                side.axis(),
+                resolver,
+                |length| Self::LengthPercentage(LengthPercentage::new_length(length)),
+                |fallback| fallback.try_resolve_anchor(side, resolver),
+            )?,
+            Self::AnchorContainingCalcFunction(lp) => Self::LengthPercentage(lp.resolve_anchor(
+                AllowAnchorPosResolutionInCalcPercentage::Both(side),
+                resolver,
+            )?),
+        })
+    }
+}
+
 impl TryTacticAdjustment for Inset {
     // https://drafts.csswg.org/css-anchor-position-1/#swap-due-to-a-try-tactic:
     //
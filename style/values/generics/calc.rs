@@ -1950,34 +1950,75 @@ impl<L: CalcNodeLeaf> CalcNode<L> {
     }
 
     fn to_typed_impl(&self, level: ArgumentLevel) -> Option<TypedValue> {
-        // XXX Only supporting Sum and Leaf for now
-        match *self {
+        fn numeric<L: CalcNodeLeaf>(node: &CalcNode<L>) -> Option<NumericValue> {
+            match node.to_typed_impl(ArgumentLevel::Nested)? {
+                TypedValue::Numeric(inner) => Some(inner),
+                _ => None,
+            }
+        }
+
+        fn numeric_list<L: CalcNodeLeaf>(nodes: &[CalcNode<L>]) -> Option<ThinVec<NumericValue>> {
+            nodes.iter().map(numeric).collect()
+        }
+
+        // XXX round(), mod(), rem(), hypot(), abs(), sign() and the anchor
+        // functions aren't supported yet.
+        let value = match *self {
             Self::Sum(ref children) => {
-                let mut values = ThinVec::new();
-                for child in &**children {
-                    if let Some(TypedValue::Numeric(inner)) =
-                        child.to_typed_impl(ArgumentLevel::Nested)
-                    {
-                        values.push(inner);
-                    }
+                let mut values = ThinVec::with_capacity(children.len());
+                for (i, child) in children.iter().enumerate() {
+                    // Like in to_css(), subtracted terms are negated leaves.
+                    let value = match *child {
+                        Self::Leaf(ref l) if i != 0 && l.is_negative() == Ok(true) => {
+                            let mut negated = l.clone();
+                            negated.map(std::ops::Neg::neg).ok()?;
+                            NumericValue::Negate {
+                                value: Box::new(numeric(&Self::Leaf(negated))?),
+                            }
+                        },
+                        _ => numeric(child)?,
+                    };
+                    values.push(value);
                 }
-                Some(TypedValue::Numeric(NumericValue::Sum { values }))
+                NumericValue::Sum { values }
             },
-            Self::Leaf(ref l) => match l.to_typed() {
-                Some(TypedValue::Numeric(inner)) => match level {
-                    ArgumentLevel::CalculationRoot => {
-                        Some(TypedValue::Numeric(NumericValue::Sum {
-                            values: ThinVec::from([inner]),
-                        }))
-                    },
-                    ArgumentLevel::ArgumentRoot | ArgumentLevel::Nested => {
-                        Some(TypedValue::Numeric(inner))
+            Self::Product(ref children) => NumericValue::Product {
+                values: numeric_list(children)?,
+            },
+            Self::Negate(ref child) => NumericValue::Negate {
+                value: Box::new(numeric(child)?),
+            },
+            Self::Invert(ref child) => NumericValue::Invert {
+                value: Box::new(numeric(child)?),
+            },
+            Self::MinMax(ref children, op) => {
+                let values = numeric_list(children)?;
+                match op {
+                    MinMaxOp::Min => NumericValue::Min { values },
+                    MinMaxOp::Max => NumericValue::Max { values },
+                }
+            },
+            Self::Clamp {
+                ref min,
+                ref center,
+                ref max,
+            } => NumericValue::Clamp {
+                lower: Box::new(numeric(min)?),
+                value: Box::new(numeric(center)?),
+                upper: Box::new(numeric(max)?),
+            },
+            Self::Leaf(ref l) => match l.to_typed()? {
+                TypedValue::Numeric(inner) => match level {
+                    ArgumentLevel::CalculationRoot => NumericValue::Sum {
+                        values: ThinVec::from([inner]),
                     },
+                    ArgumentLevel::ArgumentRoot | ArgumentLevel::Nested => inner,
                 },
-                _ => None,
+                _ => return None,
             },
-            _ => None,
-        }
+            _ => return None,
+        };
+        Some(TypedValue::Numeric(value))
     }
 
     fn compare(
@@ -17,16 +17,21 @@ use style_traits::{owned_slice::OwnedSlice, CssWriter, ToCss};
 /// the current foreground color (currentcolor keyword).
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, ToAnimatedValue, ToShmem, ToTyped)]
 #[repr(C)]
+#[typed_value(derive_fields)]
 pub enum GenericColor<Percentage> {
     /// The actual numeric color.
     Absolute(AbsoluteColor),
     /// A unresolvable color.
+    #[typed_value(todo)]
     ColorFunction(Box<ColorFunction<Self>>),
     /// The `CurrentColor` keyword.
+    #[css(keyword = "currentcolor")]
     CurrentColor,
     /// The color-mix() function.
+    #[typed_value(todo)]
     ColorMix(Box<GenericColorMix<Self, Percentage>>),
     /// The contrast-color() function.
+    #[typed_value(todo)]
     ContrastColor(Box<Self>),
 }
 
@@ -219,6 +224,7 @@ impl<Percentage> Color<Percentage> {
     ToTyped,
 )]
 #[repr(C, u8)]
+#[typed_value(derive_fields)]
 pub enum GenericColorOrAuto<C> {
     /// A `<color>`.
     Color(C),
@@ -247,6 +253,7 @@ pub use self::GenericColorOrAuto as ColorOrAuto;
     ToTyped,
 )]
 #[repr(transparent)]
+#[typed_value(derive_fields)]
 pub struct GenericCaretColor<C>(pub GenericColorOrAuto<C>);
 
 impl<C> GenericCaretColor<C> {
@@ -4,9 +4,6 @@
 
 //! Generic types for counters-related CSS values.
 
-#[cfg(feature = "servo")]
-use crate::computed_values::list_style_type::T as ListStyleType;
-#[cfg(feature = "gecko")]
 use crate::counter_style::CounterStyle;
 use crate::derives::*;
 use crate::values::specified::Attr;
@@ -189,21 +186,8 @@ pub struct GenericCounters<I>(
 );
 pub use self::GenericCounters as Counters;
 
-#[cfg(feature = "servo")]
-type CounterStyleType = ListStyleType;
-
-#[cfg(feature = "gecko")]
-type CounterStyleType = CounterStyle;
-
-#[cfg(feature = "servo")]
-#[inline]
-fn is_decimal(counter_type: &CounterStyleType) -> bool {
-    *counter_type == ListStyleType::Decimal
-}
-
-#[cfg(feature = "gecko")]
 #[inline]
-fn is_decimal(counter_type: &CounterStyleType) -> bool {
+fn is_decimal(counter_type: &CounterStyle) -> bool {
     *counter_type == CounterStyle::decimal()
 }
 
@@ -302,13 +286,13 @@ pub enum GenericContentItem<I> {
     String(crate::OwnedStr),
     /// `counter(name, style)`.
     #[css(comma, function)]
-    Counter(CustomIdent, #[css(skip_if = "is_decimal")] CounterStyleType),
+    Counter(CustomIdent, #[css(skip_if = "is_decimal")] CounterStyle),
     /// `counters(name, separator, style)`.
     #[css(comma, function)]
     Counters(
         CustomIdent,
         crate::OwnedStr,
-        #[css(skip_if = "is_decimal")] CounterStyleType,
+        #[css(skip_if = "is_decimal")] CounterStyle,
     ),
     /// `open-quote`.
     OpenQuote,
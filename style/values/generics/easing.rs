@@ -134,6 +134,12 @@ impl<Integer, Number, LinearStops> TimingFunction<Integer, Number, LinearStops>
         TimingFunction::Keyword(TimingKeyword::Ease)
     }
 
+    /// `linear`
+    #[inline]
+    pub fn linear() -> Self {
+        TimingFunction::Keyword(TimingKeyword::Linear)
+    }
+
     /// Returns true if it is `ease`.
     #[inline]
     pub fn is_ease(&self) -> bool {
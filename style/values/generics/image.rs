@@ -26,15 +26,18 @@ pub enum GenericImage<G, ImageUrl, Color, Percentage, Resolution> {
     None,
 
     /// A `<url()>` image.
+    #[typed_value(image)]
     Url(ImageUrl),
 
     /// A `<gradient>` image.  Gradients are rather large, and not nearly as
     /// common as urls, so we box them here to keep the size of this enum sane.
+    #[typed_value(image)]
     Gradient(Box<G>),
 
     /// A `-moz-element(# <element-id>)`
     #[cfg(feature = "gecko")]
     #[css(function = "-moz-element")]
+    #[typed_value(image)]
     Element(Atom),
 
     /// A `-moz-symbolic-icon(<icon-id>)`
@@ -47,20 +50,24 @@ pub enum GenericImage<G, ImageUrl, Color, Percentage, Resolution> {
     /// A paint worklet image.
     /// <https://drafts.css-houdini.org/css-paint-api/>
     #[cfg(feature = "servo")]
+    #[typed_value(image)]
     PaintWorklet(Box<PaintWorklet>),
 
     /// A `<cross-fade()>` image. Storing this directly inside of
     /// GenericImage increases the size by 8 bytes so we box it here
     /// and store images directly inside of cross-fade instead of
     /// boxing them there.
+    #[typed_value(image)]
     CrossFade(Box<GenericCrossFade<Self, Color, Percentage>>),
 
     /// An `image-set()` function.
+    #[typed_value(image)]
     ImageSet(Box<GenericImageSet<Self, Resolution>>),
 
     /// A `light-dark()` function.
     /// NOTE(emilio): #[css(skip)] only affects SpecifiedValueInfo. Remove or make conditional
     /// if/when shipping light-dark() for content.
+    #[typed_value(todo)]
     LightDark(#[css(skip)] Box<GenericLightDark<Self>>),
 }
 
@@ -15,7 +15,10 @@ use crate::{Zero, ZeroNoPercent};
 use euclid::default::{Rect, Transform3D};
 use std::fmt::{self, Write};
 use std::ops::Neg;
-use style_traits::{CssWriter, ToCss};
+use style_traits::{
+    CssWriter, NumericValue, NumericValueOrNone, ToCss, ToTyped, TransformComponent, TypedValue,
+};
+use thin_vec::ThinVec;
 
 /// A generic 2D transformation matrix.
 #[allow(missing_docs)]
@@ -327,11 +330,188 @@ pub use self::GenericTransformOperation as TransformOperation;
     ToTyped,
 )]
 #[repr(C)]
+#[typed_value(transform_list)]
 /// A value of the `transform` property
 pub struct GenericTransform<T>(#[css(if_empty = "none", iterable)] pub crate::OwnedSlice<T>);
 
 pub use self::GenericTransform as Transform;
 
+fn to_numeric<T: ToTyped>(value: &T) -> Option<NumericValue> {
+    match value.to_typed()? {
+        TypedValue::Numeric(numeric) => Some(numeric),
+        _ => None,
+    }
+}
+
+fn to_number<T: ToTyped>(value: &T) -> Option<f32> {
+    to_numeric(value)?.as_number()
+}
+
+impl<Angle, Number, Length, Integer, LengthPercentage> ToTyped
+    for TransformOperation<Angle, Number, Length, Integer, LengthPercentage>
+where
+    Angle: Zero + ToTyped,
+    Number: PartialEq + ToTyped,
+    Length: ToTyped,
+    LengthPercentage: Zero + ZeroNoPercent + ToTyped,
+{
+    /// https://drafts.css-houdini.org/css-typed-om-1/#reify-a-transform-list
+    fn to_typed(&self) -> Option<TypedValue> {
+        use self::TransformOperation::*;
+
+        let zero = || NumericValue::px(0.);
+        let one = || NumericValue::number(1.);
+        let axis = |x: f32, y: f32, z: f32| {
+            (
+                NumericValue::number(x),
+                NumericValue::number(y),
+                NumericValue::number(z),
+            )
+        };
+
+        let component = match *self {
+            Matrix(ref m) => TransformComponent::Matrix {
+                #[cfg_attr(rustfmt, rustfmt_skip)]
+                matrix: [
+                    to_number(&m.a)?, to_number(&m.b)?, 0., 0.,
+                    to_number(&m.c)?, to_number(&m.d)?, 0., 0.,
+                    0.,               0.,               1., 0.,
+                    to_number(&m.e)?, to_number(&m.f)?, 0., 1.,
+                ],
+                is_2d: true,
+            },
+            Matrix3D(ref m) => TransformComponent::Matrix {
+                #[cfg_attr(rustfmt, rustfmt_skip)]
+                matrix: [
+                    to_number(&m.m11)?, to_number(&m.m12)?, to_number(&m.m13)?, to_number(&m.m14)?,
+                    to_number(&m.m21)?, to_number(&m.m22)?, to_number(&m.m23)?, to_number(&m.m24)?,
+                    to_number(&m.m31)?, to_number(&m.m32)?, to_number(&m.m33)?, to_number(&m.m34)?,
+                    to_number(&m.m41)?, to_number(&m.m42)?, to_number(&m.m43)?, to_number(&m.m44)?,
+                ],
+                is_2d: false,
+            },
+            Skew(ref ax, ref ay) => TransformComponent::Skew {
+                ax: to_numeric(ax)?,
+                ay: to_numeric(ay)?,
+            },
+            SkewX(ref ax) => TransformComponent::SkewX {
+                ax: to_numeric(ax)?,
+            },
+            SkewY(ref ay) => TransformComponent::SkewY {
+                ay: to_numeric(ay)?,
+            },
+            Translate(ref x, ref y) => TransformComponent::Translate {
+                x: to_numeric(x)?,
+                y: to_numeric(y)?,
+                z: zero(),
+                is_2d: true,
+            },
+            TranslateX(ref x) => TransformComponent::Translate {
+                x: to_numeric(x)?,
+                y: zero(),
+                z: zero(),
+                is_2d: true,
+            },
+            TranslateY(ref y) => TransformComponent::Translate {
+                x: zero(),
+                y: to_numeric(y)?,
+                z: zero(),
+                is_2d: true,
+            },
+            TranslateZ(ref z) => TransformComponent::Translate {
+                x: zero(),
+                y: zero(),
+                z: to_numeric(z)?,
+                is_2d: false,
+            },
+            Translate3D(ref x, ref y, ref z) => TransformComponent::Translate {
+                x: to_numeric(x)?,
+                y: to_numeric(y)?,
+                z: to_numeric(z)?,
+                is_2d: false,
+            },
+            Scale(ref x, ref y) => TransformComponent::Scale {
+                x: to_numeric(x)?,
+                y: to_numeric(y)?,
+                z: one(),
+                is_2d: true,
+            },
+            ScaleX(ref x) => TransformComponent::Scale {
+                x: to_numeric(x)?,
+                y: one(),
+                z: one(),
+                is_2d: true,
+            },
+            ScaleY(ref y) => TransformComponent::Scale {
+                x: one(),
+                y: to_numeric(y)?,
+                z: one(),
+                is_2d: true,
+            },
+            ScaleZ(ref z) => TransformComponent::Scale {
+                x: one(),
+                y: one(),
+                z: to_numeric(z)?,
+                is_2d: false,
+            },
+            Scale3D(ref x, ref y, ref z) => TransformComponent::Scale {
+                x: to_numeric(x)?,
+                y: to_numeric(y)?,
+                z: to_numeric(z)?,
+                is_2d: false,
+            },
+            Rotate(ref angle) | RotateZ(ref angle) => {
+                let (x, y, z) = axis(0., 0., 1.);
+                TransformComponent::Rotate {
+                    x,
+                    y,
+                    z,
+                    angle: to_numeric(angle)?,
+                    is_2d: matches!(*self, Rotate(..)),
+                }
+            },
+            RotateX(ref angle) => {
+                let (x, y, z) = axis(1., 0., 0.);
+                TransformComponent::Rotate {
+                    x,
+                    y,
+                    z,
+                    angle: to_numeric(angle)?,
+                    is_2d: false,
+                }
+            },
+            RotateY(ref angle) => {
+                let (x, y, z) = axis(0., 1., 0.);
+                TransformComponent::Rotate {
+                    x,
+                    y,
+                    z,
+                    angle: to_numeric(angle)?,
+                    is_2d: false,
+                }
+            },
+            Rotate3D(ref x, ref y, ref z, ref angle) => TransformComponent::Rotate {
+                x: to_numeric(x)?,
+                y: to_numeric(y)?,
+                z: to_numeric(z)?,
+                angle: to_numeric(angle)?,
+                is_2d: false,
+            },
+            Perspective(ref p) => TransformComponent::Perspective {
+                length: match *p {
+                    PerspectiveFunction::None => NumericValueOrNone::None,
+                    PerspectiveFunction::Length(ref l) => {
+                        NumericValueOrNone::Numeric(to_numeric(l)?)
+                    },
+                },
+            },
+            // These only exist in the middle of animations.
+            InterpolateMatrix { .. } | AccumulateMatrix { .. } => return None,
+        };
+        Some(TypedValue::Transform(ThinVec::from([component])))
+    }
+}
+
 impl<Angle, Number, Length, Integer, LengthPercentage>
     TransformOperation<Angle, Number, Length, Integer, LengthPercentage>
 where
@@ -695,7 +695,6 @@ impl KeyframesName {
     }
 
     /// Create a new KeyframesName from Atom.
-    #[cfg(feature = "gecko")]
     pub fn from_atom(atom: Atom) -> Self {
         Self(atom)
     }
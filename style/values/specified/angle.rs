@@ -5,7 +5,7 @@
 //! Specified angles.
 
 use crate::derives::*;
-use crate::parser::{Parse, ParserContext};
+use crate::parser::{FromTypedError, Parse, ParserContext};
 use crate::values::computed::angle::Angle as ComputedAngle;
 use crate::values::computed::{Context, ToComputedValue};
 use crate::values::specified::calc::CalcNode;
@@ -15,7 +15,9 @@ use cssparser::{match_ignore_ascii_case, Parser, Token};
 use std::f32::consts::PI;
 use std::fmt::{self, Write};
 use std::ops::Neg;
-use style_traits::{CssWriter, ParseError, SpecifiedValueInfo, ToCss};
+use style_traits::{
+    CssString, CssWriter, NumericValue, ParseError, SpecifiedValueInfo, ToCss, ToTyped, TypedValue,
+};
 
 /// A specified angle dimension.
 #[cfg_attr(feature = "servo", derive(Deserialize, Serialize))]
@@ -116,6 +118,14 @@ impl ToCss for Angle {
     }
 }
 
+impl ToTyped for Angle {
+    fn to_typed(&self) -> Option<TypedValue> {
+        let value = self.value.unitless_value();
+        let unit = CssString::from(self.value.unit());
+        Some(TypedValue::Numeric(NumericValue::Unit { value, unit }))
+    }
+}
+
 impl ToComputedValue for Angle {
     type ComputedValue = ComputedAngle;
 
@@ -215,6 +225,13 @@ impl Parse for Angle {
     ) -> Result<Self, ParseError<'i>> {
         Self::parse_internal(context, input, AllowUnitlessZeroAngle::No)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        match *value {
+            TypedValue::Numeric(ref value) => Self::from_numeric(context, value),
+            _ => Err(FromTypedError::Unsupported),
+        }
+    }
 }
 
 impl Angle {
@@ -231,6 +248,24 @@ impl Angle {
         Ok(Self { value, was_calc })
     }
 
+    /// Builds an angle out of a typed `CSSNumericValue`, see
+    /// `Parse::from_typed`.
+    pub(super) fn from_numeric(
+        context: &ParserContext,
+        value: &NumericValue,
+    ) -> Result<Self, FromTypedError> {
+        match *value {
+            NumericValue::Unit { value, .. } if !value.is_finite() => {
+                Err(FromTypedError::TypeMismatch)
+            },
+            NumericValue::Unit { value, ref unit } => {
+                Self::parse_dimension(value, unit, /* was_calc = */ false)
+                    .map_err(|()| FromTypedError::Unsupported)
+            },
+            _ => CalcNode::angle_from_numeric(context, value),
+        }
+    }
+
     /// Parse an `<angle>` allowing unitless zero to represent a zero angle.
     ///
     /// See the comment in `AllowUnitlessZeroAngle` for why.
@@ -587,6 +587,11 @@ impl TimelineName {
     pub fn is_none(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns the name of the timeline, which is empty for `none`.
+    pub fn as_atom(&self) -> &Atom {
+        &self.0 .0
+    }
 }
 
 impl Parse for TimelineName {
@@ -615,6 +620,68 @@ impl ToCss for TimelineName {
     }
 }
 
+/// The `timeline-scope` property: `none | all | <dashed-ident>#`.
+///
+/// https://drafts.csswg.org/scroll-animations-1/#timeline-scope
+#[derive(
+    Clone,
+    Debug,
+    MallocSizeOf,
+    PartialEq,
+    SpecifiedValueInfo,
+    ToComputedValue,
+    ToCss,
+    ToResolvedValue,
+    ToShmem,
+    ToTyped,
+)]
+#[repr(u8)]
+pub enum TimelineScope {
+    /// `none`
+    None,
+    /// `all`
+    All,
+    /// `<dashed-ident>#`
+    #[css(comma)]
+    Names(
+        #[css(iterable)]
+        #[ignore_malloc_size_of = "Arc"]
+        crate::ArcSlice<DashedIdent>,
+    ),
+}
+
+impl TimelineScope {
+    /// Returns the `none` value.
+    pub fn none() -> Self {
+        Self::None
+    }
+
+    /// Whether this scope includes the timeline with the given name.
+    pub fn contains(&self, name: &Atom) -> bool {
+        match *self {
+            Self::None => false,
+            Self::All => true,
+            Self::Names(ref names) => names.iter().any(|ident| ident.0 == *name),
+        }
+    }
+}
+
+impl Parse for TimelineScope {
+    fn parse<'i, 't>(
+        context: &ParserContext,
+        input: &mut Parser<'i, 't>,
+    ) -> Result<Self, ParseError<'i>> {
+        if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+            return Ok(Self::None);
+        }
+        if input.try_parse(|i| i.expect_ident_matching("all")).is_ok() {
+            return Ok(Self::All);
+        }
+        let names = input.parse_comma_separated(|i| DashedIdent::parse(context, i))?;
+        Ok(Self::Names(crate::ArcSlice::from_iter(names.into_iter())))
+    }
+}
+
 /// A specified value for the `animation-timeline` property.
 pub type AnimationTimeline = generics::GenericAnimationTimeline<LengthPercentage>;
 
@@ -782,6 +849,10 @@ impl ViewTransitionClass {
     pub fn is_none(&self) -> bool {
         self.0.is_empty()
     }
+    /// Returns an iterator over the class names.
+    pub fn iter(&self) -> impl Iterator<Item = &Atom> {
+        self.0.iter().map(|class| &class.0)
+    }
 }
 
 impl Parse for ViewTransitionClass {
@@ -8,7 +8,7 @@
 
 use crate::color::parsing::ChannelKeyword;
 use crate::derives::*;
-use crate::parser::{Parse, ParserContext};
+use crate::parser::{FromTypedError, Parse, ParserContext};
 use crate::values::generics::calc::{
     self as generic, CalcNodeLeaf, CalcUnits, MinMaxOp, ModRemOp, PositivePercentageBasis,
     RoundingStrategy, SortKey,
@@ -28,7 +28,8 @@ use std::cmp;
 use std::fmt::{self, Write};
 use style_traits::values::specified::AllowedNumericType;
 use style_traits::{
-    CssWriter, ParseError, SpecifiedValueInfo, StyleParseErrorKind, ToCss, ToTyped, TypedValue,
+    CssWriter, NumericValue, ParseError, SpecifiedValueInfo, StyleParseErrorKind, ToCss, ToTyped,
+    TypedValue,
 };
 
 /// The name of the mathematical function that we're parsing.
@@ -126,10 +127,14 @@ impl ToCss for Leaf {
 
 impl ToTyped for Leaf {
     fn to_typed(&self) -> Option<TypedValue> {
-        // XXX Only supporting Length for now
         match *self {
             Self::Length(ref l) => l.to_typed(),
-            _ => None,
+            Self::Angle(ref a) => a.to_typed(),
+            Self::Time(ref t) => t.to_typed(),
+            Self::Percentage(p) => Some(TypedValue::Numeric(NumericValue::percentage(p))),
+            Self::Number(n) => Some(TypedValue::Numeric(NumericValue::number(n))),
+            // XXX Resolution and color channel keywords aren't supported yet.
+            Self::Resolution(..) | Self::ColorComponent(..) => None,
         }
     }
 }
@@ -1107,6 +1112,161 @@ impl CalcNode {
         closure().map_err(|()| input.new_custom_error(StyleParseErrorKind::UnspecifiedError))
     }
 
+    /// Builds an expression out of a typed `CSSNumericValue`, like `parse`
+    /// does out of a math function.
+    pub fn from_numeric(
+        context: &ParserContext,
+        value: &NumericValue,
+        allowed: CalcUnits,
+    ) -> Result<Self, FromTypedError> {
+        let nodes = |values: &[NumericValue]| {
+            values
+                .iter()
+                .map(|value| Self::from_numeric(context, value, allowed))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(match *value {
+            NumericValue::Unit { value, ref unit } => {
+                if value.is_nan() {
+                    return Err(FromTypedError::TypeMismatch);
+                }
+                let leaf = if unit == "number" {
+                    Leaf::Number(value)
+                } else if unit == "percent" && allowed.intersects(CalcUnits::PERCENTAGE) {
+                    Leaf::Percentage(value / 100.)
+                } else if let Some(l) = allowed
+                    .intersects(CalcUnits::LENGTH)
+                    .then(|| NoCalcLength::parse_dimension(context, value, unit).ok())
+                    .flatten()
+                {
+                    Leaf::Length(l)
+                } else if let Some(a) = allowed
+                    .intersects(CalcUnits::ANGLE)
+                    .then(|| Angle::parse_dimension(value, unit, /* from_calc = */ true).ok())
+                    .flatten()
+                {
+                    Leaf::Angle(a)
+                } else if let Some(t) = allowed
+                    .intersects(CalcUnits::TIME)
+                    .then(|| Time::parse_dimension(value, unit).ok())
+                    .flatten()
+                {
+                    Leaf::Time(t)
+                } else {
+                    return Err(FromTypedError::TypeMismatch);
+                };
+                Self::Leaf(leaf)
+            },
+            NumericValue::Sum { ref values } => {
+                let mut sum = SmallVec::<[CalcNode; 1]>::new();
+                for node in nodes(values)? {
+                    let merged = sum
+                        .last_mut()
+                        .is_some_and(|last| last.try_sum_in_place(&node).is_ok());
+                    if !merged {
+                        sum.push(node);
+                    }
+                }
+                match sum.len() {
+                    0 => return Err(FromTypedError::TypeMismatch),
+                    1 => sum.pop().unwrap(),
+                    _ => Self::Sum(sum.into_boxed_slice().into()),
+                }
+            },
+            NumericValue::Product { ref values } => {
+                let mut product = SmallVec::<[CalcNode; 1]>::new();
+                for mut node in nodes(values)? {
+                    let merged = product
+                        .last_mut()
+                        .is_some_and(|last| last.try_product_in_place(&mut node));
+                    if !merged {
+                        product.push(node);
+                    }
+                }
+                match product.len() {
+                    0 => return Err(FromTypedError::TypeMismatch),
+                    1 => product.pop().unwrap(),
+                    _ => Self::Product(product.into_boxed_slice().into()),
+                }
+            },
+            NumericValue::Negate { ref value } => {
+                let mut node = Self::from_numeric(context, value, allowed)?;
+                node.negate();
+                node
+            },
+            NumericValue::Invert { ref value } => {
+                // Only numbers can be divisors.
+                let node = Self::from_numeric(context, value, allowed)?;
+                if node.unit() != Ok(CalcUnits::empty()) {
+                    return Err(FromTypedError::TypeMismatch);
+                }
+                Self::Invert(Box::new(node))
+            },
+            NumericValue::Min { ref values } | NumericValue::Max { ref values } => {
+                let op = match *value {
+                    NumericValue::Min { .. } => MinMaxOp::Min,
+                    _ => MinMaxOp::Max,
+                };
+                let arguments = nodes(values)?;
+                if arguments.is_empty() {
+                    return Err(FromTypedError::TypeMismatch);
+                }
+                Self::MinMax(arguments.into(), op)
+            },
+            NumericValue::Clamp {
+                ref lower,
+                ref value,
+                ref upper,
+            } => Self::Clamp {
+                min: Box::new(Self::from_numeric(context, lower, allowed)?),
+                center: Box::new(Self::from_numeric(context, value, allowed)?),
+                max: Box::new(Self::from_numeric(context, upper, allowed)?),
+            },
+        })
+    }
+
+    /// Builds a `<number>` out of a typed math expression.
+    pub fn number_from_numeric(
+        context: &ParserContext,
+        value: &NumericValue,
+    ) -> Result<CSSFloat, FromTypedError> {
+        Self::from_numeric(context, value, CalcUnits::empty())?
+            .to_number()
+            .map_err(|()| FromTypedError::TypeMismatch)
+    }
+
+    /// Builds a `<percentage>` out of a typed math expression.
+    pub fn percentage_from_numeric(
+        context: &ParserContext,
+        value: &NumericValue,
+    ) -> Result<CSSFloat, FromTypedError> {
+        Self::from_numeric(context, value, CalcUnits::PERCENTAGE)?
+            .to_percentage()
+            .map(crate::values::normalize)
+            .map_err(|()| FromTypedError::TypeMismatch)
+    }
+
+    /// Builds an `<angle>` out of a typed math expression.
+    pub fn angle_from_numeric(
+        context: &ParserContext,
+        value: &NumericValue,
+    ) -> Result<Angle, FromTypedError> {
+        Self::from_numeric(context, value, CalcUnits::ANGLE)?
+            .to_angle()
+            .map_err(|()| FromTypedError::TypeMismatch)
+    }
+
+    /// Builds a `<time>` out of a typed math expression.
+    pub fn time_from_numeric(
+        context: &ParserContext,
+        value: &NumericValue,
+        clamping_mode: AllowedNumericType,
+    ) -> Result<Time, FromTypedError> {
+        Self::from_numeric(context, value, CalcUnits::TIME)?
+            .to_time(Some(clamping_mode))
+            .map_err(|()| FromTypedError::TypeMismatch)
+    }
+
     /// Tries to simplify this expression into a `<length>` or `<percentage>`
     /// value.
     pub fn into_length_or_percentage(
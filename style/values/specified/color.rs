@@ -9,7 +9,7 @@ use crate::color::mix::ColorInterpolationMethod;
 use crate::color::{parsing, AbsoluteColor, ColorFunction, ColorMixItemList, ColorSpace};
 use crate::derives::*;
 use crate::media_queries::Device;
-use crate::parser::{Parse, ParserContext};
+use crate::parser::{FromTypedError, Parse, ParserContext};
 use crate::values::computed::{Color as ComputedColor, Context, ToComputedValue};
 use crate::values::generics::color::{
     ColorMixFlags, GenericCaretColor, GenericColorMix, GenericColorMixItem, GenericColorOrAuto,
@@ -22,8 +22,8 @@ use cssparser::{match_ignore_ascii_case, BasicParseErrorKind, ParseErrorKind, Pa
 use std::fmt::{self, Write};
 use std::io::Write as IoWrite;
 use style_traits::{
-    owned_slice::OwnedSlice, CssType, CssWriter, KeywordsCollectFn, ParseError, SpecifiedValueInfo,
-    StyleParseErrorKind, ToCss, ValueParseErrorKind,
+    owned_slice::OwnedSlice, CssString, CssType, CssWriter, KeywordsCollectFn, ParseError,
+    SpecifiedValueInfo, StyleParseErrorKind, ToCss, ToTyped, TypedValue, ValueParseErrorKind,
 };
 
 /// A specified color-mix().
@@ -155,28 +155,46 @@ impl ToCss for Absolute {
     }
 }
 
+impl ToTyped for Absolute {
+    fn to_typed(&self) -> Option<TypedValue> {
+        if let Some(ref authored) = self.authored {
+            Some(TypedValue::Keyword(CssString::from(&**authored)))
+        } else {
+            self.color.to_typed()
+        }
+    }
+}
+
 /// Specified color value
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, ToShmem, ToTyped)]
+#[typed_value(derive_fields)]
 pub enum Color {
     /// The 'currentColor' keyword
+    #[css(keyword = "currentcolor")]
     CurrentColor,
     /// An absolute color.
     /// https://w3c.github.io/csswg-drafts/css-color-4/#typedef-absolute-color-function
     Absolute(Box<Absolute>),
     /// A color function that could not be resolved to a [Color::Absolute] color at parse time.
     /// Right now this is only the case for relative colors with `currentColor` as the origin.
+    #[typed_value(todo)]
     ColorFunction(Box<ColorFunction<Self>>),
     /// A system color.
     #[cfg(feature = "gecko")]
+    #[typed_value(todo)]
     System(SystemColor),
     /// A color mix.
+    #[typed_value(todo)]
     ColorMix(Box<ColorMix>),
     /// A light-dark() color.
+    #[typed_value(todo)]
     LightDark(Box<GenericLightDark<Self>>),
     /// The contrast-color function.
+    #[typed_value(todo)]
     ContrastColor(Box<Color>),
     /// Quirksmode-only rule for inheriting color from the body
     #[cfg(feature = "gecko")]
+    #[typed_value(skip)]
     InheritFromBodyQuirk,
 }
 
@@ -454,6 +472,17 @@ impl Parse for Color {
     ) -> Result<Self, ParseError<'i>> {
         Self::parse_internal(context, input, PreserveAuthored::Yes)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        let color = match *value {
+            TypedValue::Color(ref color) => color,
+            _ => return Err(FromTypedError::Unsupported),
+        };
+        parsing::color_function_from_typed(context, color)?
+            .resolve_to_absolute()
+            .map(Self::from_absolute_color)
+            .map_err(|()| FromTypedError::TypeMismatch)
+    }
 }
 
 impl Color {
@@ -916,6 +945,7 @@ impl SpecifiedValueInfo for Color {
 /// keyword to the parent color instead of self's color.
 #[cfg_attr(feature = "gecko", derive(MallocSizeOf))]
 #[derive(Clone, Debug, PartialEq, SpecifiedValueInfo, ToCss, ToShmem, ToTyped)]
+#[typed_value(derive_fields)]
 pub struct ColorPropertyValue(pub Color);
 
 impl ToComputedValue for ColorPropertyValue {
@@ -942,6 +972,10 @@ impl Parse for ColorPropertyValue {
     ) -> Result<Self, ParseError<'i>> {
         Color::parse_quirky(context, input, AllowQuirks::Yes).map(ColorPropertyValue)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        Color::from_typed(context, value).map(ColorPropertyValue)
+    }
 }
 
 /// auto | <color>
@@ -9,7 +9,7 @@
 
 use crate::color::mix::ColorInterpolationMethod;
 use crate::derives::*;
-use crate::parser::{Parse, ParserContext};
+use crate::parser::{FromTypedError, Parse, ParserContext};
 use crate::stylesheets::CorsMode;
 use crate::values::generics::color::{ColorMixFlags, GenericLightDark};
 use crate::values::generics::image::{
@@ -27,12 +27,12 @@ use crate::values::specified::{
 };
 use crate::values::specified::{Number, NumberOrPercentage, Percentage};
 use crate::Atom;
-use cssparser::{match_ignore_ascii_case, Delimiter, Parser, Token};
+use cssparser::{match_ignore_ascii_case, Delimiter, Parser, ParserInput, Token};
 use selectors::parser::SelectorParseErrorKind;
 use std::cmp::Ordering;
 use std::fmt::{self, Write};
 use style_traits::{CssType, CssWriter, KeywordsCollectFn, ParseError};
-use style_traits::{SpecifiedValueInfo, StyleParseErrorKind, ToCss};
+use style_traits::{SpecifiedValueInfo, StyleParseErrorKind, ToCss, TypedValue};
 
 #[inline]
 fn gradient_color_interpolation_method_enabled() -> bool {
@@ -203,6 +203,19 @@ impl Parse for Image {
     ) -> Result<Image, ParseError<'i>> {
         Image::parse_with_cors_mode(context, input, CorsMode::None, ParseImageFlags::empty())
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        // `CSSImageValue`s are opaque to script, so their CSS text is all there
+        // is to build them out of.
+        let css = match *value {
+            TypedValue::Image(ref css) => css,
+            _ => return Err(FromTypedError::Unsupported),
+        };
+        let mut input = ParserInput::new(css);
+        Parser::new(&mut input)
+            .parse_entirely(|input| Self::parse(context, input))
+            .map_err(|_| FromTypedError::TypeMismatch)
+    }
 }
 
 impl Image {
@@ -12,8 +12,9 @@ use crate::derives::*;
 use crate::font_metrics::{FontMetrics, FontMetricsOrientation};
 #[cfg(feature = "gecko")]
 use crate::gecko_bindings::structs::GeckoFontMetrics;
-use crate::parser::{Parse, ParserContext};
+use crate::parser::{FromTypedError, Parse, ParserContext};
 use crate::values::computed::{self, CSSPixelLength, Context, FontSize};
+use crate::values::generics::calc::CalcUnits;
 use crate::values::generics::length as generics;
 use crate::values::generics::length::{
     GenericAnchorSizeFunction, GenericLengthOrNumber, GenericLengthPercentageOrNormal,
@@ -1586,6 +1587,35 @@ impl Length {
         }
     }
 
+    /// Builds a length out of a typed `CSSNumericValue`, see
+    /// `Parse::from_typed`.
+    ///
+    /// Numbers are rejected even when they'd parse as a length, like a
+    /// unitless zero does.
+    pub(super) fn from_numeric(
+        context: &ParserContext,
+        value: &NumericValue,
+        num_context: AllowedNumericType,
+    ) -> Result<Self, FromTypedError> {
+        match *value {
+            NumericValue::Unit { value, ref unit } => {
+                if !value.is_finite() {
+                    return Err(FromTypedError::TypeMismatch);
+                }
+                let length = NoCalcLength::parse_dimension(context, value, unit)
+                    .map_err(|()| FromTypedError::TypeMismatch)?;
+                if !num_context.is_ok(context.parsing_mode, value) {
+                    return Err(FromTypedError::TypeMismatch);
+                }
+                Ok(Length::NoCalc(length))
+            },
+            _ => CalcNode::from_numeric(context, value, CalcUnits::LENGTH)?
+                .into_length_or_percentage(num_context)
+                .map(|calc| Length::Calc(Box::new(calc)))
+                .map_err(|()| FromTypedError::TypeMismatch),
+        }
+    }
+
     /// Parse a non-negative length
     #[inline]
     pub fn parse_non_negative<'i, 't>(
@@ -1644,6 +1674,15 @@ impl Parse for Length {
     ) -> Result<Self, ParseError<'i>> {
         Self::parse_quirky(context, input, AllowQuirks::No)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        match *value {
+            TypedValue::Numeric(ref value) => {
+                Self::from_numeric(context, value, AllowedNumericType::All)
+            },
+            _ => Err(FromTypedError::Unsupported),
+        }
+    }
 }
 
 impl Zero for Length {
@@ -1683,6 +1722,16 @@ impl Parse for NonNegativeLength {
     ) -> Result<Self, ParseError<'i>> {
         Ok(NonNegative(Length::parse_non_negative(context, input)?))
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        match *value {
+            TypedValue::Numeric(ref value) => {
+                Length::from_numeric(context, value, AllowedNumericType::NonNegative)
+                    .map(NonNegative)
+            },
+            _ => Err(FromTypedError::Unsupported),
+        }
+    }
 }
 
 impl From<NoCalcLength> for NonNegativeLength {
@@ -1727,6 +1776,7 @@ impl NonNegativeLength {
 /// https://drafts.csswg.org/css-values-4/#typedef-length-percentage
 #[allow(missing_docs)]
 #[derive(Clone, Debug, MallocSizeOf, PartialEq, SpecifiedValueInfo, ToCss, ToShmem, ToTyped)]
+#[typed_value(derive_fields)]
 pub enum LengthPercentage {
     Length(NoCalcLength),
     Percentage(computed::Percentage),
@@ -1778,6 +1828,15 @@ impl Parse for LengthPercentage {
     ) -> Result<Self, ParseError<'i>> {
         Self::parse_quirky(context, input, AllowQuirks::No)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        match *value {
+            TypedValue::Numeric(ref value) => {
+                Self::from_numeric(context, value, AllowedNumericType::All)
+            },
+            _ => Err(FromTypedError::Unsupported),
+        }
+    }
 }
 
 impl LengthPercentage {
@@ -1793,6 +1852,39 @@ impl LengthPercentage {
         LengthPercentage::Percentage(computed::Percentage::hundred())
     }
 
+    /// Builds a length-percentage out of a typed `CSSNumericValue`, see
+    /// `Parse::from_typed`.
+    ///
+    /// As for `Length::from_numeric`, numbers are rejected.
+    pub(super) fn from_numeric(
+        context: &ParserContext,
+        value: &NumericValue,
+        num_context: AllowedNumericType,
+    ) -> Result<Self, FromTypedError> {
+        match *value {
+            NumericValue::Unit { value, ref unit } => {
+                if !value.is_finite() {
+                    return Err(FromTypedError::TypeMismatch);
+                }
+                let length_percentage = if unit == "percent" {
+                    LengthPercentage::Percentage(computed::Percentage(value / 100.))
+                } else {
+                    NoCalcLength::parse_dimension(context, value, unit)
+                        .map(LengthPercentage::Length)
+                        .map_err(|()| FromTypedError::TypeMismatch)?
+                };
+                if !num_context.is_ok(context.parsing_mode, value) {
+                    return Err(FromTypedError::TypeMismatch);
+                }
+                Ok(length_percentage)
+            },
+            _ => CalcNode::from_numeric(context, value, CalcUnits::LENGTH_PERCENTAGE)?
+                .into_length_or_percentage(num_context)
+                .map(|calc| LengthPercentage::Calc(Box::new(calc)))
+                .map_err(|()| FromTypedError::TypeMismatch),
+        }
+    }
+
     fn parse_internal<'i, 't>(
         context: &ParserContext,
         input: &mut Parser<'i, 't>,
@@ -2060,6 +2152,16 @@ impl Parse for NonNegativeLengthPercentage {
     ) -> Result<Self, ParseError<'i>> {
         Self::parse_quirky(context, input, AllowQuirks::No)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        match *value {
+            TypedValue::Numeric(ref value) => {
+                LengthPercentage::from_numeric(context, value, AllowedNumericType::NonNegative)
+                    .map(NonNegative)
+            },
+            _ => Err(FromTypedError::Unsupported),
+        }
+    }
 }
 
 impl NonNegativeLengthPercentage {
@@ -2131,6 +2233,10 @@ impl Parse for Size {
     ) -> Result<Self, ParseError<'i>> {
         Size::parse_quirky(context, input, AllowQuirks::No)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        NonNegativeLengthPercentage::from_typed(context, value).map(GenericSize::LengthPercentage)
+    }
 }
 
 macro_rules! parse_size_non_length {
@@ -2320,6 +2426,11 @@ impl Parse for MaxSize {
     ) -> Result<Self, ParseError<'i>> {
         MaxSize::parse_quirky(context, input, AllowQuirks::No)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        NonNegativeLengthPercentage::from_typed(context, value)
+            .map(GenericMaxSize::LengthPercentage)
+    }
 }
 
 impl MaxSize {
@@ -4,8 +4,9 @@
 
 //! `list` specified values.
 
+use crate::counter_style::CounterStyle;
 #[cfg(feature = "gecko")]
-use crate::counter_style::{CounterStyle, CounterStyleParsingFlags};
+use crate::counter_style::CounterStyleParsingFlags;
 use crate::derives::*;
 use crate::parser::{Parse, ParserContext};
 use cssparser::{Parser, Token};
@@ -220,6 +221,63 @@ impl ListStyleType {
     pub fn none() -> Self {
         Self::None
     }
+
+    /// Returns the counter style this value refers to, in order to generate
+    /// marker text with it.
+    pub fn counter_style(&self) -> CounterStyle {
+        use crate::values::CustomIdent;
+
+        CounterStyle::Name(CustomIdent(match *self {
+            Self::None => return CounterStyle::None,
+            Self::Disc => atom!("disc"),
+            Self::Circle => atom!("circle"),
+            Self::Square => atom!("square"),
+            Self::DisclosureOpen => atom!("disclosure-open"),
+            Self::DisclosureClosed => atom!("disclosure-closed"),
+            Self::Decimal => atom!("decimal"),
+            Self::LowerAlpha => atom!("lower-alpha"),
+            Self::UpperAlpha => atom!("upper-alpha"),
+            Self::ArabicIndic => atom!("arabic-indic"),
+            Self::Bengali => atom!("bengali"),
+            Self::Cambodian => atom!("cambodian"),
+            Self::CjkDecimal => atom!("cjk-decimal"),
+            Self::Devanagari => atom!("devanagari"),
+            Self::Gujarati => atom!("gujarati"),
+            Self::Gurmukhi => atom!("gurmukhi"),
+            Self::Kannada => atom!("kannada"),
+            Self::Khmer => atom!("khmer"),
+            Self::Lao => atom!("lao"),
+            Self::Malayalam => atom!("malayalam"),
+            Self::Mongolian => atom!("mongolian"),
+            Self::Myanmar => atom!("myanmar"),
+            Self::Oriya => atom!("oriya"),
+            Self::Persian => atom!("persian"),
+            Self::Telugu => atom!("telugu"),
+            Self::Thai => atom!("thai"),
+            Self::Tibetan => atom!("tibetan"),
+            Self::CjkEarthlyBranch => atom!("cjk-earthly-branch"),
+            Self::CjkHeavenlyStem => atom!("cjk-heavenly-stem"),
+            Self::LowerGreek => atom!("lower-greek"),
+            Self::Hiragana => atom!("hiragana"),
+            Self::HiraganaIroha => atom!("hiragana-iroha"),
+            Self::Katakana => atom!("katakana"),
+            Self::KatakanaIroha => atom!("katakana-iroha"),
+        }))
+    }
+
+    /// Returns the keyword for the given counter style, for layout code that
+    /// only renders the styles `list-style-type` supports, or `None` if it has
+    /// no keyword.
+    ///
+    /// This is the inverse of `counter_style`, and is useful for the counter
+    /// styles of `counter()` and `counters()` in `content`.
+    pub fn from_counter_style(counter_style: &CounterStyle) -> Option<Self> {
+        match *counter_style {
+            CounterStyle::None => Some(Self::None),
+            CounterStyle::Name(ref name) => Self::from_ident(&name.0).ok(),
+            _ => None,
+        }
+    }
 }
 
 /// A quote pair.
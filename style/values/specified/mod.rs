@@ -16,7 +16,7 @@ use super::generics::{self, GreaterThanOrEqualToOne, NonNegative};
 use super::{CSSFloat, CSSInteger};
 use crate::context::QuirksMode;
 use crate::derives::*;
-use crate::parser::{Parse, ParserContext};
+use crate::parser::{FromTypedError, Parse, ParserContext};
 use crate::values::specified::calc::CalcNode;
 use crate::values::{serialize_atom_identifier, serialize_number, AtomString};
 use crate::{Atom, Namespace, One, Prefix, Zero};
@@ -34,8 +34,8 @@ pub use self::angle::{AllowUnitlessZeroAngle, Angle};
 pub use self::animation::{
     AnimationComposition, AnimationDirection, AnimationDuration, AnimationFillMode,
     AnimationIterationCount, AnimationName, AnimationPlayState, AnimationTimeline, ScrollAxis,
-    TimelineName, TransitionBehavior, TransitionProperty, ViewTimelineInset, ViewTransitionClass,
-    ViewTransitionName,
+    TimelineName, TimelineScope, TransitionBehavior, TransitionProperty, ViewTimelineInset,
+    ViewTransitionClass, ViewTransitionName,
 };
 pub use self::background::{BackgroundRepeat, BackgroundSize};
 pub use self::basic_shape::FillRule;
@@ -232,6 +232,31 @@ fn parse_number_with_clamping_mode<'i, 't>(
     }
 }
 
+/// Builds a number out of a typed `CSSNumericValue`, see `Parse::from_typed`.
+fn number_from_numeric(
+    context: &ParserContext,
+    value: &NumericValue,
+    clamping_mode: AllowedNumericType,
+) -> Result<Number, FromTypedError> {
+    if let NumericValue::Unit { .. } = *value {
+        return match value.as_number() {
+            Some(value)
+                if value.is_finite() && clamping_mode.is_ok(context.parsing_mode, value) =>
+            {
+                Ok(Number::new(value))
+            },
+            Some(..) => Err(FromTypedError::TypeMismatch),
+            // Other units may still match the type that tried to build a
+            // number, like a percentage for an opacity.
+            None => Err(FromTypedError::Unsupported),
+        };
+    }
+    Ok(Number {
+        value: CalcNode::number_from_numeric(context, value)?,
+        calc_clamping_mode: Some(clamping_mode),
+    })
+}
+
 /// A CSS `<number>` specified value.
 ///
 /// https://drafts.csswg.org/css-values-3/#number-value
@@ -251,6 +276,15 @@ impl Parse for Number {
     ) -> Result<Self, ParseError<'i>> {
         parse_number_with_clamping_mode(context, input, AllowedNumericType::All)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        match *value {
+            TypedValue::Numeric(ref value) => {
+                number_from_numeric(context, value, AllowedNumericType::All)
+            },
+            _ => Err(FromTypedError::Unsupported),
+        }
+    }
 }
 
 impl PartialEq<Number> for Number {
@@ -424,6 +458,16 @@ impl Parse for NonNegativeNumber {
         parse_number_with_clamping_mode(context, input, AllowedNumericType::NonNegative)
             .map(NonNegative::<Number>)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        match *value {
+            TypedValue::Numeric(ref value) => {
+                number_from_numeric(context, value, AllowedNumericType::NonNegative)
+                    .map(NonNegative::<Number>)
+            },
+            _ => Err(FromTypedError::Unsupported),
+        }
+    }
 }
 
 impl One for NonNegativeNumber {
@@ -591,6 +635,15 @@ impl Parse for Opacity {
         let number = NumberOrPercentage::parse(context, input)?.to_number();
         Ok(Opacity(number))
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        match Percentage::from_typed(context, value) {
+            Ok(percentage) => return Ok(Opacity(percentage.to_number())),
+            Err(FromTypedError::TypeMismatch) => return Err(FromTypedError::TypeMismatch),
+            Err(FromTypedError::Unsupported) => {},
+        }
+        Number::from_typed(context, value).map(Opacity)
+    }
 }
 
 impl ToComputedValue for Opacity {
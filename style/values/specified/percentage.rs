@@ -5,7 +5,7 @@
 //! Specified percentages.
 
 use crate::derives::*;
-use crate::parser::{Parse, ParserContext};
+use crate::parser::{FromTypedError, Parse, ParserContext};
 use crate::values::computed::percentage::Percentage as ComputedPercentage;
 use crate::values::computed::{Context, ToComputedValue};
 use crate::values::generics::NonNegative;
@@ -15,10 +15,12 @@ use crate::values::{normalize, serialize_percentage, CSSFloat};
 use cssparser::{Parser, Token};
 use std::fmt::{self, Write};
 use style_traits::values::specified::AllowedNumericType;
-use style_traits::{CssWriter, ParseError, SpecifiedValueInfo, ToCss};
+use style_traits::{
+    CssWriter, NumericValue, ParseError, SpecifiedValueInfo, ToCss, ToTyped, TypedValue,
+};
 
 /// A percentage value.
-#[derive(Clone, Copy, Debug, Default, MallocSizeOf, PartialEq, ToShmem, ToTyped)]
+#[derive(Clone, Copy, Debug, Default, MallocSizeOf, PartialEq, ToShmem)]
 pub struct Percentage {
     /// The percentage value as a float.
     ///
@@ -47,6 +49,12 @@ impl ToCss for Percentage {
     }
 }
 
+impl ToTyped for Percentage {
+    fn to_typed(&self) -> Option<TypedValue> {
+        Some(TypedValue::Numeric(NumericValue::percentage(self.value)))
+    }
+}
+
 impl Percentage {
     /// Creates a percentage from a numeric value.
     pub(super) fn new_with_clamping_mode(
@@ -166,6 +174,27 @@ impl Parse for Percentage {
     ) -> Result<Self, ParseError<'i>> {
         Self::parse_with_clamping_mode(context, input, AllowedNumericType::All)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        match *value {
+            TypedValue::Numeric(NumericValue::Unit { value, ref unit }) if unit == "percent" => {
+                if !value.is_finite() {
+                    return Err(FromTypedError::TypeMismatch);
+                }
+                Ok(Percentage::new(value / 100.))
+            },
+            TypedValue::Numeric(NumericValue::Unit { .. }) => Err(FromTypedError::Unsupported),
+            // Math expressions of other types may still match the type that
+            // tried to build a percentage, like a number for an opacity.
+            TypedValue::Numeric(ref value) => CalcNode::percentage_from_numeric(context, value)
+                .map(|value| Percentage {
+                    value,
+                    calc_clamping_mode: Some(AllowedNumericType::All),
+                })
+                .map_err(|_| FromTypedError::Unsupported),
+            _ => Err(FromTypedError::Unsupported),
+        }
+    }
 }
 
 impl ToComputedValue for Percentage {
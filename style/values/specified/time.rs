@@ -5,7 +5,7 @@
 //! Specified time values.
 
 use crate::derives::*;
-use crate::parser::{Parse, ParserContext};
+use crate::parser::{FromTypedError, Parse, ParserContext};
 use crate::values::computed::time::Time as ComputedTime;
 use crate::values::computed::{Context, ToComputedValue};
 use crate::values::specified::calc::CalcNode;
@@ -14,7 +14,10 @@ use crate::Zero;
 use cssparser::{match_ignore_ascii_case, Parser, Token};
 use std::fmt::{self, Write};
 use style_traits::values::specified::AllowedNumericType;
-use style_traits::{CssWriter, ParseError, SpecifiedValueInfo, StyleParseErrorKind, ToCss};
+use style_traits::{
+    CssString, CssWriter, NumericValue, ParseError, ParsingMode, SpecifiedValueInfo,
+    StyleParseErrorKind, ToCss, ToTyped, TypedValue,
+};
 
 /// A time value according to CSS-VALUES § 6.2.
 #[derive(Clone, Copy, Debug, MallocSizeOf, PartialEq, ToShmem)]
@@ -93,8 +96,6 @@ impl Time {
         input: &mut Parser<'i, 't>,
         clamping_mode: AllowedNumericType,
     ) -> Result<Self, ParseError<'i>> {
-        use style_traits::ParsingMode;
-
         let location = input.current_source_location();
         match *input.next()? {
             // Note that we generally pass ParserContext to is_ok() to check
@@ -123,6 +124,34 @@ impl Time {
     ) -> Result<Self, ParseError<'i>> {
         Self::parse_with_clamping_mode(context, input, AllowedNumericType::NonNegative)
     }
+
+    fn from_typed_with_clamping_mode(
+        context: &ParserContext,
+        value: &TypedValue,
+        clamping_mode: AllowedNumericType,
+    ) -> Result<Self, FromTypedError> {
+        match *value {
+            TypedValue::Numeric(NumericValue::Unit { value, ref unit }) => {
+                if !value.is_finite() || !clamping_mode.is_ok(ParsingMode::DEFAULT, value) {
+                    return Err(FromTypedError::TypeMismatch);
+                }
+                Time::parse_dimension(value, unit).map_err(|()| FromTypedError::TypeMismatch)
+            },
+            TypedValue::Numeric(ref value) => {
+                CalcNode::time_from_numeric(context, value, clamping_mode)
+            },
+            _ => Err(FromTypedError::Unsupported),
+        }
+    }
+
+    /// Builds a non-negative time out of a Typed OM value, see
+    /// `Parse::from_typed`.
+    pub fn from_typed_non_negative(
+        context: &ParserContext,
+        value: &TypedValue,
+    ) -> Result<Self, FromTypedError> {
+        Self::from_typed_with_clamping_mode(context, value, AllowedNumericType::NonNegative)
+    }
 }
 
 impl Zero for Time {
@@ -165,6 +194,10 @@ impl Parse for Time {
     ) -> Result<Self, ParseError<'i>> {
         Self::parse_with_clamping_mode(context, input, AllowedNumericType::All)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        Self::from_typed_with_clamping_mode(context, value, AllowedNumericType::All)
+    }
 }
 
 impl ToCss for Time {
@@ -181,4 +214,12 @@ impl ToCss for Time {
     }
 }
 
+impl ToTyped for Time {
+    fn to_typed(&self) -> Option<TypedValue> {
+        let value = self.unitless_value();
+        let unit = CssString::from(self.unit());
+        Some(TypedValue::Numeric(NumericValue::Unit { value, unit }))
+    }
+}
+
 impl SpecifiedValueInfo for Time {}
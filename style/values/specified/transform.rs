@@ -5,7 +5,7 @@
 //! Specified types for CSS values that are related to transformations.
 
 use crate::derives::*;
-use crate::parser::{Parse, ParserContext};
+use crate::parser::{FromTypedError, Parse, ParserContext};
 use crate::values::computed::{Context, LengthPercentage as ComputedLengthPercentage};
 use crate::values::computed::{Percentage as ComputedPercentage, ToComputedValue};
 use crate::values::generics::transform as generic;
@@ -18,7 +18,11 @@ use crate::values::specified::{
 };
 use crate::Zero;
 use cssparser::{match_ignore_ascii_case, Parser};
-use style_traits::{ParseError, StyleParseErrorKind};
+use style_traits::values::specified::AllowedNumericType;
+use style_traits::{
+    NumericValue, NumericValueOrNone, ParseError, StyleParseErrorKind, TransformComponent,
+    TypedValue,
+};
 
 pub use crate::values::generics::transform::TransformStyle;
 
@@ -339,6 +343,119 @@ impl Parse for Transform {
     ) -> Result<Self, ParseError<'i>> {
         Transform::parse_internal(context, input, AllowUnitlessPerspective::No)
     }
+
+    fn from_typed(context: &ParserContext, value: &TypedValue) -> Result<Self, FromTypedError> {
+        let components = match *value {
+            TypedValue::Transform(ref components) if !components.is_empty() => components,
+            _ => return Err(FromTypedError::Unsupported),
+        };
+        let number = |value: &NumericValue| {
+            super::number_from_numeric(context, value, AllowedNumericType::All)
+        };
+        let length =
+            |value: &NumericValue| Length::from_numeric(context, value, AllowedNumericType::All);
+        let length_percentage = |value: &NumericValue| {
+            LengthPercentage::from_numeric(context, value, AllowedNumericType::All)
+        };
+        let mut operations = Vec::with_capacity(components.len());
+        for component in components.iter() {
+            operations.push(match *component {
+                TransformComponent::Translate {
+                    ref x,
+                    ref y,
+                    ref z,
+                    is_2d,
+                } => {
+                    if is_2d {
+                        generic::TransformOperation::Translate(
+                            length_percentage(x)?,
+                            length_percentage(y)?,
+                        )
+                    } else {
+                        generic::TransformOperation::Translate3D(
+                            length_percentage(x)?,
+                            length_percentage(y)?,
+                            length(z)?,
+                        )
+                    }
+                },
+                TransformComponent::Rotate {
+                    ref x,
+                    ref y,
+                    ref z,
+                    ref angle,
+                    is_2d,
+                } => {
+                    if is_2d {
+                        generic::TransformOperation::Rotate(Angle::from_numeric(context, angle)?)
+                    } else {
+                        generic::TransformOperation::Rotate3D(
+                            number(x)?,
+                            number(y)?,
+                            number(z)?,
+                            Angle::from_numeric(context, angle)?,
+                        )
+                    }
+                },
+                TransformComponent::Scale {
+                    ref x,
+                    ref y,
+                    ref z,
+                    is_2d,
+                } => {
+                    if is_2d {
+                        generic::TransformOperation::Scale(number(x)?, number(y)?)
+                    } else {
+                        generic::TransformOperation::Scale3D(number(x)?, number(y)?, number(z)?)
+                    }
+                },
+                TransformComponent::Skew { ref ax, ref ay } => generic::TransformOperation::Skew(
+                    Angle::from_numeric(context, ax)?,
+                    Angle::from_numeric(context, ay)?,
+                ),
+                TransformComponent::SkewX { ref ax } => {
+                    generic::TransformOperation::SkewX(Angle::from_numeric(context, ax)?)
+                },
+                TransformComponent::SkewY { ref ay } => {
+                    generic::TransformOperation::SkewY(Angle::from_numeric(context, ay)?)
+                },
+                TransformComponent::Perspective { ref length } => {
+                    generic::TransformOperation::Perspective(match *length {
+                        NumericValueOrNone::Numeric(ref length) => {
+                            generic::PerspectiveFunction::Length(Length::from_numeric(
+                                context,
+                                length,
+                                AllowedNumericType::NonNegative,
+                            )?)
+                        },
+                        NumericValueOrNone::None => generic::PerspectiveFunction::None,
+                    })
+                },
+                TransformComponent::Matrix { ref matrix, is_2d } => {
+                    let m = matrix.map(Number::new);
+                    if is_2d {
+                        generic::TransformOperation::Matrix(Matrix {
+                            a: m[0],
+                            b: m[1],
+                            c: m[4],
+                            d: m[5],
+                            e: m[12],
+                            f: m[13],
+                        })
+                    } else {
+                        #[cfg_attr(rustfmt, rustfmt_skip)]
+                        generic::TransformOperation::Matrix3D(Matrix3D {
+                            m11: m[0], m12: m[1], m13: m[2], m14: m[3],
+                            m21: m[4], m22: m[5], m23: m[6], m24: m[7],
+                            m31: m[8], m32: m[9], m33: m[10], m34: m[11],
+                            m41: m[12], m42: m[13], m43: m[14], m44: m[15],
+                        })
+                    }
+                },
+            });
+        }
+        Ok(generic::Transform(operations.into()))
+    }
 }
 
 /// The specified value of a component of a CSS `<transform-origin>`.
@@ -275,6 +275,34 @@ pub fn derive(mut input: DeriveInput) -> TokenStream {
 
     let has_non_keywords = !non_keywords.is_empty();
 
+    // Enums with only keywords build them out of Typed OM keywords directly,
+    // and don't match any other Typed OM value.
+    let from_typed = if has_non_keywords {
+        quote! {}
+    } else {
+        let context_ident = if saw_condition {
+            quote! { context }
+        } else {
+            quote! { _ }
+        };
+        quote! {
+            fn from_typed(
+                #context_ident: &crate::parser::ParserContext,
+                value: &style_traits::TypedValue,
+            ) -> Result<Self, crate::parser::FromTypedError> {
+                let keyword: &str = match *value {
+                    style_traits::TypedValue::Keyword(ref keyword) => keyword,
+                    _ => return Err(crate::parser::FromTypedError::TypeMismatch),
+                };
+                let result: Result<Self, ()> = cssparser::match_ignore_ascii_case! { keyword,
+                    #match_keywords
+                    _ => Err(()),
+                };
+                result.map_err(|()| crate::parser::FromTypedError::TypeMismatch)
+            }
+        }
+    };
+
     input.generics.where_clause = where_clause;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
@@ -287,6 +315,8 @@ pub fn derive(mut input: DeriveInput) -> TokenStream {
             ) -> Result<Self, style_traits::ParseError<'i>> {
                 #parse_body
             }
+
+            #from_typed
         }
     };
 
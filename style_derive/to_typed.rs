@@ -141,6 +141,10 @@ pub fn derive(mut input: DeriveInput) -> TokenStream {
 ///   provided through `#[css(keyword = "...")]`.
 /// * Variants marked with `#[css(skip)]` or `#[typed_value(skip)]` or
 ///   `#[typed(todo)]` return `None`.
+/// * Variants marked with `#[typed_value(image)]` are reified into
+///   `TypedValue::Image`, using the `ToCss` serialization of the whole value.
+/// * Variants marked with `#[typed_value(transform_list)]` are reified into
+///   `TypedValue::Transform` through `derive_transform_list_expr()`.
 /// * Variants with fields delegate to `derive_variant_fields_expr()` when
 ///   `derive_fields` is enabled; otherwise they return `None`.
 ///
@@ -179,6 +183,23 @@ fn derive_variant_arm(
         return quote!(None);
     }
 
+    // If the variant is marked #[typed_value(image)], reify the whole value
+    // as an opaque image, using its serialization.
+    if variant_attrs.image {
+        cg::add_predicate(where_clause, parse_quote!(Self: style_traits::ToCss));
+        return quote! {
+            Some(style_traits::TypedValue::Image(
+                style_traits::ToCss::to_css_cssstring(self)
+            ))
+        };
+    }
+
+    // If the variant is marked #[typed_value(transform_list)], reify its
+    // iterable field as a list of transform components.
+    if variant_attrs.transform_list {
+        return derive_transform_list_expr(bindings, where_clause);
+    }
+
     // If the variant has no bindings (i.e. no data fields), treat it as a unit
     // variant and reify it as a keyword.
     if bindings.is_empty() {
@@ -258,6 +279,52 @@ fn derive_variant_fields_expr(
     }
 }
 
+/// Generate the match arm expression for a variant marked with
+/// `#[typed_value(transform_list)]`.
+///
+/// The variant needs exactly one non-skipped, iterable field, whose items
+/// reify into transform components. An empty list is reified into its
+/// `#[css(if_empty = "...")]` keyword, if any.
+fn derive_transform_list_expr(
+    bindings: &[BindingInfo],
+    where_clause: &mut Option<WhereClause>,
+) -> TokenStream {
+    let mut iter = bindings.iter().filter_map(|binding| {
+        let css_field_attrs = cg::parse_field_attrs::<CssFieldAttrs>(binding.ast());
+        if css_field_attrs.skip {
+            return None;
+        }
+        Some((binding, css_field_attrs))
+    });
+    let (list, css_field_attrs) = match (iter.next(), iter.next()) {
+        (Some(pair), None) if pair.1.iterable => pair,
+        _ => panic!("#[typed_value(transform_list)] requires exactly one iterable field"),
+    };
+
+    let ty = &list.ast().ty;
+    cg::add_predicate(
+        where_clause,
+        parse_quote!(<#ty as IntoIterator>::Item: style_traits::ToTyped),
+    );
+
+    let if_empty = match css_field_attrs.if_empty {
+        Some(ref keyword) => quote! {
+            Some(style_traits::TypedValue::Keyword(
+                style_traits::CssString::from(#keyword)
+            ))
+        },
+        None => quote! { None },
+    };
+    quote! {{
+        let mut iter = #list.iter().peekable();
+        if iter.peek().is_none() {
+            #if_empty
+        } else {
+            style_traits::values::to_typed_transform_list(iter)
+        }
+    }}
+}
+
 #[derive(Default, FromDeriveInput)]
 #[darling(attributes(typed_value), default)]
 pub struct TypedValueInputAttrs {
@@ -272,6 +339,10 @@ pub struct TypedValueInputAttrs {
     /// reification coverage is complete, this attribute may be replaced by
     /// an opposite flag (see bug 1995184).
     pub derive_fields: bool,
+
+    /// Same as the variant-level `transform_list`, but included here because
+    /// structs are represented as both a variant and a type definition.
+    pub transform_list: bool,
 }
 
 #[derive(Default, FromVariant)]
@@ -290,4 +361,12 @@ pub struct TypedValueVariantAttrs {
     /// Behavior is the same as `skip`, but used to indicate that reification
     /// is intentionally left unimplemented for now.
     pub todo: bool,
+
+    /// If present, this variant is reified as a `CSSImageValue`, using the
+    /// `ToCss` serialization of the whole value.
+    pub image: bool,
+
+    /// If present, the single iterable field of this variant is reified as a
+    /// `CSSTransformValue`, with each item as a transform component.
+    pub transform_list: bool,
 }
@@ -76,8 +76,9 @@ pub mod owned_str;
 
 pub use crate::specified_value_info::{CssType, KeywordsCollectFn, SpecifiedValueInfo};
 pub use crate::values::{
-    Comma, CommaWithSpace, CssString, CssStringWriter, CssWriter, NumericValue, OneOrMoreSeparated,
-    Separator, Space, ToCss, ToTyped, TypedValue,
+    ColorValue, ColorValueFunction, Comma, CommaWithSpace, CssString, CssStringWriter, CssWriter,
+    NumericValue, NumericValueOrNone, OneOrMoreSeparated, Separator, Space, ToCss, ToTyped,
+    TransformComponent, TypedValue, TypedValueError, UnparsedSegment,
 };
 
 /// The error type for all CSS parsing routines.
@@ -6,7 +6,7 @@
 
 use app_units::Au;
 use cssparser::ToCss as CssparserToCss;
-use cssparser::{serialize_string, ParseError, Parser, Token, UnicodeRange};
+use cssparser::{serialize_identifier, serialize_string, ParseError, Parser, Token, UnicodeRange};
 use servo_arc::Arc;
 use std::fmt::{self, Write};
 use thin_vec::ThinVec;
@@ -634,6 +634,272 @@ pub enum NumericValue {
         /// The list of numeric terms that make up the sum.
         values: ThinVec<NumericValue>,
     },
+
+    /// A product of multiple numeric values.
+    ///
+    /// This corresponds to `CSSMathProduct`, representing an expression such
+    /// as `2 * 10px` or, together with `Invert`, `10px / 2`.
+    Product {
+        /// The list of numeric factors that make up the product.
+        values: ThinVec<NumericValue>,
+    },
+
+    /// The negation of a numeric value.
+    ///
+    /// This corresponds to `CSSMathNegate`, representing the subtracted terms
+    /// of a sum.
+    Negate {
+        /// The negated value.
+        value: Box<NumericValue>,
+    },
+
+    /// The reciprocal of a numeric value.
+    ///
+    /// This corresponds to `CSSMathInvert`, representing the divisors of a
+    /// product.
+    Invert {
+        /// The inverted value.
+        value: Box<NumericValue>,
+    },
+
+    /// The smallest of multiple numeric values.
+    ///
+    /// This corresponds to `CSSMathMin`, representing a `min()` function.
+    Min {
+        /// The arguments of the function.
+        values: ThinVec<NumericValue>,
+    },
+
+    /// The largest of multiple numeric values.
+    ///
+    /// This corresponds to `CSSMathMax`, representing a `max()` function.
+    Max {
+        /// The arguments of the function.
+        values: ThinVec<NumericValue>,
+    },
+
+    /// A numeric value clamped to a range.
+    ///
+    /// This corresponds to `CSSMathClamp`, representing a `clamp()` function.
+    Clamp {
+        /// The lower bound of the range.
+        lower: Box<NumericValue>,
+        /// The clamped value.
+        value: Box<NumericValue>,
+        /// The upper bound of the range.
+        upper: Box<NumericValue>,
+    },
+}
+
+impl NumericValue {
+    /// Returns a `CSSUnitValue` with the `number` unit.
+    pub fn number(value: f32) -> Self {
+        Self::Unit {
+            value,
+            unit: CssString::from("number"),
+        }
+    }
+
+    /// Returns a `CSSUnitValue` with the `percent` unit, given a percentage
+    /// where `1.0` means `100%`.
+    pub fn percentage(value: f32) -> Self {
+        Self::Unit {
+            value: value * 100.,
+            unit: CssString::from("percent"),
+        }
+    }
+
+    /// Returns a `CSSUnitValue` with the `deg` unit.
+    pub fn degrees(value: f32) -> Self {
+        Self::Unit {
+            value,
+            unit: CssString::from("deg"),
+        }
+    }
+
+    /// Returns a `CSSUnitValue` with the `px` unit.
+    pub fn px(value: f32) -> Self {
+        Self::Unit {
+            value,
+            unit: CssString::from("px"),
+        }
+    }
+
+    /// Returns the value of a `CSSUnitValue` with the `number` unit, if this
+    /// is one.
+    pub fn as_number(&self) -> Option<f32> {
+        match *self {
+            Self::Unit { value, ref unit } if unit == "number" => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// A numeric value or the `none` keyword, used by the Typed OM for color
+/// channels and `perspective()` lengths.
+#[derive(Clone, Debug)]
+#[repr(C, u8)]
+pub enum NumericValueOrNone {
+    /// A numeric value.
+    Numeric(NumericValue),
+    /// The `none` keyword.
+    None,
+}
+
+/// A transform function used by the Typed OM.
+///
+/// This corresponds to `CSSTransformComponent` and its subclasses in the
+/// Typed OM specification.
+#[derive(Clone, Debug)]
+#[repr(C, u8)]
+pub enum TransformComponent {
+    /// A translation.
+    ///
+    /// This corresponds to `CSSTranslate`, representing the `translate*()`
+    /// functions.
+    Translate {
+        /// The translation along the x axis.
+        x: NumericValue,
+        /// The translation along the y axis.
+        y: NumericValue,
+        /// The translation along the z axis, zero for 2D translations.
+        z: NumericValue,
+        /// Whether this is a 2D translation.
+        is_2d: bool,
+    },
+
+    /// A rotation.
+    ///
+    /// This corresponds to `CSSRotate`, representing the `rotate*()`
+    /// functions. The rotation of 2D rotations is around the z axis.
+    Rotate {
+        /// The x component of the rotation axis.
+        x: NumericValue,
+        /// The y component of the rotation axis.
+        y: NumericValue,
+        /// The z component of the rotation axis.
+        z: NumericValue,
+        /// The rotation angle.
+        angle: NumericValue,
+        /// Whether this is a 2D rotation.
+        is_2d: bool,
+    },
+
+    /// A scale.
+    ///
+    /// This corresponds to `CSSScale`, representing the `scale*()`
+    /// functions.
+    Scale {
+        /// The scale factor along the x axis.
+        x: NumericValue,
+        /// The scale factor along the y axis.
+        y: NumericValue,
+        /// The scale factor along the z axis, one for 2D scales.
+        z: NumericValue,
+        /// Whether this is a 2D scale.
+        is_2d: bool,
+    },
+
+    /// A `skew()` function, corresponding to `CSSSkew`.
+    Skew {
+        /// The skew angle along the x axis.
+        ax: NumericValue,
+        /// The skew angle along the y axis.
+        ay: NumericValue,
+    },
+
+    /// A `skewX()` function, corresponding to `CSSSkewX`.
+    SkewX {
+        /// The skew angle along the x axis.
+        ax: NumericValue,
+    },
+
+    /// A `skewY()` function, corresponding to `CSSSkewY`.
+    SkewY {
+        /// The skew angle along the y axis.
+        ay: NumericValue,
+    },
+
+    /// A `perspective()` function, corresponding to `CSSPerspective`.
+    Perspective {
+        /// The perspective length, or `none`.
+        length: NumericValueOrNone,
+    },
+
+    /// A `matrix()` or `matrix3d()` function.
+    ///
+    /// This corresponds to `CSSMatrixComponent`. The `matrix` field holds the
+    /// entries of the equivalent `DOMMatrix` in `m11, m12, m13, m14, m21, ...`
+    /// order, with the entries that a 2D matrix doesn't have set to those of
+    /// the identity matrix.
+    Matrix {
+        /// The matrix entries.
+        matrix: [f32; 16],
+        /// Whether this is a 2D matrix.
+        is_2d: bool,
+    },
+}
+
+/// The color function of a Typed OM color value, which determines the
+/// `CSSColorValue` subclass it is exposed as.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum ColorValueFunction {
+    /// `CSSRGB`, with `r`, `g` and `b` channels.
+    Rgb,
+    /// `CSSHSL`, with `h`, `s` and `l` channels.
+    Hsl,
+    /// `CSSHWB`, with `h`, `w` and `b` channels.
+    Hwb,
+    /// `CSSLab`, with `l`, `a` and `b` channels.
+    Lab,
+    /// `CSSLCH`, with `l`, `c` and `h` channels.
+    Lch,
+    /// `CSSOKLab`, with `l`, `a` and `b` channels.
+    Oklab,
+    /// `CSSOKLCH`, with `l`, `c` and `h` channels.
+    Oklch,
+    /// `CSSColor`, with the channels of a predefined color space.
+    Color,
+}
+
+/// A color value used by the Typed OM.
+///
+/// This corresponds to the `CSSColorValue` subclasses in the Typed OM
+/// specification.
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ColorValue {
+    /// The color function this value is exposed as.
+    pub function: ColorValueFunction,
+    /// The color space of a `CSSColor` (e.g. `"display-p3"`), empty for the
+    /// other color functions.
+    pub color_space: CssString,
+    /// The three channels of the color, in the order of the color function.
+    pub channels: [NumericValueOrNone; 3],
+    /// The alpha channel of the color.
+    pub alpha: NumericValueOrNone,
+}
+
+/// A segment of a value containing variable references, used by the Typed OM.
+///
+/// This corresponds to the members of `CSSUnparsedValue` in the Typed OM
+/// specification.
+#[derive(Clone, Debug)]
+#[repr(C, u8)]
+pub enum UnparsedSegment {
+    /// A run of tokens without any variable reference, as CSS text.
+    String(CssString),
+    /// A `var()` function.
+    ///
+    /// This corresponds to `CSSVariableReferenceValue`.
+    VariableReference {
+        /// The name of the referenced custom property, including the leading
+        /// dashes.
+        variable: CssString,
+        /// The segments of the fallback value, if any.
+        fallback: Option<ThinVec<UnparsedSegment>>,
+    },
 }
 
 /// A property-agnostic representation of a value, used by Typed OM.
@@ -654,8 +920,360 @@ pub enum TypedValue {
     /// A numeric value such as a length, angle, time, or a sum thereof.
     ///
     /// This corresponds to the `CSSNumericValue` hierarchy in the Typed OM
-    /// specification, including `CSSUnitValue` and `CSSMathSum`.
+    /// specification, including `CSSUnitValue` and the `CSSMathValue`
+    /// subclasses.
     Numeric(NumericValue),
+
+    /// A list of transform functions.
+    ///
+    /// This corresponds to `CSSTransformValue` in the Typed OM specification.
+    Transform(ThinVec<TransformComponent>),
+
+    /// A color value.
+    ///
+    /// This corresponds to the `CSSColorValue` subclasses in the Typed OM
+    /// specification.
+    Color(ColorValue),
+
+    /// An image value, such as a `url()` or a gradient, as CSS text.
+    ///
+    /// This corresponds to `CSSImageValue` in the Typed OM specification,
+    /// which is opaque to script.
+    Image(CssString),
+
+    /// A value containing `var()` references.
+    ///
+    /// This corresponds to `CSSUnparsedValue` in the Typed OM specification.
+    Unparsed(ThinVec<UnparsedSegment>),
+}
+
+impl NumericValue {
+    fn is_math_function(&self) -> bool {
+        matches!(
+            *self,
+            Self::Min { .. } | Self::Max { .. } | Self::Clamp { .. }
+        )
+    }
+
+    fn write_values<W>(values: &[NumericValue], dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        for (i, value) in values.iter().enumerate() {
+            if i != 0 {
+                dest.write_str(", ")?;
+            }
+            value.write_operand(dest, /* nested = */ false)?;
+        }
+        Ok(())
+    }
+
+    /// Writes this value as an operand of a math expression, wrapping sums,
+    /// products and their operands in parentheses if `nested` is true.
+    fn write_operand<W>(&self, dest: &mut CssWriter<W>, nested: bool) -> fmt::Result
+    where
+        W: Write,
+    {
+        let needs_parens =
+            nested && !matches!(*self, Self::Unit { .. }) && !self.is_math_function();
+        if needs_parens {
+            dest.write_char('(')?;
+        }
+        match *self {
+            Self::Unit { value, ref unit } => {
+                ToCss::to_css(&value, dest)?;
+                if unit == "percent" {
+                    dest.write_char('%')?;
+                } else if unit != "number" {
+                    write!(dest, "{}", unit)?;
+                }
+            },
+            Self::Sum { ref values } => {
+                for (i, value) in values.iter().enumerate() {
+                    match (i, value) {
+                        (0, _) => value.write_operand(dest, true)?,
+                        (_, Self::Negate { value }) => {
+                            dest.write_str(" - ")?;
+                            value.write_operand(dest, true)?;
+                        },
+                        _ => {
+                            dest.write_str(" + ")?;
+                            value.write_operand(dest, true)?;
+                        },
+                    }
+                }
+            },
+            Self::Product { ref values } => {
+                for (i, value) in values.iter().enumerate() {
+                    match (i, value) {
+                        (0, _) => value.write_operand(dest, true)?,
+                        (_, Self::Invert { value }) => {
+                            dest.write_str(" / ")?;
+                            value.write_operand(dest, true)?;
+                        },
+                        _ => {
+                            dest.write_str(" * ")?;
+                            value.write_operand(dest, true)?;
+                        },
+                    }
+                }
+            },
+            Self::Negate { ref value } => {
+                dest.write_str("-1 * ")?;
+                value.write_operand(dest, true)?;
+            },
+            Self::Invert { ref value } => {
+                dest.write_str("1 / ")?;
+                value.write_operand(dest, true)?;
+            },
+            Self::Min { ref values } => {
+                dest.write_str("min(")?;
+                Self::write_values(values, dest)?;
+                dest.write_char(')')?;
+            },
+            Self::Max { ref values } => {
+                dest.write_str("max(")?;
+                Self::write_values(values, dest)?;
+                dest.write_char(')')?;
+            },
+            Self::Clamp {
+                ref lower,
+                ref value,
+                ref upper,
+            } => {
+                dest.write_str("clamp(")?;
+                lower.write_operand(dest, false)?;
+                dest.write_str(", ")?;
+                value.write_operand(dest, false)?;
+                dest.write_str(", ")?;
+                upper.write_operand(dest, false)?;
+                dest.write_char(')')?;
+            },
+        }
+        if needs_parens {
+            dest.write_char(')')?;
+        }
+        Ok(())
+    }
+}
+
+/// https://drafts.css-houdini.org/css-typed-om-1/#numericvalue-serialization
+impl ToCss for NumericValue {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        if matches!(*self, Self::Unit { .. }) || self.is_math_function() {
+            return self.write_operand(dest, false);
+        }
+        dest.write_str("calc(")?;
+        self.write_operand(dest, false)?;
+        dest.write_char(')')
+    }
+}
+
+impl ToCss for NumericValueOrNone {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        match *self {
+            Self::Numeric(ref value) => value.to_css(dest),
+            Self::None => dest.write_str("none"),
+        }
+    }
+}
+
+/// https://drafts.css-houdini.org/css-typed-om-1/#transformvalue-serialization
+impl ToCss for TransformComponent {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        fn write_function<W>(
+            name: &str,
+            args: &[&NumericValue],
+            dest: &mut CssWriter<W>,
+        ) -> fmt::Result
+        where
+            W: Write,
+        {
+            dest.write_str(name)?;
+            dest.write_char('(')?;
+            for (i, arg) in args.iter().enumerate() {
+                if i != 0 {
+                    dest.write_str(", ")?;
+                }
+                arg.to_css(dest)?;
+            }
+            dest.write_char(')')
+        }
+
+        match *self {
+            Self::Translate {
+                ref x,
+                ref y,
+                ref z,
+                is_2d,
+            } => {
+                if is_2d {
+                    write_function("translate", &[x, y], dest)
+                } else {
+                    write_function("translate3d", &[x, y, z], dest)
+                }
+            },
+            Self::Rotate {
+                ref x,
+                ref y,
+                ref z,
+                ref angle,
+                is_2d,
+            } => {
+                if is_2d {
+                    write_function("rotate", &[angle], dest)
+                } else {
+                    write_function("rotate3d", &[x, y, z, angle], dest)
+                }
+            },
+            Self::Scale {
+                ref x,
+                ref y,
+                ref z,
+                is_2d,
+            } => {
+                if is_2d {
+                    write_function("scale", &[x, y], dest)
+                } else {
+                    write_function("scale3d", &[x, y, z], dest)
+                }
+            },
+            Self::Skew { ref ax, ref ay } => write_function("skew", &[ax, ay], dest),
+            Self::SkewX { ref ax } => write_function("skewX", &[ax], dest),
+            Self::SkewY { ref ay } => write_function("skewY", &[ay], dest),
+            Self::Perspective { ref length } => {
+                dest.write_str("perspective(")?;
+                length.to_css(dest)?;
+                dest.write_char(')')
+            },
+            Self::Matrix { ref matrix, is_2d } => {
+                let entries: &[usize] = if is_2d {
+                    &[0, 1, 4, 5, 12, 13]
+                } else {
+                    &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
+                };
+                dest.write_str(if is_2d { "matrix(" } else { "matrix3d(" })?;
+                for (i, entry) in entries.iter().enumerate() {
+                    if i != 0 {
+                        dest.write_str(", ")?;
+                    }
+                    ToCss::to_css(&matrix[*entry], dest)?;
+                }
+                dest.write_char(')')
+            },
+        }
+    }
+}
+
+/// https://drafts.css-houdini.org/css-typed-om-1/#colorvalue-serialization
+impl ToCss for ColorValue {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        dest.write_str(match self.function {
+            ColorValueFunction::Rgb => "rgb(",
+            ColorValueFunction::Hsl => "hsl(",
+            ColorValueFunction::Hwb => "hwb(",
+            ColorValueFunction::Lab => "lab(",
+            ColorValueFunction::Lch => "lch(",
+            ColorValueFunction::Oklab => "oklab(",
+            ColorValueFunction::Oklch => "oklch(",
+            ColorValueFunction::Color => "color(",
+        })?;
+        if self.function == ColorValueFunction::Color {
+            write!(dest, "{} ", self.color_space)?;
+        }
+        for (i, channel) in self.channels.iter().enumerate() {
+            if i != 0 {
+                dest.write_char(' ')?;
+            }
+            channel.to_css(dest)?;
+        }
+        dest.write_str(" / ")?;
+        self.alpha.to_css(dest)?;
+        dest.write_char(')')
+    }
+}
+
+impl ToCss for UnparsedSegment {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        match *self {
+            Self::String(ref css) => write!(dest, "{}", css),
+            Self::VariableReference {
+                ref variable,
+                ref fallback,
+            } => {
+                write!(dest, "var({}", variable)?;
+                if let Some(ref fallback) = *fallback {
+                    dest.write_char(',')?;
+                    for segment in fallback.iter() {
+                        segment.to_css(dest)?;
+                    }
+                }
+                dest.write_char(')')
+            },
+        }
+    }
+}
+
+/// Serializes a typed value into CSS text, following the serialization of
+/// the corresponding `CSSStyleValue` subclass.
+///
+/// Keywords are serialized as identifiers, so that they can't be parsed back
+/// as anything else.
+impl ToCss for TypedValue {
+    fn to_css<W>(&self, dest: &mut CssWriter<W>) -> fmt::Result
+    where
+        W: Write,
+    {
+        match *self {
+            Self::Keyword(ref keyword) => serialize_identifier(&keyword.to_string(), dest),
+            Self::Numeric(ref value) => value.to_css(dest),
+            Self::Transform(ref components) => {
+                for (i, component) in components.iter().enumerate() {
+                    if i != 0 {
+                        dest.write_char(' ')?;
+                    }
+                    component.to_css(dest)?;
+                }
+                Ok(())
+            },
+            Self::Color(ref color) => color.to_css(dest),
+            Self::Image(ref css) => write!(dest, "{}", css),
+            Self::Unparsed(ref segments) => {
+                for segment in segments.iter() {
+                    segment.to_css(dest)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+/// The reason why Typed OM values can't be set on a property, which the
+/// Typed OM reports as a `TypeError`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum TypedValueError {
+    /// The property doesn't accept this kind of value at all, like numeric
+    /// values for custom properties and shorthands, which only accept
+    /// keywords and `CSSUnparsedValue`s.
+    InvalidKind,
+    /// The values don't match the grammar of the property.
+    TypeMismatch,
 }
 
 /// Reifies a value into its Typed OM representation.
@@ -676,6 +1294,15 @@ pub enum TypedValue {
 ///   to call `.to_typed()` recursively on inner fields or variant payloads,
 ///   producing a nested [`TypedValue`] representation when possible.
 ///
+/// * Iterable fields: A single `#[css(iterable)]` field is reified as a
+///   [`TypedValue::Transform`], concatenating the components its items
+///   reify into. An empty list is reified into its `#[css(if_empty)]`
+///   keyword.
+///
+/// * Image variants: Variants marked with `#[typed_value(image)]` are
+///   reified as [`TypedValue::Image`], using the same serialization logic as
+///   [`ToCss`].
+///
 /// * Other cases: If no automatic mapping is defined or recursion is not
 ///   enabled, the derived implementation falls back to the default method,
 ///   returning `None`.
@@ -684,9 +1311,6 @@ pub enum TypedValue {
 /// forcing types that do not participate in reification to implement
 /// [`ToTyped`]. Once Typed OM coverage stabilizes, this behavior is expected
 /// to become the default (see the corresponding follow-up bug).
-///
-/// Over time, the derive may be extended to handle additional CSS value
-/// categories such as numeric, color, and transform types.
 pub trait ToTyped {
     /// Attempt to convert `self` into a [`TypedValue`].
     ///
@@ -708,11 +1332,39 @@ where
     }
 }
 
+/// Reifies a list of transform functions into a [`TypedValue::Transform`].
+///
+/// Returns `None` if the list is empty or if any of the items doesn't reify
+/// into transform components.
+pub fn to_typed_transform_list<'a, T>(items: impl Iterator<Item = &'a T>) -> Option<TypedValue>
+where
+    T: ToTyped + 'a,
+{
+    let mut components = ThinVec::new();
+    for item in items {
+        match item.to_typed()? {
+            TypedValue::Transform(c) => components.extend(c),
+            _ => return None,
+        }
+    }
+    if components.is_empty() {
+        return None;
+    }
+    Some(TypedValue::Transform(components))
+}
+
+impl<T> ToTyped for Arc<T>
+where
+    T: ?Sized + ToTyped,
+{
+    fn to_typed(&self) -> Option<TypedValue> {
+        (**self).to_typed()
+    }
+}
+
 impl ToTyped for Au {
     fn to_typed(&self) -> Option<TypedValue> {
-        let value = self.to_f32_px();
-        let unit = CssString::from("px");
-        Some(TypedValue::Numeric(NumericValue::Unit { value, unit }))
+        Some(TypedValue::Numeric(NumericValue::px(self.to_f32_px())))
     }
 }
 
@@ -720,9 +1372,7 @@ macro_rules! impl_to_typed_for_predefined_type {
     ($name: ty) => {
         impl<'a> ToTyped for $name {
             fn to_typed(&self) -> Option<TypedValue> {
-                // XXX Should return TypedValue::Numeric with unit "number"
-                // once that variant is available. Tracked in bug 1990419.
-                None
+                Some(TypedValue::Numeric(NumericValue::number(*self as f32)))
             }
         }
     };
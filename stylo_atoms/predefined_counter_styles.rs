@@ -5,7 +5,8 @@
  // THIS FILE IS DUPLICATED FROM style/counter_style/predefined.rs.
  // TO UPDATE IT:
  //   - Run `python style/counter_style/updated_predefined.py`
- //   - Re-copy style/counter_style/predefined.rs to this location
+ //   - Re-copy the names in style/counter_style/predefined.rs to this location,
+ //     without their definitions
 
 predefined! {
     "decimal",
@@ -3,14 +3,136 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
 use std::collections::HashMap;
-use std::sync::{LazyLock, RwLock};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
 
 static PREFS: LazyLock<Preferences> = LazyLock::new(Preferences::default);
 
-#[derive(Debug, Default)]
+/// The value of a preference.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PrefValue {
+    Bool(bool),
+    Int(i32),
+    Float(f32),
+    String(String),
+}
+
+impl From<bool> for PrefValue {
+    fn from(value: bool) -> Self {
+        PrefValue::Bool(value)
+    }
+}
+
+impl From<i32> for PrefValue {
+    fn from(value: i32) -> Self {
+        PrefValue::Int(value)
+    }
+}
+
+impl From<f32> for PrefValue {
+    fn from(value: f32) -> Self {
+        PrefValue::Float(value)
+    }
+}
+
+impl From<&str> for PrefValue {
+    fn from(value: &str) -> Self {
+        PrefValue::String(value.to_owned())
+    }
+}
+
+impl From<String> for PrefValue {
+    fn from(value: String) -> Self {
+        PrefValue::String(value)
+    }
+}
+
+/// A preference that Stylo reads, and the value it has when it isn't set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrefDescription {
+    pub name: &'static str,
+    pub default: PrefValue,
+}
+
+/// An error while setting preferences, or loading them from a document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PrefError {
+    /// The document couldn't be parsed.
+    Syntax(String),
+    /// The value of the given preference isn't a boolean, a 32-bit integer, a
+    /// number or a string.
+    InvalidValue(String),
+    /// The value of the given preference doesn't match the type it was
+    /// registered with.
+    TypeMismatch(String),
+}
+
+impl fmt::Display for PrefError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PrefError::Syntax(ref error) => write!(f, "invalid preferences document: {}", error),
+            PrefError::InvalidValue(ref name) => write!(f, "invalid value for pref {}", name),
+            PrefError::TypeMismatch(ref name) => {
+                write!(f, "value of the wrong type for pref {}", name)
+            },
+        }
+    }
+}
+
+impl std::error::Error for PrefError {}
+
+/// The handle of an observer added with `Preferences::add_observer`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ObserverId(usize);
+
+/// A value of a document with preferences.
+#[cfg(any(feature = "json", feature = "toml"))]
+enum DocumentValue<V> {
+    Pref(PrefValue),
+    Table(Vec<(String, V)>),
+    Invalid,
+}
+
+type Observer = Arc<dyn Fn(&str, &PrefValue) + Send + Sync>;
+
+#[derive(Default)]
 pub struct Preferences {
     bool_prefs: RwLock<HashMap<String, bool>>,
     i32_prefs: RwLock<HashMap<String, i32>>,
+    f32_prefs: RwLock<HashMap<String, f32>>,
+    string_prefs: RwLock<HashMap<String, String>>,
+    /// The default values of the registered prefs, which give their types.
+    registry: RwLock<HashMap<&'static str, PrefValue>>,
+    observers: RwLock<Vec<(ObserverId, Observer)>>,
+    next_observer_id: AtomicUsize,
+}
+
+impl fmt::Debug for Preferences {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Preferences")
+            .field("bool_prefs", &self.bool_prefs)
+            .field("i32_prefs", &self.i32_prefs)
+            .field("f32_prefs", &self.f32_prefs)
+            .field("string_prefs", &self.string_prefs)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sets a pref in the given map, and returns whether its value changed.
+fn set_pref<T: PartialEq>(prefs: &RwLock<HashMap<String, T>>, key: &str, value: T) -> bool {
+    let mut prefs = prefs.write().expect("RwLock is poisoned");
+
+    // Avoid cloning the key if it exists.
+    if let Some(pref) = prefs.get_mut(key) {
+        if *pref == value {
+            return false;
+        }
+        *pref = value;
+    } else {
+        prefs.insert(key.to_owned(), value);
+    }
+    true
 }
 
 impl Preferences {
@@ -24,26 +146,218 @@ impl Preferences {
         *prefs.get(key).unwrap_or(&default)
     }
 
-    pub fn set_bool(&self, key: &str, value: bool) {
-        let mut prefs = self.bool_prefs.write().expect("RwLock is poisoned");
+    pub fn get_f32(&self, key: &str, default: f32) -> f32 {
+        let prefs = self.f32_prefs.read().expect("RwLock is poisoned");
+        *prefs.get(key).unwrap_or(&default)
+    }
+
+    pub fn get_string(&self, key: &str, default: &str) -> String {
+        let prefs = self.string_prefs.read().expect("RwLock is poisoned");
+        prefs.get(key).map_or(default, |s| s).to_owned()
+    }
+
+    /// Returns an error if the given pref is registered with a type other
+    /// than the one of `value`.
+    fn check_type(&self, key: &str, value: &PrefValue) -> Result<(), PrefError> {
+        let registry = self.registry.read().expect("RwLock is poisoned");
+        match registry.get(key) {
+            Some(default) if std::mem::discriminant(default) != std::mem::discriminant(value) => {
+                Err(PrefError::TypeMismatch(key.to_owned()))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    pub fn set_bool(&self, key: &str, value: bool) -> Result<(), PrefError> {
+        self.check_type(key, &PrefValue::Bool(value))?;
+        if set_pref(&self.bool_prefs, key, value) {
+            self.notify(key, &PrefValue::Bool(value));
+        }
+        Ok(())
+    }
+
+    pub fn set_i32(&self, key: &str, value: i32) -> Result<(), PrefError> {
+        self.check_type(key, &PrefValue::Int(value))?;
+        if set_pref(&self.i32_prefs, key, value) {
+            self.notify(key, &PrefValue::Int(value));
+        }
+        Ok(())
+    }
+
+    pub fn set_f32(&self, key: &str, value: f32) -> Result<(), PrefError> {
+        self.check_type(key, &PrefValue::Float(value))?;
+        if set_pref(&self.f32_prefs, key, value) {
+            self.notify(key, &PrefValue::Float(value));
+        }
+        Ok(())
+    }
+
+    pub fn set_string(&self, key: &str, value: &str) -> Result<(), PrefError> {
+        let pref_value = PrefValue::String(value.to_owned());
+        self.check_type(key, &pref_value)?;
+        if set_pref(&self.string_prefs, key, value.to_owned()) {
+            self.notify(key, &pref_value);
+        }
+        Ok(())
+    }
+
+    /// Sets a pref of the type of the given value. Like the other setters,
+    /// this fails if the pref is registered with another type.
+    pub fn set(&self, key: &str, value: PrefValue) -> Result<(), PrefError> {
+        match value {
+            PrefValue::Bool(value) => self.set_bool(key, value),
+            PrefValue::Int(value) => self.set_i32(key, value),
+            PrefValue::Float(value) => self.set_f32(key, value),
+            PrefValue::String(ref value) => self.set_string(key, value),
+        }
+    }
+
+    /// Returns all the prefs that have been set, sorted by name.
+    pub fn entries(&self) -> Vec<(String, PrefValue)> {
+        fn collect<T: Clone + Into<PrefValue>>(
+            prefs: &RwLock<HashMap<String, T>>,
+            entries: &mut Vec<(String, PrefValue)>,
+        ) {
+            let prefs = prefs.read().expect("RwLock is poisoned");
+            entries.extend(prefs.iter().map(|(k, v)| (k.clone(), v.clone().into())));
+        }
+
+        let mut entries = vec![];
+        collect(&self.bool_prefs, &mut entries);
+        collect(&self.i32_prefs, &mut entries);
+        collect(&self.f32_prefs, &mut entries);
+        collect(&self.string_prefs, &mut entries);
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    /// Registers the types of the given prefs, which the values set afterwards
+    /// are checked against, and the values of documents loaded afterwards are
+    /// coerced to.
+    pub fn register(&self, prefs: impl IntoIterator<Item = PrefDescription>) {
+        let mut registry = self.registry.write().expect("RwLock is poisoned");
+        registry.extend(prefs.into_iter().map(|pref| (pref.name, pref.default)));
+    }
+
+    /// Adds a callback to run every time the value of a pref changes. Setting
+    /// a pref that wasn't set before counts as a change, even if the value is
+    /// its default.
+    ///
+    /// The callback runs on the thread that sets the pref, and can read prefs.
+    pub fn add_observer<F>(&self, observer: F) -> ObserverId
+    where
+        F: Fn(&str, &PrefValue) + Send + Sync + 'static,
+    {
+        let id = ObserverId(self.next_observer_id.fetch_add(1, Ordering::Relaxed));
+        let mut observers = self.observers.write().expect("RwLock is poisoned");
+        observers.push((id, Arc::new(observer)));
+        id
+    }
 
-        // Avoid cloning the key if it exists.
-        if let Some(pref) = prefs.get_mut(key) {
-            *pref = value;
-        } else {
-            prefs.insert(key.to_owned(), value);
+    /// Removes a callback added with `add_observer`.
+    pub fn remove_observer(&self, id: ObserverId) {
+        let mut observers = self.observers.write().expect("RwLock is poisoned");
+        observers.retain(|(observer_id, _)| *observer_id != id);
+    }
+
+    fn notify(&self, key: &str, value: &PrefValue) {
+        // Don't hold the lock while running the observers, so that they can
+        // add or remove observers.
+        let observers: Vec<_> = {
+            let observers = self.observers.read().expect("RwLock is poisoned");
+            observers.iter().map(|(_, o)| o.clone()).collect()
+        };
+        for observer in observers {
+            observer(key, value);
         }
     }
 
-    pub fn set_i32(&self, key: &str, value: i32) {
-        let mut prefs = self.i32_prefs.write().expect("RwLock is poisoned");
+    /// Converts a value of a document to the registered type of the pref, if
+    /// any.
+    #[cfg(any(feature = "json", feature = "toml"))]
+    fn coerce(&self, name: &str, value: PrefValue) -> Result<PrefValue, PrefError> {
+        let value = {
+            let registry = self.registry.read().expect("RwLock is poisoned");
+            match (registry.get(name), value) {
+                // Documents can't tell integral numbers apart from integers.
+                (Some(PrefValue::Float(..)), PrefValue::Int(i)) => PrefValue::Float(i as f32),
+                (_, value) => value,
+            }
+        };
+        self.check_type(name, &value)?;
+        Ok(value)
+    }
 
-        // Avoid cloning the key if it exists.
-        if let Some(pref) = prefs.get_mut(key) {
-            *pref = value;
-        } else {
-            prefs.insert(key.to_owned(), value);
+    /// Sets all the prefs of a document, or none of them if a value is
+    /// invalid, or doesn't match the registered type of its pref.
+    #[cfg(any(feature = "json", feature = "toml"))]
+    fn load_document<V>(
+        &self,
+        values: impl IntoIterator<Item = (String, V)>,
+        classify: fn(V) -> DocumentValue<V>,
+    ) -> Result<(), PrefError> {
+        // Flatten nested tables first, joining keys with dots.
+        let mut pending: Vec<_> = values.into_iter().collect();
+        let mut prefs = vec![];
+        while let Some((name, value)) = pending.pop() {
+            match classify(value) {
+                DocumentValue::Pref(value) => {
+                    let value = self.coerce(&name, value)?;
+                    prefs.push((name, value))
+                },
+                DocumentValue::Table(table) => pending.extend(
+                    table
+                        .into_iter()
+                        .map(|(key, value)| (format!("{}.{}", name, key), value)),
+                ),
+                DocumentValue::Invalid => return Err(PrefError::InvalidValue(name)),
+            }
         }
+        for (name, value) in prefs {
+            self.set(&name, value)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the prefs of a TOML document, where each key is either the name of
+    /// a pref or a table of prefs under that prefix, like `[layout.css]`.
+    #[cfg(feature = "toml")]
+    pub fn load_toml(&self, document: &str) -> Result<(), PrefError> {
+        let table: toml::value::Table =
+            toml::from_str(document).map_err(|e| PrefError::Syntax(e.to_string()))?;
+        self.load_document(table, |value| match value {
+            toml::Value::Boolean(b) => DocumentValue::Pref(PrefValue::Bool(b)),
+            toml::Value::Integer(i) => match i32::try_from(i) {
+                Ok(i) => DocumentValue::Pref(PrefValue::Int(i)),
+                Err(..) => DocumentValue::Invalid,
+            },
+            toml::Value::Float(f) => DocumentValue::Pref(PrefValue::Float(f as f32)),
+            toml::Value::String(s) => DocumentValue::Pref(PrefValue::String(s)),
+            toml::Value::Table(table) => DocumentValue::Table(table.into_iter().collect()),
+            toml::Value::Array(..) | toml::Value::Datetime(..) => DocumentValue::Invalid,
+        })
+    }
+
+    /// Sets the prefs of a JSON object, where each key is either the name of
+    /// a pref or an object with prefs under that prefix.
+    #[cfg(feature = "json")]
+    pub fn load_json(&self, document: &str) -> Result<(), PrefError> {
+        let object: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(document).map_err(|e| PrefError::Syntax(e.to_string()))?;
+        self.load_document(object, |value| match value {
+            serde_json::Value::Bool(b) => DocumentValue::Pref(PrefValue::Bool(b)),
+            serde_json::Value::Number(n) => match (n.as_i64(), n.as_f64()) {
+                (Some(i), _) => match i32::try_from(i) {
+                    Ok(i) => DocumentValue::Pref(PrefValue::Int(i)),
+                    Err(..) => DocumentValue::Invalid,
+                },
+                (None, Some(f)) => DocumentValue::Pref(PrefValue::Float(f as f32)),
+                (None, None) => DocumentValue::Invalid,
+            },
+            serde_json::Value::String(s) => DocumentValue::Pref(PrefValue::String(s)),
+            serde_json::Value::Object(object) => DocumentValue::Table(object.into_iter().collect()),
+            serde_json::Value::Null | serde_json::Value::Array(..) => DocumentValue::Invalid,
+        })
     }
 }
 
@@ -55,14 +369,63 @@ pub fn get_i32(key: &str, default: i32) -> i32 {
     PREFS.get_i32(key, default)
 }
 
-pub fn set_bool(key: &str, value: bool) {
+pub fn get_f32(key: &str, default: f32) -> f32 {
+    PREFS.get_f32(key, default)
+}
+
+pub fn get_string(key: &str, default: &str) -> String {
+    PREFS.get_string(key, default)
+}
+
+pub fn set_bool(key: &str, value: bool) -> Result<(), PrefError> {
     PREFS.set_bool(key, value)
 }
 
-pub fn set_i32(key: &str, value: i32) {
+pub fn set_i32(key: &str, value: i32) -> Result<(), PrefError> {
     PREFS.set_i32(key, value)
 }
 
+pub fn set_f32(key: &str, value: f32) -> Result<(), PrefError> {
+    PREFS.set_f32(key, value)
+}
+
+pub fn set_string(key: &str, value: &str) -> Result<(), PrefError> {
+    PREFS.set_string(key, value)
+}
+
+pub fn set(key: &str, value: PrefValue) -> Result<(), PrefError> {
+    PREFS.set(key, value)
+}
+
+pub fn entries() -> Vec<(String, PrefValue)> {
+    PREFS.entries()
+}
+
+pub fn register(prefs: impl IntoIterator<Item = PrefDescription>) {
+    PREFS.register(prefs)
+}
+
+pub fn add_observer<F>(observer: F) -> ObserverId
+where
+    F: Fn(&str, &PrefValue) + Send + Sync + 'static,
+{
+    PREFS.add_observer(observer)
+}
+
+pub fn remove_observer(id: ObserverId) {
+    PREFS.remove_observer(id)
+}
+
+#[cfg(feature = "toml")]
+pub fn load_toml(document: &str) -> Result<(), PrefError> {
+    PREFS.load_toml(document)
+}
+
+#[cfg(feature = "json")]
+pub fn load_json(document: &str) -> Result<(), PrefError> {
+    PREFS.load_json(document)
+}
+
 pub trait Getter {
     fn get(key: &str, default: Self) -> Self;
 }
@@ -79,6 +442,12 @@ impl Getter for i32 {
     }
 }
 
+impl Getter for f32 {
+    fn get(key: &str, default: Self) -> Self {
+        get_f32(key, default)
+    }
+}
+
 #[test]
 fn test() {
     let prefs = Preferences::default();
@@ -91,15 +460,15 @@ fn test() {
     assert_eq!(prefs.get_i32("bar", 2), 2);
 
     // Prefs can be set and retrieved.
-    prefs.set_bool("foo", true);
-    prefs.set_i32("bar", 1);
+    prefs.set_bool("foo", true).unwrap();
+    prefs.set_i32("bar", 1).unwrap();
     assert_eq!(prefs.get_bool("foo", false), true);
     assert_eq!(prefs.get_bool("foo", true), true);
     assert_eq!(prefs.get_i32("bar", 0), 1);
     assert_eq!(prefs.get_i32("bar", 1), 1);
     assert_eq!(prefs.get_i32("bar", 2), 1);
-    prefs.set_bool("foo", false);
-    prefs.set_i32("bar", 2);
+    prefs.set_bool("foo", false).unwrap();
+    prefs.set_i32("bar", 2).unwrap();
     assert_eq!(prefs.get_bool("foo", false), false);
     assert_eq!(prefs.get_bool("foo", true), false);
     assert_eq!(prefs.get_i32("bar", 0), 2);
@@ -107,10 +476,176 @@ fn test() {
     assert_eq!(prefs.get_i32("bar", 2), 2);
 
     // Each value type currently has an independent namespace.
-    prefs.set_i32("foo", 3);
-    prefs.set_bool("bar", true);
+    prefs.set_i32("foo", 3).unwrap();
+    prefs.set_bool("bar", true).unwrap();
     assert_eq!(prefs.get_i32("foo", 0), 3);
     assert_eq!(prefs.get_bool("foo", false), false);
     assert_eq!(prefs.get_bool("bar", false), true);
     assert_eq!(prefs.get_i32("bar", 0), 2);
 }
+
+#[test]
+fn test_typed_and_observed() {
+    use std::sync::Mutex;
+
+    let prefs = Preferences::default();
+    assert_eq!(prefs.get_f32("ratio", 0.5), 0.5);
+    assert_eq!(prefs.get_string("family", "serif"), "serif");
+
+    let changes = Arc::new(Mutex::new(vec![]));
+    let id = prefs.add_observer({
+        let changes = changes.clone();
+        move |key, value| {
+            changes
+                .lock()
+                .unwrap()
+                .push((key.to_owned(), value.clone()))
+        }
+    });
+    prefs.set_f32("ratio", 1.5).unwrap();
+    prefs.set_string("family", "monospace").unwrap();
+    prefs
+        .set("layout.css.foo.enabled", PrefValue::Bool(true))
+        .unwrap();
+    // Setting the same value again is not a change.
+    prefs.set_f32("ratio", 1.5).unwrap();
+    prefs.remove_observer(id);
+    prefs.set_f32("ratio", 2.).unwrap();
+
+    assert_eq!(prefs.get_f32("ratio", 0.5), 2.);
+    assert_eq!(prefs.get_string("family", "serif"), "monospace");
+    assert_eq!(
+        *changes.lock().unwrap(),
+        [
+            ("ratio".to_owned(), PrefValue::Float(1.5)),
+            ("family".to_owned(), PrefValue::from("monospace")),
+            ("layout.css.foo.enabled".to_owned(), PrefValue::Bool(true)),
+        ]
+    );
+    assert_eq!(
+        prefs.entries(),
+        [
+            ("family".to_owned(), PrefValue::from("monospace")),
+            ("layout.css.foo.enabled".to_owned(), PrefValue::Bool(true)),
+            ("ratio".to_owned(), PrefValue::Float(2.)),
+        ]
+    );
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_load_toml() {
+    let prefs = Preferences::default();
+    prefs
+        .load_toml(
+            r#"
+"layout.threads" = 4
+[layout.css]
+"scroll-state.enabled" = true
+zoom = 1.25
+font-family = "serif"
+"#,
+        )
+        .unwrap();
+    assert_eq!(prefs.get_i32("layout.threads", -1), 4);
+    assert!(prefs.get_bool("layout.css.scroll-state.enabled", false));
+    assert_eq!(prefs.get_f32("layout.css.zoom", 1.), 1.25);
+    assert_eq!(prefs.get_string("layout.css.font-family", ""), "serif");
+
+    // Invalid documents don't set anything.
+    assert_eq!(
+        prefs.load_toml("a = true\nb = [1]"),
+        Err(PrefError::InvalidValue("b".to_owned()))
+    );
+    assert!(!prefs.get_bool("a", false));
+    assert!(matches!(
+        prefs.load_toml("a = "),
+        Err(PrefError::Syntax(..))
+    ));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_load_registered_toml() {
+    let prefs = Preferences::default();
+    prefs.register([
+        PrefDescription {
+            name: "layout.css.zoom",
+            default: PrefValue::Float(1.),
+        },
+        PrefDescription {
+            name: "layout.grid.enabled",
+            default: PrefValue::Bool(false),
+        },
+    ]);
+
+    // Integers are coerced to numbers.
+    prefs.load_toml("layout.css.zoom = 2").unwrap();
+    assert_eq!(prefs.get_f32("layout.css.zoom", 1.), 2.);
+    assert_eq!(prefs.get_i32("layout.css.zoom", 0), 0);
+
+    // Other mismatches are rejected, and nothing is set.
+    assert_eq!(
+        prefs.load_toml("layout.css.zoom = 3\nlayout.grid.enabled = 1"),
+        Err(PrefError::TypeMismatch("layout.grid.enabled".to_owned()))
+    );
+    assert_eq!(prefs.get_f32("layout.css.zoom", 1.), 2.);
+    assert_eq!(
+        prefs.load_toml("layout.css.zoom = \"2\""),
+        Err(PrefError::TypeMismatch("layout.css.zoom".to_owned()))
+    );
+
+    // Prefs that aren't registered keep the type of their value.
+    prefs.load_toml("layout.threads = 4").unwrap();
+    assert_eq!(prefs.get_i32("layout.threads", -1), 4);
+
+    // Setters check the registered type too.
+    assert_eq!(
+        prefs.set_i32("layout.grid.enabled", 1),
+        Err(PrefError::TypeMismatch("layout.grid.enabled".to_owned()))
+    );
+    assert_eq!(
+        prefs.set("layout.css.zoom", PrefValue::Bool(true)),
+        Err(PrefError::TypeMismatch("layout.css.zoom".to_owned()))
+    );
+    assert_eq!(prefs.get_i32("layout.grid.enabled", 0), 0);
+    prefs.set_bool("layout.grid.enabled", true).unwrap();
+    assert!(prefs.get_bool("layout.grid.enabled", false));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_load_json() {
+    let prefs = Preferences::default();
+    prefs
+        .load_json(
+            r#"{ "layout": { "css.zoom": 1.25, "threads": 4 }, "layout.grid.enabled": true }"#,
+        )
+        .unwrap();
+    assert_eq!(prefs.get_f32("layout.css.zoom", 1.), 1.25);
+    assert_eq!(prefs.get_i32("layout.threads", -1), 4);
+    assert!(prefs.get_bool("layout.grid.enabled", false));
+
+    assert_eq!(
+        prefs.load_json(r#"{ "a": true, "b": null }"#),
+        Err(PrefError::InvalidValue("b".to_owned()))
+    );
+    assert!(!prefs.get_bool("a", false));
+    assert!(matches!(prefs.load_json("{"), Err(PrefError::Syntax(..))));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_load_registered_json() {
+    let prefs = Preferences::default();
+    prefs.register([PrefDescription {
+        name: "layout.css.zoom",
+        default: PrefValue::Float(1.),
+    }]);
+    prefs.load_json(r#"{ "layout.css.zoom": 2 }"#).unwrap();
+    assert_eq!(prefs.get_f32("layout.css.zoom", 1.), 2.);
+    assert_eq!(
+        prefs.load_json(r#"{ "layout.css.zoom": true }"#),
+        Err(PrefError::TypeMismatch("layout.css.zoom".to_owned()))
+    );
+}
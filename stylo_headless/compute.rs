@@ -0,0 +1,340 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! Computes the style of every element of an HTML-like fixture, and prints it
+//! as JSON.
+//!
+//! See `stylo-compute --help` for usage.
+
+use cssparser::ToCss;
+use euclid::{Scale, Size2D};
+use serde_json::{Map, Value};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use style::context::QuirksMode;
+use style::dom::{TDocument, TElement, TNode, TShadowRoot};
+use style::media_queries::MediaType;
+use style::properties::style_structs::Font;
+use style::properties::{ComputedValues, OwnedPropertyDeclarationId, PropertyId, ShorthandId};
+use style::queries::values::PrefersColorScheme;
+use style::selector_parser::{PseudoElement, EAGER_PSEUDO_COUNT};
+use style::servo::media_queries::Device;
+use style::shared_lock::StylesheetGuards;
+use style::stylesheets::Origin;
+use style::stylist::Stylist;
+use stylo_headless::fixture::{self, Fixture};
+use stylo_headless::{DefaultFontMetricsProvider, NodeRef};
+
+const USAGE: &str = "\
+Usage: stylo-compute [OPTIONS] <FIXTURE> [STYLESHEET...]
+
+Computes the style of every element in FIXTURE, an HTML-like file whose
+<style> elements and the given author STYLESHEETs apply, and prints it as JSON.
+
+Options:
+  -p, --property <NAME>       Only print this property (repeatable). Shorthands
+                              print their longhands, and custom properties are
+                              supported.
+      --user-agent <FILE>     Add a user-agent stylesheet (repeatable)
+      --user <FILE>           Add a user stylesheet (repeatable)
+      --viewport <WxH>        Viewport size in CSS pixels [default: 800x600]
+      --dpr <RATIO>           Device pixel ratio [default: 1]
+      --media <TYPE>          Media type, `screen` or `print` [default: screen]
+      --color-scheme <SCHEME> `light` or `dark` [default: light]
+      --reduced-motion        Match `prefers-reduced-motion: reduce`
+      --quirks                Style the fixture in quirks mode
+  -h, --help                  Print this message
+";
+
+/// The parsed command line.
+struct Options {
+    fixture: PathBuf,
+    author_sheets: Vec<PathBuf>,
+    user_agent_sheets: Vec<PathBuf>,
+    user_sheets: Vec<PathBuf>,
+    properties: Vec<String>,
+    viewport: Size2D<f32, style_traits::CSSPixel>,
+    device_pixel_ratio: f32,
+    media_type: MediaType,
+    color_scheme: PrefersColorScheme,
+    reduced_motion: bool,
+    quirks_mode: QuirksMode,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut options = Options {
+            fixture: PathBuf::new(),
+            author_sheets: vec![],
+            user_agent_sheets: vec![],
+            user_sheets: vec![],
+            properties: vec![],
+            viewport: Size2D::new(800., 600.),
+            device_pixel_ratio: 1.,
+            media_type: MediaType::screen(),
+            color_scheme: PrefersColorScheme::Light,
+            reduced_motion: false,
+            quirks_mode: QuirksMode::NoQuirks,
+        };
+        let mut positional = vec![];
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("Missing value for {}", name))
+            };
+            match &*arg {
+                "-h" | "--help" => return Ok(None),
+                "-p" | "--property" => options.properties.push(value(&arg)?),
+                "--user-agent" => options.user_agent_sheets.push(value(&arg)?.into()),
+                "--user" => options.user_sheets.push(value(&arg)?.into()),
+                "--viewport" => {
+                    let size = value(&arg)?;
+                    let (width, height) = size
+                        .split_once('x')
+                        .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+                        .ok_or_else(|| format!("Invalid viewport size: {}", size))?;
+                    options.viewport = Size2D::new(width, height);
+                },
+                "--dpr" => {
+                    let ratio = value(&arg)?;
+                    options.device_pixel_ratio = ratio
+                        .parse()
+                        .map_err(|_| format!("Invalid device pixel ratio: {}", ratio))?;
+                },
+                "--media" => {
+                    options.media_type = match &*value(&arg)? {
+                        "screen" => MediaType::screen(),
+                        "print" => MediaType::print(),
+                        other => return Err(format!("Unknown media type: {}", other)),
+                    }
+                },
+                "--color-scheme" => {
+                    options.color_scheme = match &*value(&arg)? {
+                        "light" => PrefersColorScheme::Light,
+                        "dark" => PrefersColorScheme::Dark,
+                        other => return Err(format!("Unknown color scheme: {}", other)),
+                    }
+                },
+                "--reduced-motion" => options.reduced_motion = true,
+                "--quirks" => options.quirks_mode = QuirksMode::Quirks,
+                _ if arg.starts_with('-') && arg.len() > 1 => {
+                    return Err(format!("Unknown option: {}", arg));
+                },
+                _ => positional.push(PathBuf::from(arg)),
+            }
+        }
+        let mut positional = positional.into_iter();
+        options.fixture = positional.next().ok_or("Missing fixture")?;
+        options.author_sheets.extend(positional);
+        Ok(Some(options))
+    }
+
+    fn device(&self) -> Device {
+        let mut device = Device::new(
+            self.media_type.clone(),
+            self.quirks_mode,
+            self.viewport,
+            Scale::new(self.device_pixel_ratio),
+            Box::new(DefaultFontMetricsProvider),
+            ComputedValues::initial_values_with_font_override(Font::initial_values()),
+            self.color_scheme,
+        );
+        device.set_prefers_reduced_motion(self.reduced_motion);
+        device
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("Couldn't read {}: {}", path.display(), e))
+}
+
+fn url_for(path: &Path) -> url::Url {
+    std::fs::canonicalize(path)
+        .ok()
+        .and_then(|path| url::Url::from_file_path(path).ok())
+        .unwrap_or_else(|| url::Url::parse("about:blank").unwrap())
+}
+
+/// The properties to print for each element.
+struct Properties(Vec<OwnedPropertyDeclarationId>);
+
+impl Properties {
+    fn new(names: &[String]) -> Result<Self, String> {
+        if names.is_empty() {
+            let mut longhands: Vec<_> = ShorthandId::All
+                .longhands()
+                .filter(|longhand| !longhand.is_logical())
+                .collect();
+            longhands.sort_by_key(|longhand| longhand.name());
+            return Ok(Properties(
+                longhands
+                    .into_iter()
+                    .map(OwnedPropertyDeclarationId::Longhand)
+                    .collect(),
+            ));
+        }
+        let mut properties = vec![];
+        for name in names {
+            let id = PropertyId::parse_enabled_for_all_content(name)
+                .map_err(|_| format!("Unknown property: {}", name))?;
+            match id.as_shorthand() {
+                Ok(shorthand) => properties.extend(
+                    shorthand
+                        .longhands()
+                        .map(OwnedPropertyDeclarationId::Longhand),
+                ),
+                Err(id) => properties.push(id.to_owned()),
+            }
+        }
+        Ok(Properties(properties))
+    }
+
+    fn to_json(&self, style: &ComputedValues) -> Value {
+        let mut values = Map::with_capacity(self.0.len());
+        for property in self.0.iter() {
+            let id = property.as_borrowed();
+            values.insert(
+                id.name().into_owned(),
+                style.computed_value_to_string(id).into(),
+            );
+        }
+        Value::Object(values)
+    }
+}
+
+/// Returns a short, human-readable description of a node for the element
+/// paths.
+fn describe(node: NodeRef) -> String {
+    let Some(element) = node.as_element() else {
+        return "#shadow-root".to_owned();
+    };
+    let mut description = element.local_name().to_string();
+    if let Some(id) = element.id() {
+        write!(description, "#{}", id).unwrap();
+    }
+    element.each_class(|class| write!(description, ".{}", class.0).unwrap());
+    description
+}
+
+/// Writes the element entries of `node`'s subtree, in tree order, including
+/// shadow trees.
+fn write_subtree(node: NodeRef, path: &str, properties: &Properties, entries: &mut Vec<Value>) {
+    let path = match node.as_element() {
+        Some(element) => {
+            let path = if path.is_empty() {
+                describe(node)
+            } else {
+                format!("{} > {}", path, describe(node))
+            };
+            entries.push(element_entry(node, &path, properties));
+            if let Some(shadow_root) = element.shadow_root() {
+                let shadow_path = format!("{} > #shadow-root", path);
+                for child in shadow_root.as_node().dom_children() {
+                    write_subtree(child, &shadow_path, properties, entries);
+                }
+            }
+            path
+        },
+        None => path.to_owned(),
+    };
+    for child in node.dom_children() {
+        write_subtree(child, &path, properties, entries);
+    }
+}
+
+fn element_entry(node: NodeRef, path: &str, properties: &Properties) -> Value {
+    let element = node.as_element().unwrap();
+    let mut entry = Map::new();
+    entry.insert("node".into(), node.id().index().into());
+    entry.insert("path".into(), path.into());
+    let Some(style) = element.style() else {
+        entry.insert("style".into(), Value::Null);
+        return Value::Object(entry);
+    };
+    entry.insert("style".into(), properties.to_json(&style));
+
+    let data = element.borrow_data().unwrap();
+    let pseudos: Map<_, _> = (0..EAGER_PSEUDO_COUNT)
+        .map(PseudoElement::from_eager_index)
+        .filter_map(|pseudo| {
+            let style = data.styles.pseudos.get(&pseudo)?;
+            Some((pseudo.to_css_string(), properties.to_json(style)))
+        })
+        .collect();
+    if !pseudos.is_empty() {
+        entry.insert("pseudos".into(), Value::Object(pseudos));
+    }
+    Value::Object(entry)
+}
+
+fn run(options: Options) -> Result<String, String> {
+    let properties = Properties::new(&options.properties)?;
+    let Fixture {
+        mut document,
+        stylesheets,
+    } = fixture::parse(
+        &read(&options.fixture)?,
+        url_for(&options.fixture),
+        options.quirks_mode,
+    );
+
+    let mut stylist = Stylist::new(options.device(), options.quirks_mode);
+    {
+        let origins = [
+            (Origin::UserAgent, &options.user_agent_sheets),
+            (Origin::User, &options.user_sheets),
+            (Origin::Author, &options.author_sheets),
+        ];
+        let mut sheets = vec![];
+        for (origin, paths) in origins {
+            for path in paths.iter() {
+                sheets.push(document.parse_stylesheet(&read(path)?, origin));
+            }
+        }
+        // Sheets from the fixture come before the author sheets from the
+        // command line.
+        let author_index = sheets.len() - options.author_sheets.len();
+        sheets.splice(author_index..author_index, stylesheets);
+
+        let guard = document.shared_lock().read();
+        for sheet in sheets {
+            stylist.append_stylesheet(sheet, &guard);
+        }
+        stylist.flush(&StylesheetGuards::same(&guard));
+    }
+    document.flush_shadow_root_styles(&mut stylist);
+    document.restyle(&stylist, None);
+
+    let mut entries = vec![];
+    write_subtree(document.document().as_node(), "", &properties, &mut entries);
+    let mut output = serde_json::to_string_pretty(&entries).unwrap();
+    output.push('\n');
+    Ok(output)
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        },
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            return ExitCode::from(2);
+        },
+    };
+    match run(options) {
+        Ok(output) => {
+            // Ignore errors from closed pipes, like when piping into `head`.
+            let _ = std::io::Write::write_all(&mut std::io::stdout(), output.as_bytes());
+            ExitCode::SUCCESS
+        },
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,2558 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! An arena-based DOM implementing the traits the style system acts over.
+//!
+//! All the nodes of a [`Document`] live in a single arena, and are referred to
+//! by [`NodeId`]. The style system gets `Copy` handles (`NodeRef`,
+//! `ElementRef`, `DocumentRef` and `ShadowRootRef`) that borrow the document
+//! for the duration of a traversal.
+
+#![allow(unsafe_code)]
+
+use crate::traversal::RecalcStyle;
+use atomic_refcell::{AtomicRef, AtomicRefCell, AtomicRefMut};
+use bitflags::bitflags;
+use dom::ElementState;
+use euclid::default::Size2D;
+use selectors::attr::{AttrSelectorOperation, CaseSensitivity, NamespaceConstraint};
+use selectors::bloom::{BloomFilter, BLOOM_HASH_MASK};
+use selectors::matching::{
+    ElementSelectorFlags, MatchingContext, SelectorCaches, VisitedHandlingMode,
+};
+use selectors::sink::Push;
+use selectors::{CellColumns, Element, OpaqueElement};
+use servo_arc::{Arc, ArcBorrow};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicIsize, AtomicU32, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use style::applicable_declarations::ApplicableDeclarationBlock;
+use style::attr::{AttrIdentifier, AttrValue};
+use style::author_styles::AuthorStyles;
+use style::bloom::each_relevant_element_hash;
+use style::context::{
+    QuirksMode, RegisteredSpeculativePainter, RegisteredSpeculativePainters, SharedStyleContext,
+    StyleSystemOptions,
+};
+use style::data::ElementData;
+use style::dom::{
+    AttributeProvider, DomChildren, LayoutIterator, NodeInfo, OpaqueNode, TDocument, TElement,
+    TNode, TShadowRoot,
+};
+use style::invalidation::element::restyle_hints::RestyleHint;
+use style::invalidation::element::state_and_attributes::StateAndAttrInvalidationProcessor;
+use style::invalidation::element::table_columns;
+use style::media_queries::MediaList;
+use style::properties::{parse_style_attribute, ComputedValues, PropertyDeclarationBlock};
+use style::rule_tree::CascadeLevel;
+use style::selector_parser::{
+    extended_filtering, AttrValue as SelectorAttrValue, Lang, NonTSPseudoClass, PseudoElement,
+    SelectorImpl, SnapshotMap,
+};
+use style::servo::animation::{AnimationSetKey, DocumentAnimationSet};
+use style::servo::selector_parser::ServoElementSnapshot;
+use style::servo::view_transition::CapturedElement;
+use style::shared_lock::{Locked, SharedRwLock, SharedRwLockReadGuard, StylesheetGuards};
+use style::stylesheets::container_rule::ContainerScrollState;
+use style::stylesheets::layer_rule::LayerOrder;
+use style::stylesheets::{
+    AllowImportRules, CssRuleType, DocumentStyleSheet, Origin, Stylesheet, UrlExtraData,
+};
+use style::stylist::{CascadeData, Stylist};
+use style::traversal::DomTraversal;
+use style::traversal_flags::TraversalFlags;
+use style::values::computed::Display;
+use style::values::{AtomIdent, AtomString};
+use style::{driver, Atom, CaseSensitivityExt, LocalName, Namespace};
+use unicode_bidi::{bidi_class, BidiClass};
+use web_atoms::{local_name, ns};
+
+/// The index of a node in the arena of a [`Document`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    /// The id of the document node, which is always the first node of the
+    /// arena.
+    pub const DOCUMENT: NodeId = NodeId(0);
+
+    /// The index of this node in the arena.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+bitflags! {
+    /// Flags stored on every node, which may be mutated during the traversal.
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+    struct NodeFlags: u32 {
+        /// Whether a descendant of this node needs to be traversed.
+        const DIRTY_DESCENDANTS = 1 << 0;
+        /// Whether this element has a snapshot in the document's snapshot map.
+        const HAS_SNAPSHOT = 1 << 1;
+        /// Whether the snapshot of this element has been handled.
+        const HANDLED_SNAPSHOT = 1 << 2;
+    }
+}
+
+/// The data of an element node.
+struct ElementNode {
+    local_name: web_atoms::LocalName,
+    namespace: web_atoms::Namespace,
+    attrs: Vec<(AttrIdentifier, AttrValue)>,
+    id: Option<Atom>,
+    style_attribute: Option<Arc<Locked<PropertyDeclarationBlock>>>,
+    state: ElementState,
+    custom_states: Vec<AtomIdent>,
+    scroll_state: ContainerScrollState,
+    shadow_root: Option<NodeId>,
+    assigned_slot: Option<NodeId>,
+    assigned_nodes: Vec<NodeId>,
+    implemented_pseudo: Option<PseudoElement>,
+    selector_flags: AtomicUsize,
+    children_to_process: AtomicIsize,
+    data: AtomicRefCell<Option<ElementData>>,
+    /// The column layout of a `<table>` element, computed when selectors
+    /// first need it and reset when the structure of the table changes.
+    table_layout: OnceLock<TableLayout>,
+}
+
+/// The columns of a table, and the columns spanned by each of its cells.
+struct TableLayout {
+    /// The column elements of the table, one per column it defines.
+    ///
+    /// Column groups without `col` children define columns, but aren't
+    /// column elements themselves.
+    columns: Vec<Option<NodeId>>,
+    /// The first column and the number of columns of each cell.
+    cells: HashMap<NodeId, (u32, u32)>,
+    /// The number of columns of the table.
+    column_count: u32,
+}
+
+/// The state of the `dir` attribute of an HTML element.
+#[derive(Clone, Copy, PartialEq)]
+enum DirAttribute {
+    Ltr,
+    Rtl,
+    Auto,
+}
+
+/// The data of a shadow root node.
+struct ShadowRootNode {
+    host: NodeId,
+    styles: AuthorStyles<DocumentStyleSheet>,
+}
+
+enum NodeKind {
+    Document,
+    Text(String),
+    Element(ElementNode),
+    ShadowRoot(ShadowRootNode),
+}
+
+struct Node {
+    id: NodeId,
+    owner: *const DocumentData,
+    parent: Option<NodeId>,
+    first_child: Option<NodeId>,
+    last_child: Option<NodeId>,
+    prev_sibling: Option<NodeId>,
+    next_sibling: Option<NodeId>,
+    flags: AtomicU32,
+    kind: NodeKind,
+}
+
+// The owner pointer is only dereferenced through handles that borrow the
+// document, and everything else in a node is thread-safe.
+unsafe impl Send for Node {}
+unsafe impl Sync for Node {}
+
+impl Node {
+    fn new(id: NodeId, owner: *const DocumentData, kind: NodeKind) -> Self {
+        Self {
+            id,
+            owner,
+            parent: None,
+            first_child: None,
+            last_child: None,
+            prev_sibling: None,
+            next_sibling: None,
+            flags: AtomicU32::new(0),
+            kind,
+        }
+    }
+
+    #[inline]
+    fn flags(&self) -> NodeFlags {
+        NodeFlags::from_bits_retain(self.flags.load(Ordering::Relaxed))
+    }
+
+    #[inline]
+    fn insert_flags(&self, flags: NodeFlags) {
+        self.flags.fetch_or(flags.bits(), Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn remove_flags(&self, flags: NodeFlags) {
+        self.flags.fetch_and(!flags.bits(), Ordering::Relaxed);
+    }
+
+    fn element(&self) -> Option<&ElementNode> {
+        match self.kind {
+            NodeKind::Element(ref element) => Some(element),
+            _ => None,
+        }
+    }
+
+    fn element_mut(&mut self) -> Option<&mut ElementNode> {
+        match self.kind {
+            NodeKind::Element(ref mut element) => Some(element),
+            _ => None,
+        }
+    }
+}
+
+/// A `RegisteredSpeculativePainters` implementation with no painters.
+struct NoPainters;
+
+impl RegisteredSpeculativePainters for NoPainters {
+    fn get(&self, _: &Atom) -> Option<&dyn RegisteredSpeculativePainter> {
+        None
+    }
+}
+
+/// The data of a document, boxed so that nodes can point back to it.
+///
+/// Nodes are boxed too, so that their address, which is used as their
+/// `OpaqueNode`, is stable while the arena grows.
+#[allow(clippy::vec_box)]
+struct DocumentData {
+    nodes: Vec<Box<Node>>,
+    shared_lock: SharedRwLock,
+    url_data: UrlExtraData,
+    quirks_mode: QuirksMode,
+    is_html_document: bool,
+    snapshots: SnapshotMap,
+    animations: DocumentAnimationSet,
+    current_time_for_animations: f64,
+    view_transition_rules: Vec<(PseudoElement, Arc<Locked<PropertyDeclarationBlock>>)>,
+}
+
+impl DocumentData {
+    #[inline]
+    fn node_data(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0]
+    }
+
+    #[inline]
+    fn node(&self, id: NodeId) -> NodeRef<'_> {
+        NodeRef {
+            node: self.node_data(id),
+        }
+    }
+
+    #[inline]
+    fn element(&self, id: NodeId) -> Option<ElementRef<'_>> {
+        self.node(id).as_element()
+    }
+}
+
+/// An in-memory document, owning all its nodes.
+pub struct Document {
+    data: Box<DocumentData>,
+}
+
+impl Document {
+    /// Creates an empty HTML document with the given URL.
+    pub fn new(url: url::Url, quirks_mode: QuirksMode) -> Self {
+        let mut document = Self {
+            data: Box::new(DocumentData {
+                nodes: vec![],
+                shared_lock: SharedRwLock::new(),
+                url_data: UrlExtraData::from(url),
+                quirks_mode,
+                is_html_document: true,
+                snapshots: SnapshotMap::new(),
+                animations: DocumentAnimationSet::default(),
+                current_time_for_animations: 0.0,
+                view_transition_rules: vec![],
+            }),
+        };
+        document.push(NodeKind::Document);
+        document
+    }
+
+    /// Creates an empty XML document with the given URL.
+    pub fn new_xml(url: url::Url) -> Self {
+        let mut document = Self::new(url, QuirksMode::NoQuirks);
+        document.data.is_html_document = false;
+        document
+    }
+
+    /// The lock that protects the style attributes of this document.
+    ///
+    /// Stylesheets used to style this document need to be created with this
+    /// lock too.
+    #[inline]
+    pub fn shared_lock(&self) -> &SharedRwLock {
+        &self.data.shared_lock
+    }
+
+    /// The URL data used to parse style attributes.
+    #[inline]
+    pub fn url_data(&self) -> &UrlExtraData {
+        &self.data.url_data
+    }
+
+    /// The quirks mode of this document.
+    #[inline]
+    pub fn quirks_mode(&self) -> QuirksMode {
+        self.data.quirks_mode
+    }
+
+    /// The CSS animations, transitions and script animations of this
+    /// document, keyed by the `OpaqueNode` of their target.
+    #[inline]
+    pub fn animations(&self) -> &DocumentAnimationSet {
+        &self.data.animations
+    }
+
+    /// The current value of the animation timeline, in seconds.
+    #[inline]
+    pub fn current_time_for_animations(&self) -> f64 {
+        self.data.current_time_for_animations
+    }
+
+    /// Sets the elements captured by the active view transition, whose
+    /// dynamic rules apply to the named view transition pseudo-elements. An
+    /// empty slice ends the transition.
+    ///
+    /// This needs to be called again when the geometry of the captured
+    /// elements changes.
+    pub fn set_view_transition(&mut self, captured_elements: &[CapturedElement]) {
+        let lock = &self.data.shared_lock;
+        self.data.view_transition_rules = captured_elements
+            .iter()
+            .flat_map(|element| {
+                [
+                    Some(element.group()),
+                    Some(element.image_pair()),
+                    element.old_image(),
+                    element.new_image(),
+                ]
+                .into_iter()
+                .flatten()
+                .filter_map(move |pseudo| {
+                    let block = element.dynamic_rule(&pseudo)?;
+                    Some((pseudo, Arc::new(lock.wrap(block))))
+                })
+            })
+            .collect();
+    }
+
+    /// Advances the animation timeline to the given time, and restyles every
+    /// element with animations.
+    ///
+    /// This also needs to be called after creating or modifying script
+    /// animations, so that their targets pick up the changes.
+    pub fn set_current_time_for_animations(&mut self, time: f64) {
+        self.data.current_time_for_animations = time;
+
+        let animating_nodes: HashSet<OpaqueNode> = self
+            .data
+            .animations
+            .sets
+            .read()
+            .keys()
+            .map(|key| key.node)
+            .collect();
+        let animating_elements: Vec<NodeId> = self
+            .data
+            .nodes
+            .iter()
+            .filter(|node| node.element().is_some())
+            .map(|node| node.id)
+            .filter(|&id| animating_nodes.contains(&self.node(id).opaque()))
+            .collect();
+        for element in animating_elements {
+            self.note_restyle(element, RestyleHint::RESTYLE_SELF);
+        }
+    }
+
+    /// Parses a stylesheet that can be used to style this document, either
+    /// through a `Stylist` or a shadow root.
+    pub fn parse_stylesheet(&self, css: &str, origin: Origin) -> DocumentStyleSheet {
+        let media = Arc::new(self.data.shared_lock.wrap(MediaList::empty()));
+        DocumentStyleSheet(Arc::new(Stylesheet::from_str(
+            css,
+            self.data.url_data.clone(),
+            origin,
+            media,
+            self.data.shared_lock.clone(),
+            None,
+            None,
+            self.data.quirks_mode,
+            AllowImportRules::Yes,
+        )))
+    }
+
+    /// Returns a handle to the document node.
+    #[inline]
+    pub fn document(&self) -> DocumentRef<'_> {
+        DocumentRef(self.node(NodeId::DOCUMENT))
+    }
+
+    /// Returns a handle to the given node.
+    #[inline]
+    pub fn node(&self, id: NodeId) -> NodeRef<'_> {
+        self.data.node(id)
+    }
+
+    /// Returns a handle to the given element, or `None` if `id` is not an
+    /// element.
+    #[inline]
+    pub fn element(&self, id: NodeId) -> Option<ElementRef<'_>> {
+        self.node(id).as_element()
+    }
+
+    /// Returns the root element of this document, if any.
+    pub fn root_element(&self) -> Option<ElementRef<'_>> {
+        self.node(NodeId::DOCUMENT)
+            .dom_children()
+            .find_map(|child| child.as_element())
+    }
+
+    /// Returns the primary style of the given element, if it has been styled.
+    pub fn style(&self, id: NodeId) -> Option<Arc<ComputedValues>> {
+        let element = self.element(id)?;
+        let data = element.borrow_data()?;
+        data.styles.get_primary().cloned()
+    }
+
+    /// Returns the style of an eager pseudo-element of the given element, if
+    /// it has been styled and the pseudo-element matched any rule.
+    pub fn pseudo_style(&self, id: NodeId, pseudo: &PseudoElement) -> Option<Arc<ComputedValues>> {
+        let element = self.element(id)?;
+        let data = element.borrow_data()?;
+        data.styles.pseudos.get(pseudo).cloned()
+    }
+
+    /// The snapshots of the elements that changed since the last restyle.
+    #[inline]
+    pub fn snapshots(&self) -> &SnapshotMap {
+        &self.data.snapshots
+    }
+
+    #[inline]
+    fn node_data(&self, id: NodeId) -> &Node {
+        self.data.node_data(id)
+    }
+
+    #[inline]
+    fn node_data_mut(&mut self, id: NodeId) -> &mut Node {
+        &mut self.data.nodes[id.0]
+    }
+
+    fn element_data_mut(&mut self, id: NodeId) -> &mut ElementNode {
+        self.node_data_mut(id)
+            .element_mut()
+            .expect("Expected an element")
+    }
+
+    fn push(&mut self, kind: NodeKind) -> NodeId {
+        let id = NodeId(self.data.nodes.len());
+        let owner = &*self.data as *const DocumentData;
+        self.data.nodes.push(Box::new(Node::new(id, owner, kind)));
+        id
+    }
+
+    /// Creates a new, detached, HTML element.
+    pub fn create_element(&mut self, local_name: &str) -> NodeId {
+        let local_name = if self.data.is_html_document {
+            web_atoms::LocalName::from(local_name.to_ascii_lowercase())
+        } else {
+            web_atoms::LocalName::from(local_name)
+        };
+        self.create_element_ns(ns!(html), local_name)
+    }
+
+    /// Creates a new, detached, element in the given namespace.
+    pub fn create_element_ns(
+        &mut self,
+        namespace: web_atoms::Namespace,
+        local_name: web_atoms::LocalName,
+    ) -> NodeId {
+        // Autonomous custom elements are not defined until they're upgraded,
+        // which never happens here.
+        let state = if local_name.contains('-') {
+            ElementState::empty()
+        } else {
+            ElementState::DEFINED
+        };
+        self.push(NodeKind::Element(ElementNode {
+            local_name,
+            namespace,
+            attrs: vec![],
+            id: None,
+            style_attribute: None,
+            state,
+            custom_states: vec![],
+            scroll_state: ContainerScrollState::default(),
+            shadow_root: None,
+            assigned_slot: None,
+            assigned_nodes: vec![],
+            implemented_pseudo: None,
+            selector_flags: AtomicUsize::new(0),
+            children_to_process: AtomicIsize::new(0),
+            data: AtomicRefCell::new(None),
+            table_layout: OnceLock::new(),
+        }))
+    }
+
+    /// Creates a new, detached, text node.
+    pub fn create_text_node(&mut self, text: &str) -> NodeId {
+        self.push(NodeKind::Text(text.to_owned()))
+    }
+
+    /// Attaches a shadow root to `host`, and returns it.
+    ///
+    /// Panics if `host` is not an element or already has a shadow root.
+    pub fn attach_shadow(&mut self, host: NodeId) -> NodeId {
+        assert!(
+            self.node_data(host)
+                .element()
+                .expect("Only elements can host shadow roots")
+                .shadow_root
+                .is_none(),
+            "Element already has a shadow root"
+        );
+        let shadow_root = self.push(NodeKind::ShadowRoot(ShadowRootNode {
+            host,
+            styles: AuthorStyles::new(),
+        }));
+        self.element_data_mut(host).shadow_root = Some(shadow_root);
+        self.note_subtree_changed(host);
+        shadow_root
+    }
+
+    /// Returns the shadow root attached to `host`, if any.
+    pub fn shadow_root(&self, host: NodeId) -> Option<NodeId> {
+        self.node_data(host).element()?.shadow_root
+    }
+
+    /// Appends `child` as the last child of `parent`.
+    pub fn append_child(&mut self, parent: NodeId, child: NodeId) {
+        self.insert_before(parent, child, None)
+    }
+
+    /// Inserts `child` into `parent`, before `reference`, or as the last child
+    /// if `reference` is `None`.
+    ///
+    /// `child` is removed from its current parent first, if any.
+    pub fn insert_before(&mut self, parent: NodeId, child: NodeId, reference: Option<NodeId>) {
+        assert!(
+            !matches!(
+                self.node_data(child).kind,
+                NodeKind::Document | NodeKind::ShadowRoot(..)
+            ),
+            "Can't insert documents or shadow roots into the tree"
+        );
+        if let Some(reference) = reference {
+            assert_eq!(
+                self.node_data(reference).parent,
+                Some(parent),
+                "The reference node must be a child of the parent"
+            );
+        }
+        self.remove(child);
+
+        let prev_sibling = match reference {
+            Some(reference) => self.node_data(reference).prev_sibling,
+            None => self.node_data(parent).last_child,
+        };
+        {
+            let child = self.node_data_mut(child);
+            child.parent = Some(parent);
+            child.prev_sibling = prev_sibling;
+            child.next_sibling = reference;
+        }
+        match prev_sibling {
+            Some(prev) => self.node_data_mut(prev).next_sibling = Some(child),
+            None => self.node_data_mut(parent).first_child = Some(child),
+        }
+        match reference {
+            Some(next) => self.node_data_mut(next).prev_sibling = Some(child),
+            None => self.node_data_mut(parent).last_child = Some(child),
+        }
+        self.update_directionality(child);
+        self.note_subtree_changed(parent);
+    }
+
+    /// Removes `node` from its parent, if any.
+    ///
+    /// The styles of the removed subtree are dropped.
+    pub fn remove(&mut self, node: NodeId) {
+        let Some(parent) = self.node_data(node).parent else {
+            return;
+        };
+        let (prev, next) = {
+            let node = self.node_data_mut(node);
+            let siblings = (node.prev_sibling, node.next_sibling);
+            node.parent = None;
+            node.prev_sibling = None;
+            node.next_sibling = None;
+            siblings
+        };
+        match prev {
+            Some(prev) => self.node_data_mut(prev).next_sibling = next,
+            None => self.node_data_mut(parent).first_child = next,
+        }
+        match next {
+            Some(next) => self.node_data_mut(next).prev_sibling = prev,
+            None => self.node_data_mut(parent).last_child = prev,
+        }
+        self.clear_subtree_data(node);
+        if let Some(root) = self.auto_directionality_root(parent) {
+            self.update_subtree_directionality(root);
+        }
+        self.note_subtree_changed(parent);
+    }
+
+    /// Replaces the contents of a text node.
+    pub fn set_text(&mut self, text_node: NodeId, text: &str) {
+        match self.node_data_mut(text_node).kind {
+            NodeKind::Text(ref mut contents) => {
+                contents.clear();
+                contents.push_str(text);
+            },
+            _ => panic!("Expected a text node"),
+        }
+        self.update_directionality(text_node);
+        // Text changes may affect :empty.
+        if let Some(parent) = self.node_data(text_node).parent {
+            self.note_restyle(parent, RestyleHint::RESTYLE_SELF);
+        }
+    }
+
+    /// Returns the value of an attribute in the null namespace.
+    pub fn get_attribute(&self, element: NodeId, name: &str) -> Option<&str> {
+        self.element(element)?
+            .get_attr_value(&ns!(), &web_atoms::LocalName::from(name))
+            .map(|value| &**value)
+    }
+
+    /// Sets an attribute in the null namespace.
+    ///
+    /// `id`, `class`, `part`, `exportparts` and `style` are parsed the same
+    /// way a browser would.
+    pub fn set_attribute(&mut self, element: NodeId, name: &str, value: &str) {
+        let local_name = if self.is_html_element(element) && self.data.is_html_document {
+            LocalName::from(&*name.to_ascii_lowercase())
+        } else {
+            LocalName::from(name)
+        };
+        self.set_attribute_ns(element, Namespace::default(), local_name, value)
+    }
+
+    /// Sets an attribute in the given namespace.
+    pub fn set_attribute_ns(
+        &mut self,
+        element: NodeId,
+        namespace: Namespace,
+        local_name: LocalName,
+        value: &str,
+    ) {
+        self.snapshot_attribute_change(element, &local_name);
+
+        let is_null_namespace = namespace.0 == ns!();
+        let attr_value = if is_null_namespace {
+            self.parse_attribute(&local_name, value)
+        } else {
+            AttrValue::String(value.to_owned())
+        };
+
+        let element_data = self.element_data_mut(element);
+        match element_data
+            .attrs
+            .iter_mut()
+            .find(|(name, _)| name.local_name == local_name && name.namespace == namespace)
+        {
+            Some(attr) => attr.1 = attr_value,
+            None => element_data.attrs.push((
+                AttrIdentifier {
+                    local_name: local_name.clone(),
+                    name: local_name.clone(),
+                    namespace,
+                    prefix: None,
+                },
+                attr_value,
+            )),
+        }
+        if is_null_namespace {
+            self.attribute_mutated(element, &local_name);
+        }
+    }
+
+    /// Removes an attribute in the null namespace, if present.
+    pub fn remove_attribute(&mut self, element: NodeId, name: &str) {
+        let local_name = if self.is_html_element(element) && self.data.is_html_document {
+            LocalName::from(&*name.to_ascii_lowercase())
+        } else {
+            LocalName::from(name)
+        };
+        let namespace = Namespace::default();
+        let Some(index) = self.node_data(element).element().and_then(|element| {
+            element
+                .attrs
+                .iter()
+                .position(|(name, _)| name.local_name == local_name && name.namespace == namespace)
+        }) else {
+            return;
+        };
+        self.snapshot_attribute_change(element, &local_name);
+        self.element_data_mut(element).attrs.remove(index);
+        self.attribute_mutated(element, &local_name);
+    }
+
+    /// Returns the state of the given element.
+    pub fn state(&self, element: NodeId) -> ElementState {
+        self.node_data(element)
+            .element()
+            .expect("Expected an element")
+            .state
+    }
+
+    /// Replaces the state of the given element.
+    pub fn set_state(&mut self, element: NodeId, state: ElementState) {
+        if self.state(element) == state {
+            return;
+        }
+        self.snapshot_state_change(element);
+        self.element_data_mut(element).state = state;
+    }
+
+    /// Adds the given state bits to the element.
+    pub fn insert_state(&mut self, element: NodeId, state: ElementState) {
+        let new_state = self.state(element) | state;
+        self.set_state(element, new_state)
+    }
+
+    /// Removes the given state bits from the element.
+    pub fn remove_state(&mut self, element: NodeId, state: ElementState) {
+        let new_state = self.state(element) - state;
+        self.set_state(element, new_state)
+    }
+
+    /// Adds a custom state, for the `:state()` pseudo-class.
+    pub fn add_custom_state(&mut self, element: NodeId, state: &str) {
+        let state = AtomIdent::from(state);
+        if self
+            .element_data_mut(element)
+            .custom_states
+            .contains(&state)
+        {
+            return;
+        }
+        self.element_data_mut(element).custom_states.push(state);
+        // Servo snapshots don't track custom states, so just restyle.
+        self.note_restyle(element, RestyleHint::restyle_subtree());
+    }
+
+    /// Removes a custom state.
+    pub fn remove_custom_state(&mut self, element: NodeId, state: &str) {
+        let state = AtomIdent::from(state);
+        let states = &mut self.element_data_mut(element).custom_states;
+        let Some(index) = states.iter().position(|s| *s == state) else {
+            return;
+        };
+        states.remove(index);
+        self.note_restyle(element, RestyleHint::restyle_subtree());
+    }
+
+    /// Sets the scroll state the given element exposes to scroll-state
+    /// container queries.
+    pub fn set_container_scroll_state(
+        &mut self,
+        element: NodeId,
+        state: ContainerScrollState,
+        stylist: &Stylist,
+    ) {
+        let old = std::mem::replace(&mut self.element_data_mut(element).scroll_state, state);
+        let Some(style) = self.style(element) else {
+            return;
+        };
+        let dependencies = stylist.scroll_state_dependencies(self.element(element).unwrap());
+        let hint = old.restyle_hint_for_change(&state, &style, dependencies);
+        if !hint.is_empty() {
+            self.note_restyle(element, hint);
+        }
+    }
+
+    /// Makes the given element implement a pseudo-element of its shadow host,
+    /// the same way user-agent widgets implement `::placeholder` and friends.
+    pub fn set_implemented_pseudo_element(
+        &mut self,
+        element: NodeId,
+        pseudo: Option<PseudoElement>,
+    ) {
+        self.element_data_mut(element).implemented_pseudo = pseudo;
+        self.note_restyle(element, RestyleHint::restyle_subtree());
+    }
+
+    /// Appends a stylesheet to the given shadow root.
+    ///
+    /// The sheet won't be used until the next call to
+    /// [`Document::flush_shadow_root_styles`].
+    pub fn append_shadow_root_stylesheet(
+        &mut self,
+        shadow_root: NodeId,
+        sheet: DocumentStyleSheet,
+    ) {
+        let guard = self.data.shared_lock.read();
+        let NodeKind::ShadowRoot(ref mut shadow_root) = self.data.nodes[shadow_root.0].kind else {
+            panic!("Expected a shadow root");
+        };
+        let styles = &mut shadow_root.styles;
+        styles
+            .stylesheets
+            .append_stylesheet(None, styles.data.custom_media_map(), sheet, &guard);
+    }
+
+    /// Rebuilds the cascade data of the shadow roots whose stylesheets
+    /// changed, and restyles their hosts.
+    pub fn flush_shadow_root_styles(&mut self, stylist: &mut Stylist) {
+        let mut dirty_hosts = vec![];
+        {
+            let guard = self.data.shared_lock.read();
+            for node in self.data.nodes.iter_mut() {
+                let NodeKind::ShadowRoot(ref mut shadow_root) = node.kind else {
+                    continue;
+                };
+                if !shadow_root.styles.stylesheets.dirty() {
+                    continue;
+                }
+                shadow_root.styles.flush(stylist, &guard);
+                dirty_hosts.push(shadow_root.host);
+            }
+        }
+        for host in dirty_hosts {
+            self.note_restyle(host, RestyleHint::restyle_subtree());
+        }
+    }
+
+    /// Styles the document with the given stylist, restyling only what
+    /// changed since the last call.
+    ///
+    /// The stylist must have been flushed, and its stylesheets must use this
+    /// document's shared lock. If a thread pool is given, the traversal may
+    /// run in parallel.
+    ///
+    /// Returns whether any element was traversed.
+    pub fn restyle(&mut self, stylist: &Stylist, pool: Option<&rayon::ThreadPool>) -> bool {
+        self.assign_slots();
+        self.clear_restyle_damage();
+
+        let cells = {
+            let guard = self.data.shared_lock.read();
+            self.changed_column_cells(&self.shared_context(stylist, &guard))
+        };
+        self.note_cells_changed(cells);
+
+        let traversed = {
+            let Some(root) = self.root_element() else {
+                return false;
+            };
+            let guard = self.data.shared_lock.read();
+            let context = self.shared_context(stylist, &guard);
+            let was_layout_thread = style::thread_state::get().is_layout();
+            if !was_layout_thread {
+                style::thread_state::enter(style::thread_state::ThreadState::LAYOUT);
+            }
+            let token = RecalcStyle::pre_traverse(root, &context);
+            let traversed = token.should_traverse();
+            if traversed {
+                let traversal = RecalcStyle::new(context);
+                driver::traverse_dom(&traversal, token, pool);
+            }
+            if !was_layout_thread {
+                style::thread_state::exit(style::thread_state::ThreadState::LAYOUT);
+            }
+            traversed
+        };
+
+        self.clear_snapshots();
+        traversed
+    }
+
+    fn shared_context<'a>(
+        &'a self,
+        stylist: &'a Stylist,
+        guard: &'a SharedRwLockReadGuard,
+    ) -> SharedStyleContext<'a> {
+        SharedStyleContext {
+            stylist,
+            visited_styles_enabled: false,
+            options: StyleSystemOptions::default(),
+            guards: StylesheetGuards::same(guard),
+            current_time_for_animations: self.data.current_time_for_animations,
+            traversal_flags: TraversalFlags::empty(),
+            snapshot_map: &self.data.snapshots,
+            animations: self.data.animations.clone(),
+            registered_speculative_painters: &NoPainters,
+        }
+    }
+
+    /// Returns the cells that were matched against a column element whose
+    /// changes since the last restyle affect its column dependencies.
+    ///
+    /// The invalidation of the column elements can't reach those cells, so
+    /// they need to be found before the traversal.
+    fn changed_column_cells(&self, context: &SharedStyleContext) -> Vec<NodeId> {
+        let mut cells = vec![];
+        let mut selector_caches = SelectorCaches::default();
+        for id in (0..self.data.nodes.len()).map(NodeId) {
+            let Some(element) = self.element(id) else {
+                continue;
+            };
+            if !element.has_snapshot()
+                || !element.has_selector_flags(ElementSelectorFlags::HAS_COLUMN_SELECTOR)
+            {
+                continue;
+            }
+            let Some(table) = self.table_containing(id) else {
+                continue;
+            };
+            let Some(mut data) = element.mutate_data() else {
+                continue;
+            };
+            let invalidates_columns = StateAndAttrInvalidationProcessor::new(
+                context,
+                element,
+                &mut data,
+                &mut selector_caches,
+            )
+            .invalidates_columns();
+            if invalidates_columns {
+                table_columns::invalidate_cells_for_column_element(table, element, |cell| {
+                    cells.push(cell.node_id())
+                });
+            }
+        }
+        cells
+    }
+
+    /// Drops the snapshots taken since the last restyle.
+    ///
+    /// This is done automatically by [`Document::restyle`], but needs to be
+    /// done manually after traversals driven by the embedder.
+    pub fn clear_snapshots(&mut self) {
+        for node in self.data.nodes.iter() {
+            node.remove_flags(NodeFlags::HAS_SNAPSHOT | NodeFlags::HANDLED_SNAPSHOT);
+        }
+        self.data.snapshots.clear();
+    }
+
+    /// Clears the restyle damage accumulated by previous restyles, so that
+    /// after the next restyle it only reflects what changed in it.
+    fn clear_restyle_damage(&mut self) {
+        for node in self.data.nodes.iter_mut() {
+            if let Some(element) = node.element_mut() {
+                if let Some(ref mut data) = *element.data.get_mut() {
+                    data.clear_restyle_flags_and_damage();
+                }
+            }
+        }
+    }
+
+    /// Recomputes the slot assignment of every shadow tree, using named slot
+    /// assignment.
+    pub fn assign_slots(&mut self) {
+        let hosts: Vec<(NodeId, NodeId)> = self
+            .data
+            .nodes
+            .iter()
+            .filter_map(|node| match node.kind {
+                NodeKind::ShadowRoot(ref shadow_root) => Some(shadow_root.host),
+                _ => None,
+            })
+            .map(|host| (host, self.shadow_root(host).unwrap()))
+            .collect();
+
+        for (host, shadow_root) in hosts {
+            let slots: Vec<NodeId> = self
+                .node(shadow_root)
+                .dom_descendants()
+                .filter_map(|node| node.as_element())
+                .filter(|element| element.is_html_slot_element())
+                .map(|element| element.node_id())
+                .collect();
+
+            let mut assignments: Vec<(NodeId, Vec<NodeId>)> =
+                slots.iter().map(|slot| (*slot, vec![])).collect();
+            let mut slotted = vec![];
+            for child in self.node(host).dom_children() {
+                let slot_name = match child.as_element() {
+                    Some(element) => element
+                        .get_attr_value(&ns!(), &local_name!("slot"))
+                        .map_or(String::new(), |name| name.to_string()),
+                    None => String::new(),
+                };
+                // The first slot in tree order with a matching name wins.
+                let slot = slots.iter().position(|slot| {
+                    self.node(*slot)
+                        .as_element()
+                        .unwrap()
+                        .get_attr_value(&ns!(), &local_name!("name"))
+                        .map_or("", |name| &**name)
+                        == slot_name
+                });
+                slotted.push((child.id(), slot.map(|i| slots[i])));
+                if let Some(i) = slot {
+                    assignments[i].1.push(child.id());
+                }
+            }
+
+            for (child, slot) in slotted {
+                let changed = match self.node_data_mut(child).element_mut() {
+                    Some(element) => std::mem::replace(&mut element.assigned_slot, slot) != slot,
+                    None => false,
+                };
+                if changed {
+                    self.clear_subtree_data(child);
+                }
+            }
+            for (slot, assigned) in assignments {
+                let element = self.element_data_mut(slot);
+                if element.assigned_nodes != assigned {
+                    element.assigned_nodes = assigned;
+                    self.note_restyle(slot, RestyleHint::restyle_subtree());
+                }
+            }
+        }
+    }
+
+    fn is_html_element(&self, id: NodeId) -> bool {
+        self.node_data(id)
+            .element()
+            .is_some_and(|element| element.namespace == ns!(html))
+    }
+
+    fn parse_attribute(&self, local_name: &LocalName, value: &str) -> AttrValue {
+        match local_name.0 {
+            local_name!("id") => AttrValue::from_atomic(value.to_owned()),
+            local_name!("class") | local_name!("part") => {
+                AttrValue::from_serialized_tokenlist(value.to_owned())
+            },
+            local_name!("exportparts") => AttrValue::from_shadow_parts(value.to_owned()),
+            local_name!("style") => {
+                let block = parse_style_attribute(
+                    value,
+                    &self.data.url_data,
+                    None,
+                    self.data.quirks_mode,
+                    CssRuleType::Style,
+                );
+                let block = Arc::new(self.data.shared_lock.wrap(block));
+                AttrValue::Declaration(value.to_owned(), block)
+            },
+            _ => AttrValue::String(value.to_owned()),
+        }
+    }
+
+    /// Updates the cached data derived from an attribute that just changed.
+    fn attribute_mutated(&mut self, element: NodeId, local_name: &LocalName) {
+        let value = self
+            .element(element)
+            .unwrap()
+            .get_attr_value(&ns!(), &local_name.0)
+            .cloned();
+        if matches!(
+            local_name.0,
+            local_name!("span") | local_name!("colspan") | local_name!("rowspan")
+        ) {
+            self.note_table_changed(element);
+        }
+        match local_name.0 {
+            local_name!("id") => {
+                self.element_data_mut(element).id = value.map(|v| v.as_atom().clone());
+            },
+            local_name!("style") => {
+                self.element_data_mut(element).style_attribute = match value {
+                    Some(AttrValue::Declaration(_, block)) => Some(block),
+                    _ => None,
+                };
+                self.note_restyle(element, RestyleHint::RESTYLE_STYLE_ATTRIBUTE);
+            },
+            local_name!("dir") => self.update_directionality(element),
+            _ => {},
+        }
+    }
+
+    /// Returns whether the given element has been styled, and thus changes to
+    /// it need to be tracked.
+    fn has_styles(&self, element: NodeId) -> bool {
+        self.element(element)
+            .is_some_and(|element| element.borrow_data().is_some_and(|data| data.has_styles()))
+    }
+
+    fn ensure_snapshot(&mut self, element: NodeId) -> Option<&mut ServoElementSnapshot> {
+        if !self.has_styles(element) {
+            return None;
+        }
+        let node = self.node(element);
+        node.node_data().insert_flags(NodeFlags::HAS_SNAPSHOT);
+        node.node_data().remove_flags(NodeFlags::HANDLED_SNAPSHOT);
+        self.mark_ancestors_dirty(element);
+        let opaque = self.node(element).opaque();
+        Some(self.data.snapshots.entry(opaque).or_default())
+    }
+
+    fn snapshot_state_change(&mut self, element: NodeId) {
+        let state = self.state(element);
+        if let Some(snapshot) = self.ensure_snapshot(element) {
+            if snapshot.state.is_none() {
+                snapshot.state = Some(state);
+            }
+        }
+    }
+
+    fn snapshot_attribute_change(&mut self, element: NodeId, local_name: &LocalName) {
+        let attrs = self.node_data(element).element().unwrap().attrs.clone();
+        let Some(snapshot) = self.ensure_snapshot(element) else {
+            return;
+        };
+        if snapshot.attrs.is_none() {
+            snapshot.attrs = Some(attrs);
+        }
+        if !snapshot.changed_attrs.contains(local_name) {
+            snapshot.changed_attrs.push(local_name.clone());
+        }
+        match local_name.0 {
+            local_name!("id") => snapshot.id_changed = true,
+            local_name!("class") => snapshot.class_changed = true,
+            _ => snapshot.other_attributes_changed = true,
+        }
+    }
+
+    /// Notes that the children of `parent` changed.
+    ///
+    /// Insertions and removals can affect the style of siblings through
+    /// combinators and structural pseudo-classes, so just restyle the whole
+    /// subtree.
+    fn note_subtree_changed(&mut self, parent: NodeId) {
+        match self.node_data(parent).kind {
+            NodeKind::Element(ref element)
+                if element.namespace == ns!(html)
+                    && matches!(
+                        element.local_name,
+                        local_name!("table")
+                            | local_name!("colgroup")
+                            | local_name!("tbody")
+                            | local_name!("thead")
+                            | local_name!("tfoot")
+                            | local_name!("tr")
+                    ) =>
+            {
+                self.note_restyle(parent, RestyleHint::restyle_subtree());
+                self.note_table_changed(parent)
+            },
+            NodeKind::Element(..) => self.note_restyle(parent, RestyleHint::restyle_subtree()),
+            NodeKind::ShadowRoot(ref shadow_root) => {
+                let host = shadow_root.host;
+                self.note_restyle(host, RestyleHint::restyle_subtree())
+            },
+            NodeKind::Document => {
+                if let Some(root) = self.root_element() {
+                    let root = root.node_id();
+                    self.note_restyle(root, RestyleHint::restyle_subtree())
+                }
+            },
+            NodeKind::Text(..) => unreachable!("Text nodes have no children"),
+        }
+    }
+
+    /// Notes that the structure of the table `element` is part of may have
+    /// changed, dropping its column layout and restyling the cells that depend
+    /// on its columns.
+    fn note_table_changed(&mut self, element: NodeId) {
+        let Some(table) = self.table_containing(element).map(|table| table.node_id()) else {
+            return;
+        };
+        self.element_data_mut(table).table_layout.take();
+        let table = self.element(table).unwrap();
+        let mut cells = vec![];
+        table_columns::invalidate_cells_for_structure_change(table, |cell| {
+            cells.push(cell.node_id())
+        });
+        self.note_cells_changed(cells)
+    }
+
+    fn note_cells_changed(&mut self, cells: Vec<NodeId>) {
+        for cell in cells {
+            self.note_restyle(cell, RestyleHint::restyle_subtree());
+        }
+    }
+
+    /// Returns the nearest `<table>` element that is an inclusive ancestor of
+    /// `element`.
+    fn table_containing(&self, element: NodeId) -> Option<ElementRef<'_>> {
+        let mut current = self.element(element);
+        while let Some(ancestor) = current {
+            if ancestor.is_html_element() && *ancestor.local_name() == local_name!("table") {
+                return Some(ancestor);
+            }
+            current = ancestor.parent_element();
+        }
+        None
+    }
+
+    /// Returns the state of the `dir` attribute of `element`, treating `<bdi>`
+    /// elements without a valid one as `dir=auto`.
+    fn dir_attribute(&self, element: NodeId) -> Option<DirAttribute> {
+        if !self.is_html_element(element) {
+            return None;
+        }
+        let element = self.element(element).unwrap();
+        match element.get_attr_value(&ns!(), &local_name!("dir")) {
+            Some(dir) if dir.eq_ignore_ascii_case("ltr") => Some(DirAttribute::Ltr),
+            Some(dir) if dir.eq_ignore_ascii_case("rtl") => Some(DirAttribute::Rtl),
+            Some(dir) if dir.eq_ignore_ascii_case("auto") => Some(DirAttribute::Auto),
+            _ if *element.local_name() == local_name!("bdi") => Some(DirAttribute::Auto),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the text of `element` is ignored when computing the
+    /// directionality of its `dir=auto` ancestors.
+    fn is_auto_directionality_boundary(&self, element: NodeId) -> bool {
+        if self.dir_attribute(element).is_some() {
+            return true;
+        }
+        self.is_html_element(element)
+            && matches!(
+                *self.element(element).unwrap().local_name(),
+                local_name!("script") | local_name!("style") | local_name!("textarea")
+            )
+    }
+
+    /// Returns the closest inclusive ancestor of `node` with `dir=auto` whose
+    /// directionality depends on the text of the children of `node`, if any.
+    fn auto_directionality_root(&self, node: NodeId) -> Option<NodeId> {
+        let mut current = Some(node);
+        while let Some(id) = current {
+            if self.node_data(id).element().is_none() {
+                break;
+            }
+            if self.is_auto_directionality_boundary(id) {
+                return (self.dir_attribute(id) == Some(DirAttribute::Auto)).then_some(id);
+            }
+            current = self.node_data(id).parent;
+        }
+        None
+    }
+
+    /// Returns the direction of the first character with a strong direction
+    /// in the text of the descendants of `node`, if any.
+    ///
+    /// See https://html.spec.whatwg.org/#contained-text-auto-directionality
+    fn text_directionality(&self, node: NodeId) -> Option<ElementState> {
+        let mut child = self.node_data(node).first_child;
+        while let Some(id) = child {
+            let directionality = match self.node_data(id).kind {
+                NodeKind::Text(ref text) => text.chars().find_map(|c| match bidi_class(c) {
+                    BidiClass::L => Some(ElementState::LTR),
+                    BidiClass::R | BidiClass::AL => Some(ElementState::RTL),
+                    _ => None,
+                }),
+                NodeKind::Element(..) if !self.is_auto_directionality_boundary(id) => {
+                    self.text_directionality(id)
+                },
+                _ => None,
+            };
+            if directionality.is_some() {
+                return directionality;
+            }
+            child = self.node_data(id).next_sibling;
+        }
+        None
+    }
+
+    /// Returns the directionality of `node`, as one of the `ElementState::LTR`
+    /// or `ElementState::RTL` flags.
+    ///
+    /// Shadow roots have the directionality of their host, and nodes outside
+    /// of an element are left-to-right.
+    fn directionality(&self, node: Option<NodeId>) -> ElementState {
+        match node.map(|node| &self.node_data(node).kind) {
+            Some(NodeKind::Element(ref element)) if element.state.intersects(ElementState::RTL) => {
+                ElementState::RTL
+            },
+            Some(NodeKind::ShadowRoot(ref shadow_root)) => {
+                self.directionality(Some(shadow_root.host))
+            },
+            _ => ElementState::LTR,
+        }
+    }
+
+    /// Updates the directionality of `node` and its descendants, and of the
+    /// `dir=auto` ancestor its text contributes to, if any.
+    fn update_directionality(&mut self, node: NodeId) {
+        let root = self
+            .node_data(node)
+            .parent
+            .and_then(|parent| self.auto_directionality_root(parent))
+            .unwrap_or(node);
+        self.update_subtree_directionality(root)
+    }
+
+    /// Recomputes the directionality of `root` and its descendants, including
+    /// shadow trees, and reflects it in their `:dir()` state.
+    ///
+    /// See https://html.spec.whatwg.org/#the-directionality
+    fn update_subtree_directionality(&mut self, root: NodeId) {
+        let parent_directionality = self.directionality(self.node_data(root).parent);
+        let mut stack = vec![(root, parent_directionality)];
+        while let Some((id, parent_directionality)) = stack.pop() {
+            let Some(element) = self.node_data(id).element() else {
+                continue;
+            };
+            let shadow_root = element.shadow_root;
+            let directionality = match self.dir_attribute(id) {
+                Some(DirAttribute::Ltr) => ElementState::LTR,
+                Some(DirAttribute::Rtl) => ElementState::RTL,
+                Some(DirAttribute::Auto) => {
+                    self.text_directionality(id).unwrap_or(ElementState::LTR)
+                },
+                None => parent_directionality,
+            };
+            let state = (self.state(id) - ElementState::DIR_STATES) | directionality;
+            self.set_state(id, state);
+
+            for parent in std::iter::once(id).chain(shadow_root) {
+                let mut child = self.node_data(parent).first_child;
+                while let Some(child_id) = child {
+                    stack.push((child_id, directionality));
+                    child = self.node_data(child_id).next_sibling;
+                }
+            }
+        }
+    }
+
+    /// Adds a restyle hint to an element that has been styled, and makes sure
+    /// that the traversal reaches it.
+    fn note_restyle(&mut self, element: NodeId, hint: RestyleHint) {
+        {
+            let Some(element) = self.element(element) else {
+                return;
+            };
+            let Some(mut data) = element.mutate_data() else {
+                return;
+            };
+            if !data.has_styles() {
+                return;
+            }
+            data.hint.insert(hint);
+        }
+        self.mark_ancestors_dirty(element);
+    }
+
+    fn mark_ancestors_dirty(&self, element: NodeId) {
+        let mut current = self.node(element).traversal_parent();
+        while let Some(parent) = current {
+            let node = parent.0.node_data();
+            if node.flags().contains(NodeFlags::DIRTY_DESCENDANTS) {
+                break;
+            }
+            node.insert_flags(NodeFlags::DIRTY_DESCENDANTS);
+            current = parent.traversal_parent();
+        }
+    }
+
+    /// Drops the styles of a subtree, including shadow trees, so that it gets
+    /// styled from scratch next time it's traversed.
+    fn clear_subtree_data(&mut self, root: NodeId) {
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            let node = self.node_data_mut(id);
+            node.remove_flags(NodeFlags::DIRTY_DESCENDANTS);
+            if let Some(element) = node.element_mut() {
+                *element.data.get_mut() = None;
+                stack.extend(element.shadow_root);
+            }
+            let mut child = node.first_child;
+            while let Some(id) = child {
+                stack.push(id);
+                child = self.node_data(id).next_sibling;
+            }
+        }
+        if self.node_data(root).element().is_some() {
+            self.mark_ancestors_dirty(root);
+        }
+    }
+}
+
+impl fmt::Debug for Document {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Document")
+            .field("url", &self.data.url_data.as_str())
+            .field("nodes", &self.data.nodes.len())
+            .finish()
+    }
+}
+
+/// A handle to a node of a [`Document`].
+#[derive(Clone, Copy)]
+pub struct NodeRef<'dom> {
+    node: &'dom Node,
+}
+
+impl<'dom> NodeRef<'dom> {
+    /// The id of this node.
+    #[inline]
+    pub fn id(&self) -> NodeId {
+        self.node.id
+    }
+
+    /// The text of this node, if it's a text node.
+    pub fn text(&self) -> Option<&'dom str> {
+        match self.node_data().kind {
+            NodeKind::Text(ref text) => Some(text),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn node_data(&self) -> &'dom Node {
+        self.node
+    }
+
+    #[inline]
+    fn doc(&self) -> &'dom DocumentData {
+        // Nodes can't outlive the document that owns them, and the document
+        // data doesn't move while it's borrowed.
+        unsafe { &*self.node.owner }
+    }
+
+    #[inline]
+    fn with_id(&self, id: Option<NodeId>) -> Option<Self> {
+        Some(self.doc().node(id?))
+    }
+}
+
+impl<'dom> PartialEq for NodeRef<'dom> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.node, other.node)
+    }
+}
+
+impl<'dom> Eq for NodeRef<'dom> {}
+
+impl<'dom> Hash for NodeRef<'dom> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.node as *const Node).hash(state)
+    }
+}
+
+impl<'dom> fmt::Debug for NodeRef<'dom> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.node_data().kind {
+            NodeKind::Document => write!(f, "#document ({})", self.id().0),
+            NodeKind::Text(ref text) => write!(f, "#text {:?} ({})", text, self.id().0),
+            NodeKind::Element(ref element) => {
+                write!(f, "<{}", element.local_name)?;
+                if let Some(ref id) = element.id {
+                    write!(f, " id={}", id)?;
+                }
+                write!(f, "> ({})", self.id().0)
+            },
+            NodeKind::ShadowRoot(..) => write!(f, "#shadow-root ({})", self.id().0),
+        }
+    }
+}
+
+impl<'dom> NodeInfo for NodeRef<'dom> {
+    fn is_element(&self) -> bool {
+        matches!(self.node_data().kind, NodeKind::Element(..))
+    }
+
+    fn is_text_node(&self) -> bool {
+        matches!(self.node_data().kind, NodeKind::Text(..))
+    }
+}
+
+impl<'dom> TNode for NodeRef<'dom> {
+    type ConcreteElement = ElementRef<'dom>;
+    type ConcreteDocument = DocumentRef<'dom>;
+    type ConcreteShadowRoot = ShadowRootRef<'dom>;
+
+    fn parent_node(&self) -> Option<Self> {
+        self.with_id(self.node_data().parent)
+    }
+
+    fn first_child(&self) -> Option<Self> {
+        self.with_id(self.node_data().first_child)
+    }
+
+    fn last_child(&self) -> Option<Self> {
+        self.with_id(self.node_data().last_child)
+    }
+
+    fn prev_sibling(&self) -> Option<Self> {
+        self.with_id(self.node_data().prev_sibling)
+    }
+
+    fn next_sibling(&self) -> Option<Self> {
+        self.with_id(self.node_data().next_sibling)
+    }
+
+    fn owner_doc(&self) -> DocumentRef<'dom> {
+        DocumentRef(self.doc().node(NodeId::DOCUMENT))
+    }
+
+    fn is_in_document(&self) -> bool {
+        let mut current = *self;
+        loop {
+            match current.node_data().kind {
+                NodeKind::Document => return true,
+                NodeKind::ShadowRoot(ref shadow_root) => {
+                    current = self.doc().node(shadow_root.host);
+                },
+                _ => match current.parent_node() {
+                    Some(parent) => current = parent,
+                    None => return false,
+                },
+            }
+        }
+    }
+
+    fn traversal_parent(&self) -> Option<ElementRef<'dom>> {
+        if let Some(element) = self.node_data().element() {
+            if let Some(slot) = element.assigned_slot {
+                return self.doc().element(slot);
+            }
+        }
+        let parent = self.parent_node()?;
+        if let Some(shadow_root) = parent.as_shadow_root() {
+            return Some(shadow_root.host());
+        }
+        parent.as_element()
+    }
+
+    fn opaque(&self) -> OpaqueNode {
+        OpaqueNode(self.node_data() as *const Node as usize)
+    }
+
+    fn debug_id(self) -> usize {
+        self.id().0
+    }
+
+    fn as_element(&self) -> Option<ElementRef<'dom>> {
+        if self.is_element() {
+            Some(ElementRef(*self))
+        } else {
+            None
+        }
+    }
+
+    fn as_document(&self) -> Option<DocumentRef<'dom>> {
+        match self.node_data().kind {
+            NodeKind::Document => Some(DocumentRef(*self)),
+            _ => None,
+        }
+    }
+
+    fn as_shadow_root(&self) -> Option<ShadowRootRef<'dom>> {
+        match self.node_data().kind {
+            NodeKind::ShadowRoot(..) => Some(ShadowRootRef(*self)),
+            _ => None,
+        }
+    }
+}
+
+/// A handle to the document node of a [`Document`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DocumentRef<'dom>(NodeRef<'dom>);
+
+impl<'dom> TDocument for DocumentRef<'dom> {
+    type ConcreteNode = NodeRef<'dom>;
+
+    fn as_node(&self) -> NodeRef<'dom> {
+        self.0
+    }
+
+    fn is_html_document(&self) -> bool {
+        self.0.doc().is_html_document
+    }
+
+    fn quirks_mode(&self) -> QuirksMode {
+        self.0.doc().quirks_mode
+    }
+
+    fn shared_lock(&self) -> &SharedRwLock {
+        &self.0.doc().shared_lock
+    }
+}
+
+/// A handle to a shadow root of a [`Document`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShadowRootRef<'dom>(NodeRef<'dom>);
+
+impl<'dom> ShadowRootRef<'dom> {
+    fn shadow_root_data(&self) -> &'dom ShadowRootNode {
+        match self.0.node_data().kind {
+            NodeKind::ShadowRoot(ref shadow_root) => shadow_root,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'dom> TShadowRoot for ShadowRootRef<'dom> {
+    type ConcreteNode = NodeRef<'dom>;
+
+    fn as_node(&self) -> NodeRef<'dom> {
+        self.0
+    }
+
+    fn host(&self) -> ElementRef<'dom> {
+        self.0.doc().element(self.shadow_root_data().host).unwrap()
+    }
+
+    fn style_data<'a>(&self) -> Option<&'a CascadeData>
+    where
+        Self: 'a,
+    {
+        Some(&self.shadow_root_data().styles.data)
+    }
+}
+
+/// An iterator over the children of an element in the flat tree.
+pub enum TraversalChildren<'dom> {
+    /// The DOM children of a node.
+    Children(DomChildren<NodeRef<'dom>>),
+    /// The nodes assigned to a slot.
+    Assigned(NodeRef<'dom>, std::slice::Iter<'dom, NodeId>),
+}
+
+impl<'dom> Iterator for TraversalChildren<'dom> {
+    type Item = NodeRef<'dom>;
+
+    fn next(&mut self) -> Option<NodeRef<'dom>> {
+        match *self {
+            TraversalChildren::Children(ref mut children) => children.next(),
+            TraversalChildren::Assigned(slot, ref mut nodes) => {
+                nodes.next().map(|id| slot.doc().node(*id))
+            },
+        }
+    }
+}
+
+/// A handle to an element of a [`Document`].
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct ElementRef<'dom>(NodeRef<'dom>);
+
+impl<'dom> fmt::Debug for ElementRef<'dom> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<'dom> ElementRef<'dom> {
+    /// The id of this element in the arena of its document.
+    ///
+    /// Not to be confused with `TElement::id`, which returns its `id`
+    /// attribute.
+    #[inline]
+    pub fn node_id(&self) -> NodeId {
+        self.0.id()
+    }
+
+    /// The primary style of this element, if it has been styled.
+    pub fn style(&self) -> Option<Arc<ComputedValues>> {
+        self.borrow_data()?.styles.get_primary().cloned()
+    }
+
+    #[inline]
+    fn element_data(&self) -> &'dom ElementNode {
+        self.0.node_data().element().unwrap()
+    }
+
+    #[inline]
+    fn selector_flags(&self) -> ElementSelectorFlags {
+        ElementSelectorFlags::from_bits_retain(
+            self.element_data().selector_flags.load(Ordering::Relaxed),
+        )
+    }
+
+    #[inline]
+    fn insert_selector_flags(&self, flags: ElementSelectorFlags) {
+        self.element_data()
+            .selector_flags
+            .fetch_or(flags.bits(), Ordering::Relaxed);
+    }
+
+    fn get_attr_value(
+        &self,
+        namespace: &web_atoms::Namespace,
+        local_name: &web_atoms::LocalName,
+    ) -> Option<&'dom AttrValue> {
+        self.element_data()
+            .attrs
+            .iter()
+            .find(|(name, _)| name.local_name.0 == *local_name && name.namespace.0 == *namespace)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the language of this element, looking at ancestors if needed.
+    fn language(&self) -> String {
+        let mut current = Some(*self);
+        while let Some(element) = current {
+            if let Some(lang) = element
+                .get_attr_value(&ns!(xml), &local_name!("lang"))
+                .or_else(|| element.get_attr_value(&ns!(), &local_name!("lang")))
+            {
+                return lang.to_string();
+            }
+            current = element.inheritance_parent();
+        }
+        String::new()
+    }
+
+    fn is_html_element_named(&self, local_name: &web_atoms::LocalName) -> bool {
+        self.is_html_element() && self.element_data().local_name == *local_name
+    }
+
+    /// Returns the value of a `span` or `colspan` attribute, clamped the same
+    /// way HTML does.
+    fn table_span(&self, local_name: &web_atoms::LocalName) -> u32 {
+        self.get_attr_value(&ns!(), local_name)
+            .and_then(|value| value.to_string().trim().parse::<u32>().ok())
+            .filter(|span| *span > 0)
+            .map_or(1, |span| span.min(1000))
+    }
+
+    /// Returns the value of the `rowspan` attribute of a cell, clamped the
+    /// same way HTML does, with zero spanning to the end of the row group.
+    fn table_row_span(&self) -> u32 {
+        match self
+            .get_attr_value(&ns!(), &local_name!("rowspan"))
+            .and_then(|value| value.to_string().trim().parse::<u32>().ok())
+        {
+            Some(0) => u32::MAX,
+            Some(span) => span.min(65534),
+            None => 1,
+        }
+    }
+
+    fn html_element_children(&self) -> impl Iterator<Item = ElementRef<'dom>> {
+        self.0
+            .dom_children()
+            .filter_map(|child| child.as_element())
+            .filter(|child| child.is_html_element())
+    }
+
+    /// Returns the table this row belongs to.
+    fn table_for_row(&self) -> Option<Self> {
+        let parent = self.parent_element()?;
+        if parent.is_html_element_named(&local_name!("table")) {
+            return Some(parent);
+        }
+        if !matches!(
+            parent.element_data().local_name,
+            local_name!("tbody") | local_name!("thead") | local_name!("tfoot")
+        ) || !parent.is_html_element()
+        {
+            return None;
+        }
+        let table = parent.parent_element()?;
+        table
+            .is_html_element_named(&local_name!("table"))
+            .then_some(table)
+    }
+
+    /// Returns the column layout of a table, computing it if needed.
+    fn table_layout(&self) -> &'dom TableLayout {
+        self.element_data()
+            .table_layout
+            .get_or_init(|| self.compute_table_layout())
+    }
+
+    /// Computes the column layout of a table, following the HTML table
+    /// processing model.
+    ///
+    /// Cells start at the first column of their row that isn't covered by a
+    /// cell of a previous row, and rows that are direct children of the table
+    /// are treated as a single row group.
+    fn compute_table_layout(&self) -> TableLayout {
+        let mut columns = vec![];
+        for group in self.html_element_children() {
+            if group.element_data().local_name != local_name!("colgroup") {
+                continue;
+            }
+            let mut has_cols = false;
+            for col in group.html_element_children() {
+                if col.element_data().local_name != local_name!("col") {
+                    continue;
+                }
+                has_cols = true;
+                let span = col.table_span(&local_name!("span"));
+                columns.extend((0..span).map(|_| Some(col.node_id())));
+            }
+            if !has_cols {
+                let span = group.table_span(&local_name!("span"));
+                columns.extend((0..span).map(|_| None));
+            }
+        }
+
+        let mut cells = HashMap::new();
+        let mut column_count = columns.len() as u32;
+        let mut direct_rows = vec![];
+        let mut groups = vec![];
+        for child in self.html_element_children() {
+            match child.element_data().local_name {
+                local_name!("tr") => direct_rows.push(child),
+                local_name!("tbody") | local_name!("thead") | local_name!("tfoot") => groups.push(
+                    child
+                        .html_element_children()
+                        .filter(|row| row.element_data().local_name == local_name!("tr"))
+                        .collect(),
+                ),
+                _ => {},
+            }
+        }
+        groups.push(direct_rows);
+        for rows in groups {
+            // The number of rows, including the current one, that each column
+            // is still covered for by a cell of a previous row.
+            let mut covered: Vec<u32> = vec![];
+            for row in rows {
+                let mut column: u32 = 0;
+                for cell in row
+                    .html_element_children()
+                    .filter(|cell| cell.is_table_cell())
+                {
+                    while covered.get(column as usize).is_some_and(|rows| *rows > 0) {
+                        column += 1;
+                    }
+                    let span = cell.table_span(&local_name!("colspan"));
+                    let end = column.saturating_add(span);
+                    if covered.len() < end as usize {
+                        covered.resize(end as usize, 0);
+                    }
+                    let row_span = cell.table_row_span();
+                    for rows in &mut covered[column as usize..end as usize] {
+                        *rows = row_span;
+                    }
+                    cells.insert(cell.node_id(), (column, span));
+                    column_count = column_count.max(end);
+                    column = end;
+                }
+                for rows in &mut covered {
+                    *rows = rows.saturating_sub(1);
+                }
+            }
+        }
+
+        TableLayout {
+            columns,
+            cells,
+            column_count,
+        }
+    }
+
+    fn is_table_cell(&self) -> bool {
+        self.is_html_element()
+            && matches!(
+                self.element_data().local_name,
+                local_name!("td") | local_name!("th")
+            )
+    }
+
+    /// Returns the table this cell belongs to.
+    fn table_for_cell(&self) -> Option<Self> {
+        if !self.is_table_cell() {
+            return None;
+        }
+        let row = self.parent_element()?;
+        if !row.is_html_element_named(&local_name!("tr")) {
+            return None;
+        }
+        row.table_for_row()
+    }
+}
+
+impl<'dom> AttributeProvider for ElementRef<'dom> {
+    fn get_attr(&self, attr: &LocalName) -> Option<String> {
+        self.get_attr_value(&ns!(), &attr.0)
+            .map(|value| value.to_string())
+    }
+}
+
+impl<'dom> TElement for ElementRef<'dom> {
+    type ConcreteNode = NodeRef<'dom>;
+    type TraversalChildrenIterator = TraversalChildren<'dom>;
+
+    fn as_node(&self) -> NodeRef<'dom> {
+        self.0
+    }
+
+    fn traversal_children(&self) -> LayoutIterator<TraversalChildren<'dom>> {
+        let element = self.element_data();
+        if let Some(shadow_root) = element.shadow_root {
+            return LayoutIterator(TraversalChildren::Children(
+                self.0.doc().node(shadow_root).dom_children(),
+            ));
+        }
+        if !element.assigned_nodes.is_empty() {
+            return LayoutIterator(TraversalChildren::Assigned(
+                self.0,
+                element.assigned_nodes.iter(),
+            ));
+        }
+        LayoutIterator(TraversalChildren::Children(self.0.dom_children()))
+    }
+
+    fn inheritance_parent(&self) -> Option<Self> {
+        if self.is_pseudo_element() {
+            return self.pseudo_element_originating_element();
+        }
+        self.traversal_parent()
+    }
+
+    fn is_html_element(&self) -> bool {
+        self.element_data().namespace == ns!(html)
+    }
+
+    fn is_mathml_element(&self) -> bool {
+        self.element_data().namespace == ns!(mathml)
+    }
+
+    fn is_svg_element(&self) -> bool {
+        self.element_data().namespace == ns!(svg)
+    }
+
+    fn style_attribute(&self) -> Option<ArcBorrow<'_, Locked<PropertyDeclarationBlock>>> {
+        self.element_data()
+            .style_attribute
+            .as_ref()
+            .map(|block| block.borrow_arc())
+    }
+
+    fn animation_rule(
+        &self,
+        context: &SharedStyleContext,
+    ) -> Option<Arc<Locked<PropertyDeclarationBlock>>> {
+        let node = self.as_node();
+        let doc = node.owner_doc();
+        context.animations.get_animation_declarations(
+            &AnimationSetKey::new_for_non_pseudo(node.opaque()),
+            context.current_time_for_animations,
+            doc.shared_lock(),
+        )
+    }
+
+    fn transition_rule(
+        &self,
+        context: &SharedStyleContext,
+    ) -> Option<Arc<Locked<PropertyDeclarationBlock>>> {
+        let node = self.as_node();
+        let doc = node.owner_doc();
+        context.animations.get_transition_declarations(
+            &AnimationSetKey::new_for_non_pseudo(node.opaque()),
+            context.current_time_for_animations,
+            doc.shared_lock(),
+        )
+    }
+
+    fn state(&self) -> ElementState {
+        self.element_data().state
+    }
+
+    fn has_part_attr(&self) -> bool {
+        self.get_attr_value(&ns!(), &local_name!("part")).is_some()
+    }
+
+    fn exports_any_part(&self) -> bool {
+        self.get_attr_value(&ns!(), &local_name!("exportparts"))
+            .is_some()
+    }
+
+    fn id(&self) -> Option<&Atom> {
+        self.element_data().id.as_ref()
+    }
+
+    fn each_class<F>(&self, mut callback: F)
+    where
+        F: FnMut(&AtomIdent),
+    {
+        if let Some(classes) = self.get_attr_value(&ns!(), &local_name!("class")) {
+            for class in classes.as_tokens() {
+                callback(AtomIdent::cast(class));
+            }
+        }
+    }
+
+    fn each_custom_state<F>(&self, mut callback: F)
+    where
+        F: FnMut(&AtomIdent),
+    {
+        for state in self.element_data().custom_states.iter() {
+            callback(state);
+        }
+    }
+
+    fn each_part<F>(&self, mut callback: F)
+    where
+        F: FnMut(&AtomIdent),
+    {
+        if let Some(parts) = self.get_attr_value(&ns!(), &local_name!("part")) {
+            for part in parts.as_tokens() {
+                callback(AtomIdent::cast(part));
+            }
+        }
+    }
+
+    fn each_attr_name<F>(&self, mut callback: F)
+    where
+        F: FnMut(&LocalName),
+    {
+        for (name, _) in self.element_data().attrs.iter() {
+            callback(&name.local_name);
+        }
+    }
+
+    fn each_exported_part<F>(&self, name: &AtomIdent, callback: F)
+    where
+        F: FnMut(&AtomIdent),
+    {
+        if let Some(parts) = self.get_attr_value(&ns!(), &local_name!("exportparts")) {
+            parts
+                .as_shadow_parts()
+                .for_each_exported_part(&name.0, callback);
+        }
+    }
+
+    fn has_dirty_descendants(&self) -> bool {
+        self.0
+            .node_data()
+            .flags()
+            .contains(NodeFlags::DIRTY_DESCENDANTS)
+    }
+
+    fn has_snapshot(&self) -> bool {
+        self.0.node_data().flags().contains(NodeFlags::HAS_SNAPSHOT)
+    }
+
+    fn handled_snapshot(&self) -> bool {
+        self.0
+            .node_data()
+            .flags()
+            .contains(NodeFlags::HANDLED_SNAPSHOT)
+    }
+
+    unsafe fn set_handled_snapshot(&self) {
+        self.0.node_data().insert_flags(NodeFlags::HANDLED_SNAPSHOT)
+    }
+
+    unsafe fn set_dirty_descendants(&self) {
+        self.0
+            .node_data()
+            .insert_flags(NodeFlags::DIRTY_DESCENDANTS)
+    }
+
+    unsafe fn unset_dirty_descendants(&self) {
+        self.0
+            .node_data()
+            .remove_flags(NodeFlags::DIRTY_DESCENDANTS)
+    }
+
+    fn store_children_to_process(&self, n: isize) {
+        self.element_data()
+            .children_to_process
+            .store(n, Ordering::Relaxed);
+    }
+
+    fn did_process_child(&self) -> isize {
+        let old_value = self
+            .element_data()
+            .children_to_process
+            .fetch_sub(1, Ordering::Relaxed);
+        debug_assert!(old_value >= 1);
+        old_value - 1
+    }
+
+    unsafe fn ensure_data(&self) -> AtomicRefMut<'_, ElementData> {
+        AtomicRefMut::map(self.element_data().data.borrow_mut(), |data| {
+            data.get_or_insert_with(Default::default)
+        })
+    }
+
+    unsafe fn clear_data(&self) {
+        *self.element_data().data.borrow_mut() = None;
+    }
+
+    fn has_data(&self) -> bool {
+        self.element_data().data.borrow().is_some()
+    }
+
+    fn borrow_data(&self) -> Option<AtomicRef<'_, ElementData>> {
+        AtomicRef::filter_map(self.element_data().data.borrow(), |data| data.as_ref())
+    }
+
+    fn mutate_data(&self) -> Option<AtomicRefMut<'_, ElementData>> {
+        AtomicRefMut::filter_map(self.element_data().data.borrow_mut(), |data| data.as_mut())
+    }
+
+    fn skip_item_display_fixup(&self) -> bool {
+        false
+    }
+
+    fn may_have_animations(&self) -> bool {
+        true
+    }
+
+    fn has_animations(&self, context: &SharedStyleContext) -> bool {
+        self.has_css_animations(context, None) || self.has_css_transitions(context, None)
+    }
+
+    fn has_css_animations(
+        &self,
+        context: &SharedStyleContext,
+        pseudo_element: Option<PseudoElement>,
+    ) -> bool {
+        let key = AnimationSetKey::new(self.as_node().opaque(), pseudo_element);
+        context.animations.has_active_animations(&key)
+    }
+
+    fn has_css_transitions(
+        &self,
+        context: &SharedStyleContext,
+        pseudo_element: Option<PseudoElement>,
+    ) -> bool {
+        let key = AnimationSetKey::new(self.as_node().opaque(), pseudo_element);
+        context.animations.has_active_transitions(&key)
+    }
+
+    fn shadow_root(&self) -> Option<ShadowRootRef<'dom>> {
+        let shadow_root = self.element_data().shadow_root?;
+        self.0.doc().node(shadow_root).as_shadow_root()
+    }
+
+    fn containing_shadow(&self) -> Option<ShadowRootRef<'dom>> {
+        let mut current = self.0.parent_node();
+        while let Some(node) = current {
+            if let Some(shadow_root) = node.as_shadow_root() {
+                return Some(shadow_root);
+            }
+            current = node.parent_node();
+        }
+        None
+    }
+
+    fn lang_attr(&self) -> Option<SelectorAttrValue> {
+        self.get_attr_value(&ns!(xml), &local_name!("lang"))
+            .or_else(|| self.get_attr_value(&ns!(), &local_name!("lang")))
+            .map(|value| AtomString::from(&**value))
+    }
+
+    fn match_element_lang(
+        &self,
+        override_lang: Option<Option<SelectorAttrValue>>,
+        value: &Lang,
+    ) -> bool {
+        let element_lang = match override_lang {
+            Some(Some(lang)) => lang.to_string(),
+            Some(None) => String::new(),
+            None => self.language(),
+        };
+        extended_filtering(&element_lang, value)
+    }
+
+    fn is_html_document_body_element(&self) -> bool {
+        if !self.is_html_element() || *self.local_name() != local_name!("body") {
+            return false;
+        }
+        self.parent_element().is_some_and(|parent| {
+            parent.is_root()
+                && parent.is_html_element()
+                && *parent.local_name() == local_name!("html")
+        })
+    }
+
+    fn synthesize_presentational_hints_for_legacy_attributes<V>(
+        &self,
+        _visited_handling: VisitedHandlingMode,
+        _hints: &mut V,
+    ) where
+        V: Push<ApplicableDeclarationBlock>,
+    {
+    }
+
+    fn synthesize_view_transition_dynamic_rules<V>(&self, pseudo: &PseudoElement, rules: &mut V)
+    where
+        V: Push<ApplicableDeclarationBlock>,
+    {
+        let rule = self
+            .0
+            .doc()
+            .view_transition_rules
+            .iter()
+            .find(|(rule_pseudo, _)| rule_pseudo == pseudo);
+        if let Some((_, block)) = rule {
+            rules.push(ApplicableDeclarationBlock::from_declarations(
+                block.clone(),
+                CascadeLevel::UANormal,
+                LayerOrder::root(),
+            ));
+        }
+    }
+
+    fn local_name(&self) -> &web_atoms::LocalName {
+        &self.element_data().local_name
+    }
+
+    fn namespace(&self) -> &web_atoms::Namespace {
+        &self.element_data().namespace
+    }
+
+    fn query_container_size(&self, _display: &Display) -> Size2D<Option<app_units::Au>> {
+        Size2D::new(None, None)
+    }
+
+    fn query_container_scroll_state(&self) -> ContainerScrollState {
+        self.element_data().scroll_state
+    }
+
+    fn has_selector_flags(&self, flags: ElementSelectorFlags) -> bool {
+        self.selector_flags().contains(flags)
+    }
+
+    fn relative_selector_search_direction(&self) -> ElementSelectorFlags {
+        self.selector_flags()
+            .intersection(ElementSelectorFlags::RELATIVE_SELECTOR_SEARCH_DIRECTION_ANCESTOR_SIBLING)
+    }
+}
+
+impl<'dom> Element for ElementRef<'dom> {
+    type Impl = SelectorImpl;
+
+    fn opaque(&self) -> OpaqueElement {
+        OpaqueElement::new(self.0.node_data())
+    }
+
+    fn parent_element(&self) -> Option<Self> {
+        self.0.parent_node()?.as_element()
+    }
+
+    fn parent_node_is_shadow_root(&self) -> bool {
+        self.0
+            .parent_node()
+            .is_some_and(|parent| parent.as_shadow_root().is_some())
+    }
+
+    fn containing_shadow_host(&self) -> Option<Self> {
+        Some(self.containing_shadow()?.host())
+    }
+
+    fn pseudo_element_originating_element(&self) -> Option<Self> {
+        debug_assert!(self.is_pseudo_element());
+        self.containing_shadow_host()
+    }
+
+    fn is_pseudo_element(&self) -> bool {
+        self.element_data().implemented_pseudo.is_some()
+    }
+
+    fn prev_sibling_element(&self) -> Option<Self> {
+        let mut sibling = self.0.prev_sibling();
+        while let Some(node) = sibling {
+            if let Some(element) = node.as_element() {
+                return Some(element);
+            }
+            sibling = node.prev_sibling();
+        }
+        None
+    }
+
+    fn next_sibling_element(&self) -> Option<Self> {
+        let mut sibling = self.0.next_sibling();
+        while let Some(node) = sibling {
+            if let Some(element) = node.as_element() {
+                return Some(element);
+            }
+            sibling = node.next_sibling();
+        }
+        None
+    }
+
+    fn first_element_child(&self) -> Option<Self> {
+        self.0.dom_children().find_map(|child| child.as_element())
+    }
+
+    fn table_cell_columns(&self) -> Option<CellColumns> {
+        let layout = self.table_for_cell()?.table_layout();
+        let (start, span) = *layout.cells.get(&self.node_id())?;
+        Some(CellColumns {
+            start,
+            span,
+            count: layout.column_count,
+        })
+    }
+
+    fn column_element(&self, column: u32) -> Option<Self> {
+        let layout = self.table_for_cell()?.table_layout();
+        let id = (*layout.columns.get(column as usize)?)?;
+        self.0.doc().node(id).as_element()
+    }
+
+    fn is_html_element_in_html_document(&self) -> bool {
+        self.is_html_element() && self.0.doc().is_html_document
+    }
+
+    fn has_local_name(&self, local_name: &web_atoms::LocalName) -> bool {
+        self.element_data().local_name == *local_name
+    }
+
+    fn has_namespace(&self, ns: &web_atoms::Namespace) -> bool {
+        self.element_data().namespace == *ns
+    }
+
+    fn is_same_type(&self, other: &Self) -> bool {
+        self.element_data().local_name == other.element_data().local_name
+            && self.element_data().namespace == other.element_data().namespace
+    }
+
+    fn attr_matches(
+        &self,
+        ns: &NamespaceConstraint<&Namespace>,
+        local_name: &LocalName,
+        operation: &AttrSelectorOperation<&AtomString>,
+    ) -> bool {
+        match *ns {
+            NamespaceConstraint::Specific(ns) => self
+                .get_attr_value(&ns.0, &local_name.0)
+                .is_some_and(|value| value.eval_selector(operation)),
+            NamespaceConstraint::Any => self.element_data().attrs.iter().any(|(name, value)| {
+                name.local_name == *local_name && value.eval_selector(operation)
+            }),
+        }
+    }
+
+    fn match_non_ts_pseudo_class(
+        &self,
+        pseudo_class: &NonTSPseudoClass,
+        _: &mut MatchingContext<Self::Impl>,
+    ) -> bool {
+        match *pseudo_class {
+            NonTSPseudoClass::Link | NonTSPseudoClass::AnyLink => self.is_link(),
+            NonTSPseudoClass::Visited => false,
+            NonTSPseudoClass::Lang(ref lang) => self.match_element_lang(None, lang),
+            NonTSPseudoClass::CustomState(ref state) => self.has_custom_state(&state.0),
+            NonTSPseudoClass::ServoNonZeroBorder => self
+                .get_attr_value(&ns!(), &local_name!("border"))
+                .is_some_and(|border| &**border != "0"),
+            _ => self.state().intersects(pseudo_class.state_flag()),
+        }
+    }
+
+    fn match_pseudo_element(
+        &self,
+        pseudo: &PseudoElement,
+        _: &mut MatchingContext<Self::Impl>,
+    ) -> bool {
+        self.element_data()
+            .implemented_pseudo
+            .as_ref()
+            .is_some_and(|implemented| implemented.matches(pseudo))
+    }
+
+    fn apply_selector_flags(&self, flags: ElementSelectorFlags) {
+        let self_flags = flags.for_self();
+        if !self_flags.is_empty() {
+            self.insert_selector_flags(self_flags);
+        }
+
+        let parent_flags = flags.for_parent();
+        if !parent_flags.is_empty() {
+            if let Some(parent) = self.parent_element() {
+                parent.insert_selector_flags(parent_flags);
+            }
+        }
+    }
+
+    fn is_link(&self) -> bool {
+        self.is_html_element()
+            && matches!(
+                *self.local_name(),
+                local_name!("a") | local_name!("area") | local_name!("link")
+            )
+            && self.get_attr_value(&ns!(), &local_name!("href")).is_some()
+    }
+
+    fn is_html_slot_element(&self) -> bool {
+        self.is_html_element() && *self.local_name() == local_name!("slot")
+    }
+
+    fn assigned_slot(&self) -> Option<Self> {
+        self.0.doc().element(self.element_data().assigned_slot?)
+    }
+
+    fn has_id(&self, id: &AtomIdent, case_sensitivity: CaseSensitivity) -> bool {
+        self.element_data()
+            .id
+            .as_ref()
+            .is_some_and(|own_id| case_sensitivity.eq_atom(own_id, id))
+    }
+
+    fn has_class(&self, name: &AtomIdent, case_sensitivity: CaseSensitivity) -> bool {
+        self.get_attr_value(&ns!(), &local_name!("class"))
+            .is_some_and(|classes| {
+                classes
+                    .as_tokens()
+                    .iter()
+                    .any(|class| case_sensitivity.eq_atom(class, name))
+            })
+    }
+
+    fn has_custom_state(&self, name: &AtomIdent) -> bool {
+        self.element_data().custom_states.contains(name)
+    }
+
+    fn imported_part(&self, name: &AtomIdent) -> Option<AtomIdent> {
+        let parts = self.get_attr_value(&ns!(), &local_name!("exportparts"))?;
+        parts
+            .as_shadow_parts()
+            .imported_part(&name.0)
+            .map(|part| AtomIdent::new(part.clone()))
+    }
+
+    fn is_part(&self, name: &AtomIdent) -> bool {
+        self.get_attr_value(&ns!(), &local_name!("part"))
+            .is_some_and(|parts| {
+                parts
+                    .as_tokens()
+                    .iter()
+                    .any(|part| CaseSensitivity::CaseSensitive.eq_atom(part, name))
+            })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0
+            .dom_children()
+            .all(|child| match child.node_data().kind {
+                NodeKind::Element(..) => false,
+                NodeKind::Text(ref text) => text.is_empty(),
+                _ => true,
+            })
+    }
+
+    fn is_root(&self) -> bool {
+        self.0
+            .parent_node()
+            .is_some_and(|parent| parent.as_document().is_some())
+    }
+
+    fn add_element_unique_hashes(&self, filter: &mut BloomFilter) -> bool {
+        each_relevant_element_hash(*self, |hash| filter.insert_hash(hash & BLOOM_HASH_MASK));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use style::color::AbsoluteColor;
+
+    fn stylist(doc: &Document, css: &str) -> Stylist {
+        let device = crate::default_device(euclid::Size2D::new(800., 600.), doc.quirks_mode());
+        let mut stylist = Stylist::new(device, doc.quirks_mode());
+        let guard = doc.shared_lock().read();
+        stylist.append_stylesheet(doc.parse_stylesheet(css, Origin::Author), &guard);
+        stylist.flush(&StylesheetGuards::same(&guard));
+        stylist
+    }
+
+    fn color(doc: &Document, id: NodeId) -> AbsoluteColor {
+        doc.style(id).unwrap().get_inherited_text().clone_color()
+    }
+
+    #[test]
+    fn restyle_incrementally() {
+        let mut doc = Document::new(
+            url::Url::parse("about:blank").unwrap(),
+            QuirksMode::NoQuirks,
+        );
+        let html = doc.create_element("html");
+        let body = doc.create_element("body");
+        let div = doc.create_element("div");
+        let text = doc.create_text_node("Hello");
+        doc.append_child(NodeId::DOCUMENT, html);
+        doc.append_child(html, body);
+        doc.append_child(body, div);
+        doc.append_child(div, text);
+
+        let stylist = stylist(
+            &doc,
+            "div { color: red } .blue { color: blue } div:hover { color: lime }",
+        );
+        assert!(doc.restyle(&stylist, None));
+        assert_eq!(color(&doc, div), AbsoluteColor::srgb_legacy(255, 0, 0, 1.0));
+        assert!(!doc.restyle(&stylist, None), "Nothing changed");
+
+        doc.set_attribute(div, "class", "blue");
+        assert!(doc.restyle(&stylist, None));
+        assert_eq!(color(&doc, div), AbsoluteColor::srgb_legacy(0, 0, 255, 1.0));
+
+        doc.remove_attribute(div, "class");
+        doc.insert_state(div, ElementState::HOVER);
+        assert!(doc.restyle(&stylist, None));
+        assert_eq!(color(&doc, div), AbsoluteColor::srgb_legacy(0, 255, 0, 1.0));
+
+        doc.set_attribute(div, "style", "color: black");
+        assert!(doc.restyle(&stylist, None));
+        assert_eq!(color(&doc, div), AbsoluteColor::srgb_legacy(0, 0, 0, 1.0));
+    }
+
+    #[test]
+    fn restyle_shadow_tree() {
+        let mut doc = Document::new(
+            url::Url::parse("about:blank").unwrap(),
+            QuirksMode::NoQuirks,
+        );
+        let html = doc.create_element("html");
+        let host = doc.create_element("div");
+        let slotted = doc.create_element("span");
+        doc.append_child(NodeId::DOCUMENT, html);
+        doc.append_child(html, host);
+        doc.append_child(host, slotted);
+
+        let shadow_root = doc.attach_shadow(host);
+        let inner = doc.create_element("p");
+        let slot = doc.create_element("slot");
+        doc.set_attribute(inner, "part", "inner");
+        doc.append_child(shadow_root, inner);
+        doc.append_child(shadow_root, slot);
+
+        let sheet = doc.parse_stylesheet(
+            "p { color: red } ::slotted(span) { color: blue }",
+            Origin::Author,
+        );
+        doc.append_shadow_root_stylesheet(shadow_root, sheet);
+        let mut stylist = stylist(&doc, "::part(inner) { color: lime } p { color: black }");
+        doc.flush_shadow_root_styles(&mut stylist);
+
+        assert!(doc.restyle(&stylist, None));
+        assert_eq!(
+            color(&doc, inner),
+            AbsoluteColor::srgb_legacy(0, 255, 0, 1.0)
+        );
+        assert_eq!(
+            color(&doc, slotted),
+            AbsoluteColor::srgb_legacy(0, 0, 255, 1.0)
+        );
+    }
+}
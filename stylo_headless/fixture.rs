@@ -0,0 +1,402 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! A parser for small HTML-like fixtures.
+//!
+//! This is not an HTML parser: it understands start and end tags, quoted and
+//! unquoted attributes, comments, a handful of character references and void
+//! elements, and nothing else. In particular, there's no implied end tag
+//! handling beyond closing unclosed elements when an ancestor is closed.
+//!
+//! `<style>` elements are turned into author stylesheets, and
+//! `<template shadowrootmode>` elements attach a shadow root to their parent,
+//! like declarative shadow DOM does. If the fixture doesn't start with an
+//! `<html>` element, its contents are wrapped in `<html>` and `<body>`.
+
+use crate::dom::{Document, NodeId};
+use style::context::QuirksMode;
+use style::dom::TElement;
+use style::stylesheets::{DocumentStyleSheet, Origin};
+
+/// The result of parsing a fixture.
+pub struct Fixture {
+    /// The parsed document.
+    pub document: Document,
+    /// The stylesheets from the `<style>` elements of the document, in tree
+    /// order. Stylesheets in shadow trees are already attached to their
+    /// shadow root.
+    pub stylesheets: Vec<DocumentStyleSheet>,
+}
+
+/// Elements that never have children.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose contents are not parsed as markup.
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title"];
+
+/// A parser over the fixture source.
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    document: Document,
+    stylesheets: Vec<DocumentStyleSheet>,
+    /// The stack of open nodes, which are either elements or shadow roots.
+    open_nodes: Vec<NodeId>,
+}
+
+/// Parses `source` into a new document with the given URL.
+pub fn parse(source: &str, url: url::Url, quirks_mode: QuirksMode) -> Fixture {
+    let mut parser = Parser {
+        input: source,
+        position: 0,
+        document: Document::new(url, quirks_mode),
+        stylesheets: vec![],
+        open_nodes: vec![NodeId::DOCUMENT],
+    };
+    parser.parse();
+    Fixture {
+        document: parser.document,
+        stylesheets: parser.stylesheets,
+    }
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn current(&self) -> NodeId {
+        *self.open_nodes.last().unwrap()
+    }
+
+    fn parse(&mut self) {
+        while !self.rest().is_empty() {
+            let rest = self.rest();
+            if let Some(comment) = rest.strip_prefix("<!--") {
+                self.position += 4 + comment.find("-->").map_or(comment.len(), |end| end + 3);
+            } else if rest.starts_with("<!") || rest.starts_with("<?") {
+                // Doctypes and processing instructions.
+                self.position += rest.find('>').map_or(rest.len(), |end| end + 1);
+            } else if rest.starts_with("</") {
+                self.parse_end_tag();
+            } else if rest.starts_with('<')
+                && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+            {
+                self.parse_start_tag();
+            } else {
+                let end = rest[1..].find('<').map_or(rest.len(), |end| end + 1);
+                self.position += end;
+                self.insert_text(&rest[..end]);
+            }
+        }
+    }
+
+    fn insert_text(&mut self, text: &str) {
+        let parent = self.current();
+        if parent == NodeId::DOCUMENT {
+            if text.trim().is_empty() {
+                return;
+            }
+            self.ensure_body();
+        }
+        let text = self
+            .document
+            .create_text_node(&decode_character_references(text));
+        let parent = self.current();
+        self.document.append_child(parent, text);
+    }
+
+    /// Creates an implicit `<html>` and `<body>`, for fixtures that don't
+    /// have a root element, or reopens the root element for content after it.
+    fn ensure_body(&mut self) {
+        if let Some(root) = self.document.root_element() {
+            let root = root.node_id();
+            self.open_nodes.push(root);
+            return;
+        }
+        let html = self.document.create_element("html");
+        let body = self.document.create_element("body");
+        self.document.append_child(NodeId::DOCUMENT, html);
+        self.document.append_child(html, body);
+        self.open_nodes.push(html);
+        self.open_nodes.push(body);
+    }
+
+    fn parse_tag_name(&mut self) -> String {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+            .unwrap_or(rest.len());
+        self.position += end;
+        rest[..end].to_ascii_lowercase()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn parse_end_tag(&mut self) {
+        self.position += 2;
+        let name = self.parse_tag_name();
+        let rest = self.rest();
+        self.position += rest.find('>').map_or(rest.len(), |end| end + 1);
+
+        // Close the innermost open element with this name, and everything
+        // inside it. Shadow roots are closed by their template end tag.
+        let index = self
+            .open_nodes
+            .iter()
+            .rposition(|id| match self.document.element(*id) {
+                Some(element) => **element.local_name() == *name,
+                None => name == "template" && *id != NodeId::DOCUMENT,
+            });
+        if let Some(index) = index {
+            self.open_nodes.truncate(index.max(1));
+        }
+    }
+
+    fn parse_start_tag(&mut self) {
+        self.position += 1;
+        let name = self.parse_tag_name();
+        let mut attributes = vec![];
+        let mut self_closing = false;
+        loop {
+            self.skip_whitespace();
+            let rest = self.rest();
+            if rest.is_empty() {
+                break;
+            }
+            if let Some(after) = rest.strip_prefix("/>") {
+                self.position = self.input.len() - after.len();
+                self_closing = true;
+                break;
+            }
+            if rest.starts_with('>') {
+                self.position += 1;
+                break;
+            }
+            if rest.starts_with('/') {
+                self.position += 1;
+                continue;
+            }
+            attributes.push(self.parse_attribute());
+        }
+
+        if name == "template" {
+            if let Some((_, mode)) = attributes.iter().find(|(name, _)| name == "shadowrootmode") {
+                if (mode == "open" || mode == "closed") && self.attach_shadow() {
+                    return;
+                }
+            }
+        }
+
+        if self.current() == NodeId::DOCUMENT && name != "html" {
+            self.ensure_body();
+        }
+        let element = self.document.create_element(&name);
+        for (name, value) in attributes {
+            self.document.set_attribute(element, &name, &value);
+        }
+        let parent = self.current();
+        self.document.append_child(parent, element);
+
+        if RAW_TEXT_ELEMENTS.contains(&&*name) {
+            self.parse_raw_text(element, &name);
+            return;
+        }
+        if !self_closing && !VOID_ELEMENTS.contains(&&*name) {
+            self.open_nodes.push(element);
+        }
+    }
+
+    fn parse_attribute(&mut self) -> (String, String) {
+        let rest = self.rest();
+        let end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '>' || c == '/')
+            .unwrap_or(rest.len())
+            .max(1);
+        let name = rest[..end].to_ascii_lowercase();
+        self.position += end;
+        self.skip_whitespace();
+        if !self.rest().starts_with('=') {
+            return (name, String::new());
+        }
+        self.position += 1;
+        self.skip_whitespace();
+
+        let rest = self.rest();
+        let value = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = &rest[1..];
+                let end = value.find(quote).unwrap_or(value.len());
+                self.position += (end + 2).min(rest.len());
+                &value[..end]
+            },
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                    .unwrap_or(rest.len());
+                self.position += end;
+                &rest[..end]
+            },
+        };
+        (name, decode_character_references(value))
+    }
+
+    /// Parses the contents of a raw text element, up to its end tag.
+    fn parse_raw_text(&mut self, element: NodeId, name: &str) {
+        let rest = self.rest();
+        let end_tag = format!("</{}", name);
+        let end = rest
+            .to_ascii_lowercase()
+            .find(&end_tag)
+            .unwrap_or(rest.len());
+        let contents = &rest[..end];
+        self.position += end;
+        self.position += self
+            .rest()
+            .find('>')
+            .map_or(self.rest().len(), |end| end + 1);
+
+        if !contents.is_empty() {
+            let text = self.document.create_text_node(contents);
+            self.document.append_child(element, text);
+        }
+        if name != "style" {
+            return;
+        }
+        let sheet = self.document.parse_stylesheet(contents, Origin::Author);
+        let shadow_root = self
+            .open_nodes
+            .iter()
+            .rev()
+            .find(|id| **id != NodeId::DOCUMENT && self.document.element(**id).is_none());
+        match shadow_root {
+            Some(&shadow_root) => self
+                .document
+                .append_shadow_root_stylesheet(shadow_root, sheet),
+            None => self.stylesheets.push(sheet),
+        }
+    }
+
+    /// Attaches a shadow root to the current element, and returns whether it
+    /// could.
+    fn attach_shadow(&mut self) -> bool {
+        let host = self.current();
+        if self.document.element(host).is_none() || self.document.shadow_root(host).is_some() {
+            return false;
+        }
+        let shadow_root = self.document.attach_shadow(host);
+        self.open_nodes.push(shadow_root);
+        true
+    }
+}
+
+/// Decodes the few character references that fixtures are likely to use.
+fn decode_character_references(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_owned();
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let decoded = match &rest[1..end] {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => '\u{a0}',
+                reference => {
+                    let code = match reference
+                        .strip_prefix("#x")
+                        .or(reference.strip_prefix("#X"))
+                    {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => reference.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                },
+            };
+            Some((decoded, end + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                result.push(c);
+                rest = &rest[len..];
+            },
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            },
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use style::dom::{TNode, TShadowRoot};
+
+    fn parse_fixture(source: &str) -> Fixture {
+        parse(
+            source,
+            url::Url::parse("about:blank").unwrap(),
+            QuirksMode::NoQuirks,
+        )
+    }
+
+    #[test]
+    fn implied_root() {
+        let fixture = parse_fixture("<p class='a b' hidden>x &amp; y<br>z</p><style>p {}</style>");
+        let doc = &fixture.document;
+        let html = doc.root_element().unwrap();
+        assert_eq!(&**html.local_name(), "html");
+        let body = html.as_node().first_child().unwrap().as_element().unwrap();
+        assert_eq!(&**body.local_name(), "body");
+        let p = body.as_node().first_child().unwrap().as_element().unwrap();
+        assert_eq!(doc.get_attribute(p.node_id(), "class"), Some("a b"));
+        assert_eq!(doc.get_attribute(p.node_id(), "hidden"), Some(""));
+        let texts: Vec<_> = p
+            .as_node()
+            .dom_children()
+            .filter_map(|child| child.text())
+            .collect();
+        assert_eq!(texts, ["x & y", "z"]);
+        assert_eq!(fixture.stylesheets.len(), 1);
+    }
+
+    #[test]
+    fn declarative_shadow_dom() {
+        let fixture = parse_fixture(
+            "<html><body><div><template shadowrootmode=open><style>b {}</style><b></b>\
+             </template><i></i></div></body></html>",
+        );
+        let doc = &fixture.document;
+        let body = doc.root_element().unwrap().as_node().first_child().unwrap();
+        let host = body.first_child().unwrap().as_element().unwrap();
+        let shadow_root = host.shadow_root().unwrap();
+        let shadow_children: Vec<_> = shadow_root
+            .as_node()
+            .dom_children()
+            .map(|child| child.as_element().unwrap().local_name().to_string())
+            .collect();
+        assert_eq!(shadow_children, ["style", "b"]);
+        let light_child = host.as_node().first_child().unwrap().as_element().unwrap();
+        assert_eq!(&**light_child.local_name(), "i");
+        assert!(
+            fixture.stylesheets.is_empty(),
+            "Shadow styles stay in the shadow root"
+        );
+    }
+}
@@ -0,0 +1,39 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! A font metrics provider for styling without access to real fonts.
+
+use style::font_metrics::FontMetrics;
+use style::properties::style_structs::Font;
+use style::servo::media_queries::FontMetricsProvider;
+use style::values::computed::font::GenericFontFamily;
+use style::values::computed::{CSSPixelLength, Length};
+use style::values::specified::font::QueryFontMetricsFlags;
+
+/// A `FontMetricsProvider` that knows nothing about fonts.
+///
+/// Font-relative units like `ex` and `ch` fall back to the ratios the style
+/// system uses when metrics are unavailable, and the default font sizes match
+/// the ones browsers use for the generic families.
+#[derive(Debug, Default)]
+pub struct DefaultFontMetricsProvider;
+
+impl FontMetricsProvider for DefaultFontMetricsProvider {
+    fn query_font_metrics(
+        &self,
+        _vertical: bool,
+        _font: &Font,
+        _base_size: CSSPixelLength,
+        _flags: QueryFontMetricsFlags,
+    ) -> FontMetrics {
+        FontMetrics::default()
+    }
+
+    fn base_size_for_generic(&self, generic: GenericFontFamily) -> Length {
+        Length::new(match generic {
+            GenericFontFamily::Monospace => 13.,
+            _ => 16.,
+        })
+    }
+}
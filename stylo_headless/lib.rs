@@ -0,0 +1,55 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! A reference, in-memory DOM for running Stylo without an embedder.
+//!
+//! Embedding the style system normally requires implementing `TElement`,
+//! `TNode`, `TDocument` and `TShadowRoot` (and `selectors::Element`) over your
+//! own DOM. This crate provides a simple arena-based [`Document`] that
+//! implements all of them, plus a [`RecalcStyle`] traversal, so that
+//! `style::driver::traverse_dom` can run end-to-end. It is useful for
+//! server-side style computation and for writing tests against real
+//! stylesheets.
+//!
+//! The DOM is mutated through `&mut Document`, and styled through
+//! [`Document::restyle`], which takes care of snapshots and dirty bits so that
+//! incremental restyles behave like they do in a real engine.
+//!
+//! The `stylo-compute` binary builds on top of this and the [`fixture`]
+//! parser to print the computed styles of an HTML-like file as JSON, which is
+//! handy to debug the cascade without a browser.
+
+#![deny(missing_docs)]
+
+pub mod dom;
+pub mod fixture;
+mod font_metrics;
+pub mod traversal;
+
+pub use crate::dom::{Document, DocumentRef, ElementRef, NodeId, NodeRef, ShadowRootRef};
+pub use crate::font_metrics::DefaultFontMetricsProvider;
+pub use crate::traversal::RecalcStyle;
+
+use euclid::{Scale, Size2D};
+use style::context::QuirksMode;
+use style::media_queries::MediaType;
+use style::properties::style_structs::Font;
+use style::properties::ComputedValues;
+use style::queries::values::PrefersColorScheme;
+use style::servo::media_queries::Device;
+use style_traits::CSSPixel;
+
+/// Creates a screen `Device` with the given viewport size, a device pixel
+/// ratio of one, and no access to real fonts.
+pub fn default_device(viewport_size: Size2D<f32, CSSPixel>, quirks_mode: QuirksMode) -> Device {
+    Device::new(
+        MediaType::screen(),
+        quirks_mode,
+        viewport_size,
+        Scale::new(1.0),
+        Box::new(DefaultFontMetricsProvider),
+        ComputedValues::initial_values_with_font_override(Font::initial_values()),
+        PrefersColorScheme::Light,
+    )
+}
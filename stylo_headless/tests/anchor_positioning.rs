@@ -0,0 +1,190 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use style::logical_geometry::{PhysicalAxis, PhysicalSide};
+use style::shared_lock::StylesheetGuards;
+use style::values::computed::position::{AnchorPosResolver, AnchorSide};
+use style::values::computed::{Length, PositionProperty};
+use style::values::generics::length::AnchorSizeKeyword;
+use style::values::generics::position::{AnchorSideKeyword, TreeScoped};
+use style::values::DashedIdent;
+use style_traits::ToCss;
+
+/// An anchor box at (20, 30) sized 100x40, in a 800x600 containing block.
+struct Anchors;
+
+impl Anchors {
+    fn find(&self, name: &TreeScoped<DashedIdent>) -> Result<(), ()> {
+        match &*name.value.0 {
+            "" | "--tip" => Ok(()),
+            _ => Err(()),
+        }
+    }
+}
+
+impl AnchorPosResolver for Anchors {
+    fn position_property(&self) -> PositionProperty {
+        PositionProperty::Absolute
+    }
+
+    fn resolve_anchor(
+        &self,
+        anchor_name: &TreeScoped<DashedIdent>,
+        anchor_side: &AnchorSide,
+        prop_side: PhysicalSide,
+    ) -> Result<Length, ()> {
+        self.find(anchor_name)?;
+        let edge = match anchor_side.keyword_and_percentage().0 {
+            AnchorSideKeyword::Top => 30.,
+            AnchorSideKeyword::Bottom => 70.,
+            AnchorSideKeyword::Left => 20.,
+            AnchorSideKeyword::Right => 120.,
+            _ => return Err(()),
+        };
+        Ok(Length::new(match prop_side {
+            PhysicalSide::Top | PhysicalSide::Left => edge,
+            PhysicalSide::Bottom => 600. - edge,
+            PhysicalSide::Right => 800. - edge,
+        }))
+    }
+
+    fn resolve_anchor_size(
+        &self,
+        anchor_name: &TreeScoped<DashedIdent>,
+        prop_axis: PhysicalAxis,
+        anchor_size_keyword: AnchorSizeKeyword,
+    ) -> Result<Length, ()> {
+        self.find(anchor_name)?;
+        Ok(Length::new(match (anchor_size_keyword, prop_axis) {
+            (AnchorSizeKeyword::Width, _) | (AnchorSizeKeyword::None, PhysicalAxis::Horizontal) => {
+                100.
+            },
+            (AnchorSizeKeyword::Height, _) | (AnchorSizeKeyword::None, PhysicalAxis::Vertical) => {
+                40.
+            },
+            _ => return Err(()),
+        }))
+    }
+}
+
+#[test]
+fn anchor_positioning() {
+    let mut page = Page::new(
+        "<style>
+           div { anchor-name: --tip }
+           span {
+             position: absolute;
+             position-anchor: --tip;
+             top: anchor(bottom);
+             left: calc(anchor(--tip left) + 10px);
+             width: anchor-size(width);
+             max-width: calc(anchor-size(height) * 2);
+             max-height: anchor-size(--missing, anchor-size(--missing));
+             margin-top: anchor-size(--missing height, 4px);
+             position-try: most-height --above, flip-block;
+           }
+           @position-try --above { top: auto; bottom: anchor(top) }
+         </style>
+         <div id=anchor></div>
+         <span id=positioned></span>",
+    );
+    let div = page.element("anchor");
+    let span = page.element("positioned");
+    assert!(page.restyle());
+
+    let div_style = page.style(div);
+    assert_eq!(div_style.get_box().anchor_name.to_css_string(), "--tip");
+    let style = page.style(span);
+    let position = style.get_position();
+    assert_eq!(position.position_anchor.to_css_string(), "--tip");
+    assert_eq!(position.clone_top().to_css_string(), "anchor(bottom)");
+    assert_eq!(
+        position
+            .clone_top()
+            .resolve_anchor(PhysicalSide::Top, &Anchors)
+            .to_css_string(),
+        "70px"
+    );
+    assert_eq!(
+        position
+            .clone_left()
+            .resolve_anchor(PhysicalSide::Left, &Anchors)
+            .to_css_string(),
+        "30px"
+    );
+    assert_eq!(
+        position
+            .clone_width()
+            .resolve_anchor(PhysicalAxis::Horizontal, &Anchors)
+            .to_css_string(),
+        "100px"
+    );
+    assert_eq!(
+        position
+            .clone_max_width()
+            .resolve_anchor(PhysicalAxis::Horizontal, &Anchors)
+            .to_css_string(),
+        "80px"
+    );
+    assert_eq!(
+        position
+            .clone_max_height()
+            .resolve_anchor(PhysicalAxis::Vertical, &Anchors)
+            .to_css_string(),
+        "none",
+        "Invalid anchor-size() functions fall back to none"
+    );
+    let margin = style.get_margin().clone_margin_top();
+    assert_eq!(
+        margin
+            .resolve_anchor(PhysicalSide::Top, &Anchors)
+            .to_css_string(),
+        "4px",
+        "--missing falls back"
+    );
+
+    let guard = page.doc.shared_lock().read();
+    let guards = StylesheetGuards::same(&guard);
+    let element = page.doc.element(span).unwrap();
+    let fallbacks = position.clone_position_try_fallbacks();
+    assert_eq!(
+        position.clone_position_try_order().to_css_string(),
+        "most-height"
+    );
+    assert_eq!(fallbacks.0.len(), 2);
+
+    let above = page
+        .stylist
+        .resolve_position_try(&style, &guards, element, &fallbacks.0[0])
+        .unwrap();
+    let above = above.get_position();
+    assert!(above.clone_top().is_auto());
+    assert_eq!(
+        above
+            .clone_bottom()
+            .resolve_anchor(PhysicalSide::Bottom, &Anchors)
+            .to_css_string(),
+        "570px"
+    );
+
+    let flipped = page
+        .stylist
+        .resolve_position_try(&style, &guards, element, &fallbacks.0[1])
+        .unwrap();
+    assert_eq!(
+        flipped.get_position().clone_bottom().to_css_string(),
+        "anchor(top)"
+    );
+    assert_eq!(
+        flipped
+            .get_position()
+            .clone_bottom()
+            .resolve_anchor(PhysicalSide::Bottom, &Anchors)
+            .to_css_string(),
+        "570px"
+    );
+}
@@ -0,0 +1,225 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use euclid::default::{Rect, Size2D, Vector2D};
+use servo_arc::Arc;
+use style::dom::TNode;
+use style::properties::longhands::animation_direction::computed_value::single_value::T as AnimationDirection;
+use style::properties::longhands::animation_fill_mode::computed_value::single_value::T as AnimationFillMode;
+use style::properties::parse_style_attribute;
+use style::servo::animation::{AnimationSetKey, ScriptAnimationTiming, ScriptKeyframe};
+use style::servo::scroll_timeline::ScrollState;
+use style::stylesheets::CssRuleType;
+use style::values::computed::TimingFunction;
+use style_traits::ToCss;
+
+#[test]
+fn script_animation() {
+    let mut page = Page::new("<style>div { opacity: 0.5 }</style><div id=target></div>");
+    let div = page.element("target");
+    assert!(page.restyle());
+
+    let doc = &page.doc;
+    let keyframe = |css: &str| ScriptKeyframe {
+        offset: None,
+        block: Arc::new(doc.shared_lock().wrap(parse_style_attribute(
+            css,
+            doc.url_data(),
+            None,
+            doc.quirks_mode(),
+            CssRuleType::Style,
+        ))),
+    };
+    let keyframes = [keyframe("opacity: 0"), keyframe("opacity: 1")];
+    let key = AnimationSetKey::new_for_non_pseudo(doc.node(div).opaque());
+    let timing = ScriptAnimationTiming::new(
+        0.,
+        0.,
+        AnimationFillMode::None,
+        1.,
+        10.,
+        AnimationDirection::Normal,
+        TimingFunction::linear(),
+    )
+    .unwrap();
+    let id = doc
+        .animations()
+        .create_script_animation(&key, &keyframes, timing, &doc.shared_lock().read())
+        .unwrap();
+
+    let opacity = |page: &Page| page.style(div).get_effects().clone_opacity();
+    page.doc.set_current_time_for_animations(1.);
+    assert!(page.restyle());
+    assert_eq!(opacity(&page), 0.5, "Idle animations have no effect");
+
+    page.doc
+        .animations()
+        .with_script_animation(&key, id, |animation| animation.play(1.))
+        .unwrap()
+        .unwrap();
+    page.doc.set_current_time_for_animations(3.5);
+    assert!(page.restyle());
+    assert_eq!(opacity(&page), 0.25);
+
+    page.doc
+        .animations()
+        .with_script_animation(&key, id, |animation| animation.pause(3.5))
+        .unwrap()
+        .unwrap();
+    page.doc.set_current_time_for_animations(8.5);
+    assert!(page.restyle());
+    assert_eq!(opacity(&page), 0.25);
+
+    page.doc
+        .animations()
+        .with_script_animation(&key, id, |animation| animation.cancel());
+    page.doc.set_current_time_for_animations(9.);
+    assert!(page.restyle());
+    assert_eq!(opacity(&page), 0.5);
+}
+
+#[test]
+fn animation_composition() {
+    let mut page = Page::new(
+        "<style>
+           @keyframes grow { from { width: 0px } to { width: 100px } }
+           @keyframes nudge { from, to { width: 10px } }
+           @keyframes mixed { from { width: 10px; animation-composition: add } to { width: 30px } }
+           div { width: 200px; animation: grow 10s linear, nudge 10s linear; animation-composition: replace, add }
+           span { display: block; width: 200px; animation: mixed 10s linear }
+         </style>
+         <div id=div></div>
+         <span id=span></span>",
+    );
+    let div = page.element("div");
+    let span = page.element("span");
+    assert!(page.restyle());
+
+    let width = |page: &Page, id| page.style(id).get_position().clone_width().to_css_string();
+    page.doc.set_current_time_for_animations(5.);
+    assert!(page.restyle());
+    assert_eq!(width(&page, div), "60px", "nudge is added on top of grow");
+    assert_eq!(
+        width(&page, span),
+        "120px",
+        "Halfway between 200px + 10px and 30px"
+    );
+}
+
+#[test]
+fn scroll_driven_animations() {
+    let mut page = Page::new(
+        "<style>
+           @keyframes fade { from { opacity: 0 } to { opacity: 1 } }
+           html { timeline-scope: --main }
+           div { overflow: scroll; scroll-timeline: --main y }
+           p { animation: fade 1s linear; animation-timeline: view() }
+           span { animation: fade 1s linear both; animation-timeline: --main }
+         </style>
+         <div id=scroller><p id=subject></p></div>
+         <span id=span></span>",
+    );
+    let scroller = page.element("scroller");
+    let p = page.element("subject");
+    let span = page.element("span");
+    assert!(page.restyle());
+
+    let opacity = |page: &Page, id| page.style(id).get_effects().opacity;
+    let scroll_to = |page: &mut Page, y: f64| {
+        page.doc.animations().set_scroll_state(
+            page.doc.node(scroller).opaque(),
+            ScrollState {
+                scroll_offset: Vector2D::new(0., y),
+                max_scroll_offset: Vector2D::new(0., 100.),
+                scrollport_size: Size2D::new(100., 100.),
+            },
+        );
+        page.doc.animations().update_timeline_progress()
+    };
+    page.doc.animations().set_view_timeline_subject_rect(
+        page.doc.node(p).opaque(),
+        Rect::new((0., 150.).into(), Size2D::new(100., 50.)),
+    );
+    scroll_to(&mut page, 50.);
+    let changed = page
+        .doc
+        .animations()
+        .resolve_timelines(page.doc.root_element().unwrap());
+    assert_eq!(changed.len(), 2);
+    page.doc.set_current_time_for_animations(0.);
+    assert!(page.restyle());
+    assert_eq!(
+        opacity(&page, span),
+        0.5,
+        "--main is visible through timeline-scope"
+    );
+    assert_eq!(opacity(&page, p), 0., "p is entering the scrollport");
+
+    assert_eq!(scroll_to(&mut page, 125.).len(), 2);
+    page.doc.set_current_time_for_animations(0.);
+    assert!(page.restyle());
+    assert_eq!(opacity(&page, span), 1.);
+    assert_eq!(opacity(&page, p), 0.5);
+
+    // Outside of the view timeline, p isn't filled backwards.
+    assert_eq!(scroll_to(&mut page, 0.).len(), 2);
+    page.doc.set_current_time_for_animations(0.);
+    assert!(page.restyle());
+    assert_eq!(opacity(&page, span), 0.);
+    assert_eq!(opacity(&page, p), 1.);
+}
+
+#[test]
+fn scroll_driven_animation_insets_and_iterations() {
+    let mut page = Page::new(
+        "<style>
+           @keyframes fade { from { opacity: 0 } to { opacity: 1 } }
+           @keyframes half { from { opacity: 0 } to { opacity: 0.5 } }
+           html { timeline-scope: --main }
+           div { overflow: scroll; scroll-timeline: --main y; scroll-padding: 10px 0 30px }
+           p { animation: fade 1s linear; animation-timeline: view() }
+           span { animation: half 1s linear infinite both; animation-timeline: --main }
+           #alternate { animation-direction: alternate-reverse }
+           #unfilled { animation-fill-mode: none }
+         </style>
+         <div id=scroller><p id=subject></p></div>
+         <span id=infinite></span><span id=alternate></span><span id=unfilled></span>",
+    );
+    let scroller = page.element("scroller");
+    let p = page.element("subject");
+    let spans = ["infinite", "alternate", "unfilled"].map(|id| page.element(id));
+    assert!(page.restyle());
+
+    page.doc.animations().set_scroll_state(
+        page.doc.node(scroller).opaque(),
+        ScrollState {
+            scroll_offset: Vector2D::new(0., 135.),
+            max_scroll_offset: Vector2D::new(0., 200.),
+            scrollport_size: Size2D::new(100., 100.),
+        },
+    );
+    page.doc.animations().set_view_timeline_subject_rect(
+        page.doc.node(p).opaque(),
+        Rect::new((0., 150.).into(), Size2D::new(100., 50.)),
+    );
+    page.doc
+        .animations()
+        .resolve_timelines(page.doc.root_element().unwrap());
+    page.doc.set_current_time_for_animations(0.);
+    assert!(page.restyle());
+
+    // The `auto` insets are the scroll padding, so the view timeline runs
+    // from a scroll offset of 150 - 100 + 30 to 150 + 50 - 10.
+    let opacity = |page: &Page, id| page.style(id).get_effects().opacity;
+    assert_eq!(opacity(&page, p), 0.5);
+
+    // Infinitely many iterations take no room on the timeline, so the
+    // animations fill forwards, playing forwards.
+    assert_eq!(opacity(&page, spans[0]), 0.5);
+    assert_eq!(opacity(&page, spans[1]), 0.5);
+    assert_eq!(opacity(&page, spans[2]), 1.);
+}
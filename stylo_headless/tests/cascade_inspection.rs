@@ -0,0 +1,136 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use style::cascade_inspection::DeclarationStatus;
+use style::properties::{LonghandId, OwnedPropertyDeclarationId};
+use style::shared_lock::StylesheetGuards;
+use style::stylesheets::Origin;
+use style::Atom;
+use style_traits::ToCss;
+
+#[test]
+fn cascade_inspection() {
+    style_config::set_bool("layout.css.at-scope.enabled", true).unwrap();
+
+    // The lines of the rules are relative to the start of the stylesheet.
+    let mut page = Page::new(
+        "<style>@layer base {
+  div { color: red; margin-inline-start: 1px; opacity: 0.5 }
+}
+div { color: green; --x: 1px }
+#target { color: blue !important; width: var(--x); padding-top: var(--missing); margin-left: 2px; opacity: revert-layer }
+@scope (body) {
+  div { height: 3px }
+}</style>
+<div id=target style='color: black; width: 5px'></div>",
+    );
+    let div = page.element("target");
+    assert!(page.restyle());
+    let style = page.style(div);
+    assert_eq!(style.get_effects().clone_opacity(), 0.5);
+
+    let guard = page.doc.shared_lock().read();
+    let rules = page.stylist.inspect_cascade(
+        &StylesheetGuards::same(&guard),
+        page.doc.element(div).unwrap(),
+        None,
+        &style,
+    );
+    let lines: Vec<_> = rules
+        .iter()
+        .map(|rule| rule.source_location.map(|location| location.line))
+        .collect();
+    assert_eq!(lines, [Some(1), Some(3), Some(6), Some(4), None]);
+    assert!(rules.iter().all(|rule| rule.origin() == Origin::Author));
+
+    let rule = |line| {
+        rules
+            .iter()
+            .find(|rule| rule.source_location.map(|location| location.line) == line)
+            .unwrap()
+    };
+    let status = |line, id: OwnedPropertyDeclarationId| {
+        rule(line)
+            .declarations
+            .iter()
+            .find(|declaration| declaration.id == id)
+            .unwrap()
+            .status
+    };
+    let longhand = OwnedPropertyDeclarationId::Longhand;
+
+    let layered = rule(Some(1));
+    assert_eq!(layered.layer_name.as_ref().unwrap().to_css_string(), "base");
+    assert_eq!(layered.specificity, Some(1));
+    assert_eq!(layered.scope_proximity, None);
+    assert_eq!(rule(Some(6)).scope_proximity, Some(1));
+    assert_eq!(rule(Some(4)).layer_name, None);
+    assert_eq!(rule(Some(4)).specificity, Some(1 << 20));
+    assert_eq!(rule(None).specificity, Some(0));
+
+    use DeclarationStatus::*;
+    assert_eq!(status(Some(1), longhand(LonghandId::Color)), Overridden);
+    assert_eq!(status(Some(3), longhand(LonghandId::Color)), Overridden);
+    assert_eq!(status(Some(4), longhand(LonghandId::Color)), Applied);
+    assert_eq!(status(None, longhand(LonghandId::Color)), Overridden);
+    assert_eq!(
+        status(Some(1), longhand(LonghandId::MarginInlineStart)),
+        Overridden
+    );
+    assert_eq!(status(Some(4), longhand(LonghandId::MarginLeft)), Applied);
+    assert_eq!(status(Some(1), longhand(LonghandId::Opacity)), Applied);
+    assert_eq!(status(Some(4), longhand(LonghandId::Opacity)), Reverted);
+    assert_eq!(status(Some(4), longhand(LonghandId::Width)), Overridden);
+    assert_eq!(status(None, longhand(LonghandId::Width)), Applied);
+    assert_eq!(
+        status(Some(4), longhand(LonghandId::PaddingTop)),
+        InvalidAtComputedValueTime
+    );
+    assert_eq!(status(Some(6), longhand(LonghandId::Height)), Applied);
+    assert_eq!(
+        status(Some(3), OwnedPropertyDeclarationId::Custom(Atom::from("x"))),
+        Applied
+    );
+}
+
+#[test]
+fn cascade_inspection_in_shadow_tree() {
+    let mut page = Page::new(
+        "<div id=host><template shadowrootmode=open><style>@layer inner {
+  span, .c { color: red }
+}
+span { color: green }</style><span id=target class=c></span></template></div>",
+    );
+    let span = page.element("target");
+    assert!(page.restyle());
+    let style = page.style(span);
+
+    let guard = page.doc.shared_lock().read();
+    let rules = page.stylist.inspect_cascade(
+        &StylesheetGuards::same(&guard),
+        page.doc.element(span).unwrap(),
+        None,
+        &style,
+    );
+    let lines: Vec<_> = rules
+        .iter()
+        .map(|rule| rule.source_location.map(|location| location.line))
+        .collect();
+    assert_eq!(lines, [Some(1), Some(3)]);
+    assert_eq!(
+        rules[0].layer_name.as_ref().unwrap().to_css_string(),
+        "inner"
+    );
+    assert_eq!(rules[0].specificity, Some(1 << 10));
+    assert_eq!(rules[1].specificity, Some(1));
+    assert_eq!(
+        rules[1]
+            .applied_declaration(&OwnedPropertyDeclarationId::Longhand(LonghandId::Color))
+            .map(|declaration| declaration.index),
+        Some(0)
+    );
+}
@@ -0,0 +1,131 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! The fixture shared by the integration tests: a document parsed from
+//! markup, along with a stylist for its stylesheets.
+
+#![allow(dead_code)]
+
+use euclid::Size2D;
+use servo_arc::Arc;
+use style::color::AbsoluteColor;
+use style::context::QuirksMode;
+use style::dom::{TDocument, TElement, TNode, TShadowRoot};
+use style::properties::{ComputedValues, PropertyDeclarationId, PropertyId};
+use style::servo::media_queries::Device;
+use style::shared_lock::StylesheetGuards;
+use style::stylesheets::Origin;
+use style::stylist::Stylist;
+use stylo_headless::fixture::{self, Fixture};
+use stylo_headless::{Document, NodeId};
+
+/// A document parsed from a fixture, with a stylist for the stylesheets of
+/// its `<style>` elements.
+pub struct Page {
+    pub doc: Document,
+    pub stylist: Stylist,
+}
+
+impl Page {
+    /// Parses `markup`, to be styled for an 800x600 screen.
+    pub fn new(markup: &str) -> Self {
+        Self::with_device(markup, device())
+    }
+
+    /// Parses `markup`, to be styled for the given device.
+    pub fn with_device(markup: &str, device: Device) -> Self {
+        let Fixture {
+            mut document,
+            stylesheets,
+        } = fixture::parse(
+            markup,
+            url::Url::parse("about:blank").unwrap(),
+            QuirksMode::NoQuirks,
+        );
+        let mut stylist = Stylist::new(device, QuirksMode::NoQuirks);
+        {
+            let guard = document.shared_lock().read();
+            for sheet in stylesheets {
+                stylist.append_stylesheet(sheet, &guard);
+            }
+            stylist.flush(&StylesheetGuards::same(&guard));
+        }
+        document.flush_shadow_root_styles(&mut stylist);
+        Page {
+            doc: document,
+            stylist,
+        }
+    }
+
+    /// Appends a stylesheet to the stylist.
+    ///
+    /// The document isn't restyled.
+    pub fn add_stylesheet(&mut self, css: &str, origin: Origin) {
+        let sheet = self.doc.parse_stylesheet(css, origin);
+        let guard = self.doc.shared_lock().read();
+        self.stylist.append_stylesheet(sheet, &guard);
+        self.stylist.flush(&StylesheetGuards::same(&guard));
+    }
+
+    /// Returns the element with the given id, including elements in shadow
+    /// trees.
+    pub fn element(&self, id: &str) -> NodeId {
+        let mut stack = vec![self.doc.document().as_node()];
+        while let Some(node) = stack.pop() {
+            if let Some(element) = node.as_element() {
+                if element.id().is_some_and(|element_id| &**element_id == id) {
+                    return element.node_id();
+                }
+                stack.extend(
+                    element
+                        .shadow_root()
+                        .map(|shadow_root| shadow_root.as_node()),
+                );
+            }
+            stack.extend(node.dom_children());
+        }
+        panic!("No element with id {}", id)
+    }
+
+    /// Returns the root element.
+    pub fn root(&self) -> NodeId {
+        self.doc.root_element().unwrap().node_id()
+    }
+
+    /// Restyles the document, returning whether anything was traversed.
+    pub fn restyle(&mut self) -> bool {
+        self.doc.restyle(&self.stylist, None)
+    }
+
+    /// Returns the computed style of an element.
+    pub fn style(&self, element: NodeId) -> Arc<ComputedValues> {
+        self.doc.style(element).expect("Expected a styled element")
+    }
+
+    /// Returns the serialization of the computed value of a longhand on an
+    /// element.
+    pub fn computed_value(&self, element: NodeId, longhand: &str) -> String {
+        let id = PropertyId::parse_enabled_for_all_content(longhand)
+            .ok()
+            .and_then(|id| id.longhand_id())
+            .unwrap_or_else(|| panic!("{} isn't an enabled longhand", longhand));
+        self.style(element)
+            .computed_value_to_string(PropertyDeclarationId::Longhand(id))
+    }
+
+    /// Returns the computed `color` of an element.
+    pub fn color(&self, element: NodeId) -> AbsoluteColor {
+        self.style(element).get_inherited_text().clone_color()
+    }
+}
+
+/// Returns the device used by [`Page::new`].
+pub fn device() -> Device {
+    stylo_headless::default_device(Size2D::new(800., 600.), QuirksMode::NoQuirks)
+}
+
+/// Returns an opaque sRGB color.
+pub fn rgb(red: u8, green: u8, blue: u8) -> AbsoluteColor {
+    AbsoluteColor::srgb_legacy(red, green, blue, 1.0)
+}
@@ -0,0 +1,93 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+use serde_json::{json, Value};
+use std::path::Path;
+use std::process::Command;
+
+/// Runs `stylo-compute` with the given arguments, returning its exit code and
+/// standard output.
+fn stylo_compute(args: &[&str]) -> (Option<i32>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_stylo-compute"))
+        .current_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.code(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn compute_fixture() {
+    let (status, output) =
+        stylo_compute(&["-p", "color", "-p", "margin", "compute.html", "compute.css"]);
+    assert_eq!(status, Some(0));
+    let output: Value = serde_json::from_str(&output).unwrap();
+    let entries = output.as_array().unwrap();
+    let entry = |path: &str| {
+        entries
+            .iter()
+            .find(|entry| entry["path"] == path)
+            .unwrap_or_else(|| panic!("No entry for {}", path))
+    };
+
+    let p = entry("html > body > p#intro.lead");
+    assert_eq!(
+        p["style"],
+        json!({
+            "color": "rgb(0, 0, 255)",
+            "margin-top": "1px",
+            "margin-right": "2px",
+            "margin-bottom": "1px",
+            "margin-left": "2px",
+        }),
+        "Sheets from the command line come after the fixture"
+    );
+    assert_eq!(
+        p["style"].as_object().unwrap().keys().collect::<Vec<_>>(),
+        [
+            "color",
+            "margin-top",
+            "margin-right",
+            "margin-bottom",
+            "margin-left"
+        ],
+        "Properties are printed in the order they were given"
+    );
+    assert_eq!(p["pseudos"]["::before"]["color"], "rgb(0, 0, 255)");
+
+    let span = entry("html > body > div#host > #shadow-root > span");
+    assert_eq!(span["style"]["color"], "rgb(255, 0, 0)");
+    assert!(span.get("pseudos").is_none());
+    assert!(entries.iter().all(|entry| entry["node"].is_u64()));
+}
+
+#[test]
+fn compute_escapes_values() {
+    let (status, output) = stylo_compute(&["-p", "content", "compute.html"]);
+    assert_eq!(status, Some(0));
+    let output: Value = serde_json::from_str(&output).unwrap();
+    let p = output
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["path"] == "html > body > p#intro.lead")
+        .unwrap();
+    assert_eq!(p["pseudos"]["::before"]["content"], r#""\"quoted\"""#);
+}
+
+#[test]
+fn compute_errors() {
+    assert_eq!(stylo_compute(&["missing.html"]).0, Some(1));
+    assert_eq!(
+        stylo_compute(&["--viewport", "big", "compute.html"]).0,
+        Some(2)
+    );
+    assert_eq!(
+        stylo_compute(&["-p", "not-a-property", "compute.html"]).0,
+        Some(1)
+    );
+}
@@ -0,0 +1,103 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use cssparser::{Parser, ParserInput};
+use style::context::QuirksMode;
+use style::parser::ParserContext;
+use style::queries::feature::FeatureFlags;
+use style::stylesheets::container_rule::{
+    ContainerCondition, ContainerScrollState, ScrollStateAxes, ScrollStateSides,
+};
+use style::stylesheets::{CssRuleType, Origin, UrlExtraData};
+use style_traits::{ParsingMode, ToCss};
+
+/// Parses `condition` as the condition of an author `@container` rule.
+fn parse(condition: &str) -> ContainerCondition {
+    let url_data = UrlExtraData::from(url::Url::parse("about:blank").unwrap());
+    let context = ParserContext::new(
+        Origin::Author,
+        &url_data,
+        Some(CssRuleType::Container),
+        ParsingMode::DEFAULT,
+        QuirksMode::NoQuirks,
+        Default::default(),
+        None,
+        None,
+    );
+    let mut input = ParserInput::new(condition);
+    ContainerCondition::parse(&context, &mut Parser::new(&mut input)).unwrap()
+}
+
+#[test]
+fn scroll_state_container_queries() {
+    style_config::set_bool("layout.container-queries.enabled", true).unwrap();
+    style_config::set_bool("layout.css.scroll-state.enabled", true).unwrap();
+
+    let stuck = parse("scroll-state(stuck: top)");
+    assert_eq!(stuck.to_css_string(), "scroll-state(stuck: top)");
+    assert_eq!(
+        stuck.scroll_state_dependencies(),
+        FeatureFlags::SCROLL_STATE_STUCK
+    );
+    let snapped_or_scrollable = parse("scroll-state((snapped: inline) or (scrollable: block-end))");
+    assert_eq!(
+        snapped_or_scrollable.to_css_string(),
+        "scroll-state((snapped: inline) or (scrollable: block-end))"
+    );
+    assert_eq!(
+        snapped_or_scrollable.scroll_state_dependencies(),
+        FeatureFlags::SCROLL_STATE_SNAPPED | FeatureFlags::SCROLL_STATE_SCROLLABLE
+    );
+    assert!(parse("(width > 10px)")
+        .scroll_state_dependencies()
+        .is_empty());
+
+    let mut page = Page::new(
+        "<html><style>header { container-type: scroll-state }</style><body id=body><header id=header></header></body></html>",
+    );
+    let body = page.element("body");
+    let header = page.element("header");
+    assert!(page.restyle());
+    let header_style = page.style(header);
+    let body_style = page.style(body);
+
+    let none = ContainerScrollState::default();
+    let stuck = ContainerScrollState {
+        stuck: ScrollStateSides::TOP,
+        ..Default::default()
+    };
+    let snapped = ContainerScrollState {
+        snapped: ScrollStateAxes::X,
+        ..Default::default()
+    };
+    let queried = FeatureFlags::SCROLL_STATE_STUCK | FeatureFlags::SCROLL_STATE_SCROLLED;
+    assert!(!none
+        .restyle_hint_for_change(&stuck, &header_style, queried)
+        .is_empty());
+    assert!(
+        none.restyle_hint_for_change(&snapped, &header_style, queried)
+            .is_empty(),
+        "Only changes of the queried features need a restyle"
+    );
+    assert!(stuck
+        .restyle_hint_for_change(&stuck, &header_style, queried)
+        .is_empty());
+    assert!(
+        none.restyle_hint_for_change(&stuck, &body_style, queried)
+            .is_empty(),
+        "Not a scroll-state container"
+    );
+
+    // No container query of the page uses scroll-state features.
+    assert!(page
+        .stylist
+        .scroll_state_dependencies(page.doc.element(header).unwrap())
+        .is_empty());
+    page.doc
+        .set_container_scroll_state(header, stuck, &page.stylist);
+    assert!(!page.restyle());
+}
@@ -0,0 +1,119 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use style::counter_style::CounterStyle;
+use style::selector_parser::PseudoElement;
+use style::values::computed::counters::{Content, ContentItem};
+use style::values::computed::ListStyleType;
+use style::values::CustomIdent;
+use style::Atom;
+
+#[test]
+fn counter_styles() {
+    let page = Page::new(
+        "<style>
+           @counter-style thumbs { system: cyclic; symbols: '👍' '👎'; suffix: ' ' }
+           @counter-style padded { system: extends decimal; pad: 3 '0'; prefix: '('; suffix: ')' }
+           @counter-style bits { system: numeric; symbols: '0' '1'; range: 0 7; fallback: upper-roman }
+           @counter-style tally { system: additive; additive-symbols: 5 '卌', 1 '|'; negative: '(' ')'; range: infinite infinite }
+           @counter-style loop-a { system: extends loop-b }
+           @counter-style loop-b { system: extends loop-a; suffix: '] ' }
+         </style>",
+    );
+
+    let guard = page.doc.shared_lock().read();
+    let resolve = |name: &str| CounterStyle::Name(CustomIdent(Atom::from(name)));
+    let padded = resolve("padded");
+    let padded = page.stylist.resolve_counter_style(&padded, &guard);
+    assert_eq!(padded.marker_text(7), "(007)");
+    assert_eq!(padded.marker_text(-7), "(-07)");
+
+    let representation = |name: &str, value| {
+        let style = resolve(name);
+        let style = page.stylist.resolve_counter_style(&style, &guard);
+        style.representation(value)
+    };
+    assert_eq!(representation("thumbs", 3), "👍");
+    assert_eq!(representation("bits", 6), "110");
+    assert_eq!(representation("bits", 8), "VIII", "Out of range");
+    assert_eq!(representation("tally", -12), "(卌卌||)");
+    assert_eq!(representation("loop-b", 4), "4");
+
+    let lower_alpha = ListStyleType::LowerAlpha.counter_style();
+    let lower_alpha = page.stylist.resolve_counter_style(&lower_alpha, &guard);
+    assert_eq!(lower_alpha.marker_text(3), "c. ");
+    assert_eq!(
+        ListStyleType::CjkHeavenlyStem.counter_style(),
+        resolve("cjk-heavenly-stem")
+    );
+    assert_eq!(ListStyleType::None.counter_style(), CounterStyle::None);
+
+    for keyword in [
+        ListStyleType::None,
+        ListStyleType::Decimal,
+        ListStyleType::Khmer,
+    ] {
+        assert_eq!(
+            ListStyleType::from_counter_style(&keyword.counter_style()),
+            Some(keyword)
+        );
+    }
+    assert_eq!(ListStyleType::from_counter_style(&resolve("thumbs")), None);
+}
+
+#[test]
+fn css_counters() {
+    let mut page = Page::new(
+        "<html id=root>
+         <style>
+           @counter-style bits { system: numeric; symbols: '0' '1' }
+           html { counter-reset: reversed(chapter) section 4 }
+           h2 { counter-increment: section; counter-set: chapter 2 }
+           h2::before { content: counter(chapter, upper-roman) ' ' counters(section, '.', bits) }
+         </style>
+         <h2 id=heading></h2>
+         </html>",
+    );
+    let html = page.element("root");
+    let h2 = page.element("heading");
+    assert!(page.restyle());
+
+    let style = page.style(html);
+    let reset = style.get_counters().clone_counter_reset();
+    assert_eq!(reset.len(), 2);
+    assert!(reset[0].is_reversed);
+    assert_eq!(
+        (reset[1].name.0.clone(), reset[1].value),
+        (Atom::from("section"), 4)
+    );
+    let style = page.style(h2);
+    assert_eq!(style.get_counters().clone_counter_increment()[0].value, 1);
+    assert_eq!(style.get_counters().clone_counter_set()[0].value, 2);
+
+    let before = page.doc.pseudo_style(h2, &PseudoElement::Before).unwrap();
+    let Content::Items(content) = before.get_counters().clone_content() else {
+        panic!("Expected content items");
+    };
+    let guard = page.doc.shared_lock().read();
+    let text: String = content
+        .items
+        .iter()
+        .map(|item| match *item {
+            ContentItem::String(ref string) => string.to_string(),
+            ContentItem::Counter(_, ref style) => {
+                let style = page.stylist.resolve_counter_style(style, &guard);
+                style.representation(2)
+            },
+            ContentItem::Counters(_, ref separator, ref style) => {
+                let style = page.stylist.resolve_counter_style(style, &guard);
+                style.counters_representation(&[5, 2], separator)
+            },
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(text, "II 101.10");
+}
@@ -0,0 +1,69 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use style::font_face::{FontDisplay, FontFaceSourceFormatKeyword, FontFaceSourceTechFlags, Source};
+use style::font_matching::{FontQuery, FontSourceSupport};
+
+#[test]
+fn font_matching() {
+    let mut page = Page::new(
+        "<style>
+           @font-face { font-family: Foo; src: url(foo.woff2) format('woff2') }
+           @font-face { font-family: Foo; src: url(foo-heavy.woff) format(woff); font-weight: 600 900 }
+           @font-face {
+             font-family: foo;
+             src: local(Foo Bold), url(foo-bold.ttf) format(truetype);
+             font-weight: bold;
+             font-display: swap;
+             unicode-range: U+0-7F;
+           }
+           @font-face { font-family: Foo; src: url(foo-italic.woff2); font-style: italic }
+           @font-face { font-family: Bar; src: url(bar.woff2); font-weight: 300 }
+           html { font-family: Foo, Bar, serif; font-weight: bold }
+         </style>",
+    );
+    assert!(page.restyle());
+
+    let style = page.style(page.root());
+    let font = style.get_font();
+    let mut query = FontQuery {
+        family: &font.font_family,
+        weight: font.font_weight,
+        stretch: font.font_stretch,
+        style: font.font_style,
+        code_points: Some(cssparser::UnicodeRange {
+            start: 'A' as u32,
+            end: 'A' as u32,
+        }),
+    };
+    let support = FontSourceSupport {
+        formats: &[FontFaceSourceFormatKeyword::Woff2],
+        tech: FontFaceSourceTechFlags::empty(),
+    };
+    let guard = page.doc.shared_lock().read();
+
+    // The heavy face has no supported sources, so the bold face is the only
+    // candidate for Foo. Bar is matched as a fallback family.
+    let candidates = page.stylist.match_font_faces(&guard, &query, &support);
+    assert_eq!(candidates.len(), 2);
+    assert_eq!(candidates[0].family_index, 0);
+    assert_eq!(candidates[0].display, FontDisplay::Swap);
+    assert_eq!(candidates[0].sources.len(), 1);
+    assert!(matches!(candidates[0].sources[0], Source::Local(..)));
+    assert_eq!(candidates[1].family_index, 1);
+
+    // The bold face doesn't cover this code point, so the next family is used
+    // instead of a worse match in Foo.
+    query.code_points = Some(cssparser::UnicodeRange {
+        start: 0xE9,
+        end: 0xE9,
+    });
+    let candidates = page.stylist.match_font_faces(&guard, &query, &support);
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].family_index, 1);
+    assert_eq!(candidates[0].display, FontDisplay::Auto);
+}
@@ -0,0 +1,79 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use style::servo::restyle_damage::ServoRestyleDamage;
+use stylo_headless::ElementRef;
+
+#[test]
+fn mask_properties() {
+    let mut page = Page::new(
+        "<style>
+           div { mask: url(#mask) 10px 20px / cover repeat-x padding-box subtract alpha }
+           p { mask-clip: content-box; mask-clip: fill-box; mask-origin: view-box }
+         </style>
+         <div id=div></div>
+         <p id=p></p>",
+    );
+    let div = page.element("div");
+    let p = page.element("p");
+    assert!(page.restyle());
+
+    let expected = [
+        ("mask-image", "url(\"about:blank#mask\")"),
+        ("mask-position-x", "10px"),
+        ("mask-position-y", "20px"),
+        ("mask-size", "cover"),
+        ("mask-repeat", "repeat-x"),
+        ("mask-origin", "padding-box"),
+        ("mask-clip", "padding-box"),
+        ("mask-composite", "subtract"),
+        ("mask-mode", "alpha"),
+    ];
+    for (name, value) in expected {
+        assert_eq!(page.computed_value(div, name), value, "{}", name);
+    }
+
+    // The SVG geometry boxes aren't supported by Servo.
+    assert_eq!(page.computed_value(p, "mask-clip"), "content-box");
+    assert_eq!(page.computed_value(p, "mask-origin"), "border-box");
+}
+
+#[test]
+fn mask_establishes_stacking_context() {
+    let mut page = Page::new(
+        "<style>
+           .masked { mask-image: linear-gradient(black, transparent) }
+           .other-mask { mask-image: linear-gradient(white, transparent) }
+           .mask-type { mask-type: alpha }
+         </style>
+         <div id=div></div>",
+    );
+    let div = page.element("div");
+    assert!(page.restyle());
+
+    let mut set_class = |class: &str| {
+        let old = page.style(div);
+        page.doc.set_attribute(div, "class", class);
+        page.restyle();
+        let new = page.style(div);
+        let damage = ServoRestyleDamage::compute_style_difference::<ElementRef>(&old, &new).damage;
+        (damage, new.guarantees_stacking_context())
+    };
+    assert_eq!(
+        set_class("masked"),
+        (ServoRestyleDamage::REBUILD_STACKING_CONTEXT, true)
+    );
+    assert_eq!(
+        set_class("other-mask"),
+        (ServoRestyleDamage::REPAINT, true),
+        "Changing the mask keeps the stacking context"
+    );
+    assert_eq!(
+        set_class("mask-type"),
+        (ServoRestyleDamage::REBUILD_STACKING_CONTEXT, false)
+    );
+}
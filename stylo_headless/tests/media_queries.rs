@@ -0,0 +1,244 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::{device, Page};
+use cssparser::{Parser, ParserInput};
+use euclid::Size2D;
+use style::context::QuirksMode;
+use style::media_queries::MediaList;
+use style::parser::ParserContext;
+use style::queries::values::PrefersColorScheme;
+use style::servo::media_queries::{
+    ColorGamut, Device, DisplayMode, DynamicRange, PointerCapabilities, PrefersContrast, Scripting,
+};
+use style::shared_lock::StylesheetGuards;
+use style::stylesheets::{CssRuleType, CustomMediaEvaluator, Origin, UrlExtraData};
+use style::values::specified::color::ForcedColors;
+use style_traits::ParsingMode;
+
+/// Parses `query` as an author media list.
+fn parse(query: &str) -> MediaList {
+    let url_data = UrlExtraData::from(url::Url::parse("about:blank").unwrap());
+    let context = ParserContext::new(
+        Origin::Author,
+        &url_data,
+        Some(CssRuleType::Media),
+        ParsingMode::DEFAULT,
+        QuirksMode::NoQuirks,
+        Default::default(),
+        None,
+        None,
+    );
+    let mut input = ParserInput::new(query);
+    MediaList::parse(&context, &mut Parser::new(&mut input))
+}
+
+/// Asserts that `query` is supported, and that it evaluates to `expected`
+/// against `device`.
+///
+/// Unsupported queries never match, so they're told apart from queries that
+/// don't match by evaluating their negation too.
+#[track_caller]
+fn assert_matches(device: &Device, query: &str, expected: bool) {
+    let evaluate = |query: &str| {
+        parse(query).evaluate(
+            device,
+            QuirksMode::NoQuirks,
+            &mut CustomMediaEvaluator::none(),
+        )
+    };
+    assert_eq!(evaluate(query), expected, "{}", query);
+    assert_eq!(
+        evaluate(&format!("not {}", query)),
+        !expected,
+        "not {}",
+        query
+    );
+}
+
+#[test]
+fn viewport_features() {
+    let mut device = device();
+    assert_matches(&device, "(width: 800px)", true);
+    assert_matches(&device, "(min-width: 801px)", false);
+    assert_matches(&device, "(400px < width <= 800px)", true);
+    assert_matches(&device, "(height > 600px)", false);
+    assert_matches(&device, "(aspect-ratio: 4/3)", true);
+    assert_matches(&device, "(min-aspect-ratio: 16/9)", false);
+    assert_matches(&device, "(orientation: landscape)", true);
+    assert_matches(&device, "(orientation: portrait)", false);
+    assert_matches(&device, "(resolution: 1dppx)", true);
+    assert_matches(&device, "(grid)", false);
+    assert_matches(&device, "(grid: 0)", true);
+
+    device.set_viewport_size(Size2D::new(400., 600.));
+    assert_matches(&device, "(orientation: portrait)", true);
+    assert_matches(&device, "(max-width: 500px)", true);
+}
+
+#[test]
+fn viewport_dependent_features() {
+    for query in [
+        "(min-width: 100px)",
+        "(width < 100px)",
+        "(height: 600px)",
+        "(aspect-ratio: 4/3)",
+        "(orientation: portrait)",
+        "screen and (color), (max-width: 500px)",
+    ] {
+        assert!(parse(query).is_viewport_dependent(), "{}", query);
+    }
+    for query in ["screen", "(color)", "(resolution: 1dppx)", "(hover)"] {
+        assert!(!parse(query).is_viewport_dependent(), "{}", query);
+    }
+}
+
+#[test]
+fn color_features() {
+    let mut device = device();
+    assert_matches(&device, "(color)", true);
+    assert_matches(&device, "(min-color: 8)", true);
+    assert_matches(&device, "(min-color: 9)", false);
+    assert_matches(&device, "(color-index)", false);
+    assert_matches(&device, "(monochrome)", false);
+    assert_matches(&device, "(monochrome: 0)", true);
+    assert_matches(&device, "(color-gamut: srgb)", true);
+    assert_matches(&device, "(color-gamut: p3)", false);
+    assert_matches(&device, "(color-gamut)", false);
+    assert_matches(&device, "(dynamic-range: standard)", true);
+    assert_matches(&device, "(dynamic-range: high)", false);
+    assert_matches(&device, "(video-dynamic-range: high)", false);
+    assert_matches(&device, "(inverted-colors)", false);
+    assert_matches(&device, "(inverted-colors: none)", true);
+    assert_matches(&device, "(forced-colors)", false);
+    assert_matches(&device, "(forced-colors: none)", true);
+
+    device.set_color_depth(0, 1);
+    device.set_color_gamut(ColorGamut::P3);
+    device.set_dynamic_range(DynamicRange::High);
+    device.set_inverted_colors(true);
+    device.set_forced_colors(ForcedColors::Active);
+    assert_matches(&device, "(color)", false);
+    assert_matches(&device, "(monochrome)", true);
+    assert_matches(&device, "(min-monochrome: 2)", false);
+    assert_matches(&device, "(color-gamut: srgb)", true);
+    assert_matches(&device, "(color-gamut: p3)", true);
+    assert_matches(&device, "(color-gamut: rec2020)", false);
+    assert_matches(&device, "(dynamic-range: high)", true);
+    assert_matches(&device, "(video-dynamic-range: high)", false);
+    assert_matches(&device, "(inverted-colors: inverted)", true);
+    assert_matches(&device, "(forced-colors: active)", true);
+
+    device.set_video_dynamic_range(DynamicRange::High);
+    assert_matches(&device, "(video-dynamic-range: high)", true);
+}
+
+#[test]
+fn user_preference_features() {
+    let mut device = device();
+    assert_matches(&device, "(prefers-color-scheme: light)", true);
+    assert_matches(&device, "(prefers-reduced-motion)", false);
+    assert_matches(&device, "(prefers-reduced-motion: no-preference)", true);
+    assert_matches(&device, "(prefers-reduced-transparency)", false);
+    assert_matches(&device, "(prefers-contrast)", false);
+    assert_matches(&device, "(prefers-contrast: no-preference)", true);
+
+    device.set_color_scheme(PrefersColorScheme::Dark);
+    device.set_prefers_reduced_motion(true);
+    device.set_prefers_reduced_transparency(true);
+    device.set_prefers_contrast(PrefersContrast::Less);
+    assert_matches(&device, "(prefers-color-scheme: dark)", true);
+    assert_matches(&device, "(prefers-reduced-motion: reduce)", true);
+    assert_matches(&device, "(prefers-reduced-transparency: reduce)", true);
+    assert_matches(&device, "(prefers-contrast)", true);
+    assert_matches(&device, "(prefers-contrast: less)", true);
+    assert_matches(&device, "(prefers-contrast: more)", false);
+}
+
+#[test]
+fn environment_features() {
+    let mut device = device();
+    assert_matches(&device, "(overflow-block)", true);
+    assert_matches(&device, "(overflow-block: scroll)", true);
+    assert_matches(&device, "(overflow-inline: none)", false);
+    assert_matches(&device, "(update: fast)", true);
+    assert_matches(&device, "(display-mode: browser)", true);
+    assert_matches(&device, "(scripting)", true);
+    assert_matches(&device, "(scripting: enabled)", true);
+
+    device.set_display_mode(DisplayMode::Fullscreen);
+    device.set_scripting(Scripting::None);
+    assert_matches(&device, "(display-mode: fullscreen)", true);
+    assert_matches(&device, "(display-mode: browser)", false);
+    assert_matches(&device, "(scripting)", false);
+    assert_matches(&device, "(scripting: none)", true);
+}
+
+#[test]
+fn interaction_features() {
+    let mut device = device();
+    assert_matches(&device, "(pointer)", true);
+    assert_matches(&device, "(pointer: fine)", true);
+    assert_matches(&device, "(pointer: coarse)", false);
+    assert_matches(&device, "(hover)", true);
+    assert_matches(&device, "(any-hover: none)", false);
+
+    // A touchscreen, with a mouse plugged in.
+    device.set_pointer_capabilities(
+        PointerCapabilities::COARSE,
+        PointerCapabilities::FINE | PointerCapabilities::HOVER,
+    );
+    assert_matches(&device, "(pointer: coarse)", true);
+    assert_matches(&device, "(pointer: fine)", false);
+    assert_matches(&device, "(any-pointer: fine)", true);
+    assert_matches(&device, "(any-pointer: coarse)", true);
+    assert_matches(&device, "(hover)", false);
+    assert_matches(&device, "(any-hover: hover)", true);
+
+    device.set_pointer_capabilities(PointerCapabilities::empty(), PointerCapabilities::empty());
+    assert_matches(&device, "(pointer)", false);
+    assert_matches(&device, "(pointer: none)", true);
+    assert_matches(&device, "(any-pointer: none)", true);
+}
+
+#[test]
+fn media_feature_changes() {
+    let mut page = Page::new(
+        "<style>
+           @media (prefers-contrast: more) { div { color: red } }
+           @media (min-color: 8) { div { color: green } }
+         </style>",
+    );
+    let changed = |page: &Page| {
+        let guard = page.doc.shared_lock().read();
+        !page
+            .stylist
+            .media_features_change_changed_style(
+                &StylesheetGuards::same(&guard),
+                page.stylist.device(),
+            )
+            .is_empty()
+    };
+    assert!(!changed(&page));
+
+    // Changes that don't affect any media query aren't reported.
+    page.stylist
+        .device_mut()
+        .set_pointer_capabilities(PointerCapabilities::COARSE, PointerCapabilities::empty());
+    assert!(!changed(&page));
+
+    page.stylist
+        .device_mut()
+        .set_prefers_contrast(PrefersContrast::More);
+    assert!(changed(&page));
+    page.stylist
+        .device_mut()
+        .set_prefers_contrast(PrefersContrast::NoPreference);
+    assert!(!changed(&page));
+
+    page.stylist.device_mut().set_color_depth(0, 8);
+    assert!(changed(&page));
+}
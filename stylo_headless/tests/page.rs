@@ -0,0 +1,88 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use style::shared_lock::StylesheetGuards;
+use style::stylesheets::{MarginRuleType, PagePseudoClassFlags};
+use style::values::computed::page::{PageName, PageOrientation, PageSize};
+use style::values::computed::{Length, LengthPercentage, Margin};
+use style::values::CustomIdent;
+use style::{Atom, Zero};
+use stylo_headless::ElementRef;
+
+#[test]
+fn page_styles() {
+    style_config::set_bool("layout.css.margin-rules.enabled", true).unwrap();
+
+    let mut page = Page::new(
+        "<style>
+           html { color: green }
+           div { page: chapter }
+           @page { size: A4 landscape; margin-top: 10px }
+           @page :first { margin-top: 20px }
+           @page chapter { page-orientation: rotate-left; @top-center { margin-top: 5px } }
+         </style>
+         <div id=chapter></div>",
+    );
+    let html = page.root();
+    let div = page.element("chapter");
+    assert!(page.restyle());
+    assert_eq!(
+        page.style(div).get_page().clone_page(),
+        PageName::PageName(CustomIdent(Atom::from("chapter")))
+    );
+
+    let root_style = page.style(html);
+    let guard = page.doc.shared_lock().read();
+    let guards = StylesheetGuards::same(&guard);
+    let styles = page.stylist.compute_page_styles::<ElementRef>(
+        &guards,
+        &None,
+        PagePseudoClassFlags::FIRST,
+        Some(&root_style),
+    );
+    let PageSize::Size(size) = styles.page.get_page().clone_size() else {
+        panic!("Expected a page size");
+    };
+    assert!(size.width.px() > size.height.px());
+    assert_eq!(
+        styles.page.get_inherited_text().clone_color(),
+        page.color(html)
+    );
+    assert_eq!(
+        styles.page.get_margin().clone_margin_top(),
+        Margin::LengthPercentage(LengthPercentage::new_length(Length::new(20.)))
+    );
+    assert_eq!(
+        styles.page.get_page().clone_page_orientation(),
+        PageOrientation::Upright
+    );
+
+    let styles = page.stylist.compute_page_styles::<ElementRef>(
+        &guards,
+        &Some(Atom::from("chapter")),
+        PagePseudoClassFlags::NONE,
+        Some(&root_style),
+    );
+    assert_eq!(
+        styles.page.get_page().clone_page_orientation(),
+        PageOrientation::RotateLeft
+    );
+    let top_center = styles.margin_box(MarginRuleType::TopCenter);
+    assert_eq!(
+        top_center.get_margin().clone_margin_top(),
+        Margin::LengthPercentage(LengthPercentage::new_length(Length::new(5.)))
+    );
+    assert_eq!(
+        top_center.get_inherited_text().clone_color(),
+        page.color(html)
+    );
+    let bottom_center = styles.margin_box(MarginRuleType::BottomCenter);
+    assert_eq!(
+        bottom_center.get_margin().clone_margin_top(),
+        Margin::LengthPercentage(LengthPercentage::zero())
+    );
+}
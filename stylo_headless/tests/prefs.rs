@@ -0,0 +1,80 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use std::sync::{Arc, Mutex};
+use style::servo::prefs;
+use style_config::{PrefError, PrefValue};
+use style_traits::ToCss;
+
+#[test]
+fn prefs_registry_and_observers() {
+    let registry = prefs::registry();
+    let default = |name| {
+        registry
+            .iter()
+            .find(|pref| pref.name == name)
+            .map(|pref| pref.default.clone())
+    };
+    assert_eq!(default("layout.threads"), Some(PrefValue::Int(-1)));
+    assert_eq!(
+        default("layout.css.system-ui.enabled"),
+        Some(PrefValue::Bool(true))
+    );
+    assert_eq!(
+        default("layout.columns.enabled"),
+        Some(PrefValue::Bool(false))
+    );
+    assert!(prefs::enables_properties("layout.columns.enabled"));
+    assert!(!prefs::enables_properties("layout.threads"));
+
+    let markup = "<style>div { column-count: 3 }</style><div id=columns></div>";
+    let column_count = |page: &Page| {
+        page.style(page.element("columns"))
+            .get_column()
+            .clone_column_count()
+            .to_css_string()
+    };
+    let mut page = Page::new(markup);
+    assert!(page.restyle());
+    assert_eq!(column_count(&page), "auto");
+
+    let changed = Arc::new(Mutex::new(vec![]));
+    let observer = style_config::add_observer({
+        let changed = changed.clone();
+        move |name, _| {
+            if prefs::enables_properties(name) {
+                changed.lock().unwrap().push(name.to_owned());
+            }
+        }
+    });
+    style_config::load_toml("[layout]\n\"columns.enabled\" = true").unwrap();
+    style_config::remove_observer(observer);
+    assert!(changed
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|name| name == "layout.columns.enabled"));
+
+    // Stylesheets need to be parsed again for the property to be enabled.
+    let mut page = Page::new(markup);
+    assert!(page.restyle());
+    assert_eq!(column_count(&page), "3");
+}
+
+#[test]
+fn prefs_are_set_with_their_registered_type() {
+    prefs::register();
+    assert_eq!(
+        style_config::load_toml("[layout]\nthreads = true"),
+        Err(PrefError::TypeMismatch("layout.threads".to_owned()))
+    );
+    assert_eq!(
+        style_config::set_bool("layout.threads", true),
+        Err(PrefError::TypeMismatch("layout.threads".to_owned()))
+    );
+    assert_eq!(style_config::get_i32("layout.threads", -1), -1);
+}
@@ -0,0 +1,151 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::{rgb, Page};
+use cssparser::ToCss as _;
+use style::properties::ComputedValues;
+use style::selector_parser::{PseudoElement, SelectorParser};
+use style::shared_lock::StylesheetGuards;
+use style_traits::ToCss;
+
+#[test]
+fn parse_first_letter_and_first_line() {
+    let page = Page::new("");
+    let serialize = |selector: &str| {
+        SelectorParser::parse_author_origin_no_namespace(selector, page.doc.url_data())
+            .map(|list| list.to_css_string())
+            .ok()
+    };
+    assert_eq!(
+        serialize("p::first-letter").as_deref(),
+        Some("p::first-letter")
+    );
+    assert_eq!(serialize("p::FIRST-LINE").as_deref(), Some("p::first-line"));
+    // The legacy single-colon syntax is still supported.
+    assert_eq!(serialize("p:first-line").as_deref(), Some("p::first-line"));
+    assert_eq!(
+        serialize("p:first-letter").as_deref(),
+        Some("p::first-letter")
+    );
+    assert_eq!(serialize("p::first-letter span"), None);
+}
+
+#[test]
+fn first_letter_and_first_line_property_restrictions() {
+    let mut page = Page::new(
+        "<style>
+           p::first-letter, p::first-line {
+             color: green;
+             float: left;
+             position: absolute;
+             padding-left: 10px;
+             text-indent: 10px;
+           }
+         </style>
+         <p id=p>Text</p>",
+    );
+    let p = page.element("p");
+    assert!(page.restyle());
+
+    let first_letter = page
+        .doc
+        .pseudo_style(p, &PseudoElement::FirstLetter)
+        .unwrap();
+    let first_line = page.doc.pseudo_style(p, &PseudoElement::FirstLine).unwrap();
+    let green = rgb(0, 128, 0);
+    assert_eq!(first_letter.get_inherited_text().clone_color(), green);
+    assert_eq!(first_line.get_inherited_text().clone_color(), green);
+
+    let float = |style: &ComputedValues| style.get_box().clone_float().to_css_string();
+    let position = |style: &ComputedValues| style.get_box().clone_position().to_css_string();
+    let padding = |style: &ComputedValues| style.get_padding().clone_padding_left().to_css_string();
+    let indent = |style: &ComputedValues| {
+        style
+            .get_inherited_text()
+            .clone_text_indent()
+            .to_css_string()
+    };
+    assert_eq!(float(&first_letter), "left");
+    assert_eq!(
+        float(&first_line),
+        "none",
+        "float doesn't apply to ::first-line"
+    );
+    assert_eq!(position(&first_letter), "static");
+    assert_eq!(position(&first_line), "static");
+    assert_eq!(padding(&first_letter), "10px");
+    assert_eq!(padding(&first_line), "0px");
+    assert_eq!(indent(&first_letter), "0px");
+    assert_eq!(indent(&first_line), "0px");
+}
+
+#[test]
+fn reparent_style_for_first_line() {
+    let mut page = Page::new(
+        "<style>
+           p::first-line { color: green; letter-spacing: 2px }
+           span { opacity: 0.5 }
+           em { color: blue }
+         </style>
+         <p id=p><span id=span>First <em id=em>line</em></span></p>",
+    );
+    let p = page.element("p");
+    let span = page.element("span");
+    let em = page.element("em");
+    assert!(page.restyle());
+
+    let first_line = page.doc.pseudo_style(p, &PseudoElement::FirstLine).unwrap();
+    let p_style = page.style(p);
+    let guard = page.doc.shared_lock().read();
+    let guards = StylesheetGuards::same(&guard);
+    let span_style = page.style(span);
+    let reparented_span = page.stylist.reparent_style_for_first_line(
+        &guards,
+        page.doc.element(span),
+        &span_style,
+        &first_line,
+        &p_style,
+    );
+    assert_eq!(span_style.get_inherited_text().clone_color(), rgb(0, 0, 0));
+    assert_eq!(
+        reparented_span.get_inherited_text().clone_color(),
+        rgb(0, 128, 0),
+        "Inherited properties come from ::first-line"
+    );
+    assert_eq!(
+        reparented_span
+            .get_inherited_text()
+            .clone_letter_spacing()
+            .to_css_string(),
+        "2px"
+    );
+    assert_eq!(
+        reparented_span.get_effects().clone_opacity(),
+        0.5,
+        "Reset properties are kept as-is"
+    );
+
+    // Values specified for the reparented element itself are kept.
+    let em_style = page.style(em);
+    let reparented_em = page.stylist.reparent_style_for_first_line(
+        &guards,
+        page.doc.element(em),
+        &em_style,
+        &first_line,
+        &p_style,
+    );
+    assert_eq!(
+        reparented_em.get_inherited_text().clone_color(),
+        rgb(0, 0, 255)
+    );
+    assert_eq!(
+        reparented_em
+            .get_inherited_text()
+            .clone_letter_spacing()
+            .to_css_string(),
+        "2px"
+    );
+}
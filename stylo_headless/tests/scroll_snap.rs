@@ -0,0 +1,54 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use style::servo::restyle_damage::ServoRestyleDamage;
+use stylo_headless::ElementRef;
+
+#[test]
+fn scroll_snap_damage() {
+    let mut page = Page::new(
+        "<style>
+           .type { scroll-snap-type: y mandatory }
+           .align { scroll-snap-align: start end; scroll-snap-stop: always }
+           .margin { scroll-margin: 1px 2px }
+           .padding { scroll-padding-inline: 10% }
+           .padding-and-width { scroll-padding-inline: 20%; width: 10px }
+         </style>
+         <div id=div></div>",
+    );
+    let div = page.element("div");
+    assert!(page.restyle());
+
+    let mut set_class = |class: &str| {
+        let old = page.style(div);
+        page.doc.set_attribute(div, "class", class);
+        page.restyle();
+        let new = page.style(div);
+        ServoRestyleDamage::compute_style_difference::<ElementRef>(&old, &new).damage
+    };
+    for class in ["type", "align", "margin", "padding"] {
+        assert_eq!(
+            set_class(class),
+            ServoRestyleDamage::UPDATE_SCROLL_SNAP,
+            "{}",
+            class
+        );
+    }
+    assert_eq!(
+        set_class("padding-and-width"),
+        ServoRestyleDamage::UPDATE_SCROLL_SNAP | ServoRestyleDamage::RELAYOUT,
+        "Other damage is kept"
+    );
+}
+
+#[test]
+fn embedder_damage_bits() {
+    assert_eq!(
+        ServoRestyleDamage::all().bits() & ServoRestyleDamage::EMBEDDER_BITS,
+        0
+    );
+}
@@ -0,0 +1,221 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::{rgb, Page};
+use servo_arc::Arc;
+use style::color::AbsoluteColor;
+use style::dom::{TElement, TNode};
+use style::invalidation::element::table_columns;
+use stylo_headless::ElementRef;
+
+#[test]
+fn column_selectors() {
+    // The first row has a cell spanning the columns of the second `col`, and
+    // the table has a fourth column without a column element.
+    let mut page = Page::new(
+        "<style>
+           td { color: black }
+           col.highlight || td { color: red }
+           td:nth-col(4) { color: green }
+           td:nth-last-col(3) { color: blue }
+         </style>
+         <table>
+           <colgroup><col><col id=col2 span=2></colgroup>
+           <tbody>
+             <tr><td id=a1></td><td id=a2 colspan=2></td><td id=a3></td></tr>
+             <tr><td id=b1></td><td id=b2></td><td id=b3></td><td id=b4></td></tr>
+           </tbody>
+         </table>",
+    );
+    let col2 = page.element("col2");
+    let span = page.element("a2");
+    let rows = [
+        ["a1", "a2", "a3"].map(|id| page.element(id)).to_vec(),
+        ["b1", "b2", "b3", "b4"].map(|id| page.element(id)).to_vec(),
+    ];
+    let black = rgb(0, 0, 0);
+    let red = rgb(255, 0, 0);
+    let green = rgb(0, 128, 0);
+    let blue = rgb(0, 0, 255);
+    let colors = |page: &Page, row: usize| -> Vec<AbsoluteColor> {
+        rows[row].iter().map(|&td| page.color(td)).collect()
+    };
+    assert!(page.restyle());
+    assert_eq!(colors(&page, 0), [black, blue, green]);
+    assert_eq!(colors(&page, 1), [black, blue, black, green]);
+
+    // The cells aren't reachable from the column element, but still need to
+    // be restyled.
+    page.doc.set_attribute(col2, "class", "highlight");
+    assert!(page.restyle());
+    assert_eq!(colors(&page, 0), [black, red, green]);
+    assert_eq!(colors(&page, 1), [black, red, red, green]);
+
+    // Changes that don't affect the column dependencies of the column element
+    // don't restyle the cells.
+    let style = page.style(rows[1][1]);
+    page.doc.set_attribute(col2, "class", "highlight other");
+    assert!(page.restyle());
+    assert!(Arc::ptr_eq(&style, &page.style(rows[1][1])));
+
+    // Column spans change the columns of the cells after them.
+    page.doc.remove_attribute(span, "colspan");
+    assert!(page.restyle());
+    assert_eq!(colors(&page, 0), [black, red, red]);
+    assert_eq!(colors(&page, 1), [black, red, red, green]);
+
+    page.doc.remove_attribute(col2, "class");
+    assert!(page.restyle());
+    assert_eq!(colors(&page, 0), [black, blue, black]);
+    assert_eq!(colors(&page, 1), [black, blue, black, green]);
+}
+
+#[test]
+fn column_selectors_with_row_spans() {
+    // The first cell covers the first column of the second row.
+    let mut page = Page::new(
+        "<style>
+           td { color: black }
+           col.highlight || td { color: red }
+         </style>
+         <table>
+           <colgroup><col><col class=highlight></colgroup>
+           <tbody>
+             <tr><td id=a1 rowspan=2></td><td id=a2></td></tr>
+             <tr><td id=b1></td><td id=b2></td></tr>
+             <tr><td id=c1></td><td id=c2></td></tr>
+           </tbody>
+         </table>",
+    );
+    let span = page.element("a1");
+    let rows = [
+        ["a1", "a2"].map(|id| page.element(id)),
+        ["b1", "b2"].map(|id| page.element(id)),
+        ["c1", "c2"].map(|id| page.element(id)),
+    ];
+    let black = rgb(0, 0, 0);
+    let red = rgb(255, 0, 0);
+    let colors = |page: &Page, row: usize| -> Vec<AbsoluteColor> {
+        rows[row].iter().map(|&td| page.color(td)).collect()
+    };
+    assert!(page.restyle());
+    assert_eq!(colors(&page, 0), [black, red]);
+    assert_eq!(colors(&page, 1), [red, black]);
+    assert_eq!(colors(&page, 2), [black, red]);
+
+    // A zero row span covers the rest of the row group.
+    page.doc.set_attribute(span, "rowspan", "0");
+    assert!(page.restyle());
+    assert_eq!(colors(&page, 1), [red, black]);
+    assert_eq!(colors(&page, 2), [red, black]);
+
+    page.doc.remove_attribute(span, "rowspan");
+    assert!(page.restyle());
+    assert_eq!(colors(&page, 1), [black, red]);
+    assert_eq!(colors(&page, 2), [black, red]);
+}
+
+#[test]
+fn column_cell_invalidation() {
+    // Headers aren't matched against `td` selectors, so they never depend on
+    // the columns of the table.
+    let mut page = Page::new(
+        "<style>
+           col.highlight || td { color: red }
+         </style>
+         <table id=table>
+           <colgroup><col id=col1><col id=col2 span=2></colgroup>
+           <tbody>
+             <tr><td id=a1></td><td id=a2 colspan=2></td><th id=a3></th></tr>
+             <tr><td id=b1></td><td id=b2></td><td id=b3></td></tr>
+           </tbody>
+         </table>",
+    );
+    assert!(page.restyle());
+
+    let doc = &page.doc;
+    let element = |id| doc.element(page.element(id)).unwrap();
+    let table = element("table");
+    let ids = |cells: Vec<ElementRef>| -> Vec<String> {
+        cells
+            .iter()
+            .map(|cell| cell.id().unwrap().to_string())
+            .collect()
+    };
+    let for_column_element = |id| {
+        let mut cells = vec![];
+        table_columns::invalidate_cells_for_column_element(table, element(id), |cell| {
+            cells.push(cell)
+        });
+        ids(cells)
+    };
+    assert_eq!(for_column_element("col1"), ["a1", "b1"]);
+    assert_eq!(for_column_element("col2"), ["a2", "b2", "b3"]);
+    assert!(
+        for_column_element("table").is_empty(),
+        "No cell was matched against the table"
+    );
+
+    let mut cells = vec![];
+    table_columns::invalidate_cells_for_structure_change(table, |cell| cells.push(cell));
+    assert_eq!(ids(cells), ["a1", "a2", "b1", "b2", "b3"]);
+}
+
+#[test]
+fn dir_pseudo_class() {
+    let mut page = Page::new(
+        "<style>
+           * { color: black }
+           :dir(ltr) { color: green }
+           :dir(rtl) { color: red }
+           :dir(foo) { color: blue }
+         </style>
+         <div id=outer dir=RTL>
+           <p id=inner></p>
+           <span id=auto dir=auto><b id=auto-child>abc</b></span>
+           <div id=host><template shadowrootmode=open><div id=shadow-child></div></template></div>
+         </div>",
+    );
+    let outer = page.element("outer");
+    let inner = page.element("inner");
+    let auto = page.element("auto");
+    let auto_child = page.element("auto-child");
+    let host = page.element("host");
+    let shadow_child = page.element("shadow-child");
+    let text = page
+        .doc
+        .node(auto_child)
+        .dom_children()
+        .next()
+        .unwrap()
+        .id();
+    let green = rgb(0, 128, 0);
+    let red = rgb(255, 0, 0);
+    assert!(page.restyle());
+    assert_eq!(page.color(page.root()), green);
+    assert_eq!(page.color(inner), red);
+    assert_eq!(page.color(auto), green);
+    assert_eq!(page.color(auto_child), green);
+    assert_eq!(page.color(host), red);
+    assert_eq!(page.color(shadow_child), red);
+
+    // `dir=auto` follows the first character with a strong direction.
+    page.doc.set_text(text, "123 \u{5e9}\u{5dc}\u{5d5}\u{5dd}");
+    assert!(page.restyle());
+    assert_eq!(page.color(auto), red);
+    assert_eq!(page.color(auto_child), red);
+
+    // Descendants with their own direction don't contribute to it.
+    page.doc.set_attribute(auto_child, "dir", "ltr");
+    assert!(page.restyle());
+    assert_eq!(page.color(auto), green);
+    assert_eq!(page.color(auto_child), green);
+
+    page.doc.remove_attribute(outer, "dir");
+    assert!(page.restyle());
+    assert_eq!(page.color(inner), green);
+    assert_eq!(page.color(shadow_child), green);
+}
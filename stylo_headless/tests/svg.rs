@@ -0,0 +1,156 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+
+#[test]
+fn fill_properties() {
+    let mut page = Page::new(
+        "<style>
+           g { fill: url(#pattern) green; fill-opacity: 150%; fill-rule: evenodd }
+         </style>
+         <svg><g id=g><rect id=rect /></g><circle id=circle /></svg>",
+    );
+    let g = page.element("g");
+    let rect = page.element("rect");
+    let circle = page.element("circle");
+    assert!(page.restyle());
+
+    assert_eq!(
+        page.computed_value(g, "fill"),
+        "url(\"about:blank#pattern\") rgb(0, 128, 0)"
+    );
+    assert_eq!(page.computed_value(g, "fill-opacity"), "1", "Clamped");
+    assert_eq!(page.computed_value(g, "fill-rule"), "evenodd");
+    for name in ["fill", "fill-opacity", "fill-rule"] {
+        assert_eq!(
+            page.computed_value(rect, name),
+            page.computed_value(g, name),
+            "{} is inherited",
+            name
+        );
+    }
+    assert_eq!(page.computed_value(circle, "fill"), "rgb(0, 0, 0)");
+    assert_eq!(page.computed_value(circle, "fill-rule"), "nonzero");
+}
+
+#[test]
+fn stroke_properties() {
+    let mut page = Page::new(
+        "<style>
+           g {
+             stroke: context-fill;
+             stroke-width: 2em;
+             stroke-dasharray: 1px, 2%;
+             stroke-dashoffset: -3px;
+             stroke-linecap: round;
+             stroke-linejoin: bevel;
+             stroke-miterlimit: 2;
+             stroke-opacity: 0.5;
+             paint-order: markers stroke;
+             vector-effect: non-scaling-stroke;
+             font-size: 10px;
+           }
+         </style>
+         <svg><g id=g><path id=path /></g></svg>",
+    );
+    let g = page.element("g");
+    let path = page.element("path");
+    assert!(page.restyle());
+
+    let expected = [
+        ("stroke", "context-fill"),
+        ("stroke-width", "20px"),
+        ("stroke-dasharray", "1px, 2%"),
+        ("stroke-dashoffset", "-3px"),
+        ("stroke-linecap", "round"),
+        ("stroke-linejoin", "bevel"),
+        ("stroke-miterlimit", "2"),
+        ("stroke-opacity", "0.5"),
+        ("paint-order", "markers stroke"),
+        ("vector-effect", "non-scaling-stroke"),
+    ];
+    for (name, value) in expected {
+        assert_eq!(page.computed_value(g, name), value, "{}", name);
+    }
+    assert_eq!(page.computed_value(path, "stroke-width"), "20px");
+    assert_eq!(
+        page.computed_value(path, "vector-effect"),
+        "none",
+        "vector-effect isn't inherited"
+    );
+}
+
+#[test]
+fn marker_properties() {
+    let mut page = Page::new(
+        "<style>
+           g { marker: url(#dot) }
+           path { marker-mid: none }
+         </style>
+         <svg><g id=g><path id=path /></g></svg>",
+    );
+    let g = page.element("g");
+    let path = page.element("path");
+    assert!(page.restyle());
+
+    for name in ["marker-start", "marker-mid", "marker-end"] {
+        assert_eq!(
+            page.computed_value(g, name),
+            "url(\"about:blank#dot\")",
+            "{}",
+            name
+        );
+    }
+    assert_eq!(
+        page.computed_value(path, "marker-start"),
+        "url(\"about:blank#dot\")"
+    );
+    assert_eq!(page.computed_value(path, "marker-mid"), "none");
+}
+
+#[test]
+fn geometry_properties() {
+    let mut page = Page::new(
+        "<style>
+           svg { font-size: 10px }
+           circle { cx: 1em; cy: 10%; r: calc(5px + 1em) }
+           ellipse { rx: auto; ry: 3px }
+           rect { x: 2px; y: -4px }
+           path { d: path('M 0 0 L 10 10') }
+         </style>
+         <svg id=svg>
+           <circle id=circle /><ellipse id=ellipse /><rect id=rect /><path id=path />
+         </svg>",
+    );
+    let svg = page.element("svg");
+    let circle = page.element("circle");
+    let ellipse = page.element("ellipse");
+    let rect = page.element("rect");
+    let path = page.element("path");
+    assert!(page.restyle());
+
+    assert_eq!(page.computed_value(circle, "cx"), "10px");
+    assert_eq!(page.computed_value(circle, "cy"), "10%");
+    assert_eq!(page.computed_value(circle, "r"), "15px");
+    assert_eq!(page.computed_value(ellipse, "rx"), "auto");
+    assert_eq!(page.computed_value(ellipse, "ry"), "3px");
+    assert_eq!(page.computed_value(rect, "x"), "2px");
+    assert_eq!(page.computed_value(rect, "y"), "-4px");
+    assert_eq!(page.computed_value(path, "d"), "path(\"M 0 0 L 10 10\")");
+
+    // The geometry properties aren't inherited.
+    for name in ["cx", "cy", "r", "rx", "ry", "x", "y"] {
+        assert_eq!(
+            page.computed_value(path, name),
+            page.computed_value(svg, name),
+            "{}",
+            name
+        );
+    }
+    assert_eq!(page.computed_value(path, "cx"), "0px");
+    assert_eq!(page.computed_value(rect, "d"), "none");
+}
@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::{rgb, Page};
+use style::dom::{TElement, TNode};
+use style::global_style_data::StyleThreadPoolBuilder;
+
+#[test]
+fn restyle_with_embedder_thread_pool() {
+    // More threads than the global pool can have.
+    let pool = StyleThreadPoolBuilder::new()
+        .num_threads(10)
+        .thread_name(|index| format!("TenantStyle#{}", index))
+        .build()
+        .unwrap();
+    assert_eq!(pool.current_num_threads(), 10);
+    assert!(pool.install(|| {
+        std::thread::current()
+            .name()
+            .unwrap()
+            .starts_with("TenantStyle#")
+    }));
+
+    let section = format!("<section>{}</section>", "<div></div>".repeat(8));
+    let mut page = Page::new(&format!(
+        "<style>section {{ color: blue }} div:nth-child(odd) {{ color: red }}</style>{}",
+        section.repeat(64)
+    ));
+    assert!(page.doc.restyle(&page.stylist, Some(&pool)));
+
+    let divs: Vec<_> = page
+        .doc
+        .node(page.root())
+        .dom_descendants()
+        .filter_map(|node| node.as_element())
+        .filter(|element| &**element.local_name() == "div")
+        .map(|element| element.node_id())
+        .collect();
+    assert_eq!(divs.len(), 64 * 8);
+    for (i, div) in divs.iter().enumerate() {
+        let expected = if i % 2 == 0 {
+            rgb(255, 0, 0)
+        } else {
+            rgb(0, 0, 255)
+        };
+        assert_eq!(page.color(*div), expected);
+    }
+}
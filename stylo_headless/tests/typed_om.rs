@@ -0,0 +1,374 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use style::properties::declaration_block::{parse_typed_declaration_into, PropertyTypedValue};
+use style::properties::{parse_style_attribute, LonghandId, PropertyId, SourcePropertyDeclaration};
+use style::stylesheets::{CssRuleType, Origin};
+use style_traits::{
+    ColorValue, ColorValueFunction, CssString, NumericValue, NumericValueOrNone,
+    TransformComponent, TypedValue, TypedValueError, UnparsedSegment,
+};
+
+fn unit(value: &NumericValue) -> (f32, String) {
+    match *value {
+        NumericValue::Unit { value, ref unit } => (value, unit.to_string()),
+        ref other => panic!("Expected a unit value, got {:?}", other),
+    }
+}
+
+#[test]
+fn typed_om_reification() {
+    let mut page = Page::new("<style>html { color: green }</style>");
+    let block = parse_style_attribute(
+        "transform: translateX(10px) rotate(45deg) matrix(1, 0, 0, 1, 5, 6); \
+         padding-top: calc(100% - 10px); \
+         color: rgb(255 0 0 / 50%); \
+         border-image-source: linear-gradient(red, blue); \
+         margin-top: var(--a, 1px var(--b)) 2px",
+        page.doc.url_data(),
+        None,
+        page.doc.quirks_mode(),
+        CssRuleType::Style,
+    );
+    let typed = |name: &str| match block
+        .property_value_to_typed(&PropertyId::parse_unchecked_for_testing(name).unwrap())
+    {
+        PropertyTypedValue::Typed(value) => value,
+        _ => panic!("Expected {} to be reified", name),
+    };
+
+    let TypedValue::Transform(components) = typed("transform") else {
+        panic!("Expected a transform");
+    };
+    assert_eq!(components.len(), 3);
+    let TransformComponent::Translate {
+        ref x,
+        ref y,
+        is_2d,
+        ..
+    } = components[0]
+    else {
+        panic!("Expected a translation");
+    };
+    assert_eq!(unit(x), (10., "px".to_owned()));
+    assert_eq!(unit(y), (0., "px".to_owned()));
+    assert!(is_2d);
+    let TransformComponent::Rotate {
+        ref z, ref angle, ..
+    } = components[1]
+    else {
+        panic!("Expected a rotation");
+    };
+    assert_eq!(unit(z), (1., "number".to_owned()));
+    assert_eq!(unit(angle), (45., "deg".to_owned()));
+    let TransformComponent::Matrix { matrix, is_2d } = components[2] else {
+        panic!("Expected a matrix");
+    };
+    assert_eq!(matrix[12..14], [5., 6.]);
+    assert!(is_2d);
+
+    let TypedValue::Numeric(NumericValue::Sum { values }) = typed("padding-top") else {
+        panic!("Expected a sum");
+    };
+    assert_eq!(unit(&values[0]), (100., "percent".to_owned()));
+    let NumericValue::Negate { ref value } = values[1] else {
+        panic!("Expected a negation");
+    };
+    assert_eq!(unit(value), (10., "px".to_owned()));
+
+    let TypedValue::Color(color) = typed("color") else {
+        panic!("Expected a color");
+    };
+    assert_eq!(color.function, ColorValueFunction::Rgb);
+    let NumericValueOrNone::Numeric(ref red) = color.channels[0] else {
+        panic!("Expected a red channel");
+    };
+    assert_eq!(unit(red), (100., "percent".to_owned()));
+    let NumericValueOrNone::Numeric(ref alpha) = color.alpha else {
+        panic!("Expected an alpha channel");
+    };
+    assert_eq!(unit(alpha), (0.5, "number".to_owned()));
+
+    let TypedValue::Image(image) = typed("border-image-source") else {
+        panic!("Expected an image");
+    };
+    assert_eq!(image.to_string(), "linear-gradient(red, blue)");
+
+    let TypedValue::Unparsed(segments) = typed("margin-top") else {
+        panic!("Expected an unparsed value");
+    };
+    assert_eq!(segments.len(), 2);
+    let UnparsedSegment::VariableReference {
+        ref variable,
+        fallback: Some(ref fallback),
+    } = segments[0]
+    else {
+        panic!("Expected a variable reference with a fallback");
+    };
+    assert_eq!(variable.to_string(), "--a");
+    assert!(matches!(fallback[0], UnparsedSegment::String(ref s) if s == "1px "));
+    assert!(matches!(
+        fallback[1],
+        UnparsedSegment::VariableReference { ref variable, fallback: None } if variable == "--b"
+    ));
+    assert!(matches!(segments[1], UnparsedSegment::String(ref s) if s == " 2px"));
+
+    assert!(page.restyle());
+    let style = page.style(page.root());
+    assert!(matches!(
+        style.computed_typed_value(LonghandId::Color),
+        Some(TypedValue::Color(..))
+    ));
+}
+
+#[test]
+fn typed_om_write_path() {
+    let page = Page::new("");
+    let set = |name: &str, values: &[TypedValue]| {
+        let mut declarations = SourcePropertyDeclaration::default();
+        parse_typed_declaration_into(
+            &mut declarations,
+            PropertyId::parse_unchecked_for_testing(name).unwrap(),
+            values,
+            Origin::Author,
+            page.doc.url_data(),
+            page.doc.quirks_mode(),
+            CssRuleType::Style,
+        )?;
+        Ok(declarations
+            .declarations
+            .iter()
+            .map(|declaration| {
+                let mut css = String::new();
+                declaration.to_css(&mut css).unwrap();
+                css
+            })
+            .collect::<Vec<_>>())
+    };
+    let keyword = |keyword: &str| TypedValue::Keyword(CssString::from(keyword));
+    let px = |value: f32| TypedValue::Numeric(NumericValue::px(value));
+
+    // Values built directly.
+    assert_eq!(
+        set("opacity", &[TypedValue::Numeric(NumericValue::number(0.5))]),
+        Ok(vec!["0.5".to_owned()])
+    );
+    assert_eq!(
+        set(
+            "padding-top",
+            &[TypedValue::Numeric(NumericValue::percentage(0.25))]
+        ),
+        Ok(vec!["25%".to_owned()])
+    );
+    assert_eq!(
+        set("top", &[keyword("inherit")]),
+        Ok(vec!["inherit".to_owned()])
+    );
+    let transform = TypedValue::Transform(
+        vec![
+            TransformComponent::Translate {
+                x: NumericValue::px(10.),
+                y: NumericValue::percentage(0.5),
+                z: NumericValue::px(0.),
+                is_2d: true,
+            },
+            TransformComponent::Rotate {
+                x: NumericValue::number(0.),
+                y: NumericValue::number(0.),
+                z: NumericValue::number(1.),
+                angle: NumericValue::degrees(45.),
+                is_2d: true,
+            },
+        ]
+        .into(),
+    );
+    assert_eq!(
+        set("transform", &[transform]),
+        Ok(vec!["translate(10px, 50%) rotate(45deg)".to_owned()])
+    );
+
+    let sum = TypedValue::Numeric(NumericValue::Sum {
+        values: vec![
+            NumericValue::percentage(1.),
+            NumericValue::Negate {
+                value: Box::new(NumericValue::px(10.)),
+            },
+        ]
+        .into(),
+    });
+    assert_eq!(
+        set("padding-top", &[sum]),
+        Ok(vec!["calc(100% - 10px)".to_owned()])
+    );
+    assert_eq!(
+        set(
+            "width",
+            &[TypedValue::Numeric(NumericValue::Max {
+                values: vec![NumericValue::px(10.), NumericValue::percentage(0.5)].into(),
+            })]
+        ),
+        Ok(vec!["max(10px, 50%)".to_owned()])
+    );
+    assert_eq!(
+        set(
+            "opacity",
+            &[TypedValue::Numeric(NumericValue::Sum {
+                values: vec![NumericValue::number(0.25), NumericValue::number(0.25)].into(),
+            })]
+        ),
+        Ok(vec!["calc(0.5)".to_owned()])
+    );
+    assert_eq!(
+        set("width", &[keyword("auto")]),
+        Ok(vec!["auto".to_owned()])
+    );
+    assert_eq!(
+        set("display", &[keyword("Block")]),
+        Ok(vec!["block".to_owned()])
+    );
+    assert_eq!(
+        set("visibility", &[keyword("hidden")]),
+        Ok(vec!["hidden".to_owned()])
+    );
+    assert_eq!(
+        set("animation-name", &[keyword("slide"), keyword("none")]),
+        Ok(vec!["slide, none".to_owned()])
+    );
+    assert_eq!(
+        set(
+            "color",
+            &[TypedValue::Color(ColorValue {
+                function: ColorValueFunction::Rgb,
+                color_space: CssString::new(),
+                channels: [
+                    NumericValueOrNone::Numeric(NumericValue::number(255.)),
+                    NumericValueOrNone::Numeric(NumericValue::percentage(0.)),
+                    NumericValueOrNone::Numeric(NumericValue::number(0.)),
+                ],
+                alpha: NumericValueOrNone::Numeric(NumericValue::number(0.5)),
+            })]
+        ),
+        Ok(vec!["rgba(255, 0, 0, 0.5)".to_owned()])
+    );
+    assert_eq!(set("margin", &[keyword("initial")]).map(|d| d.len()), Ok(4));
+    assert_eq!(set("margin", &[keyword("auto")]).map(|d| d.len()), Ok(4));
+    assert_eq!(set("--foo", &[keyword("bar")]), Ok(vec!["bar".to_owned()]));
+    assert_eq!(
+        set(
+            "transition-duration",
+            &[
+                TypedValue::Numeric(NumericValue::Unit {
+                    value: 1.,
+                    unit: CssString::from("s")
+                }),
+                TypedValue::Numeric(NumericValue::Unit {
+                    value: 20.,
+                    unit: CssString::from("ms")
+                })
+            ]
+        ),
+        Ok(vec!["1s, 20ms".to_owned()])
+    );
+    let unparsed = TypedValue::Unparsed(
+        vec![
+            UnparsedSegment::VariableReference {
+                variable: CssString::from("--a"),
+                fallback: Some(vec![UnparsedSegment::String(CssString::from("1px"))].into()),
+            },
+            UnparsedSegment::String(CssString::from(" 2px")),
+        ]
+        .into(),
+    );
+    assert_eq!(
+        set("margin-top", std::slice::from_ref(&unparsed)),
+        Ok(vec!["var(--a,1px) 2px".to_owned()])
+    );
+    assert_eq!(
+        set("--foo", &[unparsed]),
+        Ok(vec!["var(--a,1px) 2px".to_owned()])
+    );
+
+    // Type errors.
+    assert_eq!(
+        set("opacity", &[px(1.)]),
+        Err(TypedValueError::TypeMismatch)
+    );
+    assert_eq!(set("opacity", &[]), Err(TypedValueError::TypeMismatch));
+    assert_eq!(
+        set("padding-top", &[px(-1.)]),
+        Err(TypedValueError::TypeMismatch)
+    );
+    // Numbers aren't lengths, even when their serialization would parse as
+    // one.
+    let zero = TypedValue::Numeric(NumericValue::number(0.));
+    assert_eq!(
+        set("scroll-margin-top", std::slice::from_ref(&zero)),
+        Err(TypedValueError::TypeMismatch)
+    );
+    assert_eq!(
+        set("padding-block-start", &[zero]),
+        Err(TypedValueError::TypeMismatch)
+    );
+    for value in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+        assert_eq!(
+            set("scroll-margin-top", &[px(value)]),
+            Err(TypedValueError::TypeMismatch)
+        );
+        assert_eq!(
+            set("padding-block-start", &[px(value)]),
+            Err(TypedValueError::TypeMismatch)
+        );
+        assert_eq!(
+            set(
+                "opacity",
+                &[TypedValue::Numeric(NumericValue::number(value))]
+            ),
+            Err(TypedValueError::TypeMismatch)
+        );
+    }
+    assert_eq!(
+        set("top", &[px(1.), px(2.)]),
+        Err(TypedValueError::TypeMismatch)
+    );
+    assert_eq!(
+        set("width", &[keyword("1px")]),
+        Err(TypedValueError::TypeMismatch)
+    );
+    assert_eq!(
+        set("visibility", &[keyword("auto")]),
+        Err(TypedValueError::TypeMismatch)
+    );
+    assert_eq!(
+        set("visibility", &[px(1.)]),
+        Err(TypedValueError::TypeMismatch)
+    );
+    assert_eq!(
+        set("transition-duration", &[px(1.)]),
+        Err(TypedValueError::TypeMismatch)
+    );
+    assert_eq!(
+        set(
+            "transition-duration",
+            &[TypedValue::Numeric(NumericValue::Unit {
+                value: -1.,
+                unit: CssString::from("s")
+            })]
+        ),
+        Err(TypedValueError::TypeMismatch)
+    );
+    assert_eq!(
+        set(
+            "padding-top",
+            &[TypedValue::Numeric(NumericValue::Sum {
+                values: vec![NumericValue::px(1.), NumericValue::number(1.)].into(),
+            })]
+        ),
+        Err(TypedValueError::TypeMismatch)
+    );
+    assert_eq!(set("margin", &[px(1.)]), Err(TypedValueError::InvalidKind));
+    assert_eq!(set("--foo", &[px(1.)]), Err(TypedValueError::InvalidKind));
+}
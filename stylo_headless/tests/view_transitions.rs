@@ -0,0 +1,141 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+mod common;
+
+use common::Page;
+use euclid::default::{Size2D, Transform3D};
+use style::properties::ComputedValues;
+use style::selector_parser::{PseudoElement, PtNameAndClassSelector};
+use style::servo::view_transition::{CapturedElement, CapturedState, USER_AGENT_RULES};
+use style::shared_lock::StylesheetGuards;
+use style::stylesheets::Origin;
+use style::values::computed::ViewTransitionName;
+use style::Atom;
+use style_traits::ToCss;
+
+#[test]
+fn view_transition_pseudo_elements() {
+    style_config::set_bool("dom.view_transitions.enabled", true).unwrap();
+
+    let mut page = Page::new(
+        "<style>
+           div { view-transition-name: hero; view-transition-class: card }
+           ::view-transition-group(*) { animation-duration: 1s }
+           ::view-transition-new(*.card) { opacity: 0.5 }
+           ::view-transition-new(hero.card.other) { opacity: 0.25 }
+         </style>
+         <div id=hero></div>",
+    );
+    let html = page.root();
+    let div = page.element("hero");
+    assert!(page.restyle());
+
+    let root_style = page.style(html);
+    let div_style = page.style(div);
+    let ui = div_style.get_ui();
+    let name = match ui.view_transition_name {
+        ViewTransitionName::Ident(ref name) => name.clone(),
+        ref other => panic!("Unexpected view-transition-name: {:?}", other),
+    };
+    let captured = CapturedElement {
+        name_and_class: PtNameAndClassSelector::from_name_and_classes(
+            name,
+            ui.view_transition_class.iter().cloned(),
+        ),
+        old: Some(CapturedState {
+            size: Size2D::new(100., 50.),
+            transform: Transform3D::translation(10., 20., 0.),
+            style: div_style.clone(),
+        }),
+        new: Some(CapturedState {
+            size: Size2D::new(200., 100.),
+            transform: Transform3D::identity(),
+            style: div_style.clone(),
+        }),
+    };
+
+    // A captured element that only exists in the new view, with a geometry
+    // that can't be represented in CSS.
+    let entering = CapturedElement {
+        name_and_class: PtNameAndClassSelector::from_name(Atom::from("entering")),
+        old: None,
+        new: Some(CapturedState {
+            size: Size2D::new(f32::INFINITY, 10.),
+            transform: Transform3D::identity(),
+            style: div_style.clone(),
+        }),
+    };
+    page.add_stylesheet(USER_AGENT_RULES, Origin::UserAgent);
+    page.doc
+        .set_view_transition(&[captured.clone(), entering.clone()]);
+    let guard = page.doc.shared_lock().read();
+    let guards = StylesheetGuards::same(&guard);
+    let root = page.doc.element(html).unwrap();
+
+    let compute = |pseudo: &PseudoElement, parent: &ComputedValues| {
+        page.stylist.compute_view_transition_pseudo_style(
+            &guards,
+            root,
+            &root_style,
+            pseudo,
+            parent,
+        )
+    };
+    let view_transition = compute(&PseudoElement::ViewTransition, &root_style);
+    assert_eq!(
+        view_transition.get_box().clone_position(),
+        style::computed_values::position::T::Fixed
+    );
+    let group = compute(&captured.group(), &view_transition);
+    assert_eq!(group.get_position().clone_width().to_css_string(), "200px");
+    assert_eq!(
+        group.get_ui().animation_name_mod(0).to_css_string(),
+        "-ua-view-transition-group-anim-hero"
+    );
+    assert_eq!(group.get_ui().animation_duration_mod(0).seconds(), 1.);
+    let keyframes = captured.group_keyframes(page.doc.shared_lock()).unwrap();
+    assert_eq!(
+        captured.group_keyframes_name(),
+        Atom::from("-ua-view-transition-group-anim-hero")
+    );
+    assert_eq!(keyframes.steps.len(), 2, "Animates to the group style");
+    assert!(entering.group_keyframes(page.doc.shared_lock()).is_none());
+
+    let image_pair = compute(&captured.image_pair(), &group);
+    let new = compute(&captured.new_image().unwrap(), &image_pair);
+    assert_eq!(
+        new.get_effects().opacity,
+        0.5,
+        "Matches *.card but not .other"
+    );
+    assert_eq!(new.get_ui().animation_duration_mod(0).seconds(), 1.);
+    let old = compute(&captured.old_image().unwrap(), &image_pair);
+    assert_eq!(old.get_effects().opacity, 1.);
+    assert_eq!(
+        old.get_ui().animation_name_mod(1).to_css_string(),
+        "-ua-mix-blend-mode-plus-lighter"
+    );
+
+    let entering_group = compute(&entering.group(), &view_transition);
+    assert_eq!(
+        entering_group.get_position().clone_width().to_css_string(),
+        "auto",
+        "Non-finite geometry is ignored"
+    );
+    assert_eq!(
+        entering_group
+            .get_ui()
+            .animation_name_mod(0)
+            .to_css_string(),
+        "none"
+    );
+    let entering_image_pair = compute(&entering.image_pair(), &entering_group);
+    let entering_new = compute(&entering.new_image().unwrap(), &entering_image_pair);
+    assert_eq!(entering_new.get_ui().animation_name_count(), 1);
+    assert_eq!(
+        entering_new.get_ui().animation_name_mod(0).to_css_string(),
+        "-ua-view-transition-fade-in"
+    );
+}
@@ -0,0 +1,74 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+//! The style traversal over a [`Document`](crate::Document).
+
+use crate::dom::ElementRef;
+use style::context::{SharedStyleContext, StyleContext};
+use style::dom::{NodeInfo, TElement, TNode};
+use style::traversal::{recalc_style_at, DomTraversal, PerLevelTraversalData};
+
+/// A traversal that computes the style of every element that needs it, and
+/// nothing else.
+pub struct RecalcStyle<'a> {
+    context: SharedStyleContext<'a>,
+}
+
+impl<'a> RecalcStyle<'a> {
+    /// Creates a new traversal with the given shared context.
+    pub fn new(context: SharedStyleContext<'a>) -> Self {
+        RecalcStyle { context }
+    }
+
+    /// Consumes the traversal, returning the shared context.
+    pub fn destroy(self) -> SharedStyleContext<'a> {
+        self.context
+    }
+}
+
+#[allow(unsafe_code)]
+impl<'a, 'dom> DomTraversal<ElementRef<'dom>> for RecalcStyle<'a> {
+    fn process_preorder<F>(
+        &self,
+        traversal_data: &PerLevelTraversalData,
+        context: &mut StyleContext<ElementRef<'dom>>,
+        node: <ElementRef<'dom> as TElement>::ConcreteNode,
+        note_child: F,
+    ) where
+        F: FnMut(<ElementRef<'dom> as TElement>::ConcreteNode),
+    {
+        // There's no layout to build for text nodes.
+        if node.is_text_node() {
+            return;
+        }
+
+        let element = node.as_element().unwrap();
+        let mut data = unsafe { element.ensure_data() };
+        recalc_style_at(
+            self,
+            traversal_data,
+            context,
+            element,
+            &mut data,
+            note_child,
+        );
+        unsafe { element.unset_dirty_descendants() };
+    }
+
+    fn process_postorder(
+        &self,
+        _: &mut StyleContext<ElementRef<'dom>>,
+        _: <ElementRef<'dom> as TElement>::ConcreteNode,
+    ) {
+        unreachable!();
+    }
+
+    fn needs_postorder_traversal() -> bool {
+        false
+    }
+
+    fn shared_context(&self) -> &SharedStyleContext<'_> {
+        &self.context
+    }
+}
@@ -2,59 +2,47 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
-/// Returns the default value for a preference exposed to the style crate.
-/// This is what will be used if the embedder has not set the preference.
-#[macro_export]
-macro_rules! default_value {
-    ("layout.css.contrast-color.enabled") => {
-        true
-    };
-    ("layout.css.fit-content-function.enabled") => {
-        true
-    };
-    ("layout.css.font-variations.enabled") => {
-        true
-    };
-    ("layout.css.gradient-color-interpolation-method.enabled") => {
-        true
-    };
-    ("layout.css.outline-offset.snapping") => {
-        1
-    };
-    ("layout.css.properties-and-values.enabled") => {
-        true
-    };
-    ("layout.css.relative-color-syntax.enabled") => {
-        true
-    };
-    ("layout.css.stretch-size-keyword.enabled") => {
-        true
-    };
-    ("layout.css.stylo-local-work-queue.in-main-thread") => {
-        32
-    };
-    ("layout.css.stylo-local-work-queue.in-worker") => {
-        0
-    };
-    ("layout.threads") => {
-       // Negative means auto, 0 disables the thread-pool (main-thread styling),
-       // other numbers override as specified.
-        -1
-    };
-    ("layout.css.stylo-work-unit-size") => {
-        16
-    };
-    ("layout.css.system-ui.enabled") => {
-        true
-    };
-    ("layout.css.webkit-fill-available.all-size-properties.enabled") => {
-        true
-    };
-    ("layout.css.webkit-fill-available.enabled") => {
-        true
-    };
-    ($string:literal) => {
-        false
+/// Defines the preferences exposed to the style crate, along with their
+/// default values.
+///
+/// The leading `$` is passed through so that the macros this defines can have
+/// metavariables of their own.
+macro_rules! prefs {
+    ($d:tt $($name:tt => $default:expr,)*) => {
+        /// Returns the default value for a preference exposed to the style crate.
+        /// This is what will be used if the embedder has not set the preference.
+        ///
+        /// Preferences that aren't listed here, like the ones that enable
+        /// properties, default to `false`.
+        #[macro_export]
+        macro_rules! default_value {
+            $(($name) => {
+                $default
+            };)*
+            ($d string:literal) => {
+                false
+            };
+        }
+
+        /// Like [`default_value!`], but only for the preferences listed here,
+        /// so that [`pref!`] can't read a preference missing from the
+        /// registry.
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! registered_default_value {
+            $(($name) => {
+                $default
+            };)*
+        }
+
+        /// Returns the description of every preference the style crate reads
+        /// with [`pref!`], along with its default value.
+        pub fn registry() -> Vec<style_config::PrefDescription> {
+            vec![$(style_config::PrefDescription {
+                name: $name,
+                default: style_config::PrefValue::from($default),
+            },)*]
+        }
     };
 }
 
@@ -63,6 +51,47 @@ macro_rules! default_value {
 #[macro_export]
 macro_rules! pref {
     ($string:tt) => {
-        style_config::Getter::get($string, $crate::default_value!($string))
+        style_config::Getter::get($string, $crate::registered_default_value!($string))
     };
 }
+
+// The preferences that enable properties are listed in `longhands.toml` and
+// `shorthands.toml` instead, see `style::servo::prefs::registry`.
+prefs! {
+    $
+    "layout.css.anchor-positioning.enabled" => true,
+    "layout.css.anchor-positioning.position-try-order.enabled" => true,
+    "layout.css.at-scope.enabled" => false,
+    "layout.css.attr.enabled" => false,
+    "layout.css.basic-shape-shape.enabled" => false,
+    "layout.css.color-mix-multi-color.enabled" => false,
+    "layout.css.content.alt-text.enabled" => false,
+    "layout.css.contrast-color.enabled" => true,
+    "layout.css.custom-media.enabled" => false,
+    "layout.css.fit-content-function.enabled" => true,
+    "layout.css.font-palette.enabled" => false,
+    "layout.css.font-tech.enabled" => false,
+    "layout.css.font-variations.enabled" => true,
+    "layout.css.gradient-color-interpolation-method.enabled" => true,
+    "layout.css.light-dark.images.enabled" => false,
+    "layout.css.margin-rules.enabled" => false,
+    "layout.css.motion-path-url.enabled" => false,
+    "layout.css.outline-offset.snapping" => 1,
+    "layout.css.properties-and-values.enabled" => true,
+    "layout.css.relative-color-syntax.enabled" => true,
+    "layout.css.scroll-driven-animations.enabled" => true,
+    "layout.css.scroll-state.enabled" => false,
+    "layout.css.starting-style-at-rules.enabled" => false,
+    "layout.css.stretch-size-keyword.enabled" => true,
+    "layout.css.style-queries.enabled" => false,
+    "layout.css.stylo-local-work-queue.in-main-thread" => 32,
+    "layout.css.stylo-local-work-queue.in-worker" => 0,
+    "layout.css.stylo-work-unit-size" => 16,
+    "layout.css.system-ui.enabled" => true,
+    "layout.css.webkit-fill-available.all-size-properties.enabled" => true,
+    "layout.css.webkit-fill-available.enabled" => true,
+    "layout.grid.enabled" => false,
+    // Negative means auto, 0 disables the thread-pool (main-thread styling),
+    // other numbers override as specified.
+    "layout.threads" => -1,
+}